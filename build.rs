@@ -0,0 +1,16 @@
+use std::{env, process::Command};
+
+fn main() {
+    // Dynamic plugins must be built with the same compiler as the engine, the version is embedded into
+    // the engine to be able to check it when a plugin is loaded.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+
+    println!("cargo:rustc-env=FYROX_RUSTC_VERSION={}", version.trim());
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
@@ -1,7 +1,18 @@
 use fyrox::event_loop::EventLoop;
-use fyroxed_base::Editor;
+use fyroxed_base::{build, upgrade, Editor};
 
 fn main() {
+    // `fyroxed upgrade [dir] [--dry-run]` upgrades scenes to current format without running the editor.
+    if upgrade::handle_command_line(&[]) {
+        return;
+    }
+
+    // `fyroxed build [--release] [--pack <out>]` builds the game and packs its assets without running
+    // the editor.
+    if build::handle_command_line() {
+        return;
+    }
+
     let event_loop = EventLoop::new();
     let editor = Editor::new(&event_loop, None);
     editor.run(event_loop)
@@ -0,0 +1,423 @@
+use crate::{
+    absm::{
+        command::blend::{
+            SetBlendSpace1DPointPositionCommand, SetBlendSpace2DPointPositionCommand,
+        },
+        selection::{AbsmSelection, SelectedEntity},
+    },
+    send_sync_message, Message,
+};
+use fyrox::{
+    animation::machine::{MachineLayer, ParameterContainer, PoseNode},
+    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+    gui::{
+        brush::Brush,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        message::{MessageDirection, MouseButton, UiMessage},
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Control, UiNode, UserInterface,
+    },
+    scene::node::Node,
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
+};
+
+const POINT_SIZE: f32 = 8.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlendSpaceFieldMessage {
+    Points(Vec<Vector2<f32>>),
+    Triangles(Vec<[usize; 3]>),
+    SamplePoint(Vector2<f32>),
+    // Occurs when user finishes dragging a point.
+    MovePoint {
+        index: usize,
+        position: Vector2<f32>,
+    },
+}
+
+impl BlendSpaceFieldMessage {
+    define_constructor!(BlendSpaceFieldMessage:Points => fn points(Vec<Vector2<f32>>), layout: false);
+    define_constructor!(BlendSpaceFieldMessage:Triangles => fn triangles(Vec<[usize; 3]>), layout: false);
+    define_constructor!(BlendSpaceFieldMessage:SamplePoint => fn sample_point(Vector2<f32>), layout: false);
+    define_constructor!(BlendSpaceFieldMessage:MovePoint => fn move_point(index: usize, position: Vector2<f32>), layout: false);
+}
+
+#[derive(Clone)]
+pub struct BlendSpaceField {
+    widget: Widget,
+    points: Vec<Vector2<f32>>,
+    triangles: Vec<[usize; 3]>,
+    sample_point: Vector2<f32>,
+    min: Vector2<f32>,
+    max: Vector2<f32>,
+    dragged_point: Option<usize>,
+}
+
+define_widget_deref!(BlendSpaceField);
+
+impl BlendSpaceField {
+    fn fit_bounds(&mut self) {
+        let mut min = Vector2::new(-1.0, -1.0);
+        let mut max = Vector2::new(1.0, 1.0);
+        for point in self.points.iter() {
+            min = min.inf(point);
+            max = max.sup(point);
+        }
+        let padding = (max - min).scale(0.1);
+        self.min = min - padding;
+        self.max = max + padding;
+    }
+
+    fn blend_space_to_local(&self, point: Vector2<f32>) -> Vector2<f32> {
+        let size = self.actual_local_size();
+        let k = (point - self.min).component_div(&(self.max - self.min));
+        Vector2::new(k.x * size.x, (1.0 - k.y) * size.y)
+    }
+
+    fn local_to_blend_space(&self, point: Vector2<f32>) -> Vector2<f32> {
+        let size = self.actual_local_size();
+        let k = Vector2::new(point.x / size.x, 1.0 - point.y / size.y);
+        self.min + k.component_mul(&(self.max - self.min))
+    }
+
+    fn point_rect(&self, point: Vector2<f32>, size: f32) -> Rect<f32> {
+        let center = self.blend_space_to_local(point);
+        Rect::new(center.x - size * 0.5, center.y - size * 0.5, size, size)
+    }
+}
+
+impl Control for BlendSpaceField {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let bounds = self.widget.bounding_rect();
+        ctx.push_rect_filled(&bounds, None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        // Axes.
+        let origin = self.blend_space_to_local(Vector2::default());
+        ctx.push_line(
+            Vector2::new(bounds.x(), origin.y),
+            Vector2::new(bounds.x() + bounds.w(), origin.y),
+            1.0,
+        );
+        ctx.push_line(
+            Vector2::new(origin.x, bounds.y()),
+            Vector2::new(origin.x, bounds.y() + bounds.h()),
+            1.0,
+        );
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(80, 80, 80)),
+            CommandTexture::None,
+            None,
+        );
+
+        for triangle in self.triangles.iter() {
+            for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                if let (Some(a), Some(b)) =
+                    (self.points.get(triangle[a]), self.points.get(triangle[b]))
+                {
+                    ctx.push_line(
+                        self.blend_space_to_local(*a),
+                        self.blend_space_to_local(*b),
+                        1.0,
+                    );
+                }
+            }
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(130, 130, 130)),
+            CommandTexture::None,
+            None,
+        );
+
+        for point in self.points.iter() {
+            ctx.push_rect_filled(&self.point_rect(*point, POINT_SIZE), None);
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.foreground(),
+            CommandTexture::None,
+            None,
+        );
+
+        let sample_point = self.blend_space_to_local(self.sample_point);
+        ctx.push_circle(sample_point, POINT_SIZE * 0.5, 16, Color::WHITE);
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(240, 130, 0)),
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() == self.handle() {
+                match msg {
+                    WidgetMessage::MouseDown { button, pos } => {
+                        if *button == MouseButton::Left {
+                            let local_pos = self.screen_to_local(*pos);
+                            self.dragged_point = self.points.iter().position(|p| {
+                                self.point_rect(*p, POINT_SIZE * 2.0).contains(local_pos)
+                            });
+                            if self.dragged_point.is_some() {
+                                ui.capture_mouse(self.handle());
+                                message.set_handled(true);
+                            }
+                        }
+                    }
+                    WidgetMessage::MouseMove { pos, .. } => {
+                        if let Some(index) = self.dragged_point {
+                            let position = self.local_to_blend_space(self.screen_to_local(*pos));
+                            if let Some(point) = self.points.get_mut(index) {
+                                *point = position;
+                            }
+                        }
+                    }
+                    WidgetMessage::MouseUp { button, .. } => {
+                        if *button == MouseButton::Left {
+                            if let Some(index) = self.dragged_point.take() {
+                                ui.release_mouse_capture();
+                                if let Some(position) = self.points.get(index) {
+                                    ui.send_message(BlendSpaceFieldMessage::move_point(
+                                        self.handle(),
+                                        MessageDirection::FromWidget,
+                                        index,
+                                        *position,
+                                    ));
+                                }
+                                message.set_handled(true);
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(msg) = message.data::<BlendSpaceFieldMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    BlendSpaceFieldMessage::Points(points) => {
+                        if self.dragged_point.is_none() {
+                            self.points = points.clone();
+                            self.fit_bounds();
+                        }
+                    }
+                    BlendSpaceFieldMessage::Triangles(triangles) => {
+                        self.triangles = triangles.clone();
+                    }
+                    BlendSpaceFieldMessage::SamplePoint(sample_point) => {
+                        self.sample_point = *sample_point;
+                    }
+                    BlendSpaceFieldMessage::MovePoint { .. } => {}
+                }
+            }
+        }
+    }
+}
+
+pub struct BlendSpaceFieldBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl BlendSpaceFieldBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let mut field = BlendSpaceField {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::opaque(40, 40, 40)))
+                .with_foreground(Brush::Solid(Color::opaque(0, 160, 230)))
+                .build(),
+            points: Default::default(),
+            triangles: Default::default(),
+            sample_point: Default::default(),
+            min: Default::default(),
+            max: Default::default(),
+            dragged_point: None,
+        };
+        field.fit_bounds();
+
+        ctx.add_node(UiNode::new(field))
+    }
+}
+
+pub struct BlendSpaceEditor {
+    pub window: Handle<UiNode>,
+    field: Handle<UiNode>,
+    pose_node: Handle<PoseNode>,
+}
+
+impl BlendSpaceEditor {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let field = BlendSpaceFieldBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new())
+            .with_title(WindowTitle::text("Blend Space"))
+            .with_content(field)
+            .can_close(false)
+            .can_minimize(false)
+            .build(ctx);
+
+        Self {
+            window,
+            field,
+            pose_node: Default::default(),
+        }
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        ui: &mut UserInterface,
+        layer: &MachineLayer,
+        parameters: &ParameterContainer,
+        selection: &AbsmSelection,
+    ) {
+        // Show the last selected blend space.
+        self.pose_node = selection
+            .entities
+            .iter()
+            .rev()
+            .find_map(|entity| match entity {
+                SelectedEntity::PoseNode(handle) => match layer.nodes().try_borrow(*handle) {
+                    Some(PoseNode::BlendSpace1D(_)) | Some(PoseNode::BlendSpace2D(_)) => {
+                        Some(*handle)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let (points, triangles) = match layer.nodes().try_borrow(self.pose_node) {
+            Some(PoseNode::BlendSpace1D(blend_space)) => (
+                blend_space
+                    .points
+                    .iter()
+                    .map(|p| Vector2::new(p.position, 0.0))
+                    .collect(),
+                vec![],
+            ),
+            Some(PoseNode::BlendSpace2D(blend_space)) => (
+                blend_space.points.iter().map(|p| p.position).collect(),
+                blend_space.triangles().clone(),
+            ),
+            _ => (vec![], vec![]),
+        };
+
+        send_sync_message(
+            ui,
+            BlendSpaceFieldMessage::points(self.field, MessageDirection::ToWidget, points),
+        );
+        send_sync_message(
+            ui,
+            BlendSpaceFieldMessage::triangles(self.field, MessageDirection::ToWidget, triangles),
+        );
+
+        self.update(ui, layer, parameters);
+    }
+
+    pub fn update(
+        &self,
+        ui: &UserInterface,
+        layer: &MachineLayer,
+        parameters: &ParameterContainer,
+    ) {
+        let sample_point = match layer.nodes().try_borrow(self.pose_node) {
+            Some(PoseNode::BlendSpace1D(blend_space)) => {
+                Vector2::new(blend_space.sample_position(parameters), 0.0)
+            }
+            Some(PoseNode::BlendSpace2D(blend_space)) => blend_space.sample_position(parameters),
+            _ => return,
+        };
+
+        send_sync_message(
+            ui,
+            BlendSpaceFieldMessage::sample_point(
+                self.field,
+                MessageDirection::ToWidget,
+                sample_point,
+            ),
+        );
+    }
+
+    pub fn clear(&mut self, ui: &UserInterface) {
+        self.pose_node = Handle::NONE;
+
+        send_sync_message(
+            ui,
+            BlendSpaceFieldMessage::points(self.field, MessageDirection::ToWidget, vec![]),
+        );
+        send_sync_message(
+            ui,
+            BlendSpaceFieldMessage::triangles(self.field, MessageDirection::ToWidget, vec![]),
+        );
+    }
+
+    pub fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        sender: &Sender<Message>,
+        layer: &MachineLayer,
+        absm_node_handle: Handle<Node>,
+        layer_index: usize,
+    ) {
+        if let Some(BlendSpaceFieldMessage::MovePoint { index, position }) = message.data() {
+            if message.destination() == self.field
+                && message.direction() == MessageDirection::FromWidget
+            {
+                match layer.nodes().try_borrow(self.pose_node) {
+                    Some(PoseNode::BlendSpace1D(_)) => sender
+                        .send(Message::do_scene_command(
+                            SetBlendSpace1DPointPositionCommand {
+                                node_handle: absm_node_handle,
+                                handle: self.pose_node,
+                                layer_index,
+                                index: *index,
+                                value: position.x,
+                            },
+                        ))
+                        .unwrap(),
+                    Some(PoseNode::BlendSpace2D(_)) => sender
+                        .send(Message::do_scene_command(
+                            SetBlendSpace2DPointPositionCommand {
+                                node_handle: absm_node_handle,
+                                handle: self.pose_node,
+                                layer_index,
+                                index: *index,
+                                value: *position,
+                            },
+                        ))
+                        .unwrap(),
+                    _ => (),
+                }
+            }
+        }
+    }
+}
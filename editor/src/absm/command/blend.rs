@@ -5,9 +5,10 @@ use crate::{
 use fyrox::{
     animation::machine::node::{
         blend::{BlendPose, IndexedBlendInput},
+        blendspace::{BlendSpace1DPoint, BlendSpace2DPoint},
         PoseNode,
     },
-    core::pool::Handle,
+    core::{algebra::Vector2, pool::Handle},
     scene::node::Node,
 };
 
@@ -50,3 +51,67 @@ define_set_collection_element_command!(
         }
     }
 );
+
+define_push_element_to_collection_command!(AddBlendSpace1DPointCommand<Handle<PoseNode>, BlendSpace1DPoint>(self, context) {
+    let machine = fetch_machine(context, self.node_handle);
+    match &mut machine.layers_mut()[self.layer_index].nodes_mut()[self.handle] {
+        PoseNode::BlendSpace1D(definition) => &mut definition.points,
+        _ => unreachable!(),
+    }
+});
+
+define_push_element_to_collection_command!(AddBlendSpace2DPointCommand<Handle<PoseNode>, BlendSpace2DPoint>(self, context) {
+    let machine = fetch_machine(context, self.node_handle);
+    match &mut machine.layers_mut()[self.layer_index].nodes_mut()[self.handle] {
+        PoseNode::BlendSpace2D(definition) => &mut definition.points,
+        _ => unreachable!(),
+    }
+});
+
+define_set_collection_element_command!(
+    SetBlendSpace1DPointPoseSourceCommand<Handle<PoseNode>, Handle<PoseNode>>(self, context) {
+        let machine = fetch_machine(context, self.node_handle);
+        match machine.layers_mut()[self.layer_index].nodes_mut()[self.handle] {
+            PoseNode::BlendSpace1D(ref mut definition) => {
+                &mut definition.points[self.index].pose_source
+            }
+            _ => unreachable!(),
+        }
+    }
+);
+
+define_set_collection_element_command!(
+    SetBlendSpace2DPointPoseSourceCommand<Handle<PoseNode>, Handle<PoseNode>>(self, context) {
+        let machine = fetch_machine(context, self.node_handle);
+        match machine.layers_mut()[self.layer_index].nodes_mut()[self.handle] {
+            PoseNode::BlendSpace2D(ref mut definition) => {
+                &mut definition.points[self.index].pose_source
+            }
+            _ => unreachable!(),
+        }
+    }
+);
+
+define_set_collection_element_command!(
+    SetBlendSpace1DPointPositionCommand<Handle<PoseNode>, f32>(self, context) {
+        let machine = fetch_machine(context, self.node_handle);
+        match machine.layers_mut()[self.layer_index].nodes_mut()[self.handle] {
+            PoseNode::BlendSpace1D(ref mut definition) => {
+                &mut definition.points[self.index].position
+            }
+            _ => unreachable!(),
+        }
+    }
+);
+
+define_set_collection_element_command!(
+    SetBlendSpace2DPointPositionCommand<Handle<PoseNode>, Vector2<f32>>(self, context) {
+        let machine = fetch_machine(context, self.node_handle);
+        match machine.layers_mut()[self.layer_index].nodes_mut()[self.handle] {
+            PoseNode::BlendSpace2D(ref mut definition) => {
+                &mut definition.points[self.index].position
+            }
+            _ => unreachable!(),
+        }
+    }
+);
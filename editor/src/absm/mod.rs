@@ -1,6 +1,10 @@
 use crate::{
     absm::{
-        command::blend::{AddInputCommand, AddPoseSourceCommand},
+        blendspace::BlendSpaceEditor,
+        command::blend::{
+            AddBlendSpace1DPointCommand, AddBlendSpace2DPointCommand, AddInputCommand,
+            AddPoseSourceCommand,
+        },
         node::{AbsmNode, AbsmNodeMessage},
         parameter::ParameterPanel,
         selection::AbsmSelection,
@@ -12,7 +16,10 @@ use crate::{
     Message,
 };
 use fyrox::{
-    animation::machine::{BlendPose, Event, IndexedBlendInput, Machine, PoseNode, State},
+    animation::machine::{
+        BlendPose, BlendSpace1DPoint, BlendSpace2DPoint, Event, IndexedBlendInput, Machine,
+        PoseNode, State,
+    },
     core::{color::Color, pool::Handle},
     engine::Engine,
     gui::{
@@ -32,6 +39,7 @@ use fyrox::{
 };
 use std::sync::mpsc::Sender;
 
+mod blendspace;
 mod canvas;
 pub mod command;
 mod connection;
@@ -87,6 +95,7 @@ pub struct AbsmEditor {
     state_graph_viewer: StateGraphViewer,
     state_viewer: StateViewer,
     parameter_panel: ParameterPanel,
+    blend_space_editor: BlendSpaceEditor,
     prev_absm: Handle<Node>,
     toolbar: Toolbar,
     preview_mode_data: Option<PreviewModeData>,
@@ -97,6 +106,7 @@ impl AbsmEditor {
         let state_graph_viewer = StateGraphViewer::new(ctx);
         let state_viewer = StateViewer::new(ctx);
         let parameter_panel = ParameterPanel::new(ctx, sender);
+        let blend_space_editor = BlendSpaceEditor::new(ctx);
 
         let docking_manager = DockingManagerBuilder::new(
             WidgetBuilder::new().on_row(1).with_child(
@@ -105,7 +115,21 @@ impl AbsmEditor {
                         splitter: 0.3,
                         tiles: [
                             TileBuilder::new(WidgetBuilder::new())
-                                .with_content(TileContent::Window(parameter_panel.window))
+                                .with_content(TileContent::VerticalTiles {
+                                    splitter: 0.6,
+                                    tiles: [
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::Window(
+                                                parameter_panel.window,
+                                            ))
+                                            .build(ctx),
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::Window(
+                                                blend_space_editor.window,
+                                            ))
+                                            .build(ctx),
+                                    ],
+                                })
                                 .build(ctx),
                             TileBuilder::new(WidgetBuilder::new())
                                 .with_content(TileContent::HorizontalTiles {
@@ -152,6 +176,7 @@ impl AbsmEditor {
             state_graph_viewer,
             state_viewer,
             parameter_panel,
+            blend_space_editor,
             prev_absm: Default::default(),
             toolbar,
             preview_mode_data: None,
@@ -288,11 +313,20 @@ impl AbsmEditor {
                     .sync_to_model(layer, ui, editor_scene);
                 self.state_viewer
                     .sync_to_model(ui, layer, editor_scene, absm_node, &scene.graph);
+                self.blend_space_editor.sync_to_model(
+                    ui,
+                    layer,
+                    absm_node.machine().parameters(),
+                    &selection,
+                );
+            } else {
+                self.blend_space_editor.clear(ui);
             }
         } else {
             self.parameter_panel.reset(ui);
             self.state_graph_viewer.clear(ui);
             self.state_viewer.clear(ui);
+            self.blend_space_editor.clear(ui);
         }
     }
 
@@ -306,6 +340,22 @@ impl AbsmEditor {
 
     pub fn update(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
         self.handle_machine_events(editor_scene, engine);
+
+        // Parameters could be changed in preview mode, so keep current position in blend space up-to-date.
+        let selection = fetch_selection(&editor_scene.selection);
+        if let Some(absm_node) = engine.scenes[editor_scene.scene]
+            .graph
+            .try_get(selection.absm_node_handle)
+            .and_then(|n| n.query_component_ref::<AnimationBlendingStateMachine>())
+        {
+            if let Some(layer) = absm_node.machine().layers().get(selection.layer) {
+                self.blend_space_editor.update(
+                    &engine.user_interface,
+                    layer,
+                    absm_node.machine().parameters(),
+                );
+            }
+        }
     }
 
     pub fn handle_machine_events(&self, editor_scene: &EditorScene, engine: &mut Engine) {
@@ -378,6 +428,15 @@ impl AbsmEditor {
                 absm_node,
                 self.preview_mode_data.is_some(),
             );
+            if let Some(layer) = absm_node.machine().layers().get(selection.layer) {
+                self.blend_space_editor.handle_ui_message(
+                    message,
+                    sender,
+                    layer,
+                    selection.absm_node_handle,
+                    selection.layer,
+                );
+            }
 
             let action =
                 self.toolbar
@@ -491,6 +550,30 @@ impl AbsmEditor {
                                         )))
                                         .unwrap();
                                 }
+                                PoseNode::BlendSpace1D(_) => {
+                                    sender
+                                        .send(Message::do_scene_command(
+                                            AddBlendSpace1DPointCommand::new(
+                                                selection.absm_node_handle,
+                                                node.model_handle,
+                                                selection.layer,
+                                                BlendSpace1DPoint::default(),
+                                            ),
+                                        ))
+                                        .unwrap();
+                                }
+                                PoseNode::BlendSpace2D(_) => {
+                                    sender
+                                        .send(Message::do_scene_command(
+                                            AddBlendSpace2DPointCommand::new(
+                                                selection.absm_node_handle,
+                                                node.model_handle,
+                                                selection.layer,
+                                                BlendSpace2DPoint::default(),
+                                            ),
+                                        ))
+                                        .unwrap();
+                                }
                             }
                         }
                     }
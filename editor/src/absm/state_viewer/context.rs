@@ -2,7 +2,9 @@ use crate::{
     absm::{
         command::{
             blend::{
-                SetBlendAnimationByIndexInputPoseSourceCommand, SetBlendAnimationsPoseSourceCommand,
+                SetBlendAnimationByIndexInputPoseSourceCommand,
+                SetBlendAnimationsPoseSourceCommand, SetBlendSpace1DPointPoseSourceCommand,
+                SetBlendSpace2DPointPoseSourceCommand,
             },
             AddPoseNodeCommand, DeletePoseNodeCommand, SetStateRootPoseCommand,
         },
@@ -19,8 +21,8 @@ use crate::{
 };
 use fyrox::{
     animation::machine::{
-        node::BasePoseNode, BlendAnimations, BlendAnimationsByIndex, MachineLayer, PlayAnimation,
-        PoseNode, State,
+        node::BasePoseNode, BlendAnimations, BlendAnimationsByIndex, BlendSpace1D, BlendSpace2D,
+        MachineLayer, PlayAnimation, PoseNode, State,
     },
     core::pool::Handle,
     gui::{
@@ -39,6 +41,8 @@ pub struct CanvasContextMenu {
     create_play_animation: Handle<UiNode>,
     create_blend_animations: Handle<UiNode>,
     create_blend_by_index: Handle<UiNode>,
+    create_blend_space_1d: Handle<UiNode>,
+    create_blend_space_2d: Handle<UiNode>,
     pub menu: Handle<UiNode>,
     pub canvas: Handle<UiNode>,
     pub node_context_menu: Handle<UiNode>,
@@ -49,6 +53,8 @@ impl CanvasContextMenu {
         let create_play_animation;
         let create_blend_animations;
         let create_blend_by_index;
+        let create_blend_space_1d;
+        let create_blend_space_2d;
         let menu = PopupBuilder::new(
             WidgetBuilder::new()
                 .with_enabled(false) // Disabled by default.
@@ -68,6 +74,14 @@ impl CanvasContextMenu {
                     .with_child({
                         create_blend_by_index = create_menu_item("Blend By Index", vec![], ctx);
                         create_blend_by_index
+                    })
+                    .with_child({
+                        create_blend_space_1d = create_menu_item("Blend Space 1D", vec![], ctx);
+                        create_blend_space_1d
+                    })
+                    .with_child({
+                        create_blend_space_2d = create_menu_item("Blend Space 2D", vec![], ctx);
+                        create_blend_space_2d
                     }),
             )
             .build(ctx),
@@ -78,6 +92,8 @@ impl CanvasContextMenu {
             create_play_animation,
             create_blend_animations,
             create_blend_by_index,
+            create_blend_space_1d,
+            create_blend_space_2d,
             menu,
             canvas: Default::default(),
             node_context_menu: Default::default(),
@@ -128,6 +144,20 @@ impl CanvasContextMenu {
                     blend_time: Default::default(),
                     output_pose: Default::default(),
                 }))
+            } else if message.destination() == self.create_blend_space_1d {
+                let mut blend_space = BlendSpace1D::default();
+                blend_space.base = BasePoseNode {
+                    position,
+                    parent_state: current_state,
+                };
+                Some(PoseNode::BlendSpace1D(blend_space))
+            } else if message.destination() == self.create_blend_space_2d {
+                let mut blend_space = BlendSpace2D::default();
+                blend_space.base = BasePoseNode {
+                    position,
+                    parent_state: current_state,
+                };
+                Some(PoseNode::BlendSpace2D(blend_space))
             } else {
                 None
             };
@@ -325,6 +355,28 @@ impl ConnectionContextMenu {
                             },
                         ))
                         .unwrap(),
+                    PoseNode::BlendSpace1D(_) => sender
+                        .send(Message::do_scene_command(
+                            SetBlendSpace1DPointPoseSourceCommand {
+                                node_handle: absm_node_handle,
+                                layer_index,
+                                handle: model_handle,
+                                index,
+                                value: Default::default(),
+                            },
+                        ))
+                        .unwrap(),
+                    PoseNode::BlendSpace2D(_) => sender
+                        .send(Message::do_scene_command(
+                            SetBlendSpace2DPointPoseSourceCommand {
+                                node_handle: absm_node_handle,
+                                layer_index,
+                                handle: model_handle,
+                                index,
+                                value: Default::default(),
+                            },
+                        ))
+                        .unwrap(),
                 }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
@@ -3,7 +3,9 @@ use crate::{
         canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
         command::{
             blend::{
-                SetBlendAnimationByIndexInputPoseSourceCommand, SetBlendAnimationsPoseSourceCommand,
+                SetBlendAnimationByIndexInputPoseSourceCommand,
+                SetBlendAnimationsPoseSourceCommand, SetBlendSpace1DPointPoseSourceCommand,
+                SetBlendSpace2DPointPoseSourceCommand,
             },
             MovePoseNodeCommand,
         },
@@ -126,6 +128,12 @@ fn make_pose_node_name(
             "Blend {} Animations By Index",
             blend_animations_by_index.inputs.len()
         ),
+        PoseNode::BlendSpace1D(blend_space) => {
+            format!("Blend Space 1D ({} Animations)", blend_space.points.len())
+        }
+        PoseNode::BlendSpace2D(blend_space) => {
+            format!("Blend Space 2D ({} Animations)", blend_space.points.len())
+        }
     }
 }
 
@@ -338,6 +346,32 @@ impl StateViewer {
                                         ))
                                         .unwrap();
                                 }
+                                PoseNode::BlendSpace1D(_) => {
+                                    sender
+                                        .send(Message::do_scene_command(
+                                            SetBlendSpace1DPointPoseSourceCommand {
+                                                node_handle: absm_node_handle,
+                                                layer_index,
+                                                handle: dest_node,
+                                                index: dest_socket_ref.index,
+                                                value: source_node,
+                                            },
+                                        ))
+                                        .unwrap();
+                                }
+                                PoseNode::BlendSpace2D(_) => {
+                                    sender
+                                        .send(Message::do_scene_command(
+                                            SetBlendSpace2DPointPoseSourceCommand {
+                                                node_handle: absm_node_handle,
+                                                layer_index,
+                                                handle: dest_node,
+                                                index: dest_socket_ref.index,
+                                                value: source_node,
+                                            },
+                                        ))
+                                        .unwrap();
+                                }
                             }
                         }
                         _ => (),
@@ -462,6 +496,12 @@ impl StateViewer {
                                     "Blend Animations By Index",
                                     true,
                                 ),
+                                PoseNode::BlendSpace1D(blend_space) => {
+                                    (blend_space.points.len(), "Blend Space 1D", true)
+                                }
+                                PoseNode::BlendSpace2D(blend_space) => {
+                                    (blend_space.points.len(), "Blend Space 2D", true)
+                                }
                             };
 
                             let node_view = AbsmNodeBuilder::new(
@@ -147,11 +147,11 @@ impl AssetItemBuilder {
                         kind = AssetKind::Texture;
                         Some(into_gui_texture(resource_manager.request_texture(&path)))
                     }
-                    "fbx" | "rgs" => {
+                    "fbx" | "gltf" | "glb" | "obj" | "rgs" => {
                         kind = AssetKind::Model;
                         load_image(include_bytes!("../../resources/embed/model.png"))
                     }
-                    "ogg" | "wav" => {
+                    "ogg" | "wav" | "flac" | "mp3" => {
                         kind = AssetKind::Sound;
                         load_image(include_bytes!("../../resources/embed/sound.png"))
                     }
@@ -397,12 +397,17 @@ impl AssetBrowser {
                                 ext.as_str(),
                                 "rgs"
                                     | "fbx"
+                                    | "gltf"
+                                    | "glb"
+                                    | "obj"
                                     | "jpg"
                                     | "tga"
                                     | "png"
                                     | "bmp"
                                     | "ogg"
                                     | "wav"
+                                    | "flac"
+                                    | "mp3"
                                     | "shader"
                                     | "absm"
                             )
@@ -1,7 +1,8 @@
 use crate::utils::window_content;
 use crate::{
-    scene::commands::effect::AddEffectCommand, ChangeSelectionCommand, EditorScene, GridBuilder,
-    Message, MessageDirection, Mode, SceneCommand, Selection, UserInterface,
+    gui::make_dropdown_list_option, scene::commands::effect::AddEffectCommand,
+    ChangeSelectionCommand, EditorScene, GridBuilder, Message, MessageDirection, Mode,
+    SceneCommand, Selection, UserInterface,
 };
 use fyrox::gui::widget::WidgetMessage;
 use fyrox::{
@@ -11,6 +12,7 @@ use fyrox::{
         border::BorderBuilder,
         button::{ButtonBuilder, ButtonMessage},
         decorator::DecoratorBuilder,
+        dropdown_list::{DropdownList, DropdownListBuilder},
         grid::{Column, Row},
         list_view::{ListView, ListViewBuilder, ListViewMessage},
         message::UiMessage,
@@ -20,9 +22,10 @@ use fyrox::{
         window::{WindowBuilder, WindowTitle},
         Orientation, Thickness, UiNode,
     },
-    scene::sound::effect::{BaseEffectBuilder, Effect, ReverbEffectBuilder},
+    scene::sound::effect::Effect,
 };
-use std::{cmp::Ordering, rc::Rc, sync::mpsc::Sender};
+use std::{cmp::Ordering, rc::Rc, str::FromStr, sync::mpsc::Sender};
+use strum::VariantNames;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EffectSelection {
@@ -42,6 +45,7 @@ impl EffectSelection {
 pub struct AudioPanel {
     pub window: Handle<UiNode>,
     edit_context: Handle<UiNode>,
+    effect_kind: Handle<UiNode>,
     add_effect: Handle<UiNode>,
     effects: Handle<UiNode>,
 }
@@ -57,6 +61,7 @@ impl AudioPanel {
         let ctx = &mut engine.user_interface.build_ctx();
 
         let edit_context;
+        let effect_kind;
         let add_effect;
         let effects;
         let window = WindowBuilder::new(WidgetBuilder::new())
@@ -72,13 +77,28 @@ impl AudioPanel {
                             StackPanelBuilder::new(
                                 WidgetBuilder::new()
                                     .on_row(1)
+                                    .with_child({
+                                        effect_kind = DropdownListBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_items(
+                                            Effect::VARIANTS
+                                                .iter()
+                                                .map(|v| make_dropdown_list_option(ctx, v))
+                                                .collect(),
+                                        )
+                                        .with_selected(0)
+                                        .build(ctx);
+                                        effect_kind
+                                    })
                                     .with_child({
                                         add_effect = ButtonBuilder::new(
                                             WidgetBuilder::new()
                                                 .with_margin(Thickness::uniform(1.0)),
                                         )
-                                        // TODO: Add selector when there's more effects.
-                                        .with_text("Add Reverb")
+                                        .with_text("Add Effect")
                                         .build(ctx);
                                         add_effect
                                     })
@@ -107,6 +127,7 @@ impl AudioPanel {
         Self {
             window,
             effects,
+            effect_kind,
             add_effect,
             edit_context,
         }
@@ -130,16 +151,23 @@ impl AudioPanel {
                     )))
                     .unwrap();
             } else if message.destination() == self.add_effect {
-                sender
-                    .send(Message::DoSceneCommand(SceneCommand::new(
-                        AddEffectCommand::new(
-                            ReverbEffectBuilder::new(
-                                BaseEffectBuilder::new().with_name("Reverb".to_owned()),
-                            )
-                            .build_effect(),
-                        ),
-                    )))
-                    .unwrap()
+                let selection = engine
+                    .user_interface
+                    .node(self.effect_kind)
+                    .cast::<DropdownList>()
+                    .expect("Must be DropdownList")
+                    .selection();
+
+                if let Some(name) = selection.and_then(|i| Effect::VARIANTS.get(i)) {
+                    let mut effect = Effect::from_str(name).expect("Must be valid variant");
+                    effect.set_name(name);
+
+                    sender
+                        .send(Message::DoSceneCommand(SceneCommand::new(
+                            AddEffectCommand::new(effect),
+                        )))
+                        .unwrap()
+                }
             }
         } else if let Some(ListViewMessage::SelectionChanged(Some(effect_index))) = message.data() {
             if message.destination() == self.effects
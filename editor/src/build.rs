@@ -1,5 +1,10 @@
+//! Building of the game: the editor builds the `executor` package of the project before running the
+//! game, optionally packing the assets of the game into an asset pack (see [`fyrox::asset::pack`]).
+//! The same could be done from the command line, see [`handle_command_line`].
+
 use crate::Message;
 use fyrox::{
+    asset::pack::{AssetPackError, AssetPackWriter},
     core::{parking_lot::Mutex, pool::Handle},
     gui::{
         border::BorderBuilder,
@@ -17,14 +22,83 @@ use fyrox::{
 };
 use std::{
     io::{BufRead, BufReader},
+    path::{Path, PathBuf},
     process::ChildStdout,
     sync::{
         atomic::{AtomicBool, Ordering},
-        mpsc::Sender,
+        mpsc::{self, Receiver, Sender, TryRecvError},
         Arc,
     },
 };
 
+/// Path of the asset pack that is created when the game is built with packed assets in the editor.
+/// The path is relative to the project root, the executor mounts the pack by `--pack` option.
+pub const ASSET_PACK_PATH: &str = "assets.pack";
+
+/// Packs every scene of the project at the given path along with the assets used by the scenes into
+/// the asset pack at the `out` path. Returns amount of packed files.
+pub fn pack_assets<P, O>(root: P, out: O) -> Result<usize, AssetPackError>
+where
+    P: AsRef<Path>,
+    O: AsRef<Path>,
+{
+    let mut writer = AssetPackWriter::new();
+    writer.add_project(root)?;
+    writer.save(out)?;
+    Ok(writer.paths().count())
+}
+
+/// Handles `build [--release] [--pack <out>]` command line arguments: builds the `executor` package of
+/// the project in current working directory and packs the assets of the project into the asset pack at
+/// the `out` path (see [`pack_assets`]), if the option is specified. Returns `false` if there's no such
+/// command in the arguments, so the editor could be started as usual. The process exits with non-zero
+/// code if the build or the packing has failed.
+pub fn handle_command_line() -> bool {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if args.first().map(|a| a.as_str()) != Some("build") {
+        return false;
+    }
+
+    let mut process = std::process::Command::new("cargo");
+    process.arg("build").arg("--package").arg("executor");
+    if args.iter().any(|a| a == "--release") {
+        process.arg("--release");
+    }
+
+    match process.status() {
+        Ok(status) if status.success() => (),
+        Ok(status) => {
+            eprintln!("Failed to build the game: {}", status);
+            std::process::exit(status.code().unwrap_or(1));
+        }
+        Err(e) => {
+            eprintln!("Failed to build the game: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(index) = args.iter().position(|a| a == "--pack") {
+        let out = match args.get(index + 1) {
+            Some(out) => out,
+            None => {
+                eprintln!("`--pack` option requires a path of the asset pack!");
+                std::process::exit(1);
+            }
+        };
+
+        match pack_assets(".", out) {
+            Ok(count) => println!("{} asset(s) were packed into {}", count, out),
+            Err(e) => {
+                eprintln!("Failed to pack assets: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    true
+}
+
 pub struct BuildWindow {
     window: Handle<UiNode>,
     active: Arc<AtomicBool>,
@@ -32,6 +106,7 @@ pub struct BuildWindow {
     log: Arc<Mutex<String>>,
     log_text: Handle<UiNode>,
     stop: Handle<UiNode>,
+    packing: Option<Receiver<Result<usize, AssetPackError>>>,
 }
 
 impl BuildWindow {
@@ -102,6 +177,7 @@ impl BuildWindow {
             active: Arc::new(AtomicBool::new(false)),
             changed: Arc::new(AtomicBool::new(false)),
             stop,
+            packing: None,
         }
     }
 
@@ -126,7 +202,52 @@ impl BuildWindow {
         });
     }
 
+    /// Packs the assets of the project in a separate thread, see [`pack_assets`]. The result is
+    /// available via [`Self::finish_packing`].
+    pub fn begin_packing(&mut self, root: PathBuf, out: PathBuf) {
+        let (sender, receiver) = mpsc::channel();
+        let log = self.log.clone();
+        let log_changed = self.changed.clone();
+        std::thread::spawn(move || {
+            let result = pack_assets(&root, &out);
+            match result {
+                Ok(count) => log.lock().push_str(&format!(
+                    "{} asset(s) were packed into {}\n",
+                    count,
+                    out.display()
+                )),
+                Err(ref e) => log
+                    .lock()
+                    .push_str(&format!("Failed to pack assets: {}\n", e)),
+            }
+            log_changed.store(true, Ordering::SeqCst);
+            // The receiver is gone if the build was stopped.
+            let _ = sender.send(result);
+        });
+        self.packing = Some(receiver);
+    }
+
+    /// Returns `None` while the assets are being packed, otherwise returns the result of the packing.
+    /// Returns `Some(Ok(0))` if there was no packing.
+    pub fn finish_packing(&mut self) -> Option<Result<usize, AssetPackError>> {
+        let receiver = match self.packing.as_ref() {
+            Some(receiver) => receiver,
+            None => return Some(Ok(0)),
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(AssetPackError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "the packing was interrupted",
+            ))),
+        };
+        self.packing = None;
+        Some(result)
+    }
+
     pub fn reset(&mut self, ui: &UserInterface) {
+        self.packing = None;
         self.active.store(false, Ordering::SeqCst);
         self.changed.store(false, Ordering::SeqCst);
         self.log.lock().clear();
@@ -18,7 +18,8 @@ use fyrox::animation::machine::{PoseNode, State};
 use fyrox::{
     animation::{
         machine::{
-            node::BasePoseNode, BlendAnimations, BlendAnimationsByIndex, BlendPose,
+            node::BasePoseNode, BlendAnimations, BlendAnimationsByIndex, BlendPose, BlendSpace1D,
+            BlendSpace1DPoint, BlendSpace2D, BlendSpace2DPoint, IkChain, IkSolver,
             IndexedBlendInput, Machine, PlayAnimation, PoseWeight,
         },
         AnimationContainer,
@@ -53,6 +54,7 @@ use fyrox::{
             ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection, Projection,
             SkyBox,
         },
+        character_controller::CharacterAutostep,
         collider::{
             BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexPolyhedronShape,
             CuboidShape, CylinderShape, GeometrySource, HeightfieldShape, InteractionGroups,
@@ -77,15 +79,21 @@ use fyrox::{
             },
             EmitterWrapper,
         },
+        ragdoll::Limb,
         rigidbody::RigidBodyType,
         sound::{
             self,
-            effect::{BaseEffect, Effect, EffectInput, ReverbEffect},
+            effect::{
+                BaseEffect, ChorusEffect, CompressorEffect, DelayEffect, DistortionEffect, Effect,
+                EffectInput, EqualizerBand, EqualizerBandKind, EqualizerEffect, FlangerEffect,
+                ReverbEffect,
+            },
             Biquad, DistanceModel, SoundBufferResource, SoundBufferResourceLoadError,
             SoundBufferState, Status,
         },
         terrain::Layer,
         transform::Transform,
+        vehicle::Wheel,
     },
 };
 use std::{rc::Rc, sync::mpsc::Sender};
@@ -218,15 +226,37 @@ pub fn make_property_editors_container(
     container.register_inheritable_inspectable::<RevoluteJoint>();
     container.register_inheritable_inspectable::<PrismaticJoint>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
+    container.register_inheritable_inspectable::<RopeJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::RopeJoint>();
+    container.register_inheritable_inspectable::<GenericJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::GenericJoint>();
+    container.register_inheritable_inspectable::<JointAxisParams>();
+    container.register_inheritable_inspectable::<JointMotor>();
 
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseEffect>();
     container.register_inheritable_inspectable::<BaseLight>();
+    container.register_inheritable_inspectable::<fyrox::scene::rigidbody::RigidBody>();
+    container.register_inheritable_inspectable::<dim2::rigidbody::RigidBody>();
+    container.register_inheritable_inspectable::<CharacterAutostep>();
+    container.register_inheritable_inspectable::<Limb>();
+    container.register_inheritable_vec_collection::<Limb>();
+    container.register_inheritable_inspectable::<Wheel>();
+    container.register_inheritable_vec_collection::<Wheel>();
 
     container.register_inheritable_enum::<Effect, _>();
     container.register_inheritable_enum::<Emitter, _>();
 
     container.register_inheritable_inspectable::<ReverbEffect>();
+    container.register_inheritable_inspectable::<ChorusEffect>();
+    container.register_inheritable_inspectable::<FlangerEffect>();
+    container.register_inheritable_inspectable::<DelayEffect>();
+    container.register_inheritable_inspectable::<DistortionEffect>();
+    container.register_inheritable_inspectable::<CompressorEffect>();
+    container.register_inheritable_inspectable::<EqualizerEffect>();
+    container.register_inheritable_inspectable::<EqualizerBand>();
+    container.register_inheritable_vec_collection::<EqualizerBand>();
+    container.register_inheritable_enum::<EqualizerBandKind, _>();
     container.register_inheritable_inspectable::<Biquad>();
     container.register_inheritable_inspectable::<BaseEmitter>();
     container.register_inheritable_inspectable::<SphereEmitter>();
@@ -240,6 +270,8 @@ pub fn make_property_editors_container(
     container.register_inheritable_option::<ColorGradingLut>();
     container.register_inheritable_option::<Biquad>();
     container.register_inheritable_option::<SkyBox>();
+    container.register_inheritable_option::<CharacterAutostep>();
+    container.register_inheritable_option::<JointMotor>();
 
     container.register_inheritable_inspectable::<SkyBox>();
 
@@ -293,9 +325,18 @@ pub fn make_property_editors_container(
     container.insert(InspectablePropertyEditorDefinition::<BlendPose>::new());
     container.insert(VecCollectionPropertyEditorDefinition::<BlendPose>::new());
     container.insert(EnumPropertyEditorDefinition::<PoseWeight>::new());
+    container.insert(InspectablePropertyEditorDefinition::<IkChain>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<IkChain>::new());
+    container.insert(EnumPropertyEditorDefinition::<IkSolver>::new());
     container.insert(InspectablePropertyEditorDefinition::<BlendAnimationsByIndex>::new());
     container.insert(InspectablePropertyEditorDefinition::<BlendAnimations>::new());
     container.insert(InspectablePropertyEditorDefinition::<PlayAnimation>::new());
+    container.insert(InspectablePropertyEditorDefinition::<BlendSpace1DPoint>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<BlendSpace1DPoint>::new());
+    container.insert(InspectablePropertyEditorDefinition::<BlendSpace1D>::new());
+    container.insert(InspectablePropertyEditorDefinition::<BlendSpace2DPoint>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<BlendSpace2DPoint>::new());
+    container.insert(InspectablePropertyEditorDefinition::<BlendSpace2D>::new());
 
     container.insert(InspectablePropertyEditorDefinition::<Handle<PoseNode>>::new());
     container.insert(InspectablePropertyEditorDefinition::<Handle<State>>::new());
@@ -14,7 +14,7 @@ mod absm;
 mod animation;
 mod asset;
 mod audio;
-mod build;
+pub mod build;
 mod camera;
 mod command;
 mod configurator;
@@ -31,6 +31,7 @@ mod preview;
 mod scene;
 mod scene_viewer;
 mod settings;
+pub mod upgrade;
 mod utils;
 mod world;
 
@@ -77,10 +78,11 @@ use fyrox::{
         algebra::{Matrix3, Vector2},
         color::Color,
         futures::executor::block_on,
+        io,
         pool::{ErasedHandle, Handle},
         scope_profile,
         sstorage::ImmutableString,
-        visitor::Visitor,
+        visitor::{VisitError, Visitor, VisitorFormat},
     },
     dpi::LogicalSize,
     engine::{resource_manager::ResourceManager, Engine, EngineInitParams, SerializationContext},
@@ -103,7 +105,7 @@ use fyrox::{
     },
     material::SharedMaterial,
     material::{shader::Shader, Material, PropertyValue},
-    plugin::PluginConstructor,
+    plugin::{dynamic::DynamicPluginError, PluginConstructor},
     resource::texture::{CompressionOptions, Texture, TextureKind},
     scene::{
         camera::{Camera, Projection},
@@ -206,6 +208,7 @@ pub enum Message {
     ClearSceneCommandStack,
     SelectionChanged,
     SaveScene(PathBuf),
+    SetSceneFormat(VisitorFormat),
     LoadScene(PathBuf),
     CloseScene,
     SetInteractionMode(InteractionModeKind),
@@ -238,6 +241,7 @@ pub enum Message {
     OpenSaveSceneDialog,
     OpenSaveSceneConfirmationDialog(SaveSceneConfirmationDialogAction),
     SetBuildProfile(BuildProfile),
+    SetPackAssets(bool),
     SaveSelectionAsPrefab(PathBuf),
     SyncNodeHandleName {
         view: Handle<UiNode>,
@@ -480,6 +484,7 @@ pub struct Editor {
     mode: Mode,
     build_window: BuildWindow,
     build_profile: BuildProfile,
+    pack_assets: bool,
     scene_settings: SceneSettingsWindow,
     animation_editor: AnimationEditor,
 }
@@ -779,6 +784,7 @@ impl Editor {
             absm_editor,
             build_window,
             build_profile: BuildProfile::Debug,
+            pack_assets: false,
             scene_settings,
         };
 
@@ -853,7 +859,7 @@ impl Editor {
         }
     }
 
-    fn set_scene(&mut self, mut scene: Scene, path: Option<PathBuf>) {
+    fn set_scene(&mut self, mut scene: Scene, path: Option<PathBuf>, format: VisitorFormat) {
         // Discard previous scene.
         if let Some(previous_editor_scene) = self.scene.as_ref() {
             self.engine.scenes.remove(previous_editor_scene.scene);
@@ -871,8 +877,13 @@ impl Editor {
         self.scene_viewer
             .set_render_target(&self.engine.user_interface, scene.render_target.clone());
 
-        let editor_scene =
-            EditorScene::from_native_scene(scene, &mut self.engine, path.clone(), &self.settings);
+        let editor_scene = EditorScene::from_native_scene(
+            scene,
+            &mut self.engine,
+            path.clone(),
+            format,
+            &self.settings,
+        );
 
         self.interaction_modes = vec![
             Box::new(SelectInteractionMode::new(
@@ -1254,6 +1265,10 @@ impl Editor {
 
                 process.arg("--").arg("--override-scene").arg(path);
 
+                if self.pack_assets {
+                    process.arg("--pack").arg(build::ASSET_PACK_PATH);
+                }
+
                 match process.spawn() {
                     Ok(mut process) => {
                         let active = Arc::new(AtomicBool::new(true));
@@ -1286,7 +1301,7 @@ impl Editor {
     fn set_build_mode(&mut self) {
         if let Mode::Edit = self.mode {
             if let Some(scene) = self.scene.as_ref() {
-                if scene.path.is_some() {
+                if let Some(path) = scene.path.clone() {
                     let mut process = std::process::Command::new("cargo");
                     process
                         .stdout(Stdio::piped())
@@ -1305,6 +1320,13 @@ impl Editor {
                                 &self.engine.user_interface,
                             );
 
+                            if self.pack_assets {
+                                // The pack must contain the latest version of the scene.
+                                self.save_current_scene(path);
+                                self.build_window
+                                    .begin_packing(".".into(), build::ASSET_PACK_PATH.into());
+                            }
+
                             self.mode = Mode::Build { process };
 
                             self.on_mode_changed();
@@ -1519,17 +1541,21 @@ impl Editor {
 
     fn load_scene(&mut self, scene_path: PathBuf) {
         let engine = &mut self.engine;
-        let result = {
-            block_on(SceneLoader::from_file(
-                &scene_path,
-                engine.serialization_context.clone(),
-            ))
-        };
+        // The data is loaded once, it is used to detect the format of the scene (the scene will be
+        // saved in the same format) and to load the scene itself.
+        let result = block_on(io::load_file(&scene_path))
+            .map_err(VisitError::from)
+            .and_then(|data| {
+                let format = VisitorFormat::detect(&data).unwrap_or_default();
+                let mut visitor = Visitor::load_from_memory(data)?;
+                SceneLoader::load("Scene", engine.serialization_context.clone(), &mut visitor)
+                    .map(|loader| (loader, format))
+            });
         match result {
-            Ok(loader) => {
+            Ok((loader, format)) => {
                 let scene = block_on(loader.finish(engine.resource_manager.clone()));
 
-                self.set_scene(scene, Some(scene_path));
+                self.set_scene(scene, Some(scene_path), format);
             }
             Err(e) => {
                 Log::err(e.to_string());
@@ -1577,7 +1603,7 @@ impl Editor {
 
         scene.ambient_lighting_color = Color::opaque(200, 200, 200);
 
-        self.set_scene(scene, None);
+        self.set_scene(scene, None, Default::default());
     }
 
     fn configure(&mut self, working_directory: PathBuf) {
@@ -1702,17 +1728,32 @@ impl Editor {
                 match process.try_wait() {
                     Ok(status) => {
                         if let Some(status) = status {
-                            self.build_window.reset(&self.engine.user_interface);
-
                             // https://doc.rust-lang.org/cargo/commands/cargo-build.html#exit-status
                             let err_code = 101;
                             let code = status.code().unwrap_or(err_code);
                             if code == err_code {
+                                self.build_window.reset(&self.engine.user_interface);
+
                                 Log::info("Failed to build the game!");
                                 self.mode = Mode::Edit;
                                 self.on_mode_changed();
                             } else {
-                                self.set_play_mode();
+                                // Wait until the assets are packed, if needed.
+                                match self.build_window.finish_packing() {
+                                    Some(Ok(_)) => {
+                                        self.build_window.reset(&self.engine.user_interface);
+
+                                        self.set_play_mode();
+                                    }
+                                    Some(Err(e)) => {
+                                        self.build_window.reset(&self.engine.user_interface);
+
+                                        Log::err(format!("Failed to pack assets: {}", e));
+                                        self.mode = Mode::Edit;
+                                        self.on_mode_changed();
+                                    }
+                                    None => (),
+                                }
                             }
                         }
                     }
@@ -1782,6 +1823,11 @@ impl Editor {
                         self.world_viewer.sync_selection = true;
                     }
                     Message::SaveScene(path) => self.save_current_scene(path),
+                    Message::SetSceneFormat(format) => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.format = format;
+                        }
+                    }
                     Message::LoadScene(scene_path) => {
                         self.load_scene(scene_path);
                         needs_sync = true;
@@ -1852,6 +1898,9 @@ impl Editor {
                     Message::SetBuildProfile(profile) => {
                         self.build_profile = profile;
                     }
+                    Message::SetPackAssets(pack_assets) => {
+                        self.pack_assets = pack_assets;
+                    }
                     Message::SaveSelectionAsPrefab(path) => {
                         self.try_save_selection_as_prefab(path);
                     }
@@ -1990,6 +2039,20 @@ impl Editor {
         self.engine.add_plugin_constructor(plugin)
     }
 
+    /// Loads game plugin from a dynamic library. If `reload_when_changed` is `true`, the plugin will be
+    /// reloaded automatically when the library is rebuilt, so script changes can be seen without restarting
+    /// the editor.
+    pub fn add_dynamic_game_plugin<P>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), DynamicPluginError>
+    where
+        P: AsRef<Path>,
+    {
+        self.engine.add_dynamic_plugin(path, reload_when_changed)
+    }
+
     pub fn run(mut self, event_loop: EventLoop<()>) -> ! {
         event_loop.run(move |event, _, control_flow| match event {
             Event::MainEventsCleared => {
@@ -2126,6 +2189,14 @@ fn update(editor: &mut Editor, control_flow: &mut ControlFlow) {
         }
     }
 
+    let interpolation_alpha = editor.game_loop_data.lag / FIXED_TIMESTEP;
+    editor.engine.update_frame(
+        elapsed,
+        interpolation_alpha,
+        control_flow,
+        &mut editor.game_loop_data.lag,
+    );
+
     let window = editor.engine.get_window();
     window.set_cursor_icon(translate_cursor_icon(editor.engine.user_interface.cursor()));
     window.request_redraw();
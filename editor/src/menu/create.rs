@@ -4,8 +4,8 @@ use crate::{
         animation::AnimationMenu, create_menu_item, create_root_menu_item, dim2::Dim2Menu,
         physics::PhysicsMenu, physics2d::Physics2dMenu,
     },
-    scene::commands::graph::AddNodeCommand,
-    Message, Mode,
+    scene::{commands::graph::AddNodeCommand, EditorScene},
+    GameEngine, Message, Mode,
 };
 use fyrox::{
     core::{algebra::Matrix4, pool::Handle},
@@ -17,6 +17,7 @@ use fyrox::{
         base::BaseBuilder,
         camera::CameraBuilder,
         decal::DecalBuilder,
+        graph::Graph,
         light::{
             directional::DirectionalLightBuilder, point::PointLightBuilder, spot::SpotLightBuilder,
             BaseLightBuilder,
@@ -56,13 +57,20 @@ impl CreateEntityRootMenu {
         &mut self,
         message: &UiMessage,
         sender: &Sender<Message>,
-        parent: Handle<Node>,
+        editor_scene: &EditorScene,
+        engine: &GameEngine,
     ) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
         if let Some(node) = self.sub_menus.handle_ui_message(message) {
             sender
-                .send(Message::do_scene_command(AddNodeCommand::new(node, parent)))
+                .send(Message::do_scene_command(AddNodeCommand::new(
+                    node,
+                    graph.get_root(),
+                )))
                 .unwrap();
         }
+        self.sub_menus
+            .handle_scene_message(message, editor_scene, graph, sender);
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
@@ -237,6 +245,17 @@ impl CreateEntityMenu {
         )
     }
 
+    pub fn handle_scene_message(
+        &self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        graph: &Graph,
+        sender: &Sender<Message>,
+    ) {
+        self.physics_menu
+            .handle_scene_message(message, editor_scene, graph, sender);
+    }
+
     pub fn handle_ui_message(&mut self, message: &UiMessage) -> Option<Node> {
         self.physics_menu
             .handle_ui_message(message)
@@ -6,7 +6,7 @@ use crate::{
     GameEngine, Message, Mode, Panels, SaveSceneConfirmationDialogAction,
 };
 use fyrox::{
-    core::pool::Handle,
+    core::{pool::Handle, visitor::VisitorFormat},
     gui::{
         file_browser::{FileSelectorBuilder, FileSelectorMessage},
         menu::MenuItemMessage,
@@ -24,6 +24,7 @@ pub struct FileMenu {
     new_scene: Handle<UiNode>,
     pub save: Handle<UiNode>,
     pub save_as: Handle<UiNode>,
+    pub save_as_text: Handle<UiNode>,
     load: Handle<UiNode>,
     pub close_scene: Handle<UiNode>,
     exit: Handle<UiNode>,
@@ -36,6 +37,9 @@ pub struct FileMenu {
     pub recent_files_container: Handle<UiNode>,
     pub recent_files: Vec<Handle<UiNode>>,
     pub open_scene_settings: Handle<UiNode>,
+    // Format that will be assigned to the scene when the save file selector commits. `None`
+    // means that the scene will be saved in its current format.
+    save_format: Option<VisitorFormat>,
 }
 
 fn make_recent_files_items(
@@ -54,6 +58,7 @@ impl FileMenu {
         let new_scene;
         let save;
         let save_as;
+        let save_as_text;
         let close_scene;
         let load;
         let open_settings;
@@ -91,6 +96,10 @@ impl FileMenu {
                         create_menu_item_shortcut("Save Scene As...", "Ctrl+Shift+S", vec![], ctx);
                     save_as
                 },
+                {
+                    save_as_text = create_menu_item("Save Scene As Text...", vec![], ctx);
+                    save_as_text
+                },
                 {
                     load = create_menu_item_shortcut("Load Scene...", "Ctrl+L", vec![], ctx);
                     load
@@ -141,6 +150,7 @@ impl FileMenu {
             new_scene,
             save,
             save_as,
+            save_as_text,
             close_scene,
             load,
             exit,
@@ -151,6 +161,7 @@ impl FileMenu {
             recent_files_container,
             recent_files,
             open_scene_settings,
+            save_format: None,
         }
     }
 
@@ -176,7 +187,8 @@ impl FileMenu {
         ));
     }
 
-    pub fn open_save_file_selector(&self, ui: &mut UserInterface) {
+    pub fn open_save_file_selector(&mut self, ui: &mut UserInterface) {
+        self.save_format = None;
         ui.send_message(WindowMessage::open_modal(
             self.save_file_selector,
             MessageDirection::ToWidget,
@@ -203,6 +215,9 @@ impl FileMenu {
 
         if let Some(FileSelectorMessage::Commit(path)) = message.data::<FileSelectorMessage>() {
             if message.destination() == self.save_file_selector {
+                if let Some(format) = self.save_format.take() {
+                    sender.send(Message::SetSceneFormat(format)).unwrap();
+                }
                 sender.send(Message::SaveScene(path.to_owned())).unwrap();
             } else if message.destination() == self.load_file_selector {
                 sender.send(Message::LoadScene(path.to_owned())).unwrap();
@@ -213,6 +228,7 @@ impl FileMenu {
                     sender.send(Message::SaveScene(scene_path.clone())).unwrap();
                 } else {
                     // If scene wasn't saved yet - open Save As window.
+                    self.save_format = None;
                     engine
                         .user_interface
                         .send_message(WindowMessage::open_modal(
@@ -228,7 +244,15 @@ impl FileMenu {
                             std::env::current_dir().unwrap(),
                         ));
                 }
-            } else if message.destination() == self.save_as {
+            } else if message.destination() == self.save_as
+                || message.destination() == self.save_as_text
+            {
+                self.save_format = Some(if message.destination() == self.save_as_text {
+                    VisitorFormat::Ascii
+                } else {
+                    VisitorFormat::Binary
+                });
+
                 engine
                     .user_interface
                     .send_message(WindowMessage::open_modal(
@@ -136,7 +136,7 @@ impl Menu {
         self.file_menu.open_load_file_selector(ui)
     }
 
-    pub fn open_save_file_selector(&self, ui: &mut UserInterface) {
+    pub fn open_save_file_selector(&mut self, ui: &mut UserInterface) {
         self.file_menu.open_save_file_selector(ui)
     }
 
@@ -147,6 +147,7 @@ impl Menu {
             self.file_menu.close_scene,
             self.file_menu.save,
             self.file_menu.save_as,
+            self.file_menu.save_as_text,
             self.create_entity_menu.menu,
             self.edit_menu.menu,
             self.file_menu.open_scene_settings,
@@ -170,7 +171,8 @@ impl Menu {
             self.create_entity_menu.handle_ui_message(
                 message,
                 &self.message_sender,
-                ctx.engine.scenes[scene.scene].graph.get_root(),
+                scene,
+                ctx.engine,
             );
         }
 
@@ -1,28 +1,54 @@
-use crate::menu::create_menu_item;
+use crate::{
+    menu::create_menu_item,
+    scene::{commands::graph::GenerateRagdollCommand, EditorScene, Selection},
+    Message,
+};
 use fyrox::{
-    core::pool::Handle,
+    core::{algebra::Vector3, pool::Handle},
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
-    scene::{base::BaseBuilder, collider::*, joint::*, node::Node, rigidbody::RigidBodyBuilder},
+    scene::{
+        base::BaseBuilder,
+        character_controller::CharacterControllerBuilder,
+        collider::*,
+        graph::Graph,
+        joint::*,
+        mesh::Mesh,
+        node::Node,
+        rigidbody::RigidBodyBuilder,
+        vehicle::{VehicleBuilder, Wheel},
+    },
+    utils::log::Log,
 };
+use std::sync::mpsc::Sender;
 
 pub struct PhysicsMenu {
     pub menu: Handle<UiNode>,
     create_rigid_body: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
+    create_vehicle: Handle<UiNode>,
     create_revolute_joint: Handle<UiNode>,
     create_ball_joint: Handle<UiNode>,
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
+    create_rope_joint: Handle<UiNode>,
+    create_generic_joint: Handle<UiNode>,
+    create_ragdoll: Handle<UiNode>,
     create_collider: Handle<UiNode>,
 }
 
 impl PhysicsMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_rigid_body;
+        let create_character_controller;
+        let create_vehicle;
         let create_collider;
         let create_revolute_joint;
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_rope_joint;
+        let create_generic_joint;
+        let create_ragdoll;
         let menu = create_menu_item(
             "Physics",
             vec![
@@ -30,6 +56,15 @@ impl PhysicsMenu {
                     create_rigid_body = create_menu_item("Rigid Body", vec![], ctx);
                     create_rigid_body
                 },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
+                {
+                    create_vehicle = create_menu_item("Vehicle", vec![], ctx);
+                    create_vehicle
+                },
                 {
                     create_collider = create_menu_item("Collider", vec![], ctx);
                     create_collider
@@ -50,6 +85,18 @@ impl PhysicsMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_rope_joint = create_menu_item("Rope Joint", vec![], ctx);
+                    create_rope_joint
+                },
+                {
+                    create_generic_joint = create_menu_item("Generic Joint", vec![], ctx);
+                    create_generic_joint
+                },
+                {
+                    create_ragdoll = create_menu_item("Ragdoll (Selected Mesh)", vec![], ctx);
+                    create_ragdoll
+                },
             ],
             ctx,
         );
@@ -57,14 +104,54 @@ impl PhysicsMenu {
         Self {
             menu,
             create_rigid_body,
+            create_character_controller,
+            create_vehicle,
             create_revolute_joint,
             create_ball_joint,
             create_prismatic_joint,
             create_fixed_joint,
+            create_rope_joint,
+            create_generic_joint,
+            create_ragdoll,
             create_collider,
         }
     }
 
+    pub fn handle_scene_message(
+        &self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        graph: &Graph,
+        sender: &Sender<Message>,
+    ) {
+        if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
+            if message.destination() == self.create_ragdoll {
+                let skinned_mesh = if let Selection::Graph(selection) = &editor_scene.selection {
+                    selection.nodes().iter().cloned().find(|handle| {
+                        graph
+                            .try_get(*handle)
+                            .and_then(|n| n.cast::<Mesh>())
+                            .map_or(false, |mesh| {
+                                mesh.surfaces().iter().any(|s| !s.bones().is_empty())
+                            })
+                    })
+                } else {
+                    None
+                };
+
+                if let Some(skinned_mesh) = skinned_mesh {
+                    sender
+                        .send(Message::do_scene_command(GenerateRagdollCommand::new(
+                            skinned_mesh,
+                        )))
+                        .unwrap();
+                } else {
+                    Log::warn("Select a skinned mesh to generate a ragdoll for it!");
+                }
+            }
+        }
+    }
+
     pub fn handle_ui_message(&mut self, message: &UiMessage) -> Option<Node> {
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.create_rigid_body {
@@ -75,6 +162,32 @@ impl PhysicsMenu {
                         .with_params(JointParams::RevoluteJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(RigidBodyBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller"),
+                    ))
+                    .build_node(),
+                )
+            } else if message.destination() == self.create_vehicle {
+                let wheel = |x: f32, z: f32, front: bool| Wheel {
+                    connection_point: Vector3::new(x, 0.0, z),
+                    steering: front,
+                    drive: !front,
+                    ..Default::default()
+                };
+                Some(
+                    VehicleBuilder::new(RigidBodyBuilder::new(
+                        BaseBuilder::new().with_name("Vehicle"),
+                    ))
+                    .with_wheels(vec![
+                        wheel(-0.8, 1.3, true),
+                        wheel(0.8, 1.3, true),
+                        wheel(-0.8, -1.3, false),
+                        wheel(0.8, -1.3, false),
+                    ])
+                    .build_node(),
+                )
             } else if message.destination() == self.create_ball_joint {
                 Some(
                     JointBuilder::new(BaseBuilder::new().with_name("Ball Joint"))
@@ -87,6 +200,18 @@ impl PhysicsMenu {
                         .with_params(JointParams::PrismaticJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_rope_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Rope Joint"))
+                        .with_params(JointParams::RopeJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_generic_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Generic Joint"))
+                        .with_params(JointParams::GenericJoint(Default::default()))
+                        .build_node(),
+                )
             } else if message.destination() == self.create_fixed_joint {
                 Some(
                     JointBuilder::new(BaseBuilder::new().with_name("Fixed Joint"))
@@ -4,7 +4,10 @@ use fyrox::{
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
         base::BaseBuilder,
-        dim2::{collider::*, joint::*, rigidbody::RigidBodyBuilder},
+        dim2::{
+            character_controller::CharacterControllerBuilder, collider::*, joint::*,
+            rigidbody::RigidBodyBuilder,
+        },
         node::Node,
     },
 };
@@ -12,19 +15,25 @@ use fyrox::{
 pub struct Physics2dMenu {
     pub menu: Handle<UiNode>,
     create_rigid_body: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
     create_ball_joint: Handle<UiNode>,
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
+    create_rope_joint: Handle<UiNode>,
+    create_generic_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
 }
 
 impl Physics2dMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_rigid_body;
+        let create_character_controller;
         let create_collider;
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_rope_joint;
+        let create_generic_joint;
         let menu = create_menu_item(
             "Physics 2D",
             vec![
@@ -32,6 +41,11 @@ impl Physics2dMenu {
                     create_rigid_body = create_menu_item("Rigid Body", vec![], ctx);
                     create_rigid_body
                 },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
                 {
                     create_collider = create_menu_item("Collider", vec![], ctx);
                     create_collider
@@ -48,6 +62,14 @@ impl Physics2dMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_rope_joint = create_menu_item("Rope Joint", vec![], ctx);
+                    create_rope_joint
+                },
+                {
+                    create_generic_joint = create_menu_item("Generic Joint", vec![], ctx);
+                    create_generic_joint
+                },
             ],
             ctx,
         );
@@ -55,9 +77,12 @@ impl Physics2dMenu {
         Self {
             menu,
             create_rigid_body,
+            create_character_controller,
             create_ball_joint,
             create_prismatic_joint,
             create_fixed_joint,
+            create_rope_joint,
+            create_generic_joint,
             create_collider,
         }
     }
@@ -69,6 +94,13 @@ impl Physics2dMenu {
                     RigidBodyBuilder::new(BaseBuilder::new().with_name("Rigid Body 2D"))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(RigidBodyBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller 2D"),
+                    ))
+                    .build_node(),
+                )
             } else if message.destination() == self.create_ball_joint {
                 Some(
                     JointBuilder::new(BaseBuilder::new().with_name("Ball Joint 2D"))
@@ -81,6 +113,18 @@ impl Physics2dMenu {
                         .with_params(JointParams::PrismaticJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_rope_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Rope Joint 2D"))
+                        .with_params(JointParams::RopeJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_generic_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Generic Joint 2D"))
+                        .with_params(JointParams::GenericJoint(Default::default()))
+                        .build_node(),
+                )
             } else if message.destination() == self.create_fixed_joint {
                 Some(
                     JointBuilder::new(BaseBuilder::new().with_name("Fixed Joint 2D"))
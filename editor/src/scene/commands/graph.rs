@@ -5,9 +5,10 @@ use fyrox::{
         pool::{Handle, Ticket},
     },
     scene::{
-        base::Base,
+        base::{Base, BaseBuilder},
         graph::{Graph, SubGraph},
         node::Node,
+        ragdoll::{RagdollBuilder, RagdollGenerationSettings},
     },
 };
 
@@ -258,6 +259,55 @@ impl Command for AddModelCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct GenerateRagdollCommand {
+    mesh: Handle<Node>,
+    ragdoll: Handle<Node>,
+    sub_graph: Option<SubGraph>,
+}
+
+impl GenerateRagdollCommand {
+    pub fn new(mesh: Handle<Node>) -> Self {
+        Self {
+            mesh,
+            ragdoll: Default::default(),
+            sub_graph: None,
+        }
+    }
+}
+
+impl Command for GenerateRagdollCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Generate Ragdoll".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        match self.sub_graph.take() {
+            None => {
+                let name = format!("{} Ragdoll", context.scene.graph[self.mesh].name());
+                self.ragdoll = RagdollBuilder::new(BaseBuilder::new().with_name(name)).generate(
+                    &mut context.scene.graph,
+                    self.mesh,
+                    &RagdollGenerationSettings::default(),
+                );
+            }
+            Some(sub_graph) => {
+                self.ragdoll = context.scene.graph.put_sub_graph_back(sub_graph);
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.sub_graph = Some(context.scene.graph.take_reserve_sub_graph(self.ragdoll));
+    }
+
+    fn finalize(&mut self, context: &mut SceneContext) {
+        if let Some(sub_graph) = self.sub_graph.take() {
+            context.scene.graph.forget_sub_graph(sub_graph)
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct DeleteSubGraphCommand {
     sub_graph_root: Handle<Node>,
@@ -18,7 +18,7 @@ use fyrox::{
         color::Color,
         math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, Matrix4Ext, TriangleDefinition},
         pool::Handle,
-        visitor::Visitor,
+        visitor::{Visitor, VisitorFormat},
     },
     engine::Engine,
     scene::{
@@ -51,6 +51,9 @@ pub mod commands;
 pub struct EditorScene {
     pub has_unsaved_changes: bool,
     pub path: Option<PathBuf>,
+    // Format in which the scene will be saved, it is the same as the format of the file
+    // from which the scene was loaded.
+    pub format: VisitorFormat,
     pub scene: Handle<Scene>,
     // Handle to a root for all editor nodes.
     pub editor_objects_root: Handle<Node>,
@@ -87,6 +90,7 @@ impl EditorScene {
         mut scene: Scene,
         engine: &mut Engine,
         path: Option<PathBuf>,
+        format: VisitorFormat,
         settings: &Settings,
     ) -> Self {
         let root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
@@ -129,6 +133,7 @@ impl EditorScene {
 
         EditorScene {
             path,
+            format,
             editor_objects_root: root,
             camera_controller,
             navmeshes,
@@ -206,7 +211,7 @@ impl EditorScene {
 
             let mut visitor = Visitor::new();
             pure_scene.save("Scene", &mut visitor).unwrap();
-            if let Err(e) = visitor.save_binary(&path) {
+            if let Err(e) = visitor.save_with_format(&path, self.format) {
                 Err(format!("Failed to save scene! Reason: {}", e))
             } else {
                 Ok(format!("Scene {} was successfully saved!", path.display()))
@@ -20,19 +20,21 @@ use fyrox::{
         brush::{Brush, GradientPoint},
         button::{Button, ButtonBuilder, ButtonContent, ButtonMessage},
         canvas::CanvasBuilder,
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         decorator::{DecoratorBuilder, DecoratorMessage},
         dropdown_list::DropdownListMessage,
         grid::{Column, GridBuilder, Row},
         image::{ImageBuilder, ImageMessage},
         message::{KeyCode, MessageDirection, MouseButton, UiMessage},
         stack_panel::StackPanelBuilder,
+        text::TextBuilder,
         utils::make_simple_tooltip,
         vec::vec3::{Vec3EditorBuilder, Vec3EditorMessage},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
-        BRUSH_BRIGHT_BLUE, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST, COLOR_DARKEST,
-        COLOR_LIGHTEST,
+        VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+        COLOR_DARKEST, COLOR_LIGHTEST,
     },
     resource::texture::{Texture, TextureState},
     scene::{
@@ -65,6 +67,7 @@ pub struct SceneViewer {
     camera_projection: Handle<UiNode>,
     switch_mode: Handle<UiNode>,
     build_profile: Handle<UiNode>,
+    pack_assets: Handle<UiNode>,
     sender: Sender<Message>,
     interaction_mode_panel: Handle<UiNode>,
     contextual_actions: Handle<UiNode>,
@@ -167,6 +170,7 @@ impl SceneViewer {
         let camera_projection;
         let switch_mode;
         let build_profile;
+        let pack_assets;
 
         let interaction_mode_panel = StackPanelBuilder::new(
             WidgetBuilder::new()
@@ -281,6 +285,28 @@ impl SceneViewer {
                                 .with_selected(0)
                                 .build(ctx);
                                 build_profile
+                            })
+                            .with_child({
+                                pack_assets = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                        ctx,
+                                        "Pack assets of the game into a single asset pack when \
+                                            building the game.",
+                                    )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Pack")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                pack_assets
                             }),
                     )
                     .with_orientation(Orientation::Horizontal)
@@ -396,6 +422,7 @@ impl SceneViewer {
             contextual_actions,
             global_position_display,
             build_profile,
+            pack_assets,
             preview_instance: None,
         }
     }
@@ -488,6 +515,12 @@ impl SceneViewer {
             } else if message.destination() == self.switch_mode {
                 self.sender.send(Message::SwitchMode).unwrap();
             }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.pack_assets
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.sender.send(Message::SetPackAssets(*value)).unwrap();
+            }
         } else if let Some(WidgetMessage::MouseDown { button, .. }) =
             message.data::<WidgetMessage>()
         {
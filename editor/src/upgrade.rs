@@ -0,0 +1,358 @@
+//! Upgrades scene files to the current version of the engine's data format. Every scene is loaded
+//! (old data is migrated on load, see [`Visitor::migrations`]) and then saved back in the same
+//! format (binary or text) it was stored in.
+
+use fyrox::{
+    core::visitor::{Migration, Visit, VisitError, Visitor, VisitorFormat},
+    engine::SerializationContext,
+    plugin::{PluginConstructor, PluginRegistrationContext},
+    scene::Scene,
+    walkdir::WalkDir,
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Result of an upgrade of a single scene file.
+pub struct FileUpgradeReport {
+    /// Path to the scene file.
+    pub path: PathBuf,
+    /// A list of changes that were made to the scene data, or an error if the scene cannot be loaded
+    /// or saved.
+    pub result: Result<Vec<Migration>, VisitError>,
+}
+
+/// Result of an upgrade of every scene file in a directory.
+#[derive(Default)]
+pub struct UpgradeReport {
+    /// Reports for every scene file that was found.
+    pub files: Vec<FileUpgradeReport>,
+}
+
+impl UpgradeReport {
+    /// Returns amount of files that failed to upgrade.
+    pub fn failed_count(&self) -> usize {
+        self.files.iter().filter(|f| f.result.is_err()).count()
+    }
+
+    /// Returns amount of files that had outdated data.
+    pub fn upgraded_count(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.result, Ok(ref migrations) if !migrations.is_empty()))
+            .count()
+    }
+}
+
+impl Display for UpgradeReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for file in self.files.iter() {
+            match file.result {
+                Ok(ref migrations) => {
+                    if migrations.is_empty() {
+                        writeln!(f, "{} - up to date", file.path.display())?;
+                    } else {
+                        writeln!(
+                            f,
+                            "{} - upgraded, {} change(s):",
+                            file.path.display(),
+                            migrations.len()
+                        )?;
+                        for migration in migrations {
+                            writeln!(f, "\t{}", migration)?;
+                        }
+                    }
+                }
+                Err(ref e) => writeln!(f, "{} - failed: {}", file.path.display(), e)?,
+            }
+        }
+
+        write!(
+            f,
+            "{} scene(s) total, {} upgraded, {} failed",
+            self.files.len(),
+            self.upgraded_count(),
+            self.failed_count()
+        )
+    }
+}
+
+/// Upgrades a single scene file. If `dry_run` is `true`, the file won't be overwritten, but the
+/// returned list of changes will be the same.
+pub fn upgrade_scene_file(
+    path: &Path,
+    serialization_context: Arc<SerializationContext>,
+    dry_run: bool,
+) -> Result<Vec<Migration>, VisitError> {
+    let data = std::fs::read(path)?;
+    let format = VisitorFormat::detect(&data).ok_or(VisitError::NotSupportedFormat)?;
+
+    let mut visitor = Visitor::load_from_memory(data)?;
+    visitor.environment = Some(serialization_context);
+    let mut scene = Scene::default();
+    scene.visit("Scene", &mut visitor)?;
+
+    let migrations = visitor.migrations().to_vec();
+
+    if !dry_run && !migrations.is_empty() {
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor)?;
+        visitor.save_with_format(path, format)?;
+    }
+
+    Ok(migrations)
+}
+
+/// Upgrades every scene file (`*.rgs`) in the given directory and its sub-directories. Hidden
+/// directories and `target` directories are ignored.
+pub fn upgrade_scenes(
+    root: &Path,
+    serialization_context: Arc<SerializationContext>,
+    dry_run: bool,
+) -> UpgradeReport {
+    let mut report = UpgradeReport::default();
+
+    let entries = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.depth() == 0 || !(name.starts_with('.') || name == "target")
+        })
+        .flatten();
+
+    for entry in entries {
+        let path = entry.path();
+        if path.is_file() && path.extension().map_or(false, |ext| ext == "rgs") {
+            report.files.push(FileUpgradeReport {
+                path: path.to_owned(),
+                result: upgrade_scene_file(path, serialization_context.clone(), dry_run),
+            });
+        }
+    }
+
+    report
+}
+
+/// Handles `upgrade [dir] [--dry-run]` command line arguments: upgrades every scene in the directory
+/// (current working directory by default) and prints the report. Types of the given plugins are
+/// registered before loading, so scenes that use scripts of the plugins could be upgraded too.
+/// Returns `false` if there's no such command in the arguments, so the editor could be started as
+/// usual. The process exits with non-zero code if some scenes failed to upgrade.
+pub fn handle_command_line(plugin_constructors: &[&dyn PluginConstructor]) -> bool {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if args.first().map(|a| a.as_str()) != Some("upgrade") {
+        return false;
+    }
+
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let root = args
+        .iter()
+        .skip(1)
+        .find(|a| !a.starts_with("--"))
+        .map(|a| a.as_str())
+        .unwrap_or(".");
+
+    let serialization_context = Arc::new(SerializationContext::new());
+    for constructor in plugin_constructors {
+        constructor.register(PluginRegistrationContext {
+            serialization_context: &serialization_context,
+        });
+    }
+
+    let report = upgrade_scenes(Path::new(root), serialization_context, dry_run);
+    println!("{}", report);
+
+    if report.failed_count() > 0 {
+        std::process::exit(1);
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use crate::upgrade::{upgrade_scene_file, upgrade_scenes};
+    use fyrox::{
+        core::{
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::{prelude::*, VisitorFormat},
+        },
+        engine::SerializationContext,
+        impl_component_provider,
+        scene::{base::BaseBuilder, node::TypeUuidProvider, pivot::PivotBuilder, Scene},
+        script::{Script, ScriptTrait},
+    };
+    use std::{path::Path, sync::Arc};
+
+    // Script data in the old format.
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct OldScript {
+        velocity: f32,
+    }
+
+    impl_component_provider!(OldScript);
+
+    impl TypeUuidProvider for OldScript {
+        fn type_uuid() -> Uuid {
+            uuid!("8f0b1c2d-3e4f-4a5b-9c6d-7e8f9a0b1c2d")
+        }
+    }
+
+    impl ScriptTrait for OldScript {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    fn upgrade_script(visitor: &mut Visitor, version: u32) -> VisitResult {
+        if version < 1 {
+            visitor.rename_field("Velocity", "Speed");
+        }
+        Ok(())
+    }
+
+    // The same script in the current format.
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    #[visit(version = 1, upgrade = "upgrade_script")]
+    struct NewScript {
+        speed: f32,
+    }
+
+    impl_component_provider!(NewScript);
+
+    impl TypeUuidProvider for NewScript {
+        fn type_uuid() -> Uuid {
+            OldScript::type_uuid()
+        }
+    }
+
+    impl ScriptTrait for NewScript {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    fn serialization_context<T>() -> Arc<SerializationContext>
+    where
+        T: TypeUuidProvider + ScriptTrait + Default,
+    {
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context.script_constructors.add::<T>("Script");
+        serialization_context
+    }
+
+    fn save_old_scene(path: &Path, format: VisitorFormat) {
+        let mut scene = Scene::new();
+        PivotBuilder::new(BaseBuilder::new().with_script(Script::new(OldScript { velocity: 2.0 })))
+            .build(&mut scene.graph);
+
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        visitor.save_with_format(path, format).unwrap();
+    }
+
+    // Loads a scene using current version of the script, returns the speed stored in the script
+    // and descriptions of the changes that were made to the data on load.
+    fn load_scene(path: &Path) -> (f32, Vec<String>) {
+        let mut visitor = Visitor::load_from_memory(std::fs::read(path).unwrap()).unwrap();
+        visitor.environment = Some(serialization_context::<NewScript>());
+        let mut scene = Scene::default();
+        scene.visit("Scene", &mut visitor).unwrap();
+
+        let speed = scene
+            .graph
+            .linear_iter()
+            .find_map(|node| node.script().and_then(|s| s.cast::<NewScript>()))
+            .unwrap()
+            .speed;
+        let migrations = visitor
+            .migrations()
+            .iter()
+            .map(|m| m.description.clone())
+            .collect();
+
+        (speed, migrations)
+    }
+
+    #[test]
+    fn test_upgrade_scenes() {
+        let root = std::env::temp_dir().join("fyrox_upgrade_scenes_test");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("levels")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+
+        let binary = root.join("binary.rgs");
+        let ascii = root.join("levels").join("ascii.rgs");
+        save_old_scene(&binary, VisitorFormat::Binary);
+        save_old_scene(&ascii, VisitorFormat::Ascii);
+        // Must be ignored.
+        save_old_scene(
+            &root.join("target").join("ignored.rgs"),
+            VisitorFormat::Binary,
+        );
+        std::fs::write(root.join("broken.rgs"), "not a scene").unwrap();
+
+        let (speed, migrations) = load_scene(&binary);
+        assert_eq!(speed, 2.0);
+        assert_eq!(
+            migrations,
+            [
+                "field Velocity renamed to Speed",
+                "upgraded from version 0 to 1"
+            ]
+        );
+
+        // Dry run reports the changes, but does not touch the file.
+        let data = std::fs::read(&binary).unwrap();
+        let migrations = upgrade_scene_file(&binary, serialization_context::<NewScript>(), true)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.description)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            migrations,
+            [
+                "field Velocity renamed to Speed",
+                "upgraded from version 0 to 1"
+            ]
+        );
+        assert_eq!(std::fs::read(&binary).unwrap(), data);
+
+        let report = upgrade_scenes(&root, serialization_context::<NewScript>(), false);
+        assert_eq!(report.files.len(), 3);
+        assert_eq!(report.upgraded_count(), 2);
+        assert_eq!(report.failed_count(), 1);
+        assert!(report
+            .files
+            .iter()
+            .all(|f| f.result.is_err() == f.path.ends_with("broken.rgs")));
+        assert!(report
+            .to_string()
+            .ends_with("3 scene(s) total, 2 upgraded, 1 failed"));
+
+        // The scenes must be saved in the same format with the data in the current format.
+        for (path, format) in [
+            (&binary, VisitorFormat::Binary),
+            (&ascii, VisitorFormat::Ascii),
+        ] {
+            assert_eq!(
+                VisitorFormat::detect(&std::fs::read(path).unwrap()),
+                Some(format)
+            );
+            let (speed, migrations) = load_scene(path);
+            assert_eq!(speed, 2.0);
+            assert!(migrations.is_empty());
+        }
+
+        // Nothing to upgrade anymore.
+        let report = upgrade_scenes(&root, serialization_context::<NewScript>(), false);
+        assert_eq!(report.upgraded_count(), 0);
+        assert_eq!(report.failed_count(), 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
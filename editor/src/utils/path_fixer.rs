@@ -5,7 +5,12 @@ use crate::{make_scene_file_filter, Message};
 use fyrox::{
     asset::ResourceData,
     core::{
-        color::Color, futures::executor::block_on, pool::Handle, replace_slashes, visitor::Visitor,
+        color::Color,
+        futures::executor::block_on,
+        io,
+        pool::Handle,
+        replace_slashes,
+        visitor::{VisitError, Visitor, VisitorFormat},
     },
     engine::{resource_manager::ResourceManager, SerializationContext},
     gui::{
@@ -43,6 +48,7 @@ use std::{
 pub struct PathFixer {
     pub window: Handle<UiNode>,
     scene_path_value: PathBuf,
+    scene_format: VisitorFormat,
     scene_path: Handle<UiNode>,
     scene_selector: Handle<UiNode>,
     load_scene: Handle<UiNode>,
@@ -266,6 +272,7 @@ impl PathFixer {
             new_path_selector,
             auto_fix,
             scene_path_value: Default::default(),
+            scene_format: Default::default(),
         }
     }
 
@@ -311,8 +318,16 @@ impl PathFixer {
         if let Some(FileSelectorMessage::Commit(path)) = message.data::<FileSelectorMessage>() {
             if message.destination() == self.scene_selector {
                 let message;
-                match block_on(Visitor::load_binary(path)) {
-                    Ok(mut visitor) => {
+                let result = block_on(io::load_file(path))
+                    .map_err(VisitError::from)
+                    .and_then(|data| {
+                        // The fixed scene will be saved in the format of the original file.
+                        let format = VisitorFormat::detect(&data).unwrap_or_default();
+                        Ok((format, Visitor::load_from_memory(data)?))
+                    });
+                match result {
+                    Ok((format, mut visitor)) => {
+                        self.scene_format = format;
                         match SceneLoader::load("Scene", serialization_context, &mut visitor) {
                             Err(e) => {
                                 message = format!(
@@ -508,8 +523,9 @@ impl PathFixer {
                     scene
                         .save("Scene", &mut visitor)
                         .expect("Unable to visit a scene!");
+                    // Keep the format of the original file.
                     visitor
-                        .save_binary(&self.scene_path_value)
+                        .save_with_format(&self.scene_path_value, self.scene_format)
                         .expect("Unable to save a scene!");
                 }
 
@@ -131,6 +131,13 @@ impl ItemContextMenu {
     ) {
         scope_profile!();
 
+        self.create_entity_menu.handle_scene_message(
+            message,
+            editor_scene,
+            &engine.scenes[editor_scene.scene].graph,
+            sender,
+        );
+
         if let Selection::Graph(graph_selection) = &editor_scene.selection {
             if let Some(first) = graph_selection.nodes().first() {
                 if let Some(node) = self.create_entity_menu.handle_ui_message(message) {
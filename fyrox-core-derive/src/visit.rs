@@ -27,11 +27,14 @@ fn impl_visit_struct(
         let field_visits =
             utils::create_field_visits(true, field_args.fields.iter(), field_args.style);
 
+        let version_visit = utils::create_version_visit(ty_args);
+
         quote! {
             let mut region = match visitor.enter_region(name) {
                 Ok(x) => x,
                 Err(err) => return Err(err),
             };
+            #version_visit
             #(#field_visits)*
             Ok(())
         }
@@ -185,6 +188,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
         }
     });
 
+    let version_visit = utils::create_version_visit(ty_args);
+
     utils::create_impl(
         ty_args,
         variant_args.iter().flat_map(|v| v.fields.iter()).cloned(),
@@ -194,6 +199,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  Err(err) => return Err(err),
              };
 
+             #version_visit
+
              let mut id = id(self);
              if let Err(err) = id.visit("Id", &mut region) {
                  return Err(err);
@@ -9,6 +9,17 @@ pub struct TypeArgs {
     pub generics: Generics,
     pub data: ast::Data<VariantArgs, FieldArgs>,
     // attrs: Vec<Attribute>
+    // ---
+    /// `#[visit(version = 2)]`
+    ///
+    /// Current version of the type, it is stored in the region of the type
+    pub version: Option<u32>,
+
+    /// `#[visit(upgrade = "path::to::fn")]`
+    ///
+    /// `fn(&mut Visitor, u32) -> VisitResult` that is called before reading fields of data with
+    /// older version, the second argument is the version of the data
+    pub upgrade: Option<Path>,
 }
 
 /// Parsed from struct's or enum variant's field
@@ -37,6 +48,18 @@ pub struct FieldArgs {
     /// Ignore missing field
     #[darling(default)]
     pub optional: bool,
+
+    /// `#[visit(alias = "..")]`
+    ///
+    /// Old name of the field, the field is read using the old name if there's no field with the
+    /// current name. Could be specified multiple times
+    #[darling(multiple)]
+    pub alias: Vec<String>,
+
+    /// `#[visit(default = "path::to::fn")]`
+    ///
+    /// Ignore missing field and initialize it with the value returned by the function
+    pub default: Option<Path>,
 }
 
 #[derive(FromVariant)]
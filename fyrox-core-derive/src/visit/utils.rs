@@ -104,7 +104,7 @@ pub fn create_field_visits<'a>(
                 None => name,
             };
 
-            (ident, name, field.optional)
+            (ident, name, field)
         })
         .collect::<Vec<_>>();
 
@@ -119,10 +119,34 @@ pub fn create_field_visits<'a>(
 
     visit_args
         .iter()
-        .map(|(ident, name, optional)| {
-            if *optional {
+        .map(|(ident, name, field)| {
+            // read the data written under old names
+            let renames = field.alias.iter().map(|alias| {
                 quote! {
-                    #prefix #ident.visit(#name, &mut region).ok();
+                    if !region.rename_field(#alias, #name) {
+                        region.rename_region(#alias, #name);
+                    }
+                }
+            });
+
+            let visit = if let Some(default) = field.default.as_ref() {
+                // enum variant fields are bound by reference
+                let target = if is_struct {
+                    quote!(self.#ident)
+                } else {
+                    quote!(*#ident)
+                };
+
+                quote! {
+                    if #prefix #ident.visit(#name, &mut region).is_err() && region.is_reading() {
+                        #target = #default();
+                        region.report_migration(concat!("field ", #name, " is missing, default value is used"));
+                    }
+                }
+            } else if field.optional {
+                // a missing optional field keeps its current value, the data is not changed
+                quote! {
+                    let _ = #prefix #ident.visit(#name, &mut region);
                 }
             } else {
                 quote! {
@@ -130,7 +154,49 @@ pub fn create_field_visits<'a>(
                         return Err(err);
                     }
                 }
+            };
+
+            quote! {
+                #(#renames)*
+                #visit
             }
         })
         .collect::<Vec<_>>()
 }
+
+/// Reads/writes the version of the type and upgrades older data before visiting the fields
+pub fn create_version_visit(ty_args: &args::TypeArgs) -> TokenStream2 {
+    let version = match ty_args.version {
+        Some(version) => version,
+        None => {
+            return match ty_args.upgrade {
+                Some(ref upgrade) => syn::Error::new_spanned(
+                    upgrade,
+                    "`upgrade` function requires `version` to be specified!",
+                )
+                .to_compile_error(),
+                None => quote!(),
+            };
+        }
+    };
+
+    let upgrade = ty_args.upgrade.as_ref().map(|upgrade| {
+        quote! {
+            if let Err(err) = #upgrade(&mut region, version) {
+                return Err(err);
+            }
+        }
+    });
+
+    quote! {
+        let version = match region.visit_version(#version) {
+            Ok(x) => x,
+            Err(err) => return Err(err),
+        };
+
+        if region.is_reading() && version < #version {
+            #upgrade
+            region.report_migration(format!("upgraded from version {} to {}", version, #version));
+        }
+    }
+}
@@ -25,3 +25,159 @@ fn rename() {
 
     assert_eq!(data, data_default);
 }
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct OldVersion {
+    pub x: f32,
+    pub velocity: f32,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+fn upgrade_from_old_version(visitor: &mut Visitor, version: u32) -> VisitResult {
+    if version < 1 {
+        visitor.rename_field("Velocity", "Speed");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+#[visit(version = 1, upgrade = "upgrade_from_old_version")]
+pub struct NewVersion {
+    #[visit(alias = "X")]
+    pub position: f32,
+    pub speed: f32,
+    #[visit(default = "default_scale")]
+    pub scale: f32,
+}
+
+#[test]
+fn upgrade() {
+    let mut old = OldVersion {
+        x: 1.0,
+        velocity: 2.0,
+    };
+
+    let mut visitor = Visitor::new();
+    old.visit("Data", &mut visitor).unwrap();
+    let data = visitor.save_binary_to_vec().unwrap();
+
+    let mut visitor = Visitor::load_from_memory(data).unwrap();
+    let mut new = NewVersion {
+        position: 0.0,
+        speed: 0.0,
+        scale: 0.0,
+    };
+    new.visit("Data", &mut visitor).unwrap();
+
+    assert_eq!(
+        new,
+        NewVersion {
+            position: 1.0,
+            speed: 2.0,
+            scale: 1.0,
+        }
+    );
+
+    let migrations = visitor
+        .migrations()
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        migrations,
+        [
+            "Data: field Velocity renamed to Speed",
+            "Data: upgraded from version 0 to 1",
+            "Data: field X renamed to Position",
+            "Data: field Scale is missing, default value is used",
+        ]
+    );
+
+    // Data in current version must not be upgraded.
+    let mut visitor = Visitor::new();
+    new.visit("Data", &mut visitor).unwrap();
+    let data = visitor.save_binary_to_vec().unwrap();
+
+    let mut visitor = Visitor::load_from_memory(data).unwrap();
+    let mut loaded = NewVersion {
+        position: 0.0,
+        speed: 0.0,
+        scale: 0.0,
+    };
+    loaded.visit("Data", &mut visitor).unwrap();
+    assert_eq!(loaded, new);
+    assert!(visitor.migrations().is_empty());
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+#[visit(version = 2)]
+pub struct FutureVersion {
+    pub position: f32,
+    pub speed: f32,
+    pub scale: f32,
+}
+
+#[test]
+fn newer_version_is_error() {
+    let mut future = FutureVersion {
+        position: 1.0,
+        speed: 2.0,
+        scale: 3.0,
+    };
+
+    let mut visitor = Visitor::new();
+    future.visit("Data", &mut visitor).unwrap();
+    let data = visitor.save_binary_to_vec().unwrap();
+
+    // Data written by a newer version of the code cannot be read.
+    let mut visitor = Visitor::load_from_memory(data).unwrap();
+    let mut new = NewVersion {
+        position: 0.0,
+        speed: 0.0,
+        scale: 0.0,
+    };
+    match new.visit("Data", &mut visitor) {
+        Err(VisitError::UnsupportedVersion {
+            region,
+            version,
+            current,
+        }) => {
+            assert_eq!(region, "Data");
+            assert_eq!(version, 2);
+            assert_eq!(current, 1);
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct WithoutOptional {
+    pub x: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct WithOptional {
+    pub x: f32,
+    #[visit(optional)]
+    pub y: f32,
+}
+
+#[test]
+fn optional_field_is_not_migration() {
+    let mut old = WithoutOptional { x: 1.0 };
+
+    let mut visitor = Visitor::new();
+    old.visit("Data", &mut visitor).unwrap();
+    let data = visitor.save_binary_to_vec().unwrap();
+
+    // A missing optional field keeps its value and the data is considered up to date.
+    let mut visitor = Visitor::load_from_memory(data).unwrap();
+    let mut new = WithOptional { x: 0.0, y: 2.0 };
+    new.visit("Data", &mut visitor).unwrap();
+
+    assert_eq!(new, WithOptional { x: 1.0, y: 2.0 });
+    assert!(visitor.migrations().is_empty());
+}
@@ -1,3 +1,4 @@
+use crate::vfs;
 use std::io::Error;
 use std::path::Path;
 
@@ -24,7 +25,17 @@ impl From<wasm_bindgen::JsValue> for FileLoadError {
     }
 }
 
+/// Loads the entire file using current file system, see [`crate::vfs`] for more info.
 pub async fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError> {
+    vfs::file_system().load_file(path.as_ref()).await
+}
+
+/// Checks whether the file exists in current file system, see [`crate::vfs`] for more info.
+pub async fn exists<P: AsRef<Path>>(path: P) -> bool {
+    vfs::file_system().exists(path.as_ref()).await
+}
+
+pub(crate) async fn load_native_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError> {
     #[cfg(not(target_arch = "wasm32"))]
     {
         use std::fs::File;
@@ -57,7 +68,7 @@ pub async fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError
     }
 }
 
-pub async fn exists<P: AsRef<Path>>(path: P) -> bool {
+pub(crate) async fn native_file_exists<P: AsRef<Path>>(path: P) -> bool {
     #[cfg(not(target_arch = "wasm32"))]
     {
         path.as_ref().exists()
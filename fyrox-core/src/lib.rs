@@ -42,6 +42,7 @@ pub mod reflect;
 pub mod sparse;
 pub mod sstorage;
 pub mod variable;
+pub mod vfs;
 pub mod visitor;
 
 pub use futures;
@@ -427,6 +427,22 @@ pub fn wrap_angle(angle: f32) -> f32 {
     }
 }
 
+/// Returns an angle that differs from the given `angle` by a multiple of `2π` and is the closest one to
+/// the `previous` angle. It is useful to make a sequence of angles (for example Euler angles of rotation
+/// keys) continuous, so interpolation between them won't take the "long way" around a circle.
+#[inline]
+pub fn unwrap_angle(mut angle: f32, previous: f32) -> f32 {
+    let pi = std::f32::consts::PI;
+
+    while angle - previous > pi {
+        angle -= 2.0 * pi;
+    }
+    while angle - previous < -pi {
+        angle += 2.0 * pi;
+    }
+    angle
+}
+
 /// There are two versions of remainder, the standard `%` operator which does `x - (x/y).trunc()*y` and IEEE remainder which does `x - (x/y).round()*y`.
 #[inline]
 pub fn ieee_remainder(x: f32, y: f32) -> f32 {
@@ -1132,4 +1148,23 @@ mod test {
             println!("{}", angle.update(1.0).angle().to_degrees());
         }
     }
+
+    #[test]
+    fn unwrap_angle() {
+        let approx_eq = |a: f32, b: f32| (a - b).abs() < 1.0e-5;
+
+        assert!(approx_eq(super::unwrap_angle(0.5, 0.0), 0.5));
+        assert!(approx_eq(
+            super::unwrap_angle(-170.0f32.to_radians(), 170.0f32.to_radians()),
+            190.0f32.to_radians()
+        ));
+        assert!(approx_eq(
+            super::unwrap_angle(170.0f32.to_radians(), -170.0f32.to_radians()),
+            -190.0f32.to_radians()
+        ));
+        assert!(approx_eq(
+            super::unwrap_angle(10.0f32.to_radians(), 730.0f32.to_radians()),
+            730.0f32.to_radians()
+        ));
+    }
 }
@@ -168,10 +168,91 @@ pub fn triangulate(vertices: &[Vector3<f32>], out_triangles: &mut Vec<[usize; 3]
     }
 }
 
+fn is_point_inside_circumcircle(
+    point: Vector2<f32>,
+    a: Vector2<f32>,
+    b: Vector2<f32>,
+    c: Vector2<f32>,
+) -> bool {
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    if d.abs() <= f32::EPSILON {
+        // Degenerate triangle has infinite circumcircle.
+        return true;
+    }
+    let (la, lb, lc) = (a.norm_squared(), b.norm_squared(), c.norm_squared());
+    let center = Vector2::new(
+        (la * (b.y - c.y) + lb * (c.y - a.y) + lc * (a.y - b.y)) / d,
+        (la * (c.x - b.x) + lb * (a.x - c.x) + lc * (b.x - a.x)) / d,
+    );
+    (point - center).norm_squared() < (a - center).norm_squared()
+}
+
+///
+/// Triangulates specified set of points using Delaunay triangulation (Bowyer-Watson algorithm).
+/// Unlike [`triangulate`], the points do not form a polygon - the result covers convex hull of
+/// the points and every point is a vertex of some triangle. Less than three points or collinear
+/// points produce no triangles.
+///
+pub fn triangulate_points(points: &[Vector2<f32>], out_triangles: &mut Vec<[usize; 3]>) {
+    out_triangles.clear();
+    if points.len() < 3 {
+        return;
+    }
+
+    let mut min = points[0];
+    let mut max = points[0];
+    for point in points {
+        min = min.inf(point);
+        max = max.sup(point);
+    }
+    let size = (max - min).max().max(f32::EPSILON);
+    let center = (min + max).scale(0.5);
+
+    // Super triangle that contains every point.
+    let count = points.len();
+    let mut vertices = points.to_vec();
+    vertices.push(Vector2::new(center.x - 20.0 * size, center.y - size));
+    vertices.push(Vector2::new(center.x, center.y + 20.0 * size));
+    vertices.push(Vector2::new(center.x + 20.0 * size, center.y - size));
+
+    let mut triangles = vec![[count, count + 1, count + 2]];
+    let mut edges = Vec::new();
+    for (i, point) in points.iter().enumerate() {
+        edges.clear();
+        triangles.retain(|t| {
+            if is_point_inside_circumcircle(*point, vertices[t[0]], vertices[t[1]], vertices[t[2]])
+            {
+                edges.extend_from_slice(&[[t[0], t[1]], [t[1], t[2]], [t[2], t[0]]]);
+                false
+            } else {
+                true
+            }
+        });
+
+        // Edges of the hole that are not shared between removed triangles form new triangles.
+        for (j, edge) in edges.iter().enumerate() {
+            let shared = edges.iter().enumerate().any(|(k, other)| {
+                k != j
+                    && (other[0] == edge[0] && other[1] == edge[1]
+                        || other[0] == edge[1] && other[1] == edge[0])
+            });
+            if !shared {
+                triangles.push([edge[0], edge[1], i]);
+            }
+        }
+    }
+
+    out_triangles.extend(triangles.into_iter().filter(|t| {
+        t.iter().all(|&index| index < count)
+            && math::get_signed_triangle_area(points[t[0]], points[t[1]], points[t[2]]).abs()
+                > f32::EPSILON
+    }));
+}
+
 #[cfg(test)]
 mod test {
-    use crate::algebra::{Point3, Unit, UnitQuaternion, Vector3};
-    use crate::math::triangulator::triangulate;
+    use crate::algebra::{Point3, Unit, UnitQuaternion, Vector2, Vector3};
+    use crate::math::triangulator::{triangulate, triangulate_points};
 
     #[test]
     fn quadrilaterals_triangulation_non_concave() {
@@ -239,4 +320,37 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn points_triangulation() {
+        let mut triangles = Vec::new();
+
+        let square = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 1.0),
+        ];
+        triangulate_points(&square, &mut triangles);
+        assert_eq!(triangles.len(), 2);
+
+        let square_with_center = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 1.0),
+            Vector2::new(0.5, 0.5),
+        ];
+        triangulate_points(&square_with_center, &mut triangles);
+        assert_eq!(triangles.len(), 4);
+        assert!(triangles.iter().all(|t| t.contains(&4)));
+
+        let collinear = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 0.0),
+        ];
+        triangulate_points(&collinear, &mut triangles);
+        assert!(triangles.is_empty());
+    }
 }
@@ -0,0 +1,369 @@
+//! Virtual file system. Every file that is loaded via [`crate::io::load_file`] (and so every resource,
+//! scene or [`crate::visitor::Visitor`] data) is loaded through the current [`FileSystem`], which is
+//! [`NativeFileSystem`] by default. It could be replaced by [`set_file_system`] to load files from
+//! memory ([`MemoryFileSystem`]), archives or from a set of layered providers ([`MountTable`]), for
+//! example to ship a game with packed assets or to let mods override some assets of the game.
+
+use crate::{
+    io::FileLoadError,
+    parking_lot::{Mutex, RwLock},
+};
+use fxhash::FxHashMap;
+use std::{
+    future::Future,
+    path::{Component, Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+};
+
+/// Future type of [`FileSystem`] methods.
+#[cfg(not(target_arch = "wasm32"))]
+pub type FileSystemFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Future type of [`FileSystem`] methods.
+#[cfg(target_arch = "wasm32")]
+pub type FileSystemFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A source of files.
+pub trait FileSystem: Send + Sync {
+    /// Loads the entire file at the given path.
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<Vec<u8>, FileLoadError>>;
+
+    /// Checks whether the file at the given path exists or not.
+    fn exists<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, bool>;
+
+    /// Returns a path of the file in the native file system if the file is stored there. It allows
+    /// to read large files (such as streaming sound buffers) on demand, instead of loading them at
+    /// once.
+    fn native_path(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Normalizes the given path, so it could be used as a key: removes `.` components and resolves
+/// `..` components where possible.
+pub fn normalize_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.as_ref().components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if !matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) || !normalized.pop()
+                {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn not_found(path: &Path) -> FileLoadError {
+    FileLoadError::Io(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("file {} does not exist", path.display()),
+    ))
+}
+
+/// File system of the operating system (or files served by the web server on WebAssembly).
+#[derive(Default, Debug, Clone)]
+pub struct NativeFileSystem {
+    root: PathBuf,
+}
+
+impl NativeFileSystem {
+    /// Creates new file system that uses paths as is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new file system with every path relative to the given root directory.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn full_path(&self, path: &Path) -> PathBuf {
+        if self.root.as_os_str().is_empty() {
+            path.to_path_buf()
+        } else {
+            self.root.join(path)
+        }
+    }
+}
+
+impl FileSystem for NativeFileSystem {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(crate::io::load_native_file(self.full_path(path)))
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, bool> {
+        Box::pin(crate::io::native_file_exists(self.full_path(path)))
+    }
+
+    fn native_path(&self, path: &Path) -> Option<PathBuf> {
+        if cfg!(target_arch = "wasm32") {
+            None
+        } else {
+            let full_path = self.full_path(path);
+            if full_path.is_file() {
+                Some(full_path)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// File system that stores files in memory. It is useful for tests, or for generated data.
+#[derive(Default)]
+pub struct MemoryFileSystem {
+    files: RwLock<FxHashMap<PathBuf, Arc<Vec<u8>>>>,
+}
+
+impl MemoryFileSystem {
+    /// Creates new empty file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file with the given content, the previous content of the file (if any) is replaced.
+    pub fn insert<P: AsRef<Path>>(&self, path: P, data: Vec<u8>) {
+        self.files
+            .write()
+            .insert(normalize_path(path), Arc::new(data));
+    }
+
+    /// Removes the file, returns `true` if the file existed.
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.files.write().remove(&normalize_path(path)).is_some()
+    }
+
+    /// Returns paths of every file in the file system.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files.read().keys().cloned().collect()
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        let result = match self.files.read().get(&normalize_path(path)) {
+            Some(data) => Ok(data.as_ref().clone()),
+            None => Err(not_found(path)),
+        };
+        Box::pin(async move { result })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, bool> {
+        let exists = self.files.read().contains_key(&normalize_path(path));
+        Box::pin(async move { exists })
+    }
+}
+
+struct Mount {
+    point: PathBuf,
+    priority: i32,
+    file_system: Arc<dyn FileSystem>,
+}
+
+/// File system that layers multiple file systems. Every file system is mounted at some path (mount
+/// point) with some priority, a file is loaded from the file system with the highest priority that
+/// has the file. A path inside a mounted file system is relative to its mount point, for example
+/// `data/textures/grass.png` will be loaded as `textures/grass.png` from a file system that is
+/// mounted at `data`. Use an empty path to mount a file system at the root.
+#[derive(Default)]
+pub struct MountTable {
+    mounts: RwLock<Vec<Mount>>,
+}
+
+impl MountTable {
+    /// Creates new empty mount table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts the file system at the given path with the given priority. If multiple file systems
+    /// have the same priority, the one that was mounted last wins.
+    pub fn mount<P: AsRef<Path>>(&self, point: P, priority: i32, file_system: Arc<dyn FileSystem>) {
+        let mut mounts = self.mounts.write();
+        let index = mounts
+            .iter()
+            .position(|m| m.priority <= priority)
+            .unwrap_or(mounts.len());
+        mounts.insert(
+            index,
+            Mount {
+                point: normalize_path(point),
+                priority,
+                file_system,
+            },
+        );
+    }
+
+    /// Unmounts every file system mounted at the given path. Returns amount of unmounted file
+    /// systems.
+    pub fn unmount<P: AsRef<Path>>(&self, point: P) -> usize {
+        let point = normalize_path(point);
+        let mut mounts = self.mounts.write();
+        let count = mounts.len();
+        mounts.retain(|m| m.point != point);
+        count - mounts.len()
+    }
+
+    // Returns file systems that could contain the file along with the path of the file in them,
+    // sorted by priority.
+    fn candidates(&self, path: &Path) -> Vec<(PathBuf, Arc<dyn FileSystem>)> {
+        let path = normalize_path(path);
+        self.mounts
+            .read()
+            .iter()
+            .filter_map(|m| {
+                path.strip_prefix(&m.point)
+                    .ok()
+                    .map(|relative| (relative.to_path_buf(), m.file_system.clone()))
+            })
+            .collect()
+    }
+}
+
+impl FileSystem for MountTable {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        let candidates = self.candidates(path);
+        Box::pin(async move {
+            for (relative, file_system) in candidates {
+                if file_system.exists(&relative).await {
+                    return file_system.load_file(&relative).await;
+                }
+            }
+            Err(not_found(path))
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, bool> {
+        let candidates = self.candidates(path);
+        Box::pin(async move {
+            for (relative, file_system) in candidates {
+                if file_system.exists(&relative).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+
+    fn native_path(&self, path: &Path) -> Option<PathBuf> {
+        // Only the file system with the highest priority could provide the file, otherwise a file
+        // from a file system with lower priority could be read instead.
+        self.candidates(path)
+            .into_iter()
+            .next()
+            .and_then(|(relative, file_system)| file_system.native_path(&relative))
+    }
+}
+
+lazy_static! {
+    static ref FILE_SYSTEM: RwLock<Arc<dyn FileSystem>> =
+        RwLock::new(Arc::new(NativeFileSystem::new()));
+    static ref MOUNT_TABLE: Mutex<Option<Arc<MountTable>>> = Mutex::new(None);
+}
+
+/// Returns current file system.
+pub fn file_system() -> Arc<dyn FileSystem> {
+    FILE_SYSTEM.read().clone()
+}
+
+/// Sets new file system that will be used to load every file.
+pub fn set_file_system(file_system: Arc<dyn FileSystem>) {
+    *FILE_SYSTEM.write() = file_system;
+}
+
+/// Mounts the file system on top of current file system, see [`MountTable::mount`] for more info. If
+/// current file system is not a [`MountTable`] created by this function, it is replaced with a new
+/// one with the previous file system mounted at the root with priority `0`.
+pub fn mount<P: AsRef<Path>>(point: P, priority: i32, file_system: Arc<dyn FileSystem>) {
+    let mut current = FILE_SYSTEM.write();
+    let mut table = MOUNT_TABLE.lock();
+
+    let is_current = table.as_ref().map_or(false, |table| {
+        Arc::as_ptr(table) as *const u8 == Arc::as_ptr(&current) as *const u8
+    });
+
+    if !is_current {
+        let new_table = Arc::new(MountTable::new());
+        new_table.mount("", 0, current.clone());
+        *current = new_table.clone();
+        *table = Some(new_table);
+    }
+
+    if let Some(table) = table.as_ref() {
+        table.mount(point, priority, file_system);
+    }
+}
+
+/// Unmounts every file system that was mounted at the given path by [`mount`], see [`MountTable::unmount`]
+/// for more info. Returns amount of unmounted file systems.
+pub fn unmount<P: AsRef<Path>>(point: P) -> usize {
+    let current = FILE_SYSTEM.read();
+    let table = MOUNT_TABLE.lock();
+
+    match table.as_ref() {
+        Some(table) if Arc::as_ptr(table) as *const u8 == Arc::as_ptr(&current) as *const u8 => {
+            table.unmount(point)
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vfs::{normalize_path, FileSystem, MemoryFileSystem, MountTable};
+    use futures::executor::block_on;
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("./a/b/../c"), PathBuf::from("a/c"));
+        assert_eq!(normalize_path("../a"), PathBuf::from("../a"));
+    }
+
+    #[test]
+    fn test_mount_table() {
+        let base = Arc::new(MemoryFileSystem::new());
+        base.insert("textures/a.png", vec![1]);
+        base.insert("textures/b.png", vec![2]);
+
+        let overlay = Arc::new(MemoryFileSystem::new());
+        overlay.insert("./textures/b.png", vec![3]);
+
+        let table = MountTable::new();
+        table.mount("data", 0, base);
+        table.mount("data", 1, overlay);
+
+        let load = |path: &str| block_on(table.load_file(Path::new(path))).ok();
+        assert_eq!(load("data/textures/a.png"), Some(vec![1]));
+        assert_eq!(load("data/textures/b.png"), Some(vec![3]));
+        assert_eq!(load("textures/a.png"), None);
+        assert!(block_on(table.exists(Path::new("./data/textures/a.png"))));
+
+        assert_eq!(table.unmount("data"), 2);
+        assert_eq!(load("data/textures/a.png"), None);
+    }
+}
@@ -38,6 +38,7 @@ use std::{
     ops::{Deref, DerefMut, Range},
     path::{Path, PathBuf},
     rc::Rc,
+    str::FromStr,
     string::FromUtf8Error,
     sync::{Arc, Mutex, RwLock},
     time::Duration,
@@ -390,6 +391,351 @@ impl FieldKind {
     }
 }
 
+/// Format of the data produced by [`Visitor`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VisitorFormat {
+    /// Compact binary format. It is the fastest one to save and load, but it is opaque to version
+    /// control systems.
+    Binary,
+    /// Human-readable, line-oriented text format. Every field and every region is stored on a separate
+    /// line in the order they were visited, which makes the format diff- and merge-friendly.
+    Ascii,
+}
+
+impl Default for VisitorFormat {
+    fn default() -> Self {
+        Self::Binary
+    }
+}
+
+impl VisitorFormat {
+    /// Tries to detect the format of the given data by its header.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(Visitor::MAGIC.as_bytes()) {
+            Some(Self::Binary)
+        } else if data.starts_with(Visitor::ASCII_MAGIC.as_bytes()) {
+            Some(Self::Ascii)
+        } else {
+            None
+        }
+    }
+
+    /// Tries to detect the format of the given file by its header. The file is loaded using current file
+    /// system, see [`crate::vfs`] for more info. The entire file is loaded, so if the data is already in
+    /// memory, use [`Self::detect`] instead.
+    pub async fn detect_from_file<P: AsRef<Path>>(path: P) -> Option<Self> {
+        Self::detect(&io::load_file(path).await.ok()?)
+    }
+}
+
+fn ascii_quote(string: &str) -> String {
+    let mut out = String::with_capacity(string.len() + 2);
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn ascii_values<T, I>(type_name: &str, values: I) -> String
+where
+    T: Display,
+    I: IntoIterator<Item = T>,
+{
+    let mut out = type_name.to_owned();
+    for value in values {
+        out.push(' ');
+        out += &value.to_string();
+    }
+    out
+}
+
+#[derive(Debug, PartialEq)]
+enum AsciiToken {
+    Quoted(String),
+    Bare(String),
+}
+
+fn tokenize_ascii_line(line: &str) -> Result<Vec<AsciiToken>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut string = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('"') => string.push('"'),
+                        Some('\\') => string.push('\\'),
+                        Some('n') => string.push('\n'),
+                        Some('r') => string.push('\r'),
+                        Some('t') => string.push('\t'),
+                        Some('u') => {
+                            if chars.next() != Some('{') {
+                                return Err("invalid unicode escape sequence".to_owned());
+                            }
+                            let mut code = String::new();
+                            loop {
+                                match chars.next() {
+                                    Some('}') => break,
+                                    Some(c) => code.push(c),
+                                    None => {
+                                        return Err("invalid unicode escape sequence".to_owned())
+                                    }
+                                }
+                            }
+                            let c = u32::from_str_radix(&code, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| "invalid unicode escape sequence".to_owned())?;
+                            string.push(c);
+                        }
+                        _ => return Err("invalid escape sequence".to_owned()),
+                    },
+                    Some(c) => string.push(c),
+                    None => return Err("unterminated string".to_owned()),
+                }
+            }
+            tokens.push(AsciiToken::Quoted(string));
+        } else if c == ':' || c == '{' || c == '}' {
+            chars.next();
+            tokens.push(AsciiToken::Bare(c.to_string()));
+        } else {
+            let mut bare = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' || c == ':' || c == '{' || c == '}' {
+                    break;
+                }
+                bare.push(c);
+                chars.next();
+            }
+            tokens.push(AsciiToken::Bare(bare));
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_ascii_numbers<T: FromStr>(tokens: &[AsciiToken], count: usize) -> Result<Vec<T>, String> {
+    if tokens.len() != count {
+        return Err(format!("expected {} values, got {}", count, tokens.len()));
+    }
+
+    tokens
+        .iter()
+        .map(|token| match token {
+            AsciiToken::Bare(value) => value
+                .parse::<T>()
+                .map_err(|_| format!("invalid value {}", value)),
+            AsciiToken::Quoted(value) => Err(format!("unexpected string {}", value)),
+        })
+        .collect()
+}
+
+fn parse_ascii_string(tokens: &[AsciiToken]) -> Result<&str, String> {
+    match tokens {
+        [AsciiToken::Quoted(string)] => Ok(string),
+        _ => Err("expected a string".to_owned()),
+    }
+}
+
+fn parse_ascii_base64(tokens: &[AsciiToken]) -> Result<Vec<u8>, String> {
+    base64::decode(parse_ascii_string(tokens)?).map_err(|e| e.to_string())
+}
+
+impl FieldKind {
+    fn to_ascii(&self) -> String {
+        match self {
+            Self::Bool(data) => ascii_values("bool", [data]),
+            Self::U8(data) => ascii_values("u8", [data]),
+            Self::I8(data) => ascii_values("i8", [data]),
+            Self::U16(data) => ascii_values("u16", [data]),
+            Self::I16(data) => ascii_values("i16", [data]),
+            Self::U32(data) => ascii_values("u32", [data]),
+            Self::I32(data) => ascii_values("i32", [data]),
+            Self::U64(data) => ascii_values("u64", [data]),
+            Self::I64(data) => ascii_values("i64", [data]),
+            Self::F32(data) => ascii_values("f32", [data]),
+            Self::F64(data) => ascii_values("f64", [data]),
+            Self::UnitQuaternion(data) => ascii_values("quat", [data.i, data.j, data.k, data.w]),
+            Self::Matrix4(data) => ascii_values("mat4", data.iter()),
+            Self::Data(data) => match std::str::from_utf8(data) {
+                Ok(string) => format!("str {}", ascii_quote(string)),
+                Err(_) => format!("bytes {}", ascii_quote(&base64::encode(data))),
+            },
+            Self::Matrix3(data) => ascii_values("mat3", data.iter()),
+            Self::Uuid(data) => ascii_values("uuid", [data]),
+            Self::UnitComplex(data) => ascii_values("complex", [data.re, data.im]),
+            Self::PodArray {
+                type_id,
+                element_size,
+                bytes,
+            } => format!(
+                "podarray {} {} {}",
+                type_id,
+                element_size,
+                ascii_quote(&base64::encode(bytes))
+            ),
+            Self::Matrix2(data) => ascii_values("mat2", data.iter()),
+
+            Self::Vector2F32(data) => ascii_values("vec2f32", data.iter()),
+            Self::Vector3F32(data) => ascii_values("vec3f32", data.iter()),
+            Self::Vector4F32(data) => ascii_values("vec4f32", data.iter()),
+
+            Self::Vector2F64(data) => ascii_values("vec2f64", data.iter()),
+            Self::Vector3F64(data) => ascii_values("vec3f64", data.iter()),
+            Self::Vector4F64(data) => ascii_values("vec4f64", data.iter()),
+
+            Self::Vector2U8(data) => ascii_values("vec2u8", data.iter()),
+            Self::Vector3U8(data) => ascii_values("vec3u8", data.iter()),
+            Self::Vector4U8(data) => ascii_values("vec4u8", data.iter()),
+
+            Self::Vector2I8(data) => ascii_values("vec2i8", data.iter()),
+            Self::Vector3I8(data) => ascii_values("vec3i8", data.iter()),
+            Self::Vector4I8(data) => ascii_values("vec4i8", data.iter()),
+
+            Self::Vector2U16(data) => ascii_values("vec2u16", data.iter()),
+            Self::Vector3U16(data) => ascii_values("vec3u16", data.iter()),
+            Self::Vector4U16(data) => ascii_values("vec4u16", data.iter()),
+
+            Self::Vector2I16(data) => ascii_values("vec2i16", data.iter()),
+            Self::Vector3I16(data) => ascii_values("vec3i16", data.iter()),
+            Self::Vector4I16(data) => ascii_values("vec4i16", data.iter()),
+
+            Self::Vector2U32(data) => ascii_values("vec2u32", data.iter()),
+            Self::Vector3U32(data) => ascii_values("vec3u32", data.iter()),
+            Self::Vector4U32(data) => ascii_values("vec4u32", data.iter()),
+
+            Self::Vector2I32(data) => ascii_values("vec2i32", data.iter()),
+            Self::Vector3I32(data) => ascii_values("vec3i32", data.iter()),
+            Self::Vector4I32(data) => ascii_values("vec4i32", data.iter()),
+
+            Self::Vector2U64(data) => ascii_values("vec2u64", data.iter()),
+            Self::Vector3U64(data) => ascii_values("vec3u64", data.iter()),
+            Self::Vector4U64(data) => ascii_values("vec4u64", data.iter()),
+
+            Self::Vector2I64(data) => ascii_values("vec2i64", data.iter()),
+            Self::Vector3I64(data) => ascii_values("vec3i64", data.iter()),
+            Self::Vector4I64(data) => ascii_values("vec4i64", data.iter()),
+        }
+    }
+
+    fn from_ascii(type_name: &str, values: &[AsciiToken]) -> Result<Self, String> {
+        macro_rules! scalar {
+            ($kind:ident, $ty:ty) => {
+                Self::$kind(parse_ascii_numbers::<$ty>(values, 1)?[0])
+            };
+        }
+
+        macro_rules! vector {
+            ($kind:ident, $vec:ident, $ty:ty, $n:expr) => {
+                Self::$kind($vec::from_iterator(parse_ascii_numbers::<$ty>(values, $n)?))
+            };
+        }
+
+        Ok(match type_name {
+            "bool" => scalar!(Bool, bool),
+            "u8" => scalar!(U8, u8),
+            "i8" => scalar!(I8, i8),
+            "u16" => scalar!(U16, u16),
+            "i16" => scalar!(I16, i16),
+            "u32" => scalar!(U32, u32),
+            "i32" => scalar!(I32, i32),
+            "u64" => scalar!(U64, u64),
+            "i64" => scalar!(I64, i64),
+            "f32" => scalar!(F32, f32),
+            "f64" => scalar!(F64, f64),
+            "quat" => {
+                let v = parse_ascii_numbers::<f32>(values, 4)?;
+                Self::UnitQuaternion(UnitQuaternion::new_unchecked(Quaternion::new(
+                    v[3], v[0], v[1], v[2],
+                )))
+            }
+            "mat4" => Self::Matrix4(Matrix4::from_column_slice(&parse_ascii_numbers::<f32>(
+                values, 16,
+            )?)),
+            "str" => Self::Data(parse_ascii_string(values)?.as_bytes().to_vec()),
+            "bytes" => Self::Data(parse_ascii_base64(values)?),
+            "mat3" => Self::Matrix3(Matrix3::from_column_slice(&parse_ascii_numbers::<f32>(
+                values, 9,
+            )?)),
+            "uuid" => scalar!(Uuid, Uuid),
+            "complex" => {
+                let v = parse_ascii_numbers::<f32>(values, 2)?;
+                Self::UnitComplex(UnitComplex::new_unchecked(Complex::new(v[0], v[1])))
+            }
+            "podarray" => {
+                if values.len() != 3 {
+                    return Err("expected 3 values".to_owned());
+                }
+                let header = parse_ascii_numbers::<u32>(&values[..2], 2)?;
+                Self::PodArray {
+                    type_id: u8::try_from(header[0]).map_err(|e| e.to_string())?,
+                    element_size: header[1],
+                    bytes: parse_ascii_base64(&values[2..])?,
+                }
+            }
+            "mat2" => Self::Matrix2(Matrix2::from_column_slice(&parse_ascii_numbers::<f32>(
+                values, 4,
+            )?)),
+
+            "vec2f32" => vector!(Vector2F32, Vector2, f32, 2),
+            "vec3f32" => vector!(Vector3F32, Vector3, f32, 3),
+            "vec4f32" => vector!(Vector4F32, Vector4, f32, 4),
+
+            "vec2f64" => vector!(Vector2F64, Vector2, f64, 2),
+            "vec3f64" => vector!(Vector3F64, Vector3, f64, 3),
+            "vec4f64" => vector!(Vector4F64, Vector4, f64, 4),
+
+            "vec2u8" => vector!(Vector2U8, Vector2, u8, 2),
+            "vec3u8" => vector!(Vector3U8, Vector3, u8, 3),
+            "vec4u8" => vector!(Vector4U8, Vector4, u8, 4),
+
+            "vec2i8" => vector!(Vector2I8, Vector2, i8, 2),
+            "vec3i8" => vector!(Vector3I8, Vector3, i8, 3),
+            "vec4i8" => vector!(Vector4I8, Vector4, i8, 4),
+
+            "vec2u16" => vector!(Vector2U16, Vector2, u16, 2),
+            "vec3u16" => vector!(Vector3U16, Vector3, u16, 3),
+            "vec4u16" => vector!(Vector4U16, Vector4, u16, 4),
+
+            "vec2i16" => vector!(Vector2I16, Vector2, i16, 2),
+            "vec3i16" => vector!(Vector3I16, Vector3, i16, 3),
+            "vec4i16" => vector!(Vector4I16, Vector4, i16, 4),
+
+            "vec2u32" => vector!(Vector2U32, Vector2, u32, 2),
+            "vec3u32" => vector!(Vector3U32, Vector3, u32, 3),
+            "vec4u32" => vector!(Vector4U32, Vector4, u32, 4),
+
+            "vec2i32" => vector!(Vector2I32, Vector2, i32, 2),
+            "vec3i32" => vector!(Vector3I32, Vector3, i32, 3),
+            "vec4i32" => vector!(Vector4I32, Vector4, i32, 4),
+
+            "vec2u64" => vector!(Vector2U64, Vector2, u64, 2),
+            "vec3u64" => vector!(Vector3U64, Vector3, u64, 3),
+            "vec4u64" => vector!(Vector4U64, Vector4, u64, 4),
+
+            "vec2i64" => vector!(Vector2I64, Vector2, i64, 2),
+            "vec3i64" => vector!(Vector3I64, Vector3, i64, 3),
+            "vec4i64" => vector!(Vector4I64, Vector4, i64, 4),
+
+            _ => return Err(format!("unknown field type {}", type_name)),
+        })
+    }
+}
+
 macro_rules! impl_field_data {
     ($type_name:ty, $($kind:tt)*) => {
         impl Visit for $type_name {
@@ -531,6 +877,15 @@ pub enum VisitError {
     UnexpectedRcNullIndex,
     PoisonedMutex,
     FileLoadError(FileLoadError),
+    Parse {
+        line: usize,
+        reason: String,
+    },
+    UnsupportedVersion {
+        region: String,
+        version: u32,
+        current: u32,
+    },
 }
 
 impl Display for VisitError {
@@ -553,6 +908,16 @@ impl Display for VisitError {
             Self::UnexpectedRcNullIndex => write!(f, "unexpected rc null index"),
             Self::PoisonedMutex => write!(f, "attempt to lock poisoned mutex"),
             Self::FileLoadError(e) => write!(f, "file load error: {:?}", e),
+            Self::Parse { line, reason } => write!(f, "parse error at line {}: {}", line, reason),
+            Self::UnsupportedVersion {
+                region,
+                version,
+                current,
+            } => write!(
+                f,
+                "region {} has version {}, which is newer than supported version {}",
+                region, version, current
+            ),
         }
     }
 }
@@ -1070,9 +1435,26 @@ pub struct Visitor {
     reading: bool,
     current_node: Handle<Node>,
     root: Handle<Node>,
+    migrations: Vec<Migration>,
     pub environment: Option<Arc<dyn Any>>,
 }
 
+/// A record about a change that was made to the data while reading it, for example a field rename
+/// or an upgrade of a region to newer version. See [`Visitor::migrations`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Migration {
+    /// Path of the region in which the change was made, region names are separated by `/`.
+    pub region: String,
+    /// Human-readable description of the change.
+    pub description: String,
+}
+
+impl Display for Migration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.region, self.description)
+    }
+}
+
 pub trait Visit {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult;
 }
@@ -1085,6 +1467,8 @@ impl Default for Visitor {
 
 impl Visitor {
     const MAGIC: &'static str = "RG3D";
+    const ASCII_MAGIC: &'static str = "FYROX_ASCII_VISITOR 1";
+    const VERSION_FIELD: &'static str = "__Version";
 
     pub fn new() -> Self {
         let mut nodes = Pool::new();
@@ -1096,6 +1480,7 @@ impl Visitor {
             reading: false,
             current_node: root,
             root,
+            migrations: Vec::new(),
             environment: None,
         }
     }
@@ -1159,6 +1544,175 @@ impl Visitor {
             .map(|n| n.name.as_str())
     }
 
+    /// Stores the `current` version of the current region when writing, returns the stored version
+    /// when reading. Regions that were written without version have version `0`. Reading data with a
+    /// version newer than `current` (i.e. data that was written by a newer version of the code) fails
+    /// with [`VisitError::UnsupportedVersion`]. The version allows [`Visit`] implementations to upgrade
+    /// old data, for example:
+    ///
+    /// ```rust
+    /// use fyrox_core::visitor::prelude::*;
+    ///
+    /// #[derive(Default)]
+    /// struct Foo {
+    ///     speed: f32,
+    /// }
+    ///
+    /// impl Visit for Foo {
+    ///     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+    ///         let mut region = visitor.enter_region(name)?;
+    ///
+    ///         let version = region.visit_version(1)?;
+    ///         if region.is_reading() && version < 1 {
+    ///             // Version 0 stored speed under different name.
+    ///             region.rename_field("Velocity", "Speed");
+    ///         }
+    ///
+    ///         self.speed.visit("Speed", &mut region)
+    ///     }
+    /// }
+    /// ```
+    pub fn visit_version(&mut self, current: u32) -> Result<u32, VisitError> {
+        if self.reading {
+            let version = match self.find_field(Self::VERSION_FIELD) {
+                Some(field) => match field.kind {
+                    FieldKind::U32(version) => version,
+                    _ => return Err(VisitError::FieldTypeDoesNotMatch),
+                },
+                None => 0,
+            };
+
+            if version > current {
+                Err(VisitError::UnsupportedVersion {
+                    region: self.region_path(),
+                    version,
+                    current,
+                })
+            } else {
+                Ok(version)
+            }
+        } else {
+            let mut version = current;
+            version.visit(Self::VERSION_FIELD, self)?;
+            Ok(current)
+        }
+    }
+
+    /// Returns `true` if the current region has a field with the given name.
+    pub fn has_field(&self, name: &str) -> bool {
+        self.nodes
+            .borrow(self.current_node)
+            .fields
+            .iter()
+            .any(|field| field.name == name)
+    }
+
+    /// Returns `true` if the current region has a child region with the given name.
+    pub fn has_region(&self, name: &str) -> bool {
+        self.nodes
+            .borrow(self.current_node)
+            .children
+            .iter()
+            .any(|child| self.nodes.borrow(*child).name == name)
+    }
+
+    /// Renames a field of the current region when reading, so the data written under an old name could
+    /// be read using the new one. Does nothing (and returns `false`) when writing, when there is no field
+    /// with the old name, or when a field with the new name already exists.
+    pub fn rename_field(&mut self, old_name: &str, new_name: &str) -> bool {
+        if !self.reading || self.has_field(new_name) {
+            return false;
+        }
+
+        match self.find_field(old_name) {
+            Some(field) => {
+                field.name = new_name.to_owned();
+                self.report_migration(format!("field {} renamed to {}", old_name, new_name));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Renames a child region of the current region when reading. See [`Self::rename_field`] for more
+    /// info.
+    pub fn rename_region(&mut self, old_name: &str, new_name: &str) -> bool {
+        if !self.reading || self.has_region(new_name) {
+            return false;
+        }
+
+        let child = self
+            .nodes
+            .borrow(self.current_node)
+            .children
+            .iter()
+            .cloned()
+            .find(|child| self.nodes.borrow(*child).name == old_name);
+
+        match child {
+            Some(child) => {
+                self.nodes.borrow_mut(child).name = new_name.to_owned();
+                self.report_migration(format!("region {} renamed to {}", old_name, new_name));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn region_path(&self) -> String {
+        let mut names = Vec::new();
+        let mut handle = self.current_node;
+        while let Some(node) = self.nodes.try_borrow(handle) {
+            // Skip the root node.
+            if node.parent.is_some() {
+                names.push(node.name.as_str());
+            }
+            handle = node.parent;
+        }
+        names.reverse();
+        names.join("/")
+    }
+
+    /// Adds a record about a change that was made to the data of the current region while reading it.
+    /// The method is used by [`Self::rename_field`], [`Self::rename_region`] and by `#[derive(Visit)]`,
+    /// custom [`Visit`] implementations could use it to report their own changes.
+    pub fn report_migration<S: Into<String>>(&mut self, description: S) {
+        let region = self.region_path();
+        self.migrations.push(Migration {
+            region,
+            description: description.into(),
+        });
+    }
+
+    /// Returns every string (and path) stored in the visitor, no matter in which region. It could be
+    /// used to find references to external files (such as resources) without reading the data.
+    pub fn collect_strings(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter_map(|node| match node.fields.as_slice() {
+                [Field {
+                    name: length_name,
+                    kind: FieldKind::U32(length),
+                }, Field {
+                    name: data_name,
+                    kind: FieldKind::Data(data),
+                }] if length_name == "Length"
+                    && data_name == "Data"
+                    && *length as usize == data.len() =>
+                {
+                    String::from_utf8(data.clone()).ok()
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the list of changes that were made to the data while reading it. Saving the data back
+    /// will store it in current format, so the list could be used as a report of what was upgraded.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
     fn leave_region(&mut self) -> VisitResult {
         self.current_node = self.nodes.borrow(self.current_node).parent;
         if self.current_node.is_none() {
@@ -1227,6 +1781,130 @@ impl Visitor {
         self.save_binary_to_memory(writer)
     }
 
+    fn write_node_ascii(&self, node_handle: Handle<Node>, nesting: usize, out: &mut String) {
+        let offset = (0..nesting).map(|_| "\t").collect::<String>();
+        let node = self.nodes.borrow(node_handle);
+
+        *out += &format!("{}{} {{\n", offset, ascii_quote(&node.name));
+
+        for field in node.fields.iter() {
+            *out += &format!(
+                "{}\t{}: {}\n",
+                offset,
+                ascii_quote(&field.name),
+                field.kind.to_ascii()
+            );
+        }
+
+        for child_handle in node.children.iter() {
+            self.write_node_ascii(*child_handle, nesting + 1, out);
+        }
+
+        *out += &format!("{}}}\n", offset);
+    }
+
+    /// Saves the visitor in human-readable, line-oriented text format (see [`VisitorFormat::Ascii`]).
+    /// Unlike [`Self::save_text`], which is intended for debugging only, the output could be loaded back
+    /// using [`Self::load_ascii_from_string`] or [`Self::load_from_memory`].
+    pub fn save_ascii_to_string(&self) -> String {
+        let mut out = format!("{}\n", Self::ASCII_MAGIC);
+        self.write_node_ascii(self.root, 0, &mut out);
+        out
+    }
+
+    /// Saves the visitor to a file in text format. See [`Self::save_ascii_to_string`] for more info.
+    pub fn save_ascii<P: AsRef<Path>>(&self, path: P) -> VisitResult {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(self.save_ascii_to_string().as_bytes())?;
+        Ok(())
+    }
+
+    /// Saves the visitor to a file in the given format.
+    pub fn save_with_format<P: AsRef<Path>>(&self, path: P, format: VisitorFormat) -> VisitResult {
+        match format {
+            VisitorFormat::Binary => self.save_binary(path),
+            VisitorFormat::Ascii => self.save_ascii(path),
+        }
+    }
+
+    /// Loads a visitor from a string in text format. See [`Self::save_ascii_to_string`] for more info.
+    pub fn load_ascii_from_string(string: &str) -> Result<Self, VisitError> {
+        let mut lines = string.lines().enumerate();
+
+        match lines.next() {
+            Some((_, header)) if header.trim() == Self::ASCII_MAGIC => (),
+            _ => return Err(VisitError::NotSupportedFormat),
+        }
+
+        let mut visitor = Self {
+            nodes: Pool::new(),
+            rc_map: Default::default(),
+            arc_map: Default::default(),
+            reading: true,
+            current_node: Handle::NONE,
+            root: Handle::NONE,
+            migrations: Vec::new(),
+            environment: None,
+        };
+
+        let mut stack: Vec<Handle<Node>> = Vec::new();
+        for (line_index, line) in lines {
+            let parse_error = |reason: String| VisitError::Parse {
+                line: line_index + 1,
+                reason,
+            };
+
+            let tokens = tokenize_ascii_line(line).map_err(parse_error)?;
+
+            match tokens.as_slice() {
+                [] => (),
+                [AsciiToken::Bare(close)] if close == "}" => {
+                    if stack.pop().is_none() {
+                        return Err(parse_error("unbalanced braces".to_owned()));
+                    }
+                }
+                [AsciiToken::Quoted(name), AsciiToken::Bare(open)] if open == "{" => {
+                    let parent = stack.last().cloned().unwrap_or_default();
+                    if parent.is_none() && visitor.root.is_some() {
+                        return Err(parse_error("multiple root regions".to_owned()));
+                    }
+
+                    let handle = visitor.nodes.spawn(Node::new(name, parent));
+                    if parent.is_some() {
+                        visitor.nodes.borrow_mut(parent).children.push(handle);
+                    } else {
+                        visitor.root = handle;
+                    }
+                    stack.push(handle);
+                }
+                [AsciiToken::Quoted(name), AsciiToken::Bare(colon), AsciiToken::Bare(type_name), values @ ..]
+                    if colon == ":" =>
+                {
+                    let node = *stack
+                        .last()
+                        .ok_or_else(|| parse_error("field outside of a region".to_owned()))?;
+                    let kind = FieldKind::from_ascii(type_name, values).map_err(parse_error)?;
+                    visitor
+                        .nodes
+                        .borrow_mut(node)
+                        .fields
+                        .push(Field::new(name, kind));
+                }
+                _ => return Err(parse_error("unexpected tokens".to_owned())),
+            }
+        }
+
+        if !stack.is_empty() || visitor.root.is_none() {
+            return Err(VisitError::Parse {
+                line: string.lines().count(),
+                reason: "unexpected end of data".to_owned(),
+            });
+        }
+
+        visitor.current_node = visitor.root;
+        Ok(visitor)
+    }
+
     fn load_node_binary(&mut self, file: &mut dyn Read) -> Result<Handle<Node>, VisitError> {
         let name_len = file.read_u32::<LittleEndian>()? as usize;
         let mut raw_name = vec![Default::default(); name_len];
@@ -1260,11 +1938,29 @@ impl Visitor {
         Ok(handle)
     }
 
+    /// Loads a visitor from a file in binary format, files in other formats are rejected with
+    /// [`VisitError::NotSupportedFormat`]. Use [`Self::load_from_file`] to load a file in any of
+    /// supported formats.
     pub async fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        Self::load_binary_from_memory(io::load_file(path).await?)
+    }
+
+    /// Loads a visitor from a file in any of supported formats (see [`VisitorFormat`]), the format is
+    /// detected automatically.
+    pub async fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
         Self::load_from_memory(io::load_file(path).await?)
     }
 
+    /// Loads a visitor from the given data in any of supported formats (see [`VisitorFormat`]), the format
+    /// is detected automatically.
     pub fn load_from_memory(data: Vec<u8>) -> Result<Self, VisitError> {
+        match VisitorFormat::detect(&data) {
+            Some(VisitorFormat::Ascii) => Self::load_ascii_from_string(&String::from_utf8(data)?),
+            _ => Self::load_binary_from_memory(data),
+        }
+    }
+
+    fn load_binary_from_memory(data: Vec<u8>) -> Result<Self, VisitError> {
         let mut reader = Cursor::new(data);
         let mut magic: [u8; 4] = Default::default();
         reader.read_exact(&mut magic)?;
@@ -1278,6 +1974,7 @@ impl Visitor {
             reading: true,
             current_node: Handle::NONE,
             root: Handle::NONE,
+            migrations: Vec::new(),
             environment: None,
         };
         visitor.root = visitor.load_node_binary(&mut reader)?;
@@ -1657,6 +2354,25 @@ where
     }
 }
 
+// Hash maps and hash sets are written sorted by keys, otherwise the same data could be written in
+// different order every time, which makes text output unusable for diffs. Keys are not required to be
+// `Ord`, so their text representation is used for sorting.
+fn sorted_keys<'a, K, I>(keys: I) -> Result<Vec<&'a K>, VisitError>
+where
+    K: Visit + Clone + 'a,
+    I: Iterator<Item = &'a K>,
+{
+    let mut keys = keys
+        .map(|key| {
+            let mut visitor = Visitor::new();
+            key.clone().visit("Key", &mut visitor)?;
+            Ok((visitor.save_ascii_to_string(), key))
+        })
+        .collect::<Result<Vec<_>, VisitError>>()?;
+    keys.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(keys.into_iter().map(|(_, key)| key).collect())
+}
+
 impl<K, V, S> Visit for HashMap<K, V, S>
 where
     K: Visit + Default + Clone + Hash + Eq,
@@ -1685,15 +2401,20 @@ where
                 self.insert(key, value);
             }
         } else {
-            for (i, (key, value)) in self.iter_mut().enumerate() {
+            let keys = sorted_keys(self.keys())?
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            for (i, mut key) in keys.into_iter().enumerate() {
                 let name = format!("Item{}", i);
 
                 let mut region = region.enter_region(name.as_str())?;
 
-                let mut key = key.clone();
                 key.visit("Key", &mut region)?;
 
-                value.visit("Value", &mut region)?;
+                if let Some(value) = self.get_mut(&key) {
+                    value.visit("Value", &mut region)?;
+                }
             }
         }
 
@@ -1725,7 +2446,8 @@ where
                 self.insert(key);
             }
         } else {
-            for (i, mut key) in self.clone().into_iter().enumerate() {
+            for (i, key) in sorted_keys(self.iter())?.into_iter().enumerate() {
+                let mut key = key.clone();
                 let name = format!("Item{}", i);
 
                 let mut region = region.enter_region(name.as_str())?;
@@ -1803,8 +2525,21 @@ impl<T: Visit> Visit for Range<T> {
 
 #[cfg(test)]
 mod test {
-    use crate::visitor::{Data, Visit, VisitResult, Visitor};
-    use std::{fs::File, io::Write, path::Path, rc::Rc};
+    use crate::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        vfs::{self, MemoryFileSystem},
+        visitor::{Data, Visit, VisitError, VisitResult, Visitor, VisitorFormat},
+    };
+    use futures::executor::block_on;
+    use std::{
+        collections::{HashMap, HashSet},
+        fs::File,
+        io::Write,
+        path::Path,
+        rc::Rc,
+        sync::Arc,
+    };
+    use uuid::Uuid;
 
     #[derive(Visit, Default)]
     pub struct Model {
@@ -1907,4 +2642,127 @@ mod test {
             objects.visit("Objects", &mut visitor).unwrap();
         }
     }
+
+    #[test]
+    fn visitor_ascii_test() {
+        #[derive(Visit, Default, Debug, PartialEq)]
+        struct Data {
+            flag: bool,
+            number: i32,
+            float: f32,
+            position: Vector3<f32>,
+            rotation: UnitQuaternion<f32>,
+            transform: Matrix4<f32>,
+            id: Uuid,
+            name: String,
+            bytes: Vec<u8>,
+            items: Vec<u64>,
+        }
+
+        let mut data = Data {
+            flag: true,
+            number: -123,
+            float: 0.1,
+            position: Vector3::new(1.0, -2.5, f32::MAX),
+            rotation: UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+            transform: Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0)),
+            id: Uuid::new_v4(),
+            name: "Quoted \"name\"\n\twith escapes".to_owned(),
+            bytes: vec![0, 255, 128, 7],
+            items: vec![1, 2, 3],
+        };
+
+        let mut visitor = Visitor::new();
+        data.visit("Data", &mut visitor).unwrap();
+        let text = visitor.save_ascii_to_string();
+
+        let mut visitor = Visitor::load_from_memory(text.clone().into_bytes()).unwrap();
+        let mut loaded = Data::default();
+        loaded.visit("Data", &mut visitor).unwrap();
+        assert_eq!(data, loaded);
+
+        // Saving the loaded data again must produce exactly the same text.
+        let mut visitor = Visitor::new();
+        loaded.visit("Data", &mut visitor).unwrap();
+        assert_eq!(visitor.save_ascii_to_string(), text);
+    }
+
+    #[test]
+    fn test_hash_map_order() {
+        let keys = (0..32).map(|i| format!("Key{}", i)).collect::<Vec<_>>();
+
+        let save = |keys: &mut dyn Iterator<Item = &String>| {
+            let mut map = HashMap::new();
+            let mut set = HashSet::new();
+            for key in keys {
+                map.insert(key.clone(), key.len() as u32);
+                set.insert(key.clone());
+            }
+
+            let mut visitor = Visitor::new();
+            map.visit("Map", &mut visitor).unwrap();
+            set.visit("Set", &mut visitor).unwrap();
+            visitor.save_ascii_to_string()
+        };
+
+        // The same data must be written in the same way, no matter in which order it was inserted.
+        let text = save(&mut keys.iter());
+        assert_eq!(save(&mut keys.iter().rev()), text);
+
+        let mut visitor = Visitor::load_ascii_from_string(&text).unwrap();
+        let mut map = HashMap::<String, u32>::new();
+        map.visit("Map", &mut visitor).unwrap();
+        assert_eq!(map.len(), keys.len());
+        assert_eq!(map["Key10"], 5);
+        let mut set = HashSet::<String>::new();
+        set.visit("Set", &mut visitor).unwrap();
+        assert_eq!(set.len(), keys.len());
+    }
+
+    #[test]
+    fn test_format_detection_from_file() {
+        let mut data = Model { data: 123 };
+
+        let mut visitor = Visitor::new();
+        data.visit("Data", &mut visitor).unwrap();
+
+        let file_system = MemoryFileSystem::new();
+        file_system.insert("binary.bin", visitor.save_binary_to_vec().unwrap());
+        file_system.insert("ascii.txt", visitor.save_ascii_to_string().into_bytes());
+        file_system.insert("unknown.txt", b"unknown".to_vec());
+        vfs::mount("__visitor_format_test", 1, Arc::new(file_system));
+
+        // The files exist only in the virtual file system.
+        let detect = |path| block_on(VisitorFormat::detect_from_file(path));
+        assert_eq!(
+            detect("__visitor_format_test/binary.bin"),
+            Some(VisitorFormat::Binary)
+        );
+        assert_eq!(
+            detect("__visitor_format_test/ascii.txt"),
+            Some(VisitorFormat::Ascii)
+        );
+        assert_eq!(detect("__visitor_format_test/unknown.txt"), None);
+        assert_eq!(detect("__visitor_format_test/missing.bin"), None);
+
+        // Only `load_from_file` accepts any format.
+        for path in [
+            "__visitor_format_test/binary.bin",
+            "__visitor_format_test/ascii.txt",
+        ] {
+            let mut visitor = block_on(Visitor::load_from_file(path)).unwrap();
+            let mut loaded = Model::default();
+            loaded.visit("Data", &mut visitor).unwrap();
+            assert_eq!(loaded.data, 123);
+        }
+        assert!(block_on(Visitor::load_binary("__visitor_format_test/binary.bin")).is_ok());
+        assert!(matches!(
+            block_on(Visitor::load_binary("__visitor_format_test/ascii.txt")),
+            Err(VisitError::NotSupportedFormat)
+        ));
+
+        // The file system is global, other tests must not see the files.
+        assert_eq!(vfs::unmount("__visitor_format_test"), 1);
+        assert_eq!(detect("__visitor_format_test/binary.bin"), None);
+    }
 }
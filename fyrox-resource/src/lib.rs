@@ -21,6 +21,8 @@ use std::{
 
 pub use fyrox_core as core;
 
+pub mod pack;
+
 /// A trait for resource data.
 pub trait ResourceData: 'static + Default + Debug + Visit + Send {
     /// Returns path of resource data.
@@ -0,0 +1,760 @@
+//! Asset pack is an archive that contains many asset files, it allows to ship a game as a few files
+//! instead of thousands of loose assets. [`AssetPack`] implements [`FileSystem`], so it could be
+//! mounted into the virtual file system (see [`mount`]) and every resource will be loaded from the
+//! pack transparently. Use [`AssetPackWriter`] to create packs.
+//!
+//! # Format
+//!
+//! All numbers are stored in little-endian byte order. The file starts with a header:
+//!
+//! - `FYRXPACK` magic (8 bytes)
+//! - format version (`u32`)
+//! - amount of entries (`u32`)
+//! - offset of the index from the beginning of the file (`u64`)
+//!
+//! Then goes the data of every entry, every entry starts at an offset that is a multiple of
+//! [`AssetPack::ALIGNMENT`], so the file could be memory-mapped and uncompressed entries could be
+//! read without copying. The index is at the end of the file, for every entry it contains:
+//!
+//! - length of the path (`u32`) and the path itself (UTF-8, `/` is used as separator)
+//! - offset of the data (`u64`)
+//! - size of the stored data (`u64`)
+//! - size of the original data (`u64`)
+//! - flags (`u32`), the first bit is set if the data is compressed using LZ4
+
+use crate::core::{
+    byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt},
+    io::FileLoadError,
+    replace_slashes,
+    vfs::{self, normalize_path, FileSystem, FileSystemFuture},
+    visitor::Visitor,
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{BufWriter, Cursor, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// An error that may occur during asset pack reading or writing.
+#[derive(Debug)]
+pub enum AssetPackError {
+    /// An i/o error has occurred.
+    Io(std::io::Error),
+    /// The data is not an asset pack.
+    NotAnAssetPack,
+    /// The pack was made with unsupported version of the format.
+    UnsupportedVersion(u32),
+    /// The pack has invalid index or the data of an entry is corrupted.
+    Corrupted(String),
+    /// There's no entry with the given path in the pack.
+    NoSuchEntry(PathBuf),
+}
+
+impl Display for AssetPackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "io error: {}", e),
+            Self::NotAnAssetPack => write!(f, "the data is not an asset pack"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported asset pack version {}", version)
+            }
+            Self::Corrupted(reason) => write!(f, "the asset pack is corrupted: {}", reason),
+            Self::NoSuchEntry(path) => write!(f, "there's no {} in the pack", path.display()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AssetPackError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<FileLoadError> for AssetPackError {
+    fn from(e: FileLoadError) -> Self {
+        match e {
+            FileLoadError::Io(e) => Self::Io(e),
+            FileLoadError::Custom(reason) => {
+                Self::Io(std::io::Error::new(std::io::ErrorKind::Other, reason))
+            }
+        }
+    }
+}
+
+const MAGIC: &[u8; 8] = b"FYRXPACK";
+const VERSION: u32 = 1;
+const HEADER_SIZE: u64 = 24;
+const FLAG_COMPRESSED: u32 = 1;
+// LZ4 cannot compress data better than this, so the size of an entry that exceeds the size of its
+// stored data by more is certainly corrupted (and must not be used to allocate memory).
+const MAX_COMPRESSION_RATIO: usize = 255;
+
+fn entry_key(path: &Path) -> PathBuf {
+    replace_slashes(normalize_path(path))
+}
+
+#[derive(Clone, Debug)]
+struct PackEntry {
+    offset: usize,
+    stored_size: usize,
+    size: usize,
+    compressed: bool,
+}
+
+enum PackData {
+    Memory(Vec<u8>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Mapped(memmap2::Mmap),
+}
+
+impl PackData {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            PackData::Memory(data) => data,
+            #[cfg(not(target_arch = "wasm32"))]
+            PackData::Mapped(map) => map,
+        }
+    }
+}
+
+/// Asset pack reader. See [module docs](self) for more info.
+pub struct AssetPack {
+    data: PackData,
+    entries: HashMap<PathBuf, PackEntry>,
+}
+
+impl AssetPack {
+    /// Every entry in a pack starts at an offset that is a multiple of this value.
+    pub const ALIGNMENT: u64 = 16;
+
+    /// Opens the pack using memory mapping, the data of entries is read on demand.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetPackError> {
+        let file = File::open(path)?;
+        // SAFETY: The pack is read-only, it must not be modified while it is opened.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Self::from_data(PackData::Mapped(map))
+    }
+
+    /// Loads the entire pack in memory using current file system (see [`crate::core::vfs`]). Unlike
+    /// [`Self::open`] it works on every platform.
+    pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self, AssetPackError> {
+        Self::from_memory(crate::core::io::load_file(path).await?)
+    }
+
+    /// Creates the pack from the data in memory.
+    pub fn from_memory(data: Vec<u8>) -> Result<Self, AssetPackError> {
+        Self::from_data(PackData::Memory(data))
+    }
+
+    fn from_data(data: PackData) -> Result<Self, AssetPackError> {
+        let bytes = data.as_slice();
+        let mut reader = Cursor::new(bytes);
+
+        let mut magic = [0; 8];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| AssetPackError::NotAnAssetPack)?;
+        if &magic != MAGIC {
+            return Err(AssetPackError::NotAnAssetPack);
+        }
+
+        let version = reader.read_u32::<LittleEndian>()?;
+        if version != VERSION {
+            return Err(AssetPackError::UnsupportedVersion(version));
+        }
+
+        let entry_count = reader.read_u32::<LittleEndian>()?;
+        let index_offset = reader.read_u64::<LittleEndian>()?;
+        reader.seek(SeekFrom::Start(index_offset))?;
+
+        let mut entries = HashMap::default();
+        for _ in 0..entry_count {
+            let path_len = reader.read_u32::<LittleEndian>()? as usize;
+            if path_len > bytes.len().saturating_sub(reader.position() as usize) {
+                return Err(AssetPackError::Corrupted(
+                    "entry path is out of bounds".to_owned(),
+                ));
+            }
+            let mut path = vec![0; path_len];
+            reader.read_exact(&mut path)?;
+            let path = String::from_utf8(path)
+                .map_err(|_| AssetPackError::Corrupted("invalid entry path".to_owned()))?;
+
+            let entry = PackEntry {
+                offset: reader.read_u64::<LittleEndian>()? as usize,
+                stored_size: reader.read_u64::<LittleEndian>()? as usize,
+                size: reader.read_u64::<LittleEndian>()? as usize,
+                compressed: reader.read_u32::<LittleEndian>()? & FLAG_COMPRESSED != 0,
+            };
+
+            if entry
+                .offset
+                .checked_add(entry.stored_size)
+                .map_or(true, |end| end > bytes.len())
+            {
+                return Err(AssetPackError::Corrupted(format!(
+                    "data of {} is out of bounds",
+                    path
+                )));
+            }
+
+            let max_size = if entry.compressed {
+                entry.stored_size.saturating_mul(MAX_COMPRESSION_RATIO)
+            } else {
+                entry.stored_size
+            };
+            if entry.size > max_size {
+                return Err(AssetPackError::Corrupted(format!(
+                    "size of {} is invalid",
+                    path
+                )));
+            }
+
+            entries.insert(PathBuf::from(path), entry);
+        }
+
+        Ok(Self { data, entries })
+    }
+
+    /// Returns `true` if the pack contains a file at the given path.
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.entries.contains_key(&entry_key(path.as_ref()))
+    }
+
+    /// Returns paths of every file in the pack.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(|p| p.as_path())
+    }
+
+    /// Returns amount of files in the pack.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the pack has no files.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Reads the file at the given path, decompressing it if needed. Data of uncompressed files is
+    /// borrowed from the pack without copying.
+    pub fn read<P: AsRef<Path>>(&self, path: P) -> Result<Cow<'_, [u8]>, AssetPackError> {
+        let entry = self
+            .entries
+            .get(&entry_key(path.as_ref()))
+            .ok_or_else(|| AssetPackError::NoSuchEntry(path.as_ref().to_path_buf()))?;
+
+        let stored = &self.data.as_slice()[entry.offset..entry.offset + entry.stored_size];
+        if entry.compressed {
+            lz4_flex::decompress(stored, entry.size)
+                .map(Cow::Owned)
+                .map_err(|e| {
+                    AssetPackError::Corrupted(format!(
+                        "unable to decompress {}: {}",
+                        path.as_ref().display(),
+                        e
+                    ))
+                })
+        } else {
+            Ok(Cow::Borrowed(stored))
+        }
+    }
+}
+
+impl FileSystem for AssetPack {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        let result = self.read(path).map(Cow::into_owned).map_err(|e| match e {
+            AssetPackError::Io(e) => FileLoadError::Io(e),
+            AssetPackError::NoSuchEntry(path) => FileLoadError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("file {} does not exist", path.display()),
+            )),
+            _ => FileLoadError::Custom(e.to_string()),
+        });
+        Box::pin(async move { result })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, bool> {
+        let exists = self.contains(path);
+        Box::pin(async move { exists })
+    }
+}
+
+/// Mounts the asset pack at the root of the virtual file system (see [`crate::core::vfs::mount`]),
+/// so every file that is in the pack will be loaded from it. Files that are not in the pack are
+/// loaded from the file system that was used before. Keep in mind, that the virtual file system is
+/// global, so the pack is used by every resource manager of the process. The pack must be mounted
+/// before requesting resources from it, already loaded resources won't be reloaded.
+pub fn mount(pack: AssetPack, priority: i32) {
+    vfs::mount("", priority, Arc::new(pack));
+}
+
+enum PendingData {
+    Memory(Vec<u8>),
+    // The file is read only when the pack is written, so the writer does not hold the data of every
+    // file in memory.
+    NativeFile(PathBuf),
+}
+
+struct PendingEntry {
+    path: PathBuf,
+    data: PendingData,
+    compress: bool,
+}
+
+/// Asset pack writer. See [module docs](self) for more info.
+///
+/// ```no_run
+/// use fyrox_resource::pack::AssetPackWriter;
+///
+/// let mut writer = AssetPackWriter::new();
+/// writer.add_project(".").unwrap();
+/// writer.save("assets.pack").unwrap();
+/// ```
+#[derive(Default)]
+pub struct AssetPackWriter {
+    entries: Vec<PendingEntry>,
+    indices: HashMap<PathBuf, usize>,
+    compress: bool,
+}
+
+impl AssetPackWriter {
+    /// Creates new writer with compression enabled.
+    pub fn new() -> Self {
+        Self {
+            entries: Default::default(),
+            indices: Default::default(),
+            compress: true,
+        }
+    }
+
+    /// Enables or disables compression of the files that will be added after the call.
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    fn add_entry(&mut self, path: &Path, data: PendingData) {
+        let path = entry_key(path);
+        let entry = PendingEntry {
+            path: path.clone(),
+            data,
+            compress: self.compress,
+        };
+
+        match self.indices.get(&path) {
+            Some(&index) => self.entries[index] = entry,
+            None => {
+                self.indices.insert(path, self.entries.len());
+                self.entries.push(entry);
+            }
+        }
+    }
+
+    /// Adds a file with the given content, the previous content of the file (if any) is replaced.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P, data: Vec<u8>) {
+        self.add_entry(path.as_ref(), PendingData::Memory(data));
+    }
+
+    /// Returns `true` if the file was added to the pack.
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.indices.contains_key(&entry_key(path.as_ref()))
+    }
+
+    /// Returns paths of every file that was added to the pack.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.iter().map(|e| e.path.as_path())
+    }
+
+    /// Adds a file of the native file system to the pack, the file will be stored in the pack at the
+    /// path relative to the `root`. The file is read when the pack is written.
+    pub fn add_native_file<R, P>(&mut self, root: R, path: P) -> Result<(), AssetPackError>
+    where
+        R: AsRef<Path>,
+        P: AsRef<Path>,
+    {
+        let native_path = root.as_ref().join(path.as_ref());
+        if !native_path.is_file() {
+            return Err(AssetPackError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("file {} does not exist", native_path.display()),
+            )));
+        }
+        self.add_entry(path.as_ref(), PendingData::NativeFile(native_path));
+        Ok(())
+    }
+
+    /// Adds given scenes (paths are relative to the `root`) and every file they reference to the
+    /// pack. See [`collect_dependencies`] for more info.
+    pub fn add_scenes<R: AsRef<Path>>(
+        &mut self,
+        root: R,
+        scenes: &[PathBuf],
+    ) -> Result<(), AssetPackError> {
+        for path in collect_dependencies(root.as_ref(), scenes) {
+            if !self.contains(&path) {
+                self.add_native_file(root.as_ref(), path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Walks the project at the given path and adds every scene (`*.rgs`) of it along with every
+    /// file referenced by the scenes. Hidden and `target` directories are ignored. Files that are
+    /// not referenced by scenes (for example, resources that are requested from code) must be added
+    /// manually.
+    pub fn add_project<R: AsRef<Path>>(&mut self, root: R) -> Result<(), AssetPackError> {
+        let root = root.as_ref();
+        let mut scenes = Vec::new();
+        find_scenes(root, Path::new(""), &mut scenes)?;
+        scenes.sort();
+        self.add_scenes(root, &scenes)
+    }
+
+    /// Writes the pack.
+    pub fn write<W: Write + Seek>(&self, writer: &mut W) -> Result<(), AssetPackError> {
+        writer.write_all(MAGIC)?;
+        writer.write_u32::<LittleEndian>(VERSION)?;
+        writer.write_u32::<LittleEndian>(self.entries.len() as u32)?;
+        // Index offset will be written when the index is ready.
+        writer.write_u64::<LittleEndian>(0)?;
+
+        let mut position = HEADER_SIZE;
+        let mut index = Vec::new();
+        for entry in self.entries.iter() {
+            let padding =
+                (AssetPack::ALIGNMENT - position % AssetPack::ALIGNMENT) % AssetPack::ALIGNMENT;
+            writer.write_all(&vec![0; padding as usize])?;
+            position += padding;
+
+            // Only the data of the current entry is loaded in memory.
+            let native_data;
+            let data = match &entry.data {
+                PendingData::Memory(data) => data,
+                PendingData::NativeFile(path) => {
+                    native_data = std::fs::read(path)?;
+                    &native_data
+                }
+            };
+
+            // Store the data as is if compression is useless.
+            let compressed = if entry.compress {
+                Some(lz4_flex::compress(data)).filter(|c| c.len() < data.len())
+            } else {
+                None
+            };
+            let stored = compressed.as_ref().unwrap_or(data);
+            writer.write_all(stored)?;
+
+            let path = entry.path.to_string_lossy();
+            index.write_u32::<LittleEndian>(path.len() as u32)?;
+            index.write_all(path.as_bytes())?;
+            index.write_u64::<LittleEndian>(position)?;
+            index.write_u64::<LittleEndian>(stored.len() as u64)?;
+            index.write_u64::<LittleEndian>(data.len() as u64)?;
+            index.write_u32::<LittleEndian>(if compressed.is_some() {
+                FLAG_COMPRESSED
+            } else {
+                0
+            })?;
+
+            position += stored.len() as u64;
+        }
+
+        writer.write_all(&index)?;
+        writer.seek(SeekFrom::Start(HEADER_SIZE - 8))?;
+        writer.write_u64::<LittleEndian>(position)?;
+        writer.seek(SeekFrom::End(0))?;
+
+        Ok(())
+    }
+
+    /// Writes the pack to a file at the given path.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AssetPackError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn is_ignored_dir(name: &str) -> bool {
+    name.starts_with('.') || name == "target"
+}
+
+fn find_scenes(root: &Path, dir: &Path, scenes: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(root.join(dir))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let path = dir.join(&name);
+        if entry.file_type()?.is_dir() {
+            if !is_ignored_dir(&name.to_string_lossy()) {
+                find_scenes(root, &path, scenes)?;
+            }
+        } else if path.extension().map_or(false, |ext| ext == "rgs") {
+            scenes.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Model formats that reference their textures (and other data) by relative paths.
+const EXTERNAL_MODEL_EXTENSIONS: [&str; 4] = ["fbx", "gltf", "glb", "obj"];
+
+const MODEL_DEPENDENCY_EXTENSIONS: [&str; 12] = [
+    "png", "jpg", "jpeg", "tga", "bmp", "dds", "tif", "tiff", "gif", "mtl", "bin", "options",
+];
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| {
+            extensions.iter().any(|e| e.eq_ignore_ascii_case(ext))
+        })
+}
+
+/// Collects given scenes (paths are relative to the `root`) and every file they reference, directly
+/// or through other scenes (for example, model resources). References are found by looking for
+/// strings in the scene data which are paths to existing files. Import options of the files are
+/// collected too, and for models in external formats (FBX, glTF, OBJ) every texture next to them is
+/// collected, since such models reference textures by relative paths. The returned paths are
+/// relative to the `root`.
+pub fn collect_dependencies(root: &Path, scenes: &[PathBuf]) -> Vec<PathBuf> {
+    let mut collected = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = scenes.iter().map(|p| entry_key(p)).collect::<VecDeque<_>>();
+
+    while let Some(path) = queue.pop_front() {
+        if visited.contains(&path) || !root.join(&path).is_file() {
+            continue;
+        }
+
+        let options = crate::core::append_extension(&path, "options");
+        if root.join(&options).is_file() {
+            queue.push_back(options);
+        }
+
+        if path.extension().map_or(false, |ext| ext == "rgs") {
+            let visitor = std::fs::read(root.join(&path))
+                .ok()
+                .and_then(|data| Visitor::load_from_memory(data).ok());
+            if let Some(visitor) = visitor {
+                for string in visitor.collect_strings() {
+                    let reference = entry_key(Path::new(&string));
+                    if !string.is_empty() && root.join(&reference).is_file() {
+                        queue.push_back(reference);
+                    }
+                }
+            }
+        } else if has_extension(&path, &EXTERNAL_MODEL_EXTENSIONS) {
+            let parent = path.parent().unwrap_or_else(|| Path::new(""));
+            if let Ok(entries) = std::fs::read_dir(root.join(parent)) {
+                for entry in entries.flatten() {
+                    let sibling = parent.join(entry.file_name());
+                    if has_extension(&sibling, &MODEL_DEPENDENCY_EXTENSIONS) {
+                        queue.push_back(entry_key(&sibling));
+                    }
+                }
+            }
+        }
+
+        visited.insert(path.clone());
+        collected.push(path);
+    }
+
+    collected
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::visitor::{Visit, Visitor},
+        pack::{collect_dependencies, AssetPack, AssetPackError, AssetPackWriter},
+    };
+    use std::{
+        borrow::Cow,
+        io::Cursor,
+        path::{Path, PathBuf},
+    };
+
+    #[test]
+    fn test_asset_pack() {
+        let compressible = vec![7; 1000];
+        let random = (0..100u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect::<Vec<_>>();
+
+        let mut writer = AssetPackWriter::new();
+        writer.add_file("data/a.bin", compressible.clone());
+        writer.add_file("./data/b.bin", random.clone());
+        let mut writer = writer.with_compression(false);
+        writer.add_file("data/c.bin", compressible.clone());
+
+        let mut data = Cursor::new(Vec::new());
+        writer.write(&mut data).unwrap();
+        let data = data.into_inner();
+
+        // The compressed file must be smaller than the original.
+        assert!(data.len() < compressible.len() * 2);
+
+        let pack = AssetPack::from_memory(data).unwrap();
+        assert_eq!(pack.len(), 3);
+        assert_eq!(pack.read("data/a.bin").unwrap(), compressible);
+        assert_eq!(pack.read("data/b.bin").unwrap(), random);
+        assert_eq!(pack.read("data/c.bin").unwrap(), compressible);
+        assert!(pack.contains("./data/../data/a.bin"));
+        // Uncompressed data is not copied.
+        assert!(matches!(pack.read("data/b.bin").unwrap(), Cow::Borrowed(_)));
+        assert!(matches!(pack.read("data/c.bin").unwrap(), Cow::Borrowed(_)));
+        assert!(matches!(pack.read("data/a.bin").unwrap(), Cow::Owned(_)));
+        assert!(pack.read("data/d.bin").is_err());
+    }
+
+    #[test]
+    fn test_corrupted_asset_pack() {
+        let mut writer = AssetPackWriter::new();
+        writer.add_file("a.bin", vec![1, 2, 3]);
+
+        let mut data = Cursor::new(Vec::new());
+        writer.write(&mut data).unwrap();
+        let mut data = data.into_inner();
+
+        // Index of the single entry: path length, path, offset, stored size, size and flags.
+        let offset_position = data.len() - 28;
+        data[offset_position..offset_position + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        assert!(matches!(
+            AssetPack::from_memory(data),
+            Err(AssetPackError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn test_corrupted_entry_size() {
+        let mut writer = AssetPackWriter::new();
+        writer.add_file("a.bin", vec![7; 1000]);
+
+        let mut data = Cursor::new(Vec::new());
+        writer.write(&mut data).unwrap();
+        let data = data.into_inner();
+        assert!(AssetPack::from_memory(data.clone()).is_ok());
+
+        // Index of the single entry: path length, path, offset, stored size, size and flags. Sizes must
+        // be checked before allocating memory for the data.
+        let mut huge_size = data.clone();
+        let size_position = huge_size.len() - 12;
+        huge_size[size_position..size_position + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            AssetPack::from_memory(huge_size),
+            Err(AssetPackError::Corrupted(_))
+        ));
+
+        let mut huge_path = data;
+        let path_len_position = huge_path.len() - 37;
+        huge_path[path_len_position..path_len_position + 4]
+            .copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            AssetPack::from_memory(huge_path),
+            Err(AssetPackError::Corrupted(_))
+        ));
+    }
+
+    fn write_scene(root: &Path, path: &str, name: &str, references: &[&str]) {
+        let mut visitor = Visitor::new();
+        let mut region = visitor.enter_region("Scene").unwrap();
+        let mut name = name.to_owned();
+        name.visit("Name", &mut region).unwrap();
+        for (i, reference) in references.iter().enumerate() {
+            let mut resource_region = region.enter_region(&format!("Resource{}", i)).unwrap();
+            let mut path = PathBuf::from(reference);
+            path.visit("Path", &mut resource_region).unwrap();
+        }
+        drop(region);
+        visitor.save_binary(root.join(path)).unwrap();
+    }
+
+    #[test]
+    fn test_collect_dependencies() {
+        let root = std::env::temp_dir().join(format!(
+            "fyrox_test_collect_dependencies_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        for dir in ["scenes", "models", "textures"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "textures/wall.png",
+            "textures/crate.png",
+            "textures/crate.png.options",
+            "textures/unused.png",
+        ] {
+            std::fs::write(root.join(file), b"data").unwrap();
+        }
+
+        // The scene references a texture and a model, which references its own texture.
+        write_scene(
+            &root,
+            "scenes/level.rgs",
+            "Level",
+            &[
+                "textures/wall.png",
+                "models/crate.rgs",
+                "textures/missing.png",
+            ],
+        );
+        write_scene(
+            &root,
+            "models/crate.rgs",
+            "Crate",
+            &["./textures/crate.png"],
+        );
+
+        let visitor =
+            Visitor::load_from_memory(std::fs::read(root.join("scenes/level.rgs")).unwrap())
+                .unwrap();
+        let mut strings = visitor.collect_strings();
+        strings.sort();
+        assert_eq!(
+            strings,
+            vec![
+                "Level".to_owned(),
+                "models/crate.rgs".to_owned(),
+                "textures/missing.png".to_owned(),
+                "textures/wall.png".to_owned(),
+            ]
+        );
+
+        let mut collected = collect_dependencies(&root, &[PathBuf::from("scenes/level.rgs")]);
+        collected.sort();
+        assert_eq!(
+            collected,
+            vec![
+                PathBuf::from("models/crate.rgs"),
+                PathBuf::from("scenes/level.rgs"),
+                PathBuf::from("textures/crate.png"),
+                PathBuf::from("textures/crate.png.options"),
+                PathBuf::from("textures/wall.png"),
+            ]
+        );
+
+        let mut writer = AssetPackWriter::new();
+        writer.add_project(&root).unwrap();
+        assert_eq!(writer.paths().count(), collected.len());
+        let mut data = Cursor::new(Vec::new());
+        writer.write(&mut data).unwrap();
+        let pack = AssetPack::from_memory(data.into_inner()).unwrap();
+        assert_eq!(pack.read("textures/crate.png").unwrap(), b"data".as_slice());
+        assert!(!pack.contains("textures/unused.png"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
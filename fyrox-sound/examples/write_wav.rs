@@ -5,6 +5,7 @@ use fyrox_sound::{
     pool::Handle,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
+use std::time::Duration;
 
 fn main() {
     // Initialize sound engine without output device.
@@ -36,29 +37,11 @@ fn main() {
     // and returns pool handle to it by which it can be accessed later on if needed.
     let _source_handle: Handle<SoundSource> = context.state().add_source(source);
 
-    // Create output wav file. The sample rate is currently fixed.
-    let wav_spec = hound::WavSpec {
-        channels: 2,
-        sample_rate: fyrox_sound::context::SAMPLE_RATE,
-        bits_per_sample: 32,
-        sample_format: hound::SampleFormat::Float,
-    };
-    let mut wav_writer = hound::WavWriter::create("output.wav", wav_spec).unwrap();
-
-    // Create an output buffer.
-    let buf_len = SoundEngine::render_buffer_len();
-    let mut buf = vec![(0.0f32, 0.0f32); buf_len];
-    let mut samples_written = 0;
-
-    // Wait until sound will play completely.
-    while samples_written < 3 * fyrox_sound::context::SAMPLE_RATE {
-        engine.lock().unwrap().render(&mut buf);
-        for &(l, r) in buf.iter() {
-            wav_writer.write_sample(l).unwrap();
-            wav_writer.write_sample(r).unwrap();
-        }
-        samples_written += buf_len as u32;
-    }
-
-    wav_writer.finalize().unwrap();
+    // Render 3 seconds of sound into a wav file. Rendering does not depend on output device and
+    // can be done at any sample rate.
+    engine
+        .lock()
+        .unwrap()
+        .render_to_wav("output.wav", Duration::from_secs(3), 48000)
+        .unwrap();
 }
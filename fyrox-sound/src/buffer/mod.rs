@@ -42,7 +42,7 @@ pub enum DataSource {
         data: Cursor<Vec<u8>>,
     },
 
-    /// Data source is a memory block. Memory block must be in valid format (wav or vorbis/ogg). This variant can
+    /// Data source is a memory block. Memory block must be in valid format (wav, vorbis/ogg, flac or mp3). This variant can
     /// be used together with virtual file system.
     Memory(Cursor<Vec<u8>>),
 
@@ -99,22 +99,35 @@ pub trait RawStreamingDataSource: Iterator<Item = f32> + Send + Sync + Debug {
 
 impl DataSource {
     /// Tries to create new `File` data source from given path. May fail if file does not exists.
+    /// The file is read using current virtual file system (see [`fyrox_core::vfs`]), if the file
+    /// is not stored in the native file system, it is loaded in memory entirely.
     pub async fn from_file<P>(path: P) -> Result<Self, FileLoadError>
     where
         P: AsRef<Path>,
     {
-        Ok(DataSource::File {
-            path: path.as_ref().to_path_buf(),
-
-            #[cfg(not(target_arch = "wasm32"))]
-            data: std::io::BufReader::new(match std::fs::File::open(path) {
-                Ok(file) => file,
-                Err(e) => return Err(FileLoadError::Io(e)),
-            }),
-
-            #[cfg(target_arch = "wasm32")]
-            data: Cursor::new(fyrox_core::io::load_file(path).await?),
-        })
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            match fyrox_core::vfs::file_system().native_path(path.as_ref()) {
+                Some(native_path) => Ok(DataSource::File {
+                    path: path.as_ref().to_path_buf(),
+                    data: std::io::BufReader::new(match std::fs::File::open(native_path) {
+                        Ok(file) => file,
+                        Err(e) => return Err(FileLoadError::Io(e)),
+                    }),
+                }),
+                None => Ok(DataSource::Memory(Cursor::new(
+                    fyrox_core::io::load_file(path).await?,
+                ))),
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(DataSource::File {
+                path: path.as_ref().to_path_buf(),
+                data: Cursor::new(fyrox_core::io::load_file(path).await?),
+            })
+        }
     }
 
     /// Creates new data source from given memory block. This function does not checks if this is valid source or
@@ -0,0 +1,457 @@
+//! Audio bus module.
+//!
+//! # Overview
+//!
+//! Audio buses allow you to group sound sources and control them all at once. For example a game
+//! could have separate buses for music, sound effects, voices and user interface, so volume of each
+//! group can be changed independently in options menu.
+//!
+//! Buses form a tree, the root of the tree is the master bus. Every sound source is routed to a bus
+//! by its name (see [`SoundSource::set_bus`](crate::source::SoundSource::set_bus)), sources with
+//! empty or unknown bus name are routed to the master bus. Each bus mixes its sources, processes
+//! the mix by its chain of effects, applies its gain and passes the result to its parent bus.
+//!
+//! # Mute and solo
+//!
+//! Muted bus produces no sound, including the sound of its descendant buses. If any bus is soloed,
+//! only soloed buses (and their descendants) are audible, sources routed directly to the other buses
+//! are silenced, but buses on the path from soloed bus to the master bus still process the sound.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::bus::AudioBus;
+//! use fyrox_sound::context::SoundContext;
+//!
+//! fn make_buses(context: &mut SoundContext) {
+//!     let mut state = context.state();
+//!     let graph = state.bus_graph_mut();
+//!     let master = graph.root();
+//!     let music = graph.add_bus(AudioBus::new("Music"), master);
+//!     graph.add_bus(AudioBus::new("Ambient"), music);
+//!     graph.add_bus(AudioBus::new("Sfx"), master);
+//!     graph.bus_mut(music).set_gain(0.5);
+//! }
+//! ```
+
+use crate::effects::{Effect, EffectRenderTrait};
+use fyrox_core::{
+    pool::{Handle, Pool},
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::collections::HashMap;
+
+/// Name of the master bus.
+pub const MASTER_BUS_NAME: &str = "Master";
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct AudioBus {
+    name: String,
+    gain: f32,
+    muted: bool,
+    soloed: bool,
+    effects: Vec<Handle<Effect>>,
+    parent: Handle<AudioBus>,
+    children: Vec<Handle<AudioBus>>,
+    #[visit(skip)]
+    buffer: Vec<(f32, f32)>,
+}
+
+impl Default for AudioBus {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl AudioBus {
+    /// Creates new audio bus with given name.
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            gain: 1.0,
+            muted: false,
+            soloed: false,
+            effects: Default::default(),
+            parent: Default::default(),
+            children: Default::default(),
+            buffer: Default::default(),
+        }
+    }
+
+    /// Returns name of the bus.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets new name of the bus. Sound sources are routed to buses by their names, so renaming a
+    /// bus will re-route sources.
+    pub fn set_name<S: AsRef<str>>(&mut self, name: S) {
+        self.name = name.as_ref().to_owned();
+    }
+
+    /// Returns gain of the bus.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Sets gain of the bus, it is applied after the effects.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.max(0.0);
+    }
+
+    /// Returns true if the bus is muted.
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Mutes or unmutes the bus.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    /// Returns true if the bus is soloed.
+    pub fn is_soloed(&self) -> bool {
+        self.soloed
+    }
+
+    /// Solos the bus. See module docs for more info about solo.
+    pub fn set_soloed(&mut self, soloed: bool) {
+        self.soloed = soloed;
+    }
+
+    /// Returns a chain of effects of the bus.
+    pub fn effects(&self) -> &[Handle<Effect>] {
+        &self.effects
+    }
+
+    /// Sets new chain of effects of the bus. Effects are applied one after another to the mix of
+    /// the bus. Effects used by a bus ignore their inputs, and they must not be shared between
+    /// buses.
+    pub fn set_effects(&mut self, effects: Vec<Handle<Effect>>) {
+        self.effects = effects;
+    }
+
+    /// Adds new effect to the end of the chain of effects.
+    pub fn add_effect(&mut self, effect: Handle<Effect>) {
+        self.effects.push(effect);
+    }
+
+    /// Returns handle of the parent bus.
+    pub fn parent(&self) -> Handle<AudioBus> {
+        self.parent
+    }
+
+    /// Returns handles of child buses.
+    pub fn children(&self) -> &[Handle<AudioBus>] {
+        &self.children
+    }
+}
+
+/// Runtime modifiers of a bus, that are applied on top of the bus parameters. They are not
+/// serialized and intended to be used for user settings (volume sliders in options menu, etc.).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusControl {
+    /// Gain multiplier of the bus.
+    pub gain: f32,
+    /// Whether the bus is muted or not.
+    pub muted: bool,
+}
+
+impl Default for BusControl {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            muted: false,
+        }
+    }
+}
+
+/// A tree of audio buses. See module docs for more info.
+#[derive(Debug, Clone, Visit)]
+pub struct AudioBusGraph {
+    buses: Pool<AudioBus>,
+    root: Handle<AudioBus>,
+}
+
+impl Default for AudioBusGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioBusGraph {
+    /// Creates new bus graph with the master bus only.
+    pub fn new() -> Self {
+        let mut buses = Pool::new();
+        let root = buses.spawn(AudioBus::new(MASTER_BUS_NAME));
+        Self { buses, root }
+    }
+
+    /// Returns handle of the master bus.
+    pub fn root(&self) -> Handle<AudioBus> {
+        self.root
+    }
+
+    /// Adds new bus to the graph and attaches it to the given parent. If the parent is invalid,
+    /// the bus will be attached to the master bus.
+    pub fn add_bus(&mut self, mut bus: AudioBus, parent: Handle<AudioBus>) -> Handle<AudioBus> {
+        let parent = if self.buses.is_valid_handle(parent) {
+            parent
+        } else {
+            self.root
+        };
+        bus.parent = parent;
+        bus.children.clear();
+        let handle = self.buses.spawn(bus);
+        self.buses[parent].children.push(handle);
+        handle
+    }
+
+    /// Removes the bus and all its descendants from the graph. Master bus cannot be removed.
+    pub fn remove_bus(&mut self, handle: Handle<AudioBus>) {
+        if handle == self.root || !self.buses.is_valid_handle(handle) {
+            return;
+        }
+
+        let parent = self.buses[handle].parent;
+        if let Some(parent) = self.buses.try_borrow_mut(parent) {
+            parent.children.retain(|c| *c != handle);
+        }
+
+        let mut stack = vec![handle];
+        while let Some(bus) = stack.pop() {
+            stack.extend_from_slice(&self.buses[bus].children);
+            self.buses.free(bus);
+        }
+    }
+
+    /// Attaches the child bus to the new parent bus. Does nothing if the child is the master bus
+    /// or the parent is a descendant of the child.
+    pub fn link_buses(&mut self, child: Handle<AudioBus>, parent: Handle<AudioBus>) {
+        if child == self.root
+            || !self.buses.is_valid_handle(child)
+            || !self.buses.is_valid_handle(parent)
+        {
+            return;
+        }
+
+        // Prevent cycles.
+        let mut ancestor = parent;
+        while ancestor.is_some() {
+            if ancestor == child {
+                return;
+            }
+            ancestor = self.buses[ancestor].parent;
+        }
+
+        let old_parent = self.buses[child].parent;
+        if let Some(old_parent) = self.buses.try_borrow_mut(old_parent) {
+            old_parent.children.retain(|c| *c != child);
+        }
+        self.buses[child].parent = parent;
+        self.buses[parent].children.push(child);
+    }
+
+    /// Returns shared reference to a bus. Panics if the handle is invalid.
+    pub fn bus(&self, handle: Handle<AudioBus>) -> &AudioBus {
+        &self.buses[handle]
+    }
+
+    /// Returns mutable reference to a bus. Panics if the handle is invalid.
+    pub fn bus_mut(&mut self, handle: Handle<AudioBus>) -> &mut AudioBus {
+        &mut self.buses[handle]
+    }
+
+    /// Tries to borrow a bus.
+    pub fn try_get_bus(&self, handle: Handle<AudioBus>) -> Option<&AudioBus> {
+        self.buses.try_borrow(handle)
+    }
+
+    /// Tries to borrow a bus as mutable.
+    pub fn try_get_bus_mut(&mut self, handle: Handle<AudioBus>) -> Option<&mut AudioBus> {
+        self.buses.try_borrow_mut(handle)
+    }
+
+    /// Searches for a bus with the given name, returns [`Handle::NONE`] if there's no such bus.
+    pub fn find_by_name(&self, name: &str) -> Handle<AudioBus> {
+        self.buses
+            .pair_iter()
+            .find_map(|(h, b)| if b.name == name { Some(h) } else { None })
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over all buses with their handles.
+    pub fn buses(&self) -> impl Iterator<Item = (Handle<AudioBus>, &AudioBus)> {
+        self.buses.pair_iter()
+    }
+
+    /// Returns total amount of buses (including the master bus).
+    pub fn bus_count(&self) -> u32 {
+        self.buses.alive_count()
+    }
+
+    pub(crate) fn is_effect_used(&self, effect: Handle<Effect>) -> bool {
+        self.buses.iter().any(|b| b.effects.contains(&effect))
+    }
+
+    fn is_soloed_path(&self, mut bus: Handle<AudioBus>) -> bool {
+        while let Some(b) = self.buses.try_borrow(bus) {
+            if b.soloed {
+                return true;
+            }
+            bus = b.parent;
+        }
+        false
+    }
+
+    /// Clears mix buffers of every bus.
+    pub(crate) fn begin_render(&mut self, amount: usize) {
+        for bus in self.buses.iter_mut() {
+            bus.buffer.clear();
+            bus.buffer.resize(amount, (0.0, 0.0));
+        }
+    }
+
+    /// Returns mix buffer of a bus for a source that is routed to a bus with the given name. Returns
+    /// `None` if the source should not be heard because some other bus is soloed.
+    pub(crate) fn source_buffer(&mut self, bus_name: &str) -> Option<&mut [(f32, f32)]> {
+        let mut bus = self.find_by_name(bus_name);
+        if bus.is_none() {
+            bus = self.root;
+        }
+
+        if self.buses.iter().any(|b| b.soloed) && !self.is_soloed_path(bus) {
+            return None;
+        }
+
+        Some(&mut self.buses[bus].buffer)
+    }
+
+    /// Returns mix buffer of the master bus, or `None` if master bus should not be heard because
+    /// some other bus is soloed.
+    pub(crate) fn master_buffer(&mut self) -> Option<&mut [(f32, f32)]> {
+        if self.buses.iter().any(|b| b.soloed) && !self.buses[self.root].soloed {
+            return None;
+        }
+
+        Some(&mut self.buses[self.root].buffer)
+    }
+
+    /// Processes every bus (children first) and writes the mix of the master bus to the output
+    /// buffer.
+    pub(crate) fn end_render(
+        &mut self,
+        effects: &mut Pool<Effect>,
+        controls: &HashMap<String, BusControl>,
+        output: &mut [(f32, f32)],
+    ) {
+        // Post-order traversal, so every bus is processed after its children.
+        let mut order = Vec::with_capacity(self.buses.alive_count() as usize);
+        let mut stack = vec![(self.root, false)];
+        while let Some((bus, visited)) = stack.pop() {
+            if visited {
+                order.push(bus);
+            } else {
+                stack.push((bus, true));
+                for child in self.buses[bus].children.iter() {
+                    stack.push((*child, false));
+                }
+            }
+        }
+
+        for handle in order {
+            let bus = &mut self.buses[handle];
+            let mut buffer = std::mem::take(&mut bus.buffer);
+
+            let control = controls.get(&bus.name).cloned().unwrap_or_default();
+            let gain = if bus.muted || control.muted {
+                0.0
+            } else {
+                bus.gain * control.gain
+            };
+
+            if gain > 0.0 {
+                for effect in bus.effects.iter() {
+                    if let Some(effect) = effects.try_borrow_mut(*effect) {
+                        effect.process(&mut buffer);
+                    }
+                }
+
+                let parent = bus.parent;
+                let destination = match self.buses.try_borrow_mut(parent) {
+                    Some(parent) => &mut parent.buffer[..],
+                    None => &mut output[..],
+                };
+                for ((out_left, out_right), (left, right)) in
+                    destination.iter_mut().zip(buffer.iter())
+                {
+                    *out_left += gain * left;
+                    *out_right += gain * right;
+                }
+            }
+
+            self.buses[handle].buffer = buffer;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bus::{AudioBus, AudioBusGraph, BusControl};
+    use fyrox_core::pool::Pool;
+    use std::collections::HashMap;
+
+    fn render(graph: &mut AudioBusGraph, controls: &HashMap<String, BusControl>) -> (f32, f32) {
+        graph.begin_render(1);
+        for name in ["Music", "Sfx", "Unknown"] {
+            if let Some(buf) = graph.source_buffer(name) {
+                buf[0].0 += 1.0;
+                buf[0].1 += 1.0;
+            }
+        }
+        let mut output = [(0.0, 0.0)];
+        graph.end_render(&mut Pool::new(), controls, &mut output);
+        output[0]
+    }
+
+    #[test]
+    fn test_bus_routing() {
+        let mut graph = AudioBusGraph::new();
+        let master = graph.root();
+        let music = graph.add_bus(AudioBus::new("Music"), master);
+        let sfx = graph.add_bus(AudioBus::new("Sfx"), master);
+        graph.bus_mut(music).set_gain(0.5);
+
+        let mut controls = HashMap::new();
+        assert_eq!(render(&mut graph, &controls), (2.5, 2.5));
+
+        controls.insert(
+            "Sfx".to_string(),
+            BusControl {
+                gain: 0.25,
+                muted: false,
+            },
+        );
+        assert_eq!(render(&mut graph, &controls), (1.75, 1.75));
+
+        graph.bus_mut(sfx).set_muted(true);
+        assert_eq!(render(&mut graph, &controls), (1.5, 1.5));
+
+        graph.bus_mut(music).set_soloed(true);
+        assert_eq!(render(&mut graph, &controls), (0.5, 0.5));
+
+        graph.bus_mut(music).set_soloed(false);
+        graph.link_buses(sfx, music);
+        graph.bus_mut(sfx).set_muted(false);
+        assert_eq!(render(&mut graph, &controls), (1.625, 1.625));
+
+        // Cycles are not allowed.
+        graph.link_buses(music, sfx);
+        assert_eq!(graph.bus(music).parent(), master);
+
+        graph.remove_bus(music);
+        assert_eq!(graph.bus_count(), 1);
+        assert_eq!(render(&mut graph, &controls), (3.0, 3.0));
+    }
+}
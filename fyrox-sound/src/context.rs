@@ -11,8 +11,11 @@
 //! sounds, only your level will do.
 
 use crate::{
+    bus::{AudioBusGraph, BusControl},
     effects::{Effect, EffectRenderTrait},
+    error::SoundError,
     listener::Listener,
+    offline,
     pool::Ticket,
     renderer::{render_source_default, Renderer},
     source::{SoundSource, Status},
@@ -23,6 +26,8 @@ use fyrox_core::{
     visitor::prelude::*,
 };
 use std::{
+    collections::HashMap,
+    path::Path,
     sync::{Arc, Mutex, MutexGuard},
     time::Duration,
 };
@@ -108,6 +113,7 @@ pub struct State {
     effects: Pool<Effect>,
     distance_model: DistanceModel,
     paused: bool,
+    bus_graph: AudioBusGraph,
 }
 
 impl State {
@@ -261,7 +267,22 @@ impl State {
         self.effects.borrow_mut(handle)
     }
 
-    pub(crate) fn render(&mut self, master_gain: f32, buf: &mut [(f32, f32)]) {
+    /// Returns shared reference to the tree of audio buses.
+    pub fn bus_graph(&self) -> &AudioBusGraph {
+        &self.bus_graph
+    }
+
+    /// Returns mutable reference to the tree of audio buses.
+    pub fn bus_graph_mut(&mut self) -> &mut AudioBusGraph {
+        &mut self.bus_graph
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        master_gain: f32,
+        bus_controls: &HashMap<String, BusControl>,
+        buf: &mut [(f32, f32)],
+    ) {
         let last_time = fyrox_core::instant::Instant::now();
 
         if !self.paused {
@@ -270,6 +291,8 @@ impl State {
                 !done
             });
 
+            self.bus_graph.begin_render(buf.len());
+
             for source in self
                 .sources
                 .iter_mut()
@@ -277,26 +300,50 @@ impl State {
             {
                 source.render(buf.len());
 
+                let bus_buf = match self.bus_graph.source_buffer(source.bus()) {
+                    Some(bus_buf) => bus_buf,
+                    None => continue,
+                };
+
                 match self.renderer {
                     Renderer::Default => {
                         // Simple rendering path. Much faster (4-5 times) than HRTF path.
-                        render_source_default(source, &self.listener, self.distance_model, buf);
+                        render_source_default(source, &self.listener, self.distance_model, bus_buf);
                     }
                     Renderer::HrtfRenderer(ref mut hrtf_renderer) => {
                         hrtf_renderer.render_source(
                             source,
                             &self.listener,
                             self.distance_model,
-                            buf,
+                            bus_buf,
                         );
                     }
                 }
             }
 
-            for effect in self.effects.iter_mut() {
-                effect.render(&self.sources, &self.listener, self.distance_model, buf);
+            // Effects that are not used as inserts on buses work as sends - they take their inputs
+            // directly and mix the result into the master bus.
+            let bus_graph = &mut self.bus_graph;
+            let sends = self
+                .effects
+                .pair_iter_mut()
+                .filter(|(handle, _)| !bus_graph.is_effect_used(*handle))
+                .map(|(_, effect)| effect)
+                .collect::<Vec<_>>();
+            if let Some(master_buf) = bus_graph.master_buffer() {
+                for effect in sends {
+                    effect.render(
+                        &self.sources,
+                        &self.listener,
+                        self.distance_model,
+                        master_buf,
+                    );
+                }
             }
 
+            self.bus_graph
+                .end_render(&mut self.effects, bus_controls, buf);
+
             let global_gain = self.master_gain * master_gain;
 
             // Apply master gain to be able to control total sound volume.
@@ -334,6 +381,7 @@ impl SoundContext {
                 effects: Pool::new(),
                 distance_model: DistanceModel::InverseDistance,
                 paused: false,
+                bus_graph: AudioBusGraph::new(),
             }))),
         }
     }
@@ -366,6 +414,29 @@ impl SoundContext {
         }
     }
 
+    /// Renders given amount of time of the context into a buffer with given sample rate. It does
+    /// not need an output device and it is fully deterministic. The context must not be used by
+    /// an engine with an output device, otherwise the device thread will advance the state of
+    /// the context too. See [`crate::offline`] module docs for more info.
+    pub fn render_to_buffer(&self, duration: Duration, sample_rate: u32) -> Vec<(f32, f32)> {
+        let bus_controls = Default::default();
+        offline::render(duration, sample_rate, |buf| {
+            self.state().render(1.0, &bus_controls, buf)
+        })
+    }
+
+    /// Renders given amount of time of the context into a WAV file with given sample rate. See
+    /// [`Self::render_to_buffer`] for more info.
+    pub fn render_to_wav<P: AsRef<Path>>(
+        &self,
+        path: P,
+        duration: Duration,
+        sample_rate: u32,
+    ) -> Result<(), SoundError> {
+        let samples = self.render_to_buffer(duration, sample_rate);
+        offline::write_wav(path, &samples, sample_rate)
+    }
+
     /// Returns true if context is corrupted.
     pub fn is_invalid(&self) -> bool {
         self.state.is_none()
@@ -389,6 +460,8 @@ impl Visit for State {
         self.renderer.visit("Renderer", &mut region)?;
         self.paused.visit("Paused", &mut region)?;
         self.distance_model.visit("DistanceModel", &mut region)?;
+        // Backward compatibility.
+        let _ = self.bus_graph.visit("BusGraph", &mut region);
 
         Ok(())
     }
@@ -1,17 +1,58 @@
 use crate::{
     buffer::DataSource,
-    decoder::{vorbis::OggDecoder, wav::WavDecoder},
+    decoder::{symphonia::SymphoniaDecoder, vorbis::OggDecoder, wav::WavDecoder},
     error::SoundError,
 };
-use std::time::Duration;
+use std::{
+    io::{Read, Seek, SeekFrom},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
+mod symphonia;
 mod vorbis;
 mod wav;
 
+/// Shareable data source, it is used to probe a data source by a decoder that takes ownership
+/// of the source and to get the source back if probing has failed.
+#[derive(Clone)]
+struct WrappedDataSource {
+    data_source: Arc<Mutex<DataSource>>,
+}
+
+impl WrappedDataSource {
+    fn new(data_source: DataSource) -> Self {
+        Self {
+            data_source: Arc::new(Mutex::new(data_source)),
+        }
+    }
+
+    fn into_inner(self) -> DataSource {
+        Arc::try_unwrap(self.data_source)
+            .unwrap()
+            .into_inner()
+            .unwrap()
+    }
+}
+
+impl Read for WrappedDataSource {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
+        self.data_source.lock().unwrap().read(buf)
+    }
+}
+
+impl Seek for WrappedDataSource {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, std::io::Error> {
+        self.data_source.lock().unwrap().seek(pos)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum Decoder {
     Wav(WavDecoder),
     Ogg(OggDecoder),
+    Flac(SymphoniaDecoder),
+    Mp3(SymphoniaDecoder),
 }
 
 impl Iterator for Decoder {
@@ -21,6 +62,8 @@ impl Iterator for Decoder {
         match self {
             Decoder::Wav(wav) => wav.next(),
             Decoder::Ogg(ogg) => ogg.next(),
+            Decoder::Flac(flac) => flac.next(),
+            Decoder::Mp3(mp3) => mp3.next(),
         }
     }
 }
@@ -37,6 +80,16 @@ impl Decoder {
             Ok(ogg_decoder) => return Ok(Decoder::Ogg(ogg_decoder)),
             Err(source) => source,
         };
+        // Try FLAC
+        let source = match SymphoniaDecoder::new_flac(source) {
+            Ok(flac_decoder) => return Ok(Decoder::Flac(flac_decoder)),
+            Err(source) => source,
+        };
+        // Try MP3. It must be the last one, because MP3 does not have a reliable signature.
+        let source = match SymphoniaDecoder::new_mp3(source) {
+            Ok(mp3_decoder) => return Ok(Decoder::Mp3(mp3_decoder)),
+            Err(source) => source,
+        };
         Err(source)
     }
 
@@ -44,13 +97,20 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.rewind(),
             Decoder::Ogg(ogg) => ogg.rewind(),
+            Decoder::Flac(flac) => flac.rewind(),
+            Decoder::Mp3(mp3) => mp3.rewind(),
         }
     }
 
-    pub fn time_seek(&mut self, location: Duration) {
+    pub fn time_seek(&mut self, location: Duration) -> Result<(), SoundError> {
         match self {
             Decoder::Wav(wav) => wav.time_seek(location),
-            Decoder::Ogg(ogg) => ogg.time_seek(location),
+            Decoder::Ogg(ogg) => {
+                ogg.time_seek(location);
+                Ok(())
+            }
+            Decoder::Flac(flac) => flac.time_seek(location),
+            Decoder::Mp3(mp3) => mp3.time_seek(location),
         }
     }
 
@@ -58,6 +118,8 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.channel_count(),
             Decoder::Ogg(ogg) => ogg.channel_count,
+            Decoder::Flac(flac) => flac.channel_count(),
+            Decoder::Mp3(mp3) => mp3.channel_count(),
         }
     }
 
@@ -65,6 +127,8 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.sample_rate(),
             Decoder::Ogg(ogg) => ogg.sample_rate,
+            Decoder::Flac(flac) => flac.sample_rate(),
+            Decoder::Mp3(mp3) => mp3.sample_rate(),
         }
     }
 
@@ -76,6 +140,8 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.duration(),
             Decoder::Ogg(ogg) => ogg.duration(),
+            Decoder::Flac(flac) => flac.duration(),
+            Decoder::Mp3(mp3) => mp3.duration(),
         }
     }
 }
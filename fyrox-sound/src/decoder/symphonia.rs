@@ -0,0 +1,300 @@
+//! FLAC and MP3 decoders. Both are backed by `symphonia` crate, the only difference is the
+//! container reader and the codec used.
+
+use crate::{buffer::DataSource, decoder::WrappedDataSource, error::SoundError};
+use std::{
+    fmt::{Debug, Formatter},
+    io::{Read, Seek, SeekFrom},
+    time::Duration,
+};
+use symphonia::{
+    core::{
+        audio::SampleBuffer,
+        codecs::{Decoder, DecoderOptions},
+        errors::Error,
+        formats::{FormatOptions, FormatReader, SeekMode, SeekTo},
+        io::{MediaSource, MediaSourceStream},
+    },
+    default::{
+        codecs::{FlacDecoder, MpaDecoder},
+        formats::{FlacReader, MpaReader},
+    },
+};
+
+impl MediaSource for WrappedDataSource {
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        let mut source = self.data_source.lock().unwrap();
+        let pos = source.stream_position().ok()?;
+        let len = source.seek(SeekFrom::End(0)).ok()?;
+        source.seek(SeekFrom::Start(pos)).ok()?;
+        Some(len)
+    }
+}
+
+/// Decoder for formats supported by `symphonia`.
+pub(crate) struct SymphoniaDecoder {
+    reader: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+    sample_buffer: Option<SampleBuffer<f32>>,
+    position: usize,
+    // Amount of samples to drop after seeking, accurate seeking may start from an earlier
+    // packet.
+    samples_to_skip: usize,
+    channel_count: usize,
+    sample_rate: usize,
+    frame_count: Option<u64>,
+}
+
+impl Debug for SymphoniaDecoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SymphoniaDecoder")
+    }
+}
+
+// Reads first bytes of the source and restores its position, returns `None` on i/o errors.
+fn read_magic<const N: usize>(source: &mut DataSource) -> Option<[u8; N]> {
+    let pos = source.stream_position().ok()?;
+
+    let mut magic = [0; N];
+    let result = source.read_exact(&mut magic);
+
+    source.seek(SeekFrom::Start(pos)).ok()?;
+
+    result.ok().map(|_| magic)
+}
+
+fn is_flac(source: &mut DataSource) -> bool {
+    matches!(read_magic::<4>(source), Some(magic) if &magic == b"fLaC")
+}
+
+fn is_mp3(source: &mut DataSource) -> bool {
+    // MP3 file starts either with ID3v2 tag or with a frame sync word.
+    matches!(read_magic::<3>(source), Some(magic)
+        if &magic == b"ID3" || (magic[0] == 0xFF && magic[1] & 0xE0 == 0xE0))
+}
+
+impl SymphoniaDecoder {
+    fn new<R, D>(mut source: DataSource) -> Result<Self, DataSource>
+    where
+        R: FormatReader + 'static,
+        D: Decoder + 'static,
+    {
+        let pos = match source.stream_position() {
+            Ok(pos) => pos,
+            Err(_) => return Err(source),
+        };
+        let wrapped_source = WrappedDataSource::new(source);
+
+        let stream = MediaSourceStream::new(Box::new(wrapped_source.clone()), Default::default());
+        let result = R::try_new(stream, &FormatOptions::default()).and_then(|reader| {
+            let track = reader
+                .default_track()
+                .ok_or(Error::Unsupported("no tracks"))?;
+            let track_id = track.id;
+            let params = track.codec_params.clone();
+            let decoder = D::try_new(&params, &DecoderOptions::default())?;
+            Ok((reader, decoder, track_id, params))
+        });
+
+        match result {
+            Ok((reader, decoder, track_id, params)) => Ok(Self {
+                reader: Box::new(reader),
+                decoder: Box::new(decoder),
+                track_id,
+                sample_buffer: None,
+                position: 0,
+                samples_to_skip: 0,
+                channel_count: params.channels.map(|c| c.count()).unwrap_or(1),
+                sample_rate: params.sample_rate.unwrap_or(44100) as usize,
+                frame_count: params.n_frames,
+            }),
+            Err(_) => {
+                let mut source = wrapped_source.into_inner();
+                // The source is returned as is, next decoder will fail on the same i/o error.
+                let _ = source.seek(SeekFrom::Start(pos));
+                Err(source)
+            }
+        }
+    }
+
+    pub fn new_flac(mut source: DataSource) -> Result<Self, DataSource> {
+        if is_flac(&mut source) {
+            Self::new::<FlacReader, FlacDecoder>(source)
+        } else {
+            Err(source)
+        }
+    }
+
+    pub fn new_mp3(mut source: DataSource) -> Result<Self, DataSource> {
+        if is_mp3(&mut source) {
+            Self::new::<MpaReader, MpaDecoder>(source)
+        } else {
+            Err(source)
+        }
+    }
+
+    fn decode_next_packet(&mut self) -> bool {
+        loop {
+            let packet = match self.reader.next_packet() {
+                Ok(packet) => packet,
+                Err(_) => return false,
+            };
+
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    let required_capacity = decoded.capacity() * decoded.spec().channels.count();
+                    if self
+                        .sample_buffer
+                        .as_ref()
+                        .map_or(true, |b| b.capacity() < required_capacity)
+                    {
+                        self.sample_buffer = Some(SampleBuffer::new(
+                            decoded.capacity() as u64,
+                            *decoded.spec(),
+                        ));
+                    }
+                    if let Some(sample_buffer) = self.sample_buffer.as_mut() {
+                        sample_buffer.copy_interleaved_ref(decoded);
+                    }
+                    self.position = 0;
+                    return true;
+                }
+                // Malformed packets are skipped.
+                Err(Error::DecodeError(_)) => continue,
+                Err(_) => return false,
+            }
+        }
+    }
+
+    pub fn rewind(&mut self) -> Result<(), SoundError> {
+        self.seek(Duration::from_secs(0))
+            .map_err(|_| SoundError::UnsupportedFormat)
+    }
+
+    pub fn time_seek(&mut self, location: Duration) -> Result<(), SoundError> {
+        Ok(self.seek(location)?)
+    }
+
+    fn seek(&mut self, location: Duration) -> Result<(), Error> {
+        let seeked_to = self.reader.seek(
+            SeekMode::Accurate,
+            SeekTo::Time {
+                time: location.into(),
+                track_id: Some(self.track_id),
+            },
+        )?;
+        self.decoder.reset();
+        if let Some(buffer) = self.sample_buffer.as_mut() {
+            buffer.clear();
+        }
+        self.position = 0;
+        self.samples_to_skip =
+            seeked_to.required_ts.saturating_sub(seeked_to.actual_ts) as usize * self.channel_count;
+        Ok(())
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        self.frame_count
+            .map(|n| Duration::from_secs_f64(n as f64 / self.sample_rate as f64))
+    }
+
+    pub fn channel_count(&self) -> usize {
+        self.channel_count
+    }
+
+    pub fn sample_rate(&self) -> usize {
+        self.sample_rate
+    }
+}
+
+impl Iterator for SymphoniaDecoder {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sample) = self
+                .sample_buffer
+                .as_ref()
+                .and_then(|b| b.samples().get(self.position))
+            {
+                self.position += 1;
+                if self.samples_to_skip > 0 {
+                    self.samples_to_skip -= 1;
+                    continue;
+                }
+                return Some(*sample);
+            }
+
+            if !self.decode_next_packet() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::DataSource,
+        decoder::symphonia::{is_flac, is_mp3, SymphoniaDecoder},
+    };
+    use std::io::{Cursor, Seek};
+
+    fn load(path: &str) -> DataSource {
+        DataSource::Memory(Cursor::new(std::fs::read(path).unwrap()))
+    }
+
+    #[test]
+    fn test_format_detection() {
+        let mut flac = load("test_data/sine.flac");
+        assert!(is_flac(&mut flac));
+        assert!(!is_mp3(&mut flac));
+        // Detection must not change the position in the source.
+        assert_eq!(flac.stream_position().unwrap(), 0);
+
+        let mut mp3 = load("test_data/silence.mp3");
+        assert!(is_mp3(&mut mp3));
+        assert!(!is_flac(&mut mp3));
+
+        let mut empty = DataSource::Memory(Cursor::new(Vec::new()));
+        assert!(!is_flac(&mut empty));
+        assert!(!is_mp3(&mut empty));
+    }
+
+    #[test]
+    fn test_flac_decoding() {
+        assert!(SymphoniaDecoder::new_flac(load("test_data/silence.mp3")).is_err());
+
+        let decoder = SymphoniaDecoder::new_flac(load("test_data/sine.flac")).unwrap();
+        assert_eq!(decoder.channel_count(), 2);
+        assert_eq!(decoder.sample_rate(), 44100);
+
+        let samples = decoder.collect::<Vec<_>>();
+        assert_eq!(samples.len(), 2 * 1000);
+        // Right channel is inverted left channel.
+        assert!(samples.chunks(2).all(|frame| frame[0] == -frame[1]));
+        assert!(samples.iter().any(|&sample| sample != 0.0));
+    }
+
+    #[test]
+    fn test_mp3_decoding() {
+        let decoder = SymphoniaDecoder::new_mp3(load("test_data/silence.mp3")).unwrap();
+        assert_eq!(decoder.channel_count(), 1);
+        assert_eq!(decoder.sample_rate(), 44100);
+
+        // 10 frames, 1152 samples each.
+        let samples = decoder.collect::<Vec<_>>();
+        assert_eq!(samples.len(), 10 * 1152);
+        assert!(samples.iter().all(|&sample| sample == 0.0));
+    }
+}
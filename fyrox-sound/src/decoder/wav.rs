@@ -1,9 +1,8 @@
-use crate::{buffer::DataSource, error::SoundError};
+use crate::{buffer::DataSource, decoder::WrappedDataSource, error::SoundError};
 use hound::WavReader;
 use std::{
     fmt::{Debug, Formatter},
-    io::{Read, Seek, SeekFrom},
-    sync::{Arc, Mutex},
+    io::{Seek, SeekFrom},
     time::Duration,
 };
 
@@ -18,38 +17,10 @@ impl Debug for WavDecoder {
     }
 }
 
-#[derive(Clone)]
-struct WrappedDataSource {
-    data_source: Arc<Mutex<DataSource>>,
-}
-
-impl WrappedDataSource {
-    fn into_inner(self) -> DataSource {
-        Arc::try_unwrap(self.data_source)
-            .unwrap()
-            .into_inner()
-            .unwrap()
-    }
-}
-
-impl Read for WrappedDataSource {
-    fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
-        self.data_source.lock().unwrap().read(buf)
-    }
-}
-
-impl Seek for WrappedDataSource {
-    fn seek(&mut self, pos: SeekFrom) -> Result<u64, std::io::Error> {
-        self.data_source.lock().unwrap().seek(pos)
-    }
-}
-
 impl WavDecoder {
     pub fn new(mut source: DataSource) -> Result<Self, DataSource> {
         let pos = source.seek(SeekFrom::Current(0)).unwrap();
-        let mut wrapped_source = WrappedDataSource {
-            data_source: Arc::new(Mutex::new(source)),
-        };
+        let mut wrapped_source = WrappedDataSource::new(source);
 
         let reader = match WavReader::new(wrapped_source.clone()) {
             Ok(old_reader) => {
@@ -74,10 +45,10 @@ impl WavDecoder {
         Ok(())
     }
 
-    pub fn time_seek(&mut self, location: Duration) {
-        let _ = self
-            .reader
-            .seek((location.as_secs_f64() * self.reader.spec().sample_rate as f64) as u32);
+    pub fn time_seek(&mut self, location: Duration) -> Result<(), SoundError> {
+        self.reader
+            .seek((location.as_secs_f64() * self.reader.spec().sample_rate as f64) as u32)?;
+        Ok(())
     }
 
     pub fn duration(&self) -> Option<Duration> {
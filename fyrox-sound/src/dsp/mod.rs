@@ -50,6 +50,27 @@ impl DelayLine {
     pub fn last(&self) -> f32 {
         self.last
     }
+
+    /// Returns a sample that was fed `delay` samples ago, `tap(0.0)` returns the most recent sample.
+    /// Fractional delays are linearly interpolated between two neighbour samples, this allows to
+    /// smoothly modulate delay time (for chorus, flanger, etc). Delay is clamped to the length of
+    /// the line.
+    pub fn tap(&self, delay: f32) -> f32 {
+        let len = self.samples.len();
+        if len < 2 {
+            return self.samples[0];
+        }
+
+        let delay = delay.clamp(0.0, (len - 1) as f32);
+        let whole = delay as usize;
+        let fraction = delay - whole as f32;
+
+        // `pos` points to the oldest sample, so the most recent one is right before it.
+        let newer = (self.pos as usize + len - 1 - whole) % len;
+        let older = (newer + len - 1) % len;
+
+        self.samples[newer] + (self.samples[older] - self.samples[newer]) * fraction
+    }
 }
 
 impl Default for DelayLine {
@@ -62,6 +83,47 @@ impl Default for DelayLine {
     }
 }
 
+/// Low frequency oscillator, produces sine wave of given frequency. It is used to modulate
+/// parameters of effects, for example delay time of chorus or flanger.
+#[derive(Default, Debug, Clone, Visit)]
+pub struct Lfo {
+    phase: f32,
+    step: f32,
+}
+
+impl Lfo {
+    /// Creates new oscillator with given normalized frequency and initial phase. Phase is given
+    /// in periods, i.e. `0.25` means 90 degrees shift.
+    pub fn new(fc: f32, phase: f32) -> Self {
+        Self {
+            phase: phase.fract(),
+            step: fc,
+        }
+    }
+
+    /// Sets new normalized frequency of the oscillator.
+    pub fn set_fc(&mut self, fc: f32) {
+        self.step = fc;
+    }
+
+    /// Returns current value in `[-1; 1]` range and advances the oscillator by one sample.
+    pub fn next_value(&mut self) -> f32 {
+        let value = (2.0 * std::f32::consts::PI * self.phase).sin();
+        self.phase = (self.phase + self.step).fract();
+        value
+    }
+}
+
+/// Converts decibels to linear gain.
+pub fn db_to_gain(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+/// Converts linear gain to decibels.
+pub fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.max(f32::EPSILON).log10()
+}
+
 /// Calculates single coefficient of Hamming window.
 /// <https://en.wikipedia.org/wiki/Window_function#Hamming_window>
 pub fn hamming_window(i: usize, sample_count: usize) -> f32 {
@@ -0,0 +1,217 @@
+//! Chorus module
+//!
+//! # Overview
+//!
+//! Chorus mixes input signal with its copy delayed by slowly changing amount of time. This gives
+//! the impression of multiple sources playing the same sound at once, which makes sound "thicker".
+//! Delay of left and right channels is modulated with phase shift, so chorus also widens stereo
+//! image.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::chorus::Chorus;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_chorus(context: &mut SoundContext) {
+//!     let mut chorus = Chorus::new(BaseEffect::default());
+//!     chorus.set_rate(0.8);
+//!     context.state().add_effect(Effect::Chorus(chorus));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::{DelayLine, Lfo},
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+#[derive(Default, Debug, Clone, Visit)]
+struct ChannelChorus {
+    delay_line: DelayLine,
+    lfo: Lfo,
+}
+
+impl ChannelChorus {
+    fn new(max_delay: usize, rate: f32, phase: f32) -> Self {
+        Self {
+            // Extra sample is needed for interpolation.
+            delay_line: DelayLine::new(max_delay + 2),
+            lfo: Lfo::new(rate / SAMPLE_RATE as f32, phase),
+        }
+    }
+
+    fn feed(&mut self, sample: f32, delay: f32, depth: f32) -> f32 {
+        self.delay_line.feed(sample);
+        self.delay_line.tap(delay + depth * self.lfo.next_value())
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Chorus {
+    base: BaseEffect,
+    dry: f32,
+    wet: f32,
+    delay: f32,
+    depth: f32,
+    rate: f32,
+    left: ChannelChorus,
+    right: ChannelChorus,
+}
+
+impl Default for Chorus {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Chorus {
+    /// Creates new chorus effect with 20 ms delay modulated by 5 ms at 1.5 Hz rate.
+    pub fn new(base: BaseEffect) -> Self {
+        let mut chorus = Self {
+            base,
+            dry: 1.0,
+            wet: 0.5,
+            delay: 0.02,
+            depth: 0.005,
+            rate: 1.5,
+            left: Default::default(),
+            right: Default::default(),
+        };
+        chorus.reset_channels();
+        chorus
+    }
+
+    fn reset_channels(&mut self) {
+        let max_delay = ((self.delay + self.depth) * SAMPLE_RATE as f32).ceil() as usize;
+        self.left = ChannelChorus::new(max_delay, self.rate, 0.0);
+        // Quarter of period between channels gives nice stereo effect.
+        self.right = ChannelChorus::new(max_delay, self.rate, 0.25);
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output. Default value is 0.5.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Sets average delay of modulated signal. Typical values are in 10-30 ms range.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay.as_secs_f32();
+        self.reset_channels();
+    }
+
+    /// Returns average delay of modulated signal.
+    pub fn delay(&self) -> Duration {
+        Duration::from_secs_f32(self.delay)
+    }
+
+    /// Sets maximum deviation of delay from its average value. It should not exceed the delay.
+    pub fn set_depth(&mut self, depth: Duration) {
+        self.depth = depth.as_secs_f32();
+        self.reset_channels();
+    }
+
+    /// Returns maximum deviation of delay from its average value.
+    pub fn depth(&self) -> Duration {
+        Duration::from_secs_f32(self.depth)
+    }
+
+    /// Sets frequency (in Hz) of delay modulation. Typical values are in 0.1-3 Hz range.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+        let fc = self.rate / SAMPLE_RATE as f32;
+        self.left.lfo.set_fc(fc);
+        self.right.lfo.set_fc(fc);
+    }
+
+    /// Returns frequency (in Hz) of delay modulation.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    pub(crate) fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let delay = self.delay * SAMPLE_RATE as f32;
+        let depth = self.depth.min(self.delay) * SAMPLE_RATE as f32;
+
+        let modulated_left = self.left.feed(left, delay, depth);
+        let modulated_right = self.right.feed(right, delay, depth);
+
+        (
+            self.dry * left + self.wet * modulated_left,
+            self.dry * right + self.wet * modulated_right,
+        )
+    }
+}
+
+impl EffectRenderTrait for Chorus {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
+        {
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += self.base.gain * processed_left;
+            *out_right += self.base.gain * processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = gain * processed_left;
+            *right = gain * processed_right;
+        }
+    }
+}
+
+impl Deref for Chorus {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Chorus {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
@@ -0,0 +1,209 @@
+//! Compressor module
+//!
+//! # Overview
+//!
+//! Compressor reduces dynamic range of a signal - it attenuates parts of the signal that are louder
+//! than some threshold, while quiet parts are left untouched. Attenuation is defined by ratio, for
+//! example ratio of 4.0 means that every 4 dB above threshold will become 1 dB in the output.
+//! Compressor with very high ratio (20.0 and more) and short attack time acts as a limiter - it
+//! won't let the signal go above the threshold.
+//!
+//! Level of the signal is measured for both channels at once, so compression does not shift
+//! stereo image.
+//!
+//! # Usage
+//!
+//! ```
+//! use std::time::Duration;
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::compressor::Compressor;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_limiter(context: &mut SoundContext) {
+//!     let mut limiter = Compressor::new(BaseEffect::default());
+//!     limiter.set_threshold(-1.0);
+//!     limiter.set_ratio(100.0);
+//!     limiter.set_attack(Duration::from_millis(1));
+//!     context.state().add_effect(Effect::Compressor(limiter));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::{db_to_gain, gain_to_db},
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Compressor {
+    base: BaseEffect,
+    threshold: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+    makeup_gain: f32,
+    envelope: f32,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+/// Calculates coefficient of one-pole smoothing filter that reaches ~63% of the target value
+/// in given time.
+fn smoothing_coefficient(time: f32) -> f32 {
+    if time > 0.0 {
+        (-1.0 / (time * SAMPLE_RATE as f32)).exp()
+    } else {
+        0.0
+    }
+}
+
+impl Compressor {
+    /// Creates new compressor with -20 dB threshold, 4:1 ratio, 5 ms attack and 100 ms release.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            threshold: -20.0,
+            ratio: 4.0,
+            attack: 0.005,
+            release: 0.1,
+            makeup_gain: 0.0,
+            envelope: 0.0,
+        }
+    }
+
+    /// Sets level (in decibels) above which the signal will be attenuated. Should be less or
+    /// equal to zero, since 0 dB is the maximum level of the signal.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.min(0.0);
+    }
+
+    /// Returns current threshold in decibels.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Sets compression ratio, should be equal or greater than 1.0. Ratio of 1.0 means that
+    /// there will be no compression at all.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns compression ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets how fast compressor will react on signals that are louder than the threshold.
+    pub fn set_attack(&mut self, attack: Duration) {
+        self.attack = attack.as_secs_f32();
+    }
+
+    /// Returns attack time.
+    pub fn attack(&self) -> Duration {
+        Duration::from_secs_f32(self.attack)
+    }
+
+    /// Sets how fast compressor will stop attenuating the signal after it went below the
+    /// threshold.
+    pub fn set_release(&mut self, release: Duration) {
+        self.release = release.as_secs_f32();
+    }
+
+    /// Returns release time.
+    pub fn release(&self) -> Duration {
+        Duration::from_secs_f32(self.release)
+    }
+
+    /// Sets gain (in decibels) that will be applied to compressed signal. It is used to
+    /// compensate loss of loudness because of the compression.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) {
+        self.makeup_gain = makeup_gain;
+    }
+
+    /// Returns makeup gain in decibels.
+    pub fn makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+
+    pub(crate) fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let level = left.abs().max(right.abs());
+
+        let coefficient = if level > self.envelope {
+            smoothing_coefficient(self.attack)
+        } else {
+            smoothing_coefficient(self.release)
+        };
+        self.envelope = level + coefficient * (self.envelope - level);
+
+        let overshoot = gain_to_db(self.envelope) - self.threshold;
+        let reduction = if overshoot > 0.0 {
+            overshoot * (1.0 - 1.0 / self.ratio)
+        } else {
+            0.0
+        };
+
+        let gain = db_to_gain(self.makeup_gain - reduction);
+
+        (left * gain, right * gain)
+    }
+}
+
+impl EffectRenderTrait for Compressor {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
+        {
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += self.base.gain * processed_left;
+            *out_right += self.base.gain * processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = gain * processed_left;
+            *right = gain * processed_right;
+        }
+    }
+}
+
+impl Deref for Compressor {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Compressor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
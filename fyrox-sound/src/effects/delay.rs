@@ -0,0 +1,184 @@
+//! Delay (echo) module
+//!
+//! # Overview
+//!
+//! Delay repeats input signal after some period of time, every next repetition is quieter than
+//! previous one. It is the simplest way to simulate echo in large open spaces (mountains, canyons,
+//! etc.).
+//!
+//! # Usage
+//!
+//! ```
+//! use std::time::Duration;
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::delay::Delay;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_echo(context: &mut SoundContext) {
+//!     let mut delay = Delay::new(BaseEffect::default());
+//!     delay.set_delay_time(Duration::from_millis(350));
+//!     delay.set_feedback(0.4);
+//!     context.state().add_effect(Effect::Delay(delay));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::DelayLine,
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Delay {
+    base: BaseEffect,
+    dry: f32,
+    wet: f32,
+    feedback: f32,
+    delay_time: f32,
+    left: DelayLine,
+    right: DelayLine,
+}
+
+impl Default for Delay {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+fn delay_len(delay_time: f32) -> usize {
+    ((delay_time * SAMPLE_RATE as f32).round() as usize).max(1)
+}
+
+impl Delay {
+    /// Creates new delay effect with 250 ms delay and 0.5 feedback.
+    pub fn new(base: BaseEffect) -> Self {
+        let delay_time = 0.25;
+        Self {
+            base,
+            dry: 1.0,
+            wet: 1.0,
+            feedback: 0.5,
+            delay_time,
+            left: DelayLine::new(delay_len(delay_time)),
+            right: DelayLine::new(delay_len(delay_time)),
+        }
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of delayed signal should be passed to output. Default value is 1.0.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Sets how much of delayed signal should be fed back to the input, in other words it defines
+    /// how fast echoes will fade out. Should be in `[0; 1)` range, 0.0 - gives single echo.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        // Feedback of 1.0 and more will make the effect unstable.
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets time between repetitions. Changing delay time clears the echoes that are currently
+    /// playing.
+    pub fn set_delay_time(&mut self, delay_time: Duration) {
+        let delay_time = delay_time.as_secs_f32();
+        if delay_time != self.delay_time {
+            self.delay_time = delay_time;
+            self.left = DelayLine::new(delay_len(delay_time));
+            self.right = DelayLine::new(delay_len(delay_time));
+        }
+    }
+
+    /// Returns time between repetitions.
+    pub fn delay_time(&self) -> Duration {
+        Duration::from_secs_f32(self.delay_time)
+    }
+
+    pub(crate) fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let delayed_left = self.left.tap((self.left.len() - 1) as f32);
+        let delayed_right = self.right.tap((self.right.len() - 1) as f32);
+
+        self.left.feed(left + self.feedback * delayed_left);
+        self.right.feed(right + self.feedback * delayed_right);
+
+        (
+            self.dry * left + self.wet * delayed_left,
+            self.dry * right + self.wet * delayed_right,
+        )
+    }
+}
+
+impl EffectRenderTrait for Delay {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
+        {
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += self.base.gain * processed_left;
+            *out_right += self.base.gain * processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = gain * processed_left;
+            *right = gain * processed_right;
+        }
+    }
+}
+
+impl Deref for Delay {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Delay {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
@@ -0,0 +1,146 @@
+//! Distortion module
+//!
+//! # Overview
+//!
+//! Distortion amplifies input signal and then softly clips it, this adds lots of harmonics to the
+//! signal and gives it "dirty" sound, like the one of an overdriven guitar amplifier or a broken
+//! radio. Clipping is done using hyperbolic tangent, so output signal never exceeds `[-1; 1]`
+//! range.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::distortion::Distortion;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_distortion(context: &mut SoundContext) {
+//!     let mut distortion = Distortion::new(BaseEffect::default());
+//!     distortion.set_drive(20.0);
+//!     context.state().add_effect(Effect::Distortion(distortion));
+//! }
+//! ```
+
+use crate::{
+    context::DistanceModel,
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::ops::{Deref, DerefMut};
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Distortion {
+    base: BaseEffect,
+    dry: f32,
+    wet: f32,
+    drive: f32,
+}
+
+impl Default for Distortion {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Distortion {
+    /// Creates new distortion effect with drive of 10.0.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            dry: 0.0,
+            wet: 1.0,
+            drive: 10.0,
+        }
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 0.0.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of distorted signal should be passed to output. Default value is 1.0.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Sets amplification of input signal before clipping. The higher the value, the more signal
+    /// will be clipped and the more "dirty" it will sound. Values close to 1.0 give very subtle
+    /// saturation.
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive = drive.max(0.0);
+    }
+
+    /// Returns current drive.
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    pub(crate) fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        (
+            self.dry * left + self.wet * (self.drive * left).tanh(),
+            self.dry * right + self.wet * (self.drive * right).tanh(),
+        )
+    }
+}
+
+impl EffectRenderTrait for Distortion {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
+        {
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += self.base.gain * processed_left;
+            *out_right += self.base.gain * processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = gain * processed_left;
+            *right = gain * processed_right;
+        }
+    }
+}
+
+impl Deref for Distortion {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Distortion {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
@@ -0,0 +1,228 @@
+//! Equalizer module
+//!
+//! # Overview
+//!
+//! Parametric equalizer allows you to boost or cut specific frequency ranges of a signal. It
+//! consists of any number of bands, every band is a second order filter (see
+//! [`Biquad`](crate::dsp::filters::Biquad)) that affects frequencies around its center frequency.
+//! Bands are applied one after another.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::equalizer::{Equalizer, EqualizerBand, EqualizerBandKind};
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_equalizer(context: &mut SoundContext) {
+//!     let mut state = context.state();
+//!     let mut equalizer = Equalizer::new(BaseEffect::default());
+//!     equalizer.set_bands(vec![
+//!         // Cut low rumble.
+//!         EqualizerBand {
+//!             kind: EqualizerBandKind::LowShelf,
+//!             fc: state.normalize_frequency(100.0),
+//!             gain: 0.25,
+//!             quality: 0.7,
+//!         },
+//!         // Boost presence.
+//!         EqualizerBand {
+//!             kind: EqualizerBandKind::Peak,
+//!             fc: state.normalize_frequency(3000.0),
+//!             gain: 2.0,
+//!             quality: 1.0,
+//!         },
+//!     ]);
+//!     state.add_effect(Effect::Equalizer(equalizer));
+//! }
+//! ```
+
+use crate::{
+    context::DistanceModel,
+    dsp::filters::{Biquad, BiquadKind},
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    reflect::prelude::*,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines a shape of frequency response of an equalizer band.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Reflect, Visit, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum EqualizerBandKind {
+    /// Changes amplitude of all frequencies below center frequency.
+    LowShelf,
+    /// Changes amplitude of frequencies around center frequency.
+    Peak,
+    /// Changes amplitude of all frequencies above center frequency.
+    HighShelf,
+}
+
+impl Default for EqualizerBandKind {
+    fn default() -> Self {
+        Self::Peak
+    }
+}
+
+/// A single band of an equalizer.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit)]
+pub struct EqualizerBand {
+    /// Shape of frequency response of the band.
+    pub kind: EqualizerBandKind,
+    /// Normalized center frequency of the band. Use `normalize_frequency` method of the sound
+    /// context to convert frequency in hertz.
+    pub fc: f32,
+    /// Linear gain of the band, values greater than 1.0 boost frequencies, values less than 1.0
+    /// cut them.
+    pub gain: f32,
+    /// Defines width of the band, the lower it will be, the wider band will be and vice versa.
+    pub quality: f32,
+}
+
+impl Default for EqualizerBand {
+    fn default() -> Self {
+        Self {
+            kind: EqualizerBandKind::Peak,
+            fc: 1000.0 / crate::context::SAMPLE_RATE as f32,
+            gain: 1.0,
+            quality: 1.0,
+        }
+    }
+}
+
+impl EqualizerBand {
+    fn make_filter(&self) -> Biquad {
+        let gain = self.gain.max(f32::EPSILON);
+        let quality = self.quality.max(f32::EPSILON);
+        match self.kind {
+            // Shelving filters take square root of the gain.
+            EqualizerBandKind::LowShelf => {
+                Biquad::new(BiquadKind::LowShelf, self.fc, gain.sqrt(), quality)
+            }
+            EqualizerBandKind::Peak => Biquad::new(BiquadKind::Peak, self.fc, gain, quality),
+            EqualizerBandKind::HighShelf => {
+                Biquad::new(BiquadKind::HighShelf, self.fc, gain.sqrt(), quality)
+            }
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Visit)]
+struct BandFilter {
+    left: Biquad,
+    right: Biquad,
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Equalizer {
+    base: BaseEffect,
+    bands: Vec<EqualizerBand>,
+    #[visit(skip)]
+    filters: Vec<BandFilter>,
+}
+
+impl Default for Equalizer {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Equalizer {
+    /// Creates new equalizer without any bands.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            bands: Default::default(),
+            filters: Default::default(),
+        }
+    }
+
+    /// Sets new bands of the equalizer.
+    pub fn set_bands(&mut self, bands: Vec<EqualizerBand>) {
+        self.bands = bands;
+        self.filters.clear();
+    }
+
+    /// Returns a slice with bands of the equalizer.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+
+    pub(crate) fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        // Filters are not serialized, so they're created on demand.
+        if self.filters.len() != self.bands.len() {
+            self.filters = self
+                .bands
+                .iter()
+                .map(|band| {
+                    let filter = band.make_filter();
+                    BandFilter {
+                        left: filter.clone(),
+                        right: filter,
+                    }
+                })
+                .collect();
+        }
+
+        let mut left = left;
+        let mut right = right;
+        for filter in self.filters.iter_mut() {
+            left = filter.left.feed(left);
+            right = filter.right.feed(right);
+        }
+        (left, right)
+    }
+}
+
+impl EffectRenderTrait for Equalizer {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
+        {
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += self.base.gain * processed_left;
+            *out_right += self.base.gain * processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = gain * processed_left;
+            *right = gain * processed_right;
+        }
+    }
+}
+
+impl Deref for Equalizer {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Equalizer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
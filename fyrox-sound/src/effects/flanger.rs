@@ -0,0 +1,233 @@
+//! Flanger module
+//!
+//! # Overview
+//!
+//! Flanger is very similar to chorus, but uses much shorter delays and feeds delayed signal back
+//! to the input. Mixing of the signal with its slightly delayed copy produces a comb filter whose
+//! notches are sweeping over the spectrum, giving characteristic "jet plane" sound.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::flanger::Flanger;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_flanger(context: &mut SoundContext) {
+//!     let mut flanger = Flanger::new(BaseEffect::default());
+//!     flanger.set_feedback(0.7);
+//!     context.state().add_effect(Effect::Flanger(flanger));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::{DelayLine, Lfo},
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+#[derive(Default, Debug, Clone, Visit)]
+struct ChannelFlanger {
+    delay_line: DelayLine,
+    lfo: Lfo,
+    last: f32,
+}
+
+impl ChannelFlanger {
+    fn new(max_delay: usize, rate: f32, phase: f32) -> Self {
+        Self {
+            // Extra sample is needed for interpolation.
+            delay_line: DelayLine::new(max_delay + 2),
+            lfo: Lfo::new(rate / SAMPLE_RATE as f32, phase),
+            last: 0.0,
+        }
+    }
+
+    fn feed(&mut self, sample: f32, delay: f32, depth: f32, feedback: f32) -> f32 {
+        self.delay_line.feed(sample + feedback * self.last);
+        // Modulation is unipolar, so the delay never goes below zero.
+        let modulation = 0.5 * (self.lfo.next_value() + 1.0);
+        self.last = self.delay_line.tap(delay + depth * modulation);
+        self.last
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Flanger {
+    base: BaseEffect,
+    dry: f32,
+    wet: f32,
+    delay: f32,
+    depth: f32,
+    rate: f32,
+    feedback: f32,
+    left: ChannelFlanger,
+    right: ChannelFlanger,
+}
+
+impl Default for Flanger {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Flanger {
+    /// Creates new flanger effect with 1 ms delay modulated by 3 ms at 0.25 Hz rate.
+    pub fn new(base: BaseEffect) -> Self {
+        let mut flanger = Self {
+            base,
+            dry: 1.0,
+            wet: 0.7,
+            delay: 0.001,
+            depth: 0.003,
+            rate: 0.25,
+            feedback: 0.5,
+            left: Default::default(),
+            right: Default::default(),
+        };
+        flanger.reset_channels();
+        flanger
+    }
+
+    fn reset_channels(&mut self) {
+        let max_delay = ((self.delay + self.depth) * SAMPLE_RATE as f32).ceil() as usize;
+        self.left = ChannelFlanger::new(max_delay, self.rate, 0.0);
+        self.right = ChannelFlanger::new(max_delay, self.rate, 0.25);
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output. Default value is 0.7.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Sets minimal delay of modulated signal. Typical values are in 0-5 ms range.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay.as_secs_f32();
+        self.reset_channels();
+    }
+
+    /// Returns minimal delay of modulated signal.
+    pub fn delay(&self) -> Duration {
+        Duration::from_secs_f32(self.delay)
+    }
+
+    /// Sets range of delay modulation, delay will sweep from `delay` to `delay + depth`.
+    pub fn set_depth(&mut self, depth: Duration) {
+        self.depth = depth.as_secs_f32();
+        self.reset_channels();
+    }
+
+    /// Returns range of delay modulation.
+    pub fn depth(&self) -> Duration {
+        Duration::from_secs_f32(self.depth)
+    }
+
+    /// Sets frequency (in Hz) of delay modulation. Typical values are in 0.05-1 Hz range.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+        let fc = self.rate / SAMPLE_RATE as f32;
+        self.left.lfo.set_fc(fc);
+        self.right.lfo.set_fc(fc);
+    }
+
+    /// Returns frequency (in Hz) of delay modulation.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets how much of delayed signal should be fed back to the input, the higher the value
+    /// the more pronounced the effect is. Should be in `[0; 1)` range.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    pub(crate) fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let delay = self.delay * SAMPLE_RATE as f32;
+        let depth = self.depth * SAMPLE_RATE as f32;
+
+        let modulated_left = self.left.feed(left, delay, depth, self.feedback);
+        let modulated_right = self.right.feed(right, delay, depth, self.feedback);
+
+        (
+            self.dry * left + self.wet * modulated_left,
+            self.dry * right + self.wet * modulated_right,
+        )
+    }
+}
+
+impl EffectRenderTrait for Flanger {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
+        {
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += self.base.gain * processed_left;
+            *out_right += self.base.gain * processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = gain * processed_left;
+            *right = gain * processed_right;
+        }
+    }
+}
+
+impl Deref for Flanger {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Flanger {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
@@ -7,7 +7,10 @@
 use crate::{
     context::DistanceModel,
     dsp::filters::Biquad,
-    effects::reverb::Reverb,
+    effects::{
+        chorus::Chorus, compressor::Compressor, delay::Delay, distortion::Distortion,
+        equalizer::Equalizer, flanger::Flanger, reverb::Reverb,
+    },
     listener::Listener,
     source::{SoundSource, Status},
 };
@@ -18,6 +21,12 @@ use fyrox_core::{
 };
 use std::ops::{Deref, DerefMut};
 
+pub mod chorus;
+pub mod compressor;
+pub mod delay;
+pub mod distortion;
+pub mod equalizer;
+pub mod flanger;
 pub mod reverb;
 
 /// Stub effect that does nothing.
@@ -35,6 +44,8 @@ impl EffectRenderTrait for StubEffect {
         _mix_buf: &mut [(f32, f32)],
     ) {
     }
+
+    fn process(&mut self, _buf: &mut [(f32, f32)]) {}
 }
 
 impl Deref for StubEffect {
@@ -58,6 +69,18 @@ pub enum Effect {
     Stub(StubEffect),
     /// Reverberation effect. See corresponding module for more info.
     Reverb(Reverb),
+    /// Chorus effect. See corresponding module for more info.
+    Chorus(Chorus),
+    /// Flanger effect. See corresponding module for more info.
+    Flanger(Flanger),
+    /// Delay (echo) effect. See corresponding module for more info.
+    Delay(Delay),
+    /// Distortion effect. See corresponding module for more info.
+    Distortion(Distortion),
+    /// Compressor (and limiter) effect. See corresponding module for more info.
+    Compressor(Compressor),
+    /// Parametric equalizer. See corresponding module for more info.
+    Equalizer(Equalizer),
 }
 
 impl Default for Effect {
@@ -74,6 +97,10 @@ pub(crate) trait EffectRenderTrait {
         distance_model: DistanceModel,
         mix_buf: &mut [(f32, f32)],
     );
+
+    /// Processes samples of the buffer in-place, inputs of the effect are ignored. It is used
+    /// when the effect is a part of an effect chain of an audio bus.
+    fn process(&mut self, buf: &mut [(f32, f32)]);
 }
 
 /// Base effect for all other kinds of effects. It contains set of inputs (direct
@@ -258,6 +285,12 @@ macro_rules! static_dispatch {
         match $self {
             Effect::Stub(v) => v.$func($($args),*),
             Effect::Reverb(v) => v.$func($($args),*),
+            Effect::Chorus(v) => v.$func($($args),*),
+            Effect::Flanger(v) => v.$func($($args),*),
+            Effect::Delay(v) => v.$func($($args),*),
+            Effect::Distortion(v) => v.$func($($args),*),
+            Effect::Compressor(v) => v.$func($($args),*),
+            Effect::Equalizer(v) => v.$func($($args),*),
         }
     };
 }
@@ -272,6 +305,10 @@ impl EffectRenderTrait for Effect {
     ) {
         static_dispatch!(self, render, sources, listener, distance_model, mix_buf)
     }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        static_dispatch!(self, process, buf)
+    }
 }
 
 impl Deref for Effect {
@@ -281,6 +318,12 @@ impl Deref for Effect {
         match self {
             Effect::Stub(v) => v,
             Effect::Reverb(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Flanger(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Distortion(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
@@ -290,6 +333,190 @@ impl DerefMut for Effect {
         match self {
             Effect::Stub(v) => v,
             Effect::Reverb(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Flanger(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Distortion(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{
+            chorus::Chorus,
+            compressor::Compressor,
+            delay::Delay,
+            distortion::Distortion,
+            equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
+            flanger::Flanger,
+        },
+    };
+    use std::{f32::consts::PI, time::Duration};
+
+    fn impulse(len: usize) -> Vec<(f32, f32)> {
+        let mut signal = vec![(0.0, 0.0); len];
+        signal[0] = (1.0, 1.0);
+        signal
+    }
+
+    fn sine(frequency: f32, amplitude: f32, len: usize) -> Vec<(f32, f32)> {
+        (0..len)
+            .map(|i| {
+                let sample =
+                    amplitude * (2.0 * PI * frequency * i as f32 / SAMPLE_RATE as f32).sin();
+                (sample, sample)
+            })
+            .collect()
+    }
+
+    fn render<F>(signal: &[(f32, f32)], mut feed: F) -> Vec<(f32, f32)>
+    where
+        F: FnMut(f32, f32) -> (f32, f32),
+    {
+        signal
+            .iter()
+            .map(|&(left, right)| feed(left, right))
+            .collect()
+    }
+
+    fn peak(signal: &[(f32, f32)]) -> f32 {
+        signal.iter().fold(0.0f32, |peak, (left, right)| {
+            peak.max(left.abs()).max(right.abs())
+        })
+    }
+
+    #[test]
+    fn test_delay() {
+        let mut delay = Delay::default();
+        delay.set_dry(0.0);
+        delay.set_wet(1.0);
+        delay.set_feedback(0.5);
+        delay.set_delay_time(Duration::from_millis(10));
+
+        let output = render(&impulse(1000), |l, r| delay.feed(l, r));
+
+        // 10 ms is 441 samples at 44100 Hz.
+        for (i, &(left, right)) in output.iter().enumerate() {
+            let expected = match i {
+                441 => 1.0,
+                882 => 0.5,
+                _ => 0.0,
+            };
+            assert_eq!(left, expected);
+            assert_eq!(right, expected);
+        }
+    }
+
+    #[test]
+    fn test_chorus() {
+        let mut chorus = Chorus::default();
+        chorus.set_dry(0.0);
+        chorus.set_wet(1.0);
+        chorus.set_delay(Duration::from_millis(10));
+        chorus.set_depth(Duration::from_millis(2));
+        // Slow modulation, otherwise moving delay will stretch or squeeze the impulse.
+        chorus.set_rate(0.1);
+
+        let output = render(&impulse(1000), |l, r| chorus.feed(l, r));
+
+        // Impulse must arrive somewhere within modulation range, without losing its energy.
+        let (min, max) = (441 - 89, 441 + 89);
+        let (left, right) = output.iter().enumerate().fold(
+            (0.0, 0.0),
+            |(left_sum, right_sum), (i, (left, right))| {
+                if i >= min && i <= max {
+                    (left_sum + left, right_sum + right)
+                } else {
+                    assert_eq!((*left, *right), (0.0, 0.0));
+                    (left_sum, right_sum)
+                }
+            },
+        );
+        assert!((left - 1.0f32).abs() < 1.0e-2);
+        assert!((right - 1.0f32).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn test_flanger() {
+        let mut flanger = Flanger::default();
+        flanger.set_dry(0.0);
+        flanger.set_wet(1.0);
+        flanger.set_feedback(0.5);
+
+        let output = render(&impulse(SAMPLE_RATE as usize), |l, r| flanger.feed(l, r));
+
+        // Every pass through the feedback loop halves the signal, so total energy is
+        // 1 + 0.5 + 0.25 + ... = 2.
+        let sum = output.iter().map(|(left, _)| left).sum::<f32>();
+        assert!((sum - 2.0).abs() < 1.0e-2);
+        assert!(peak(&output) <= 1.0);
+    }
+
+    #[test]
+    fn test_distortion() {
+        let mut distortion = Distortion::default();
+        distortion.set_drive(10.0);
+
+        let input = sine(440.0, 0.5, 1000);
+        let output = render(&input, |l, r| distortion.feed(l, r));
+
+        let output_peak = peak(&output);
+        assert!(output_peak <= 1.0 && output_peak > 0.99);
+        for (&(input, _), &(output, _)) in input.iter().zip(output.iter()) {
+            assert_eq!(input.signum(), output.signum());
+        }
+    }
+
+    #[test]
+    fn test_compressor() {
+        let mut compressor = Compressor::default();
+        compressor.set_threshold(-20.0);
+        compressor.set_ratio(4.0);
+        compressor.set_attack(Duration::from_secs(0));
+        compressor.set_release(Duration::from_secs(0));
+
+        // Signal at 0 dB is 20 dB above the threshold, it must be reduced by 15 dB.
+        let output = render(&vec![(1.0, 1.0); 100], |l, r| compressor.feed(l, r));
+        for (left, right) in output {
+            assert!((left - 0.17783).abs() < 1.0e-4);
+            assert!((right - 0.17783).abs() < 1.0e-4);
+        }
+
+        // Quiet signal must be passed as is.
+        let output = render(&vec![(0.01, -0.01); 100], |l, r| compressor.feed(l, r));
+        for (left, right) in output {
+            assert!((left - 0.01).abs() < 1.0e-6);
+            assert!((right + 0.01).abs() < 1.0e-6);
+        }
+    }
+
+    #[test]
+    fn test_equalizer() {
+        let make_equalizer = || {
+            let mut equalizer = Equalizer::default();
+            equalizer.set_bands(vec![EqualizerBand {
+                kind: EqualizerBandKind::Peak,
+                fc: 1000.0 / SAMPLE_RATE as f32,
+                gain: 4.0,
+                quality: 1.0,
+            }]);
+            equalizer
+        };
+
+        // Skip first samples to let the filter settle down.
+        let settle = 4410;
+
+        let mut equalizer = make_equalizer();
+        let output = render(&sine(1000.0, 0.1, 8820), |l, r| equalizer.feed(l, r));
+        assert!((peak(&output[settle..]) - 0.4).abs() < 0.01);
+
+        let mut equalizer = make_equalizer();
+        let output = render(&sine(15000.0, 0.1, 8820), |l, r| equalizer.feed(l, r));
+        assert!((peak(&output[settle..]) - 0.1).abs() < 0.01);
+    }
+}
@@ -235,6 +235,22 @@ impl Reverb {
         self.left.set_fc(fc);
         self.right.set_fc(fc);
     }
+
+    fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let wet1 = self.wet;
+        let wet2 = 1.0 - self.wet;
+
+        let mid = (left + right) * 0.5;
+        let input = mid * Self::GAIN;
+
+        let processed_left = self.left.feed(input);
+        let processed_right = self.right.feed(input);
+
+        (
+            processed_left * wet1 + processed_right * wet2 + self.dry * left,
+            processed_right * wet1 + processed_left * wet2 + self.dry * right,
+        )
+    }
 }
 
 impl EffectRenderTrait for Reverb {
@@ -248,22 +264,22 @@ impl EffectRenderTrait for Reverb {
         self.base
             .render(sources, listener, distance_model, mix_buf.len());
 
-        let wet1 = self.wet;
-        let wet2 = 1.0 - self.wet;
-
-        for ((out_left, out_right), &(left, right)) in
-            mix_buf.iter_mut().zip(self.base.frame_samples.iter())
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
         {
-            let mid = (left + right) * 0.5;
-            let input = mid * Self::GAIN;
-
-            let processed_left = self.left.feed(input);
-            let processed_right = self.right.feed(input);
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += self.base.gain * processed_left;
+            *out_right += self.base.gain * processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
 
-            *out_left +=
-                self.gain * (processed_left * wet1 + processed_right * wet2 + self.dry * left);
-            *out_right +=
-                self.gain * (processed_right * wet1 + processed_left * wet2 + self.dry * right);
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = gain * processed_left;
+            *right = gain * processed_right;
         }
     }
 }
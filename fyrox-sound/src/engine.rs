@@ -4,15 +4,21 @@
 //!
 //! Sound engine manages contexts, feeds output device with data.
 
-use crate::{context::SoundContext, device};
+use crate::{bus::BusControl, context::SoundContext, device, error::SoundError, offline};
 use fyrox_core::visitor::{Visit, VisitResult, Visitor};
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 /// Internal state of sound engine.
 #[derive(Default)]
 pub struct SoundEngine {
     contexts: Vec<SoundContext>,
     master_gain: f32,
+    bus_controls: HashMap<String, BusControl>,
 }
 
 impl SoundEngine {
@@ -23,6 +29,7 @@ impl SoundEngine {
         let engine = Arc::new(Mutex::new(Self {
             contexts: Default::default(),
             master_gain: 1.0,
+            bus_controls: Default::default(),
         }));
 
         // Run the default output device. Internally it creates separate thread, so we have
@@ -46,6 +53,7 @@ impl SoundEngine {
         Arc::new(Mutex::new(Self {
             contexts: Default::default(),
             master_gain: 1.0,
+            bus_controls: Default::default(),
         }))
     }
 
@@ -89,6 +97,35 @@ impl SoundEngine {
         self.master_gain
     }
 
+    /// Sets gain multiplier of every audio bus with the given name in every context. It is applied
+    /// on top of the gain of the bus, and it is not serialized, so it can be used to implement
+    /// volume settings (music, sound effects, etc.) in options menu.
+    pub fn set_bus_gain<S: AsRef<str>>(&mut self, bus: S, gain: f32) {
+        self.bus_controls
+            .entry(bus.as_ref().to_owned())
+            .or_default()
+            .gain = gain.max(0.0);
+    }
+
+    /// Returns gain multiplier of audio buses with the given name.
+    pub fn bus_gain(&self, bus: &str) -> f32 {
+        self.bus_controls.get(bus).map_or(1.0, |c| c.gain)
+    }
+
+    /// Mutes or unmutes every audio bus with the given name in every context. See
+    /// [`Self::set_bus_gain`] for more info.
+    pub fn set_bus_muted<S: AsRef<str>>(&mut self, bus: S, muted: bool) {
+        self.bus_controls
+            .entry(bus.as_ref().to_owned())
+            .or_default()
+            .muted = muted;
+    }
+
+    /// Returns true if audio buses with the given name are muted by [`Self::set_bus_muted`].
+    pub fn is_bus_muted(&self, bus: &str) -> bool {
+        self.bus_controls.get(bus).map_or(false, |c| c.muted)
+    }
+
     /// Returns the length of buf to be passed to [`Self::render()`].
     pub fn render_buffer_len() -> usize {
         SoundContext::SAMPLES_PER_CHANNEL
@@ -107,10 +144,35 @@ impl SoundEngine {
         self.render_inner(buf);
     }
 
+    /// Renders given amount of time of every context into a buffer with given sample rate. It
+    /// does not need an output device and it is fully deterministic, so it can be used for
+    /// regression tests and baking sounds. This method must be used if and only if the engine was
+    /// created via [`Self::without_device`]. See [`crate::offline`] module docs for more info.
+    ///
+    /// ## Deadlocks
+    ///
+    /// This method internally locks added sound contexts so it must be called when all the contexts
+    /// are unlocked or you'll get a deadlock.
+    pub fn render_to_buffer(&mut self, duration: Duration, sample_rate: u32) -> Vec<(f32, f32)> {
+        offline::render(duration, sample_rate, |buf| self.render_inner(buf))
+    }
+
+    /// Renders given amount of time of every context into a WAV file with given sample rate. See
+    /// [`Self::render_to_buffer`] for more info.
+    pub fn render_to_wav<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        duration: Duration,
+        sample_rate: u32,
+    ) -> Result<(), SoundError> {
+        let samples = self.render_to_buffer(duration, sample_rate);
+        offline::write_wav(path, &samples, sample_rate)
+    }
+
     fn render_inner(&mut self, buf: &mut [(f32, f32)]) {
         let master_gain = self.master_gain;
         for context in self.contexts.iter_mut() {
-            context.state().render(master_gain, buf);
+            context.state().render(master_gain, &self.bus_controls, buf);
         }
     }
 }
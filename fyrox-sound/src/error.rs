@@ -11,6 +11,9 @@ pub enum DecoderError {
 
     /// Ogg/vorbis (lewton) specific error.
     Ogg(lewton::VorbisError),
+
+    /// FLAC/MP3 (symphonia) specific error.
+    Symphonia(symphonia::core::errors::Error),
 }
 
 /// Generic error enumeration for each error in this engine.
@@ -53,6 +56,9 @@ pub enum SoundError {
 
     /// A buffer is not loaded yet, consider to `await` it before use.
     BufferIsNotLoaded,
+
+    /// Unable to write a WAV file, exact reason stored in inner value.
+    WavWriterError(hound::Error),
 }
 
 impl From<std::io::Error> for SoundError {
@@ -73,6 +79,18 @@ impl From<lewton::VorbisError> for SoundError {
     }
 }
 
+impl From<symphonia::core::errors::Error> for SoundError {
+    fn from(e: symphonia::core::errors::Error) -> Self {
+        SoundError::DecoderError(DecoderError::Symphonia(e))
+    }
+}
+
+impl From<hound::Error> for SoundError {
+    fn from(e: hound::Error) -> Self {
+        SoundError::WavWriterError(e)
+    }
+}
+
 impl Display for SoundError {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         match self {
@@ -93,6 +111,7 @@ impl Display for SoundError {
             SoundError::DecoderError(de) => write!(f, "internal decoder error: {:?}", de),
             SoundError::BufferFailedToLoad => write!(f, "a buffer failed to load"),
             SoundError::BufferIsNotLoaded => write!(f, "a buffer is not loaded yet"),
+            SoundError::WavWriterError(e) => write!(f, "unable to write wav file: {}", e),
         }
     }
 }
@@ -3,10 +3,11 @@
 //! ## Features
 //!
 //! - Generic and spatial sounds.
-//! - WAV and OGG/Vorbis formats support.
+//! - WAV, OGG/Vorbis, FLAC and MP3 formats support.
 //! - Streaming.
 //! - Head-related transfer function support ([HRTF](https://en.wikipedia.org/wiki/Head-related_transfer_function)).
-//! - Reverb effect.
+//! - Effects: reverb, chorus, flanger, delay, distortion, compressor/limiter and parametric equalizer.
+//! - Offline (deterministic) rendering to a buffer or a WAV file.
 //!
 //! ## Examples
 //!
@@ -67,6 +68,7 @@
 extern crate winapi;
 
 pub mod buffer;
+pub mod bus;
 pub mod context;
 
 pub mod dsp;
@@ -74,6 +76,7 @@ pub mod effects;
 pub mod engine;
 pub mod error;
 pub mod listener;
+pub mod offline;
 pub mod renderer;
 pub mod source;
 
@@ -0,0 +1,250 @@
+//! Offline rendering module.
+//!
+//! # Overview
+//!
+//! Offline rendering allows you to render sound into a buffer or a WAV file without an output
+//! device and independently of the device thread. Rendering is fully deterministic - same
+//! scene will produce exactly the same samples each time, which makes it suitable for
+//! regression tests (spatialization, HRTF, effects, etc.) on headless machines and for baking
+//! sounds (ambience loops, etc.) in tools.
+//!
+//! Internally the engine always works at [`SAMPLE_RATE`], if any other sample rate is requested
+//! the output is resampled, see [`resample`] for more info.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use fyrox_sound::{context::SoundContext, engine::SoundEngine};
+//! use std::time::Duration;
+//!
+//! let engine = SoundEngine::without_device();
+//! let context = SoundContext::new();
+//! engine.lock().unwrap().add_context(context);
+//!
+//! // Add sources, effects, etc. here.
+//!
+//! engine
+//!     .lock()
+//!     .unwrap()
+//!     .render_to_wav("ambience.wav", Duration::from_secs(10), 48000)
+//!     .unwrap();
+//! ```
+//!
+//! # Important notes
+//!
+//! Offline rendering advances the state of contexts (playback position of sources, state of
+//! effects, etc.) just like the device does. Do not render a context offline while it is used by
+//! an engine with an output device, use [`crate::engine::SoundEngine::without_device`] instead.
+
+use crate::{
+    context::{SoundContext, SAMPLE_RATE},
+    error::SoundError,
+};
+use std::{path::Path, time::Duration};
+
+/// Renders the requested amount of time using given render function, which is called with zeroed
+/// blocks of [`SoundContext::SAMPLES_PER_CHANNEL`] samples at [`SAMPLE_RATE`]. The result is
+/// resampled to the requested sample rate.
+pub(crate) fn render<F>(
+    duration: Duration,
+    sample_rate: u32,
+    mut render_block: F,
+) -> Vec<(f32, f32)>
+where
+    F: FnMut(&mut [(f32, f32)]),
+{
+    let sample_rate = sample_rate.max(1);
+    let out_len = (duration.as_secs_f64() * sample_rate as f64).round() as usize;
+    // One extra sample for interpolation.
+    let in_len = if sample_rate == SAMPLE_RATE {
+        out_len
+    } else {
+        (out_len as f64 * SAMPLE_RATE as f64 / sample_rate as f64).ceil() as usize + 1
+    };
+
+    let mut samples = Vec::with_capacity(in_len + SoundContext::SAMPLES_PER_CHANNEL);
+    let mut block = vec![(0.0, 0.0); SoundContext::SAMPLES_PER_CHANNEL];
+    while samples.len() < in_len {
+        block.fill((0.0, 0.0));
+        render_block(&mut block);
+        samples.extend_from_slice(&block);
+    }
+
+    let mut output = resample(&samples, SAMPLE_RATE, sample_rate);
+    output.truncate(out_len);
+    output
+}
+
+/// Number of zero crossings of the windowed sinc on each side of its center. Higher values gives
+/// sharper cutoff of the low-pass filter at the cost of performance.
+const SINC_ZERO_CROSSINGS: f64 = 16.0;
+
+/// Blackman-windowed sinc with zero crossings at integer values of `x`.
+fn windowed_sinc(x: f64) -> f64 {
+    if x.abs() >= SINC_ZERO_CROSSINGS {
+        return 0.0;
+    }
+    let sinc = if x == 0.0 {
+        1.0
+    } else {
+        let a = std::f64::consts::PI * x;
+        a.sin() / a
+    };
+    let t = std::f64::consts::PI * x / SINC_ZERO_CROSSINGS;
+    let window = 0.42 + 0.5 * t.cos() + 0.08 * (2.0 * t).cos();
+    sinc * window
+}
+
+/// Resamples stereo samples from one sample rate to another. Upsampling uses linear interpolation.
+/// Downsampling uses windowed-sinc interpolation, which also acts as a low-pass filter with the cutoff
+/// at the Nyquist frequency of the new sample rate, so frequencies that cannot be represented at the
+/// new sample rate are removed instead of being aliased into the audible range.
+pub fn resample(samples: &[(f32, f32)], from: u32, to: u32) -> Vec<(f32, f32)> {
+    if from == to || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let step = from as f64 / to as f64;
+    let out_len = ((samples.len() - 1) as f64 / step).floor() as usize + 1;
+
+    if step > 1.0 {
+        // Distances between input samples are measured in output samples, so the zero crossings of
+        // the sinc are placed at the Nyquist frequency of the output.
+        let half_width = SINC_ZERO_CROSSINGS * step;
+        let last = (samples.len() - 1) as f64;
+        return (0..out_len)
+            .map(|i| {
+                let position = i as f64 * step;
+                let begin = (position - half_width).ceil().max(0.0) as usize;
+                let end = (position + half_width).floor().min(last) as usize;
+                let mut total_weight = 0.0;
+                let mut left = 0.0;
+                let mut right = 0.0;
+                for (index, &(l, r)) in samples.iter().enumerate().take(end + 1).skip(begin) {
+                    let weight = windowed_sinc((index as f64 - position) / step);
+                    total_weight += weight;
+                    left += l as f64 * weight;
+                    right += r as f64 * weight;
+                }
+                // Normalize to keep the gain of the filter at exactly one, this also compensates
+                // the truncated kernel at the edges.
+                ((left / total_weight) as f32, (right / total_weight) as f32)
+            })
+            .collect();
+    }
+
+    (0..out_len)
+        .map(|i| {
+            let position = i as f64 * step;
+            let index = position as usize;
+            let t = (position - index as f64) as f32;
+            let (l0, r0) = samples[index];
+            let (l1, r1) = samples.get(index + 1).cloned().unwrap_or((l0, r0));
+            (l0 + (l1 - l0) * t, r0 + (r1 - r0) * t)
+        })
+        .collect()
+}
+
+/// Writes stereo samples to a WAV file (32-bit float) with given sample rate.
+pub fn write_wav<P: AsRef<Path>>(
+    path: P,
+    samples: &[(f32, f32)],
+    sample_rate: u32,
+) -> Result<(), SoundError> {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    for &(left, right) in samples {
+        writer.write_sample(left)?;
+        writer.write_sample(right)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource},
+        context::{SoundContext, SAMPLE_RATE},
+        engine::SoundEngine,
+        offline::resample,
+        source::{SoundSourceBuilder, Status},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_resample() {
+        let samples = [(0.0, 0.0), (1.0, -1.0), (2.0, -2.0), (3.0, -3.0)];
+        assert_eq!(resample(&samples, 10, 10), samples.to_vec());
+        assert_eq!(
+            resample(&samples, 10, 20),
+            vec![
+                (0.0, 0.0),
+                (0.5, -0.5),
+                (1.0, -1.0),
+                (1.5, -1.5),
+                (2.0, -2.0),
+                (2.5, -2.5),
+                (3.0, -3.0)
+            ]
+        );
+        assert_eq!(resample(&[(1.0, -1.0); 8], 20, 10), vec![(1.0, -1.0); 4]);
+    }
+
+    // Returns RMS of the left channel of a sine wave with given frequency, downsampled from
+    // `SAMPLE_RATE` to `SAMPLE_RATE / 2`. Edges are skipped.
+    fn downsampled_sine_rms(frequency: f32) -> f32 {
+        let samples = (0..SAMPLE_RATE / 10)
+            .map(|i| {
+                let s =
+                    (2.0 * std::f32::consts::PI * frequency * i as f32 / SAMPLE_RATE as f32).sin();
+                (s, -s)
+            })
+            .collect::<Vec<_>>();
+        let output = resample(&samples, SAMPLE_RATE, SAMPLE_RATE / 2);
+        assert_eq!(output.len(), samples.len() / 2);
+        let middle = &output[100..output.len() - 100];
+        (middle.iter().map(|(l, _)| l * l).sum::<f32>() / middle.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_downsample_without_aliasing() {
+        // Frequencies below the new Nyquist frequency (11025 Hz) must pass.
+        assert!((downsampled_sine_rms(1000.0) - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.02);
+        // Frequencies above must be removed, otherwise 15 kHz would be aliased to 7050 Hz.
+        assert!(downsampled_sine_rms(15000.0) < 0.01);
+    }
+
+    #[test]
+    fn test_render_offline() {
+        let engine = SoundEngine::without_device();
+        let context = SoundContext::new();
+        engine.lock().unwrap().add_context(context.clone());
+
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: vec![0.5; SAMPLE_RATE as usize],
+        })
+        .unwrap();
+        let source = SoundSourceBuilder::new()
+            .with_buffer(buffer)
+            .with_spatial_blend_factor(0.0)
+            .with_status(Status::Playing)
+            .build()
+            .unwrap();
+        context.state().add_source(source);
+
+        let samples = engine
+            .lock()
+            .unwrap()
+            .render_to_buffer(Duration::from_millis(100), 22050);
+        assert_eq!(samples.len(), 2205);
+        assert!(samples.iter().all(|&(l, r)| l == 0.5 && r == 0.5));
+    }
+}
@@ -121,6 +121,8 @@ pub struct SoundSource {
     max_distance: f32,
     #[reflect(min_value = 0.0, step = 0.05)]
     rolloff_factor: f32,
+    #[visit(optional)] // Backward compatibility
+    bus: String,
     // Some data that needed for iterative overlap-save convolution.
     #[reflect(hidden)]
     #[visit(skip)]
@@ -159,6 +161,7 @@ impl Default for SoundSource {
             position: Vector3::new(0.0, 0.0, 0.0),
             max_distance: f32::MAX,
             rolloff_factor: 1.0,
+            bus: Default::default(),
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
@@ -183,6 +186,18 @@ impl SoundSource {
         self.name.to_owned()
     }
 
+    /// Sets the name of an audio bus to which the source will be routed. Empty name (default)
+    /// or a name of non-existing bus routes the source to the master bus. See [`crate::bus`]
+    /// module docs for more info.
+    pub fn set_bus<N: AsRef<str>>(&mut self, bus: N) {
+        self.bus = bus.as_ref().to_owned();
+    }
+
+    /// Returns the name of an audio bus to which the source is routed.
+    pub fn bus(&self) -> &str {
+        &self.bus
+    }
+
     /// Sets spatial blend factor. It defines how much the source will be 2D and 3D sound at the same
     /// time. Set it to 0.0 to make the sound fully 2D and 1.0 to make it fully 3D. Middle values
     /// will make sound proportionally 2D and 3D at the same time.
@@ -444,13 +459,15 @@ impl SoundSource {
         }
     }
 
-    /// Sets playback duration.
-    pub fn set_playback_time(&mut self, time: Duration) {
+    /// Sets playback duration. Returns an error if the decoder of a streaming buffer failed to seek
+    /// to the given position, the playback position is changed anyway.
+    pub fn set_playback_time(&mut self, time: Duration) -> Result<(), SoundError> {
+        let mut result = Ok(());
         if let Some(buffer) = self.buffer.as_ref() {
             let mut buffer = buffer.data_ref();
             if let SoundBufferState::Streaming(ref mut streaming) = *buffer {
                 // Make sure decoder is at right position.
-                streaming.time_seek(time);
+                result = streaming.time_seek(time);
             }
             // Set absolute position first.
             self.playback_pos = time.as_secs_f64() * buffer.sample_rate as f64;
@@ -469,6 +486,7 @@ impl SoundSource {
                 self.buf_read_pos * (buffer.channel_count() as f64) < buffer.samples().len() as f64
             );
         }
+        result
     }
 
     pub(crate) fn render(&mut self, amount: usize) {
@@ -702,6 +720,7 @@ pub struct SoundSourceBuilder {
     max_distance: f32,
     rolloff_factor: f32,
     spatial_blend: f32,
+    bus: String,
 }
 
 impl Default for SoundSourceBuilder {
@@ -728,6 +747,7 @@ impl SoundSourceBuilder {
             max_distance: f32::MAX,
             rolloff_factor: 1.0,
             spatial_blend: 1.0,
+            bus: Default::default(),
         }
     }
 
@@ -791,6 +811,12 @@ impl SoundSourceBuilder {
         self
     }
 
+    /// Sets desired audio bus of the source. See [`SoundSource::set_bus`].
+    pub fn with_bus<N: AsRef<str>>(mut self, bus: N) -> Self {
+        self.bus = bus.as_ref().to_owned();
+        self
+    }
+
     /// Sets desired starting playback time.
     pub fn with_playback_time(mut self, time: Duration) -> Self {
         self.playback_time = time;
@@ -838,13 +864,14 @@ impl SoundSourceBuilder {
             max_distance: self.max_distance,
             rolloff_factor: self.rolloff_factor,
             spatial_blend: self.spatial_blend,
+            bus: self.bus,
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             ..Default::default()
         };
 
         source.set_buffer(self.buffer)?;
-        source.set_playback_time(self.playback_time);
+        source.set_playback_time(self.playback_time)?;
 
         Ok(source)
     }
@@ -0,0 +1,541 @@
+//! Inverse kinematics (IK) allows you to adjust bones procedurally, so an end of a chain of bones
+//! will reach a target. See [`IkChain`] docs for more info.
+
+use crate::{
+    animation::{
+        machine::{Parameter, ParameterContainer, PoseWeight},
+        value::{BoundValue, TrackValue, ValueBinding},
+        AnimationPose,
+    },
+    core::{
+        algebra::{Matrix3, Matrix4, UnitQuaternion, Vector3},
+        math::Matrix4Ext,
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{graph::NodePool, node::Node},
+    utils::log::Log,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// A method that is used to solve an IK chain.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum IkSolver {
+    /// Analytical solver for chains of exactly three nodes (for example upper arm, forearm and
+    /// hand, or thigh, shin and foot). It is the fastest and the most stable solver, an optional
+    /// pole node defines a direction in which the middle joint (elbow, knee) will bend.
+    TwoBone,
+
+    /// Forward And Backward Reaching Inverse Kinematics - iterative solver for chains of any
+    /// length. It converges fast and produces natural looking poses for long chains (tails,
+    /// tentacles, spines).
+    Fabrik,
+
+    /// Cyclic Coordinate Descent - iterative solver for chains of any length. It rotates each
+    /// joint towards the target starting from the end of the chain, it tends to curl the end of
+    /// the chain more than its beginning.
+    Ccd,
+}
+
+impl Default for IkSolver {
+    fn default() -> Self {
+        Self::TwoBone
+    }
+}
+
+/// IK chain is a set of nodes (bones) that will be adjusted so the last node of the chain (end
+/// effector) will reach a position of a target node. IK chains are solved by a layer of animation
+/// blending state machine after all poses of the layer are blended, the result is written to the
+/// pose of the layer, so it will be blended with other layers using layer weight and respects layer
+/// mask.
+///
+/// Typical use cases are placing feet on uneven ground and placing hands on objects (door handles,
+/// weapons, etc.).
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     animation::machine::{IkChain, IkSolver, MachineLayer, PoseWeight},
+///     core::pool::Handle,
+/// };
+///
+/// // Assume that these are correct handles.
+/// let thigh = Handle::default();
+/// let shin = Handle::default();
+/// let foot = Handle::default();
+/// let foot_target = Handle::default();
+/// let knee_pole = Handle::default();
+///
+/// let mut layer = MachineLayer::new();
+///
+/// layer.add_ik_chain(IkChain {
+///     pole: knee_pole,
+///     // The weight is controlled by "LeftFootIk" parameter of the machine.
+///     weight: PoseWeight::Parameter("LeftFootIk".to_string()),
+///     ..IkChain::new("LeftFoot", IkSolver::TwoBone, vec![thigh, shin, foot], foot_target)
+/// });
+/// ```
+#[derive(Debug, Visit, Reflect, Clone, PartialEq)]
+pub struct IkChain {
+    /// Name of the chain.
+    pub name: String,
+
+    /// Whether the chain is enabled or not. Disabled chains have no effect.
+    pub enabled: bool,
+
+    /// A method that is used to solve the chain.
+    pub solver: IkSolver,
+
+    /// A list of nodes of the chain, starting from the root of the chain. Every node must be a
+    /// direct child of the previous node. The last node is the end effector, it will be moved
+    /// to the target.
+    pub bones: Vec<Handle<Node>>,
+
+    /// A node which position is used as the target of the chain.
+    pub target: Handle<Node>,
+
+    /// An optional node which position defines a direction in which the middle joint will bend.
+    /// It is used only by [`IkSolver::TwoBone`].
+    pub pole: Handle<Node>,
+
+    /// Weight of the chain in `[0; 1]` range. Zero means that the animated pose is left
+    /// untouched, one - the pose is fully replaced with the IK solution.
+    pub weight: PoseWeight,
+
+    /// Max amount of iterations for iterative solvers.
+    pub iterations: u32,
+
+    /// Distance between the end effector and the target at which an iterative solver stops.
+    pub tolerance: f32,
+}
+
+impl Default for IkChain {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            enabled: true,
+            solver: Default::default(),
+            bones: Default::default(),
+            target: Default::default(),
+            pole: Default::default(),
+            weight: PoseWeight::Constant(1.0),
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+}
+
+impl IkChain {
+    /// Creates new IK chain with full weight.
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        solver: IkSolver,
+        bones: Vec<Handle<Node>>,
+        target: Handle<Node>,
+    ) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            solver,
+            bones,
+            target,
+            ..Default::default()
+        }
+    }
+
+    fn weight(&self, parameters: &ParameterContainer) -> f32 {
+        let weight = match self.weight {
+            PoseWeight::Constant(value) => value,
+            PoseWeight::Parameter(ref param_id) => {
+                if let Some(Parameter::Weight(weight)) = parameters.get(param_id) {
+                    *weight
+                } else {
+                    0.0
+                }
+            }
+        };
+        weight.clamp(0.0, 1.0)
+    }
+
+    /// Solves the chain and writes rotations of the bones to the given pose.
+    pub(crate) fn solve(
+        &self,
+        pose: &mut AnimationPose,
+        nodes: &NodePool,
+        parameters: &ParameterContainer,
+    ) {
+        let weight = self.weight(parameters);
+        if !self.enabled || weight <= 0.0 || self.bones.len() < 2 {
+            return;
+        }
+
+        let target = match nodes.try_borrow(self.target) {
+            Some(target) => target.global_position(),
+            None => return,
+        };
+
+        let root_parent_transform = match nodes.try_borrow(self.bones[0]) {
+            Some(root) => nodes
+                .try_borrow(root.parent())
+                .map(|p| p.global_transform())
+                .unwrap_or_else(Matrix4::identity),
+            None => return,
+        };
+
+        // Calculate global transforms of the bones using the animated pose.
+        let mut parent_transform = root_parent_transform;
+        let mut locals = Vec::with_capacity(self.bones.len());
+        let mut positions = Vec::with_capacity(self.bones.len());
+        let mut rotations = Vec::with_capacity(self.bones.len());
+        for (i, handle) in self.bones.iter().enumerate() {
+            let node = match nodes.try_borrow(*handle) {
+                Some(node) => node,
+                None => return,
+            };
+
+            if i > 0 && node.parent() != self.bones[i - 1] {
+                Log::warn(format!(
+                    "Unable to solve IK chain {}, because its bones do not form a hierarchy!",
+                    self.name
+                ));
+                return;
+            }
+
+            let mut local = node.local_transform().clone();
+            if let Some(node_pose) = pose.poses().get(handle) {
+                for bound_value in node_pose.values().values.iter() {
+                    match (&bound_value.binding, &bound_value.value) {
+                        (ValueBinding::Position, TrackValue::Vector3(v)) => {
+                            local.set_position(*v);
+                        }
+                        (ValueBinding::Rotation, TrackValue::UnitQuaternion(v)) => {
+                            local.set_rotation(*v);
+                        }
+                        (ValueBinding::Scale, TrackValue::Vector3(v)) => {
+                            local.set_scale(*v);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            let global = parent_transform * local.matrix();
+            positions.push(global.position());
+            rotations.push(matrix_rotation(&global));
+            locals.push(local);
+            parent_transform = global;
+        }
+
+        let new_rotations = match self.solver {
+            IkSolver::TwoBone => {
+                if self.bones.len() != 3 {
+                    Log::warn(format!(
+                        "Unable to solve IK chain {}, two-bone solver requires exactly 3 bones!",
+                        self.name
+                    ));
+                    return;
+                }
+                let pole = nodes.try_borrow(self.pole).map(|p| p.global_position());
+                let new_positions = solve_two_bone(&positions, target, pole);
+                positions_to_rotations(&positions, &new_positions, &rotations)
+            }
+            IkSolver::Fabrik => {
+                let new_positions =
+                    solve_fabrik(&positions, target, self.iterations, self.tolerance);
+                positions_to_rotations(&positions, &new_positions, &rotations)
+            }
+            IkSolver::Ccd => solve_ccd(
+                &positions,
+                &rotations,
+                target,
+                self.iterations,
+                self.tolerance,
+            ),
+        };
+
+        // Convert world-space rotations back to local space. The end effector keeps its local
+        // rotation.
+        let mut parent_rotation = matrix_rotation(&root_parent_transform);
+        for ((handle, local), new_rotation) in self
+            .bones
+            .iter()
+            .zip(locals.iter())
+            .zip(new_rotations.iter())
+            .take(self.bones.len() - 1)
+        {
+            let pre_rotation = **local.pre_rotation();
+            let post_rotation = **local.post_rotation();
+            let ik_rotation = pre_rotation.inverse()
+                * parent_rotation.inverse()
+                * new_rotation
+                * post_rotation.inverse();
+            let rotation = local
+                .rotation()
+                .try_slerp(&ik_rotation, weight, f32::EPSILON)
+                .unwrap_or(ik_rotation);
+
+            set_rotation(pose, *handle, rotation);
+
+            parent_rotation = parent_rotation * pre_rotation * rotation * post_rotation;
+        }
+    }
+}
+
+fn set_rotation(pose: &mut AnimationPose, node: Handle<Node>, rotation: UnitQuaternion<f32>) {
+    if let Some(bound_value) = pose.poses_mut().get_mut(&node).and_then(|p| {
+        p.values
+            .values
+            .iter_mut()
+            .find(|v| v.binding == ValueBinding::Rotation)
+    }) {
+        bound_value.value = TrackValue::UnitQuaternion(rotation);
+        return;
+    }
+
+    pose.add_to_node_pose(
+        node,
+        BoundValue {
+            binding: ValueBinding::Rotation,
+            value: TrackValue::UnitQuaternion(rotation),
+        },
+    );
+}
+
+fn matrix_rotation(m: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    let normalize = |v: Vector3<f32>| v.try_normalize(f32::EPSILON).unwrap_or_default();
+    UnitQuaternion::from_matrix(&Matrix3::from_columns(&[
+        normalize(m.side()),
+        normalize(m.up()),
+        normalize(m.look()),
+    ]))
+}
+
+fn rotation_between(a: &Vector3<f32>, b: &Vector3<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::rotation_between(a, b).unwrap_or_default()
+}
+
+/// Converts new positions of joints to world-space rotations of bones. Every bone is swung from
+/// its old direction to the new one, twist of the bones is preserved.
+fn positions_to_rotations(
+    old_positions: &[Vector3<f32>],
+    new_positions: &[Vector3<f32>],
+    old_rotations: &[UnitQuaternion<f32>],
+) -> Vec<UnitQuaternion<f32>> {
+    let mut new_rotations = old_rotations.to_vec();
+    let mut parent_delta = UnitQuaternion::identity();
+    for i in 0..old_positions.len() - 1 {
+        let old_dir = parent_delta * (old_positions[i + 1] - old_positions[i]);
+        let new_dir = new_positions[i + 1] - new_positions[i];
+        let delta = rotation_between(&old_dir, &new_dir) * parent_delta;
+        new_rotations[i] = delta * old_rotations[i];
+        parent_delta = delta;
+    }
+    new_rotations
+}
+
+fn solve_two_bone(
+    positions: &[Vector3<f32>],
+    target: Vector3<f32>,
+    pole: Option<Vector3<f32>>,
+) -> Vec<Vector3<f32>> {
+    let (a, b, c) = (positions[0], positions[1], positions[2]);
+    let upper_len = (b - a).norm();
+    let lower_len = (c - b).norm();
+
+    let to_target = target - a;
+    let dir = match to_target.try_normalize(f32::EPSILON) {
+        Some(dir) => dir,
+        None => return positions.to_vec(),
+    };
+    let eps = 1.0e-4 * (upper_len + lower_len);
+    let distance = to_target.norm().clamp(
+        (upper_len - lower_len).abs() + eps,
+        upper_len + lower_len - eps,
+    );
+
+    // The middle joint bends towards the pole, or keeps its current bending direction.
+    let hint = pole.map(|p| p - a).unwrap_or(b - a);
+    let bend_dir = (hint - dir.scale(hint.dot(&dir)))
+        .try_normalize(f32::EPSILON)
+        .or_else(|| dir.cross(&Vector3::x()).try_normalize(f32::EPSILON))
+        .unwrap_or_else(Vector3::y);
+
+    // Law of cosines.
+    let cos_a = ((upper_len * upper_len + distance * distance - lower_len * lower_len)
+        / (2.0 * upper_len * distance))
+        .clamp(-1.0, 1.0);
+    let sin_a = (1.0 - cos_a * cos_a).sqrt();
+
+    let new_b = a + (dir.scale(cos_a) + bend_dir.scale(sin_a)).scale(upper_len);
+    let new_c = a + dir.scale(distance);
+
+    vec![a, new_b, new_c]
+}
+
+fn solve_fabrik(
+    positions: &[Vector3<f32>],
+    target: Vector3<f32>,
+    iterations: u32,
+    tolerance: f32,
+) -> Vec<Vector3<f32>> {
+    let mut joints = positions.to_vec();
+    let lengths = positions
+        .windows(2)
+        .map(|w| (w[1] - w[0]).norm())
+        .collect::<Vec<_>>();
+    let root = positions[0];
+    let last = joints.len() - 1;
+
+    if (target - root).norm() >= lengths.iter().sum::<f32>() {
+        // Target is unreachable - just stretch the chain towards it.
+        let dir = (target - root)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+        for i in 0..last {
+            joints[i + 1] = joints[i] + dir.scale(lengths[i]);
+        }
+        return joints;
+    }
+
+    for _ in 0..iterations {
+        if (joints[last] - target).norm() <= tolerance {
+            break;
+        }
+
+        // Backward pass.
+        joints[last] = target;
+        for i in (0..last).rev() {
+            let dir = (joints[i] - joints[i + 1])
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            joints[i] = joints[i + 1] + dir.scale(lengths[i]);
+        }
+
+        // Forward pass.
+        joints[0] = root;
+        for i in 0..last {
+            let dir = (joints[i + 1] - joints[i])
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            joints[i + 1] = joints[i] + dir.scale(lengths[i]);
+        }
+    }
+
+    joints
+}
+
+fn solve_ccd(
+    positions: &[Vector3<f32>],
+    rotations: &[UnitQuaternion<f32>],
+    target: Vector3<f32>,
+    iterations: u32,
+    tolerance: f32,
+) -> Vec<UnitQuaternion<f32>> {
+    let mut joints = positions.to_vec();
+    let mut rotations = rotations.to_vec();
+    let last = joints.len() - 1;
+
+    for _ in 0..iterations {
+        if (joints[last] - target).norm() <= tolerance {
+            break;
+        }
+
+        for i in (0..last).rev() {
+            let pivot = joints[i];
+            let delta = rotation_between(&(joints[last] - pivot), &(target - pivot));
+            for joint in joints[i + 1..].iter_mut() {
+                *joint = pivot + delta * (*joint - pivot);
+            }
+            for rotation in rotations[i..].iter_mut() {
+                *rotation = delta * *rotation;
+            }
+        }
+    }
+
+    rotations
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::machine::ik::{positions_to_rotations, solve_ccd, solve_fabrik, solve_two_bone},
+        core::algebra::{UnitQuaternion, Vector3},
+    };
+
+    fn chain() -> Vec<Vector3<f32>> {
+        vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 2.0, 0.0),
+            Vector3::new(0.0, 3.0, 0.0),
+        ]
+    }
+
+    // Reconstructs positions of joints from world-space rotations of bones.
+    fn forward_kinematics(
+        positions: &[Vector3<f32>],
+        old_rotations: &[UnitQuaternion<f32>],
+        new_rotations: &[UnitQuaternion<f32>],
+    ) -> Vec<Vector3<f32>> {
+        let mut result = vec![positions[0]];
+        for i in 0..positions.len() - 1 {
+            let bone = old_rotations[i].inverse() * (positions[i + 1] - positions[i]);
+            result.push(result[i] + new_rotations[i] * bone);
+        }
+        result
+    }
+
+    #[test]
+    fn test_two_bone_ik() {
+        let positions = &chain()[..3];
+        let target = Vector3::new(1.0, 1.0, 0.0);
+        let pole = Vector3::new(0.0, 1.0, 5.0);
+
+        let new_positions = solve_two_bone(positions, target, Some(pole));
+
+        assert_eq!(new_positions[0], positions[0]);
+        assert!((new_positions[2] - target).norm() < 1.0e-3);
+        assert!(((new_positions[1] - new_positions[0]).norm() - 1.0).abs() < 1.0e-4);
+        assert!(((new_positions[2] - new_positions[1]).norm() - 1.0).abs() < 1.0e-4);
+        // Middle joint must bend towards the pole.
+        assert!(new_positions[1].z > 0.5);
+
+        let rotations = vec![UnitQuaternion::identity(); 3];
+        let new_rotations = positions_to_rotations(positions, &new_positions, &rotations);
+        let reconstructed = forward_kinematics(positions, &rotations, &new_rotations);
+        assert!((reconstructed[2] - target).norm() < 1.0e-3);
+    }
+
+    #[test]
+    fn test_fabrik_ik() {
+        let positions = chain();
+        let target = Vector3::new(1.5, 1.5, 0.0);
+
+        let new_positions = solve_fabrik(&positions, target, 32, 1.0e-4);
+
+        assert_eq!(new_positions[0], positions[0]);
+        assert!((new_positions[3] - target).norm() < 1.0e-3);
+        for pair in new_positions.windows(2) {
+            assert!(((pair[1] - pair[0]).norm() - 1.0).abs() < 1.0e-4);
+        }
+
+        // Unreachable target - the chain must be stretched towards it.
+        let new_positions = solve_fabrik(&positions, Vector3::new(10.0, 0.0, 0.0), 32, 1.0e-4);
+        assert!((new_positions[3] - Vector3::new(3.0, 0.0, 0.0)).norm() < 1.0e-4);
+    }
+
+    #[test]
+    fn test_ccd_ik() {
+        let positions = chain();
+        let rotations = vec![UnitQuaternion::identity(); 4];
+        let target = Vector3::new(1.5, 1.5, 0.0);
+
+        let new_rotations = solve_ccd(&positions, &rotations, target, 32, 1.0e-4);
+        let reconstructed = forward_kinematics(&positions, &rotations, &new_rotations);
+
+        assert!((reconstructed[3] - target).norm() < 1.0e-3);
+    }
+}
@@ -5,8 +5,8 @@ use crate::utils::NameProvider;
 use crate::{
     animation::{
         machine::{
-            event::FixedEventQueue, Event, LayerMask, Parameter, ParameterContainer, PoseNode,
-            State, Transition,
+            event::FixedEventQueue, Event, IkChain, LayerMask, Parameter, ParameterContainer,
+            PoseNode, State, Transition,
         },
         AnimationContainer, AnimationPose,
     },
@@ -15,6 +15,7 @@ use crate::{
         reflect::prelude::*,
         visitor::prelude::*,
     },
+    scene::graph::NodePool,
     utils::{
         self,
         log::{Log, MessageKind},
@@ -73,6 +74,9 @@ pub struct MachineLayer {
 
     mask: LayerMask,
 
+    #[visit(optional)] // Backward compatibility
+    ik_chains: Vec<IkChain>,
+
     #[reflect(hidden)]
     nodes: Pool<PoseNode>,
 
@@ -127,6 +131,7 @@ impl MachineLayer {
             events: FixedEventQueue::new(2048),
             debug: false,
             mask: Default::default(),
+            ik_chains: Default::default(),
         }
     }
 
@@ -387,11 +392,37 @@ impl MachineLayer {
         &self.mask
     }
 
+    /// Adds a new IK chain to the layer. IK chains are solved after all poses of the layer are blended. See
+    /// docs of [`IkChain`] for more info.
+    #[inline]
+    pub fn add_ik_chain(&mut self, ik_chain: IkChain) {
+        self.ik_chains.push(ik_chain);
+    }
+
+    /// Removes an IK chain at given index. Panics if index is out-of-bounds.
+    #[inline]
+    pub fn remove_ik_chain(&mut self, index: usize) -> IkChain {
+        self.ik_chains.remove(index)
+    }
+
+    /// Returns a shared reference to the list of IK chains.
+    #[inline]
+    pub fn ik_chains(&self) -> &[IkChain] {
+        &self.ik_chains
+    }
+
+    /// Returns a mutable reference to the list of IK chains.
+    #[inline]
+    pub fn ik_chains_mut(&mut self) -> &mut Vec<IkChain> {
+        &mut self.ik_chains
+    }
+
     #[inline]
     pub(super) fn evaluate_pose(
         &mut self,
         animations: &AnimationContainer,
         parameters: &ParameterContainer,
+        nodes: Option<&NodePool>,
         dt: f32,
     ) -> &AnimationPose {
         self.final_pose.reset();
@@ -498,6 +529,12 @@ impl MachineLayer {
             }
         }
 
+        if let Some(nodes) = nodes {
+            for ik_chain in self.ik_chains.iter() {
+                ik_chain.solve(&mut self.final_pose, nodes, parameters);
+            }
+        }
+
         self.final_pose
             .poses_mut()
             .retain(|h, _| self.mask.should_animate(*h));
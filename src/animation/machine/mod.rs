@@ -11,14 +11,17 @@ use crate::{
         reflect::prelude::*,
         visitor::{Visit, VisitResult, Visitor},
     },
+    scene::graph::NodePool,
     utils,
 };
 
 pub use event::Event;
+pub use ik::{IkChain, IkSolver};
 pub use layer::MachineLayer;
 pub use mask::LayerMask;
 pub use node::{
     blend::{BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
+    blendspace::{BlendSpace1D, BlendSpace1DPoint, BlendSpace2D, BlendSpace2DPoint},
     play::PlayAnimation,
     EvaluatePose, PoseNode,
 };
@@ -27,6 +30,7 @@ pub use state::State;
 pub use transition::Transition;
 
 pub mod event;
+pub mod ik;
 pub mod layer;
 pub mod mask;
 pub mod node;
@@ -40,8 +44,8 @@ pub mod transition;
 /// # Terminology
 ///
 /// `Node` - is a part of sub-graph that backs _states_ with animations. Typical nodes are `PlayAnimation`, `BlendAnimations`,
-/// `BlendAnimationsByIndex`, etc. Nodes can be connected forming a tree, some node could be marked as output - its animation
-/// will be used in parent state.
+/// `BlendAnimationsByIndex`, `BlendSpace1D`, `BlendSpace2D`, etc. Nodes can be connected forming a tree, some node could be
+/// marked as output - its animation will be used in parent state.
 /// `State` - is a final source of animation for blending. There could be any number of states, for example typical
 /// states are: `run`, `idle`, `jump` etc. A state could be marked as _entry_ state - it will be active at the first frame
 /// when using the machine. There is always one state active.
@@ -52,6 +56,7 @@ pub mod transition;
 /// `Layer` - is a separate state graph, there could be any number of layers - each with its own mask.
 /// `Mask` - a set of handles to nodes which will be excluded from animation on a layer.
 /// `Pose` - a final result of blending multiple animation into one.
+/// `IK chain` - a chain of bones which is adjusted procedurally after pose blending, so the end of the chain reaches a target.
 ///
 /// Summarizing everything of this, we can describe animation blending state machine as a state graph, where each state has its
 /// own sub-graph (tree) that provides animation for blending. States can be connected via transitions.
@@ -277,14 +282,43 @@ impl Machine {
         utils::find_by_name_mut(self.layers.iter_mut().enumerate(), name)
     }
 
-    /// Computes final animation pose that could be then applied to a scene graph.
+    /// Returns final animation pose computed in the last call of [`Self::evaluate_pose`]. Use [`AnimationPose::root_motion`]
+    /// to fetch the blended movement of the root node of animations with root motion enabled.
+    #[inline]
+    pub fn pose(&self) -> &AnimationPose {
+        &self.final_pose
+    }
+
+    /// Computes final animation pose that could be then applied to a scene graph. IK chains of layers are not
+    /// solved, because it requires an access to the scene graph.
     #[inline]
     pub fn evaluate_pose(&mut self, animations: &AnimationContainer, dt: f32) -> &AnimationPose {
+        self.evaluate_pose_internal(animations, None, dt)
+    }
+
+    /// Computes final animation pose that could be then applied to a scene graph. IK chains of each layer are
+    /// solved using given nodes.
+    #[inline]
+    pub(crate) fn evaluate_pose_with_ik(
+        &mut self,
+        animations: &AnimationContainer,
+        nodes: &NodePool,
+        dt: f32,
+    ) -> &AnimationPose {
+        self.evaluate_pose_internal(animations, Some(nodes), dt)
+    }
+
+    fn evaluate_pose_internal(
+        &mut self,
+        animations: &AnimationContainer,
+        nodes: Option<&NodePool>,
+        dt: f32,
+    ) -> &AnimationPose {
         self.final_pose.reset();
 
         for layer in self.layers.iter_mut() {
             let weight = layer.weight();
-            let pose = layer.evaluate_pose(animations, &self.parameters, dt);
+            let pose = layer.evaluate_pose(animations, &self.parameters, nodes, dt);
 
             self.final_pose.blend_with(pose, weight);
         }
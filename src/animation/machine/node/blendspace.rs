@@ -0,0 +1,458 @@
+//! Blend space nodes. See [`BlendSpace1D`] and [`BlendSpace2D`] docs for more info.
+
+use crate::{
+    animation::{
+        machine::{node::BasePoseNode, EvaluatePose, Parameter, ParameterContainer, PoseNode},
+        AnimationContainer, AnimationPose,
+    },
+    core::{
+        algebra::Vector2,
+        math::{get_barycentric_coords_2d, triangulator::triangulate_points},
+        pool::{Handle, Pool},
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+};
+use std::{
+    cell::{Ref, RefCell},
+    ops::{Deref, DerefMut},
+};
+
+fn fetch_parameter(params: &ParameterContainer, name: &str) -> f32 {
+    if let Some(Parameter::Weight(value)) = params.get(name) {
+        *value
+    } else {
+        0.0
+    }
+}
+
+fn blend_sources(
+    output_pose: &RefCell<AnimationPose>,
+    weighted_sources: impl Iterator<Item = (Handle<PoseNode>, f32)>,
+    nodes: &Pool<PoseNode>,
+    params: &ParameterContainer,
+    animations: &AnimationContainer,
+    dt: f32,
+) {
+    output_pose.borrow_mut().reset();
+    for (pose_source, weight) in weighted_sources {
+        if let Some(pose) = nodes
+            .try_borrow(pose_source)
+            .map(|pose_source| pose_source.eval_pose(nodes, params, animations, dt))
+        {
+            output_pose.borrow_mut().blend_with(&pose, weight);
+        }
+    }
+}
+
+/// A sample of 1D blend space - a pose source placed on a line.
+#[derive(Default, Debug, Visit, Clone, Reflect, PartialEq)]
+pub struct BlendSpace1DPoint {
+    /// Position of the sample on the line.
+    pub position: f32,
+
+    /// A source of animation pose.
+    #[reflect(hidden)]
+    pub pose_source: Handle<PoseNode>,
+}
+
+/// 1D blend space is a set of pose sources (usually animations) placed on a line. The node blends two pose sources,
+/// that are closest to the value of a `Weight` parameter from both sides. For example, it could be used to blend
+/// walk and run animations by speed of a character: walk animation at `1.0` and run animation at `4.0` will be
+/// blended in `0.5:0.5` proportion at the speed of `2.5`. Parameter values outside of the range of the samples are
+/// clamped.
+#[derive(Default, Debug, Visit, Clone, Reflect, PartialEq)]
+pub struct BlendSpace1D {
+    /// Base node.
+    pub base: BasePoseNode,
+
+    /// A name of `Weight` parameter, that defines current position in the blend space.
+    pub x_parameter: String,
+
+    /// A set of samples of the blend space.
+    pub points: Vec<BlendSpace1DPoint>,
+
+    /// Output pose of the node, contains final result of blending.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub output_pose: RefCell<AnimationPose>,
+}
+
+impl Deref for BlendSpace1D {
+    type Target = BasePoseNode;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for BlendSpace1D {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl BlendSpace1D {
+    /// Creates new 1D blend space node using given parameter name and a set of samples.
+    pub fn new(x_parameter: String, points: Vec<BlendSpace1DPoint>) -> Self {
+        Self {
+            base: Default::default(),
+            x_parameter,
+            points,
+            output_pose: Default::default(),
+        }
+    }
+
+    /// Returns a set of handles to children pose nodes.
+    pub fn children(&self) -> Vec<Handle<PoseNode>> {
+        self.points.iter().map(|p| p.pose_source).collect()
+    }
+
+    /// Returns current position in the blend space, defined by the parameter.
+    pub fn sample_position(&self, params: &ParameterContainer) -> f32 {
+        fetch_parameter(params, &self.x_parameter)
+    }
+
+    /// Calculates weights of samples at given position. Returns pairs of indices of samples and their weights,
+    /// samples with zero weights are excluded.
+    pub fn weights(&self, position: f32) -> Vec<(usize, f32)> {
+        let mut sorted = (0..self.points.len()).collect::<Vec<_>>();
+        sorted.sort_by(|a, b| {
+            self.points[*a]
+                .position
+                .total_cmp(&self.points[*b].position)
+        });
+
+        let (first, last) = match (sorted.first(), sorted.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return vec![],
+        };
+
+        if position <= self.points[first].position {
+            return vec![(first, 1.0)];
+        }
+        if position >= self.points[last].position {
+            return vec![(last, 1.0)];
+        }
+
+        for pair in sorted.windows(2) {
+            let (left, right) = (pair[0], pair[1]);
+            let left_position = self.points[left].position;
+            let right_position = self.points[right].position;
+            if position >= left_position && position <= right_position {
+                let length = right_position - left_position;
+                let t = if length > f32::EPSILON {
+                    (position - left_position) / length
+                } else {
+                    0.0
+                };
+                return [(left, 1.0 - t), (right, t)]
+                    .into_iter()
+                    .filter(|(_, weight)| *weight > 0.0)
+                    .collect();
+            }
+        }
+
+        vec![]
+    }
+}
+
+impl EvaluatePose for BlendSpace1D {
+    fn eval_pose(
+        &self,
+        nodes: &Pool<PoseNode>,
+        params: &ParameterContainer,
+        animations: &AnimationContainer,
+        dt: f32,
+    ) -> Ref<AnimationPose> {
+        let weights = self.weights(self.sample_position(params));
+        blend_sources(
+            &self.output_pose,
+            weights
+                .into_iter()
+                .map(|(index, weight)| (self.points[index].pose_source, weight)),
+            nodes,
+            params,
+            animations,
+            dt,
+        );
+        self.output_pose.borrow()
+    }
+
+    fn pose(&self) -> Ref<AnimationPose> {
+        self.output_pose.borrow()
+    }
+}
+
+/// A sample of 2D blend space - a pose source placed on a plane.
+#[derive(Default, Debug, Visit, Clone, Reflect, PartialEq)]
+pub struct BlendSpace2DPoint {
+    /// Position of the sample on the plane.
+    pub position: Vector2<f32>,
+
+    /// A source of animation pose.
+    #[reflect(hidden)]
+    pub pose_source: Handle<PoseNode>,
+}
+
+/// 2D blend space is a set of pose sources (usually animations) placed on a plane. Current position in the blend space is
+/// defined by two `Weight` parameters. The samples are triangulated and the node blends three pose sources of a triangle,
+/// that contains current position, using barycentric coordinates of the position as weights. If the position is outside
+/// of the triangles, it is projected on the closest edge. It is usually used for locomotion - for example X axis could
+/// be used for strafe speed and Y axis for forward speed of a character, so idle animation could be placed at `(0, 0)`,
+/// walk forward animation at `(0, 1)`, strafe left at `(-1, 0)` and so on.
+#[derive(Default, Debug, Visit, Clone, Reflect, PartialEq)]
+pub struct BlendSpace2D {
+    /// Base node.
+    pub base: BasePoseNode,
+
+    /// A name of `Weight` parameter, that defines X coordinate of current position in the blend space.
+    pub x_parameter: String,
+
+    /// A name of `Weight` parameter, that defines Y coordinate of current position in the blend space.
+    pub y_parameter: String,
+
+    /// A set of samples of the blend space.
+    pub points: Vec<BlendSpace2DPoint>,
+
+    /// Output pose of the node, contains final result of blending.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub output_pose: RefCell<AnimationPose>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    triangles: RefCell<Vec<[usize; 3]>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    triangulated_positions: RefCell<Vec<Vector2<f32>>>,
+}
+
+impl Deref for BlendSpace2D {
+    type Target = BasePoseNode;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for BlendSpace2D {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl BlendSpace2D {
+    /// Creates new 2D blend space node using given parameter names and a set of samples.
+    pub fn new(x_parameter: String, y_parameter: String, points: Vec<BlendSpace2DPoint>) -> Self {
+        Self {
+            base: Default::default(),
+            x_parameter,
+            y_parameter,
+            points,
+            output_pose: Default::default(),
+            triangles: Default::default(),
+            triangulated_positions: Default::default(),
+        }
+    }
+
+    /// Returns a set of handles to children pose nodes.
+    pub fn children(&self) -> Vec<Handle<PoseNode>> {
+        self.points.iter().map(|p| p.pose_source).collect()
+    }
+
+    /// Returns current position in the blend space, defined by the parameters.
+    pub fn sample_position(&self, params: &ParameterContainer) -> Vector2<f32> {
+        Vector2::new(
+            fetch_parameter(params, &self.x_parameter),
+            fetch_parameter(params, &self.y_parameter),
+        )
+    }
+
+    /// Returns triangles of the blend space, each triangle is a triple of indices of samples. Triangulation is
+    /// performed lazily, when positions of the samples have changed.
+    pub fn triangles(&self) -> Ref<Vec<[usize; 3]>> {
+        let mut positions = self.triangulated_positions.borrow_mut();
+        if positions.len() != self.points.len()
+            || positions
+                .iter()
+                .zip(self.points.iter())
+                .any(|(position, point)| *position != point.position)
+        {
+            positions.clear();
+            positions.extend(self.points.iter().map(|p| p.position));
+            triangulate_points(&positions, &mut self.triangles.borrow_mut());
+        }
+        self.triangles.borrow()
+    }
+
+    /// Calculates weights of samples at given position. Returns pairs of indices of samples and their weights,
+    /// samples with zero weights are excluded.
+    pub fn weights(&self, position: Vector2<f32>) -> Vec<(usize, f32)> {
+        match self.points.len() {
+            0 => return vec![],
+            1 => return vec![(0, 1.0)],
+            _ => (),
+        }
+
+        let triangles = self.triangles();
+
+        for triangle in triangles.iter() {
+            let (u, v, w) = get_barycentric_coords_2d(
+                position,
+                self.points[triangle[0]].position,
+                self.points[triangle[1]].position,
+                self.points[triangle[2]].position,
+            );
+            let eps = -1.0e-5;
+            if u >= eps && v >= eps && w >= eps {
+                return [(triangle[0], u), (triangle[1], v), (triangle[2], w)]
+                    .into_iter()
+                    .filter(|(_, weight)| *weight > 0.0)
+                    .collect();
+            }
+        }
+
+        // The position is outside of the triangles (or there are no triangles at all), project it on the closest
+        // edge.
+        let mut closest: Option<(f32, usize, usize, f32)> = None;
+        let mut check_edge = |a: usize, b: usize| {
+            let pa = self.points[a].position;
+            let ab = self.points[b].position - pa;
+            let length_sqr = ab.norm_squared();
+            let t = if length_sqr > f32::EPSILON {
+                ((position - pa).dot(&ab) / length_sqr).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance = (pa + ab.scale(t) - position).norm_squared();
+            if closest.map_or(true, |(closest_distance, ..)| distance < closest_distance) {
+                closest = Some((distance, a, b, t));
+            }
+        };
+
+        if triangles.is_empty() {
+            for a in 0..self.points.len() {
+                for b in (a + 1)..self.points.len() {
+                    check_edge(a, b);
+                }
+            }
+        } else {
+            for triangle in triangles.iter() {
+                check_edge(triangle[0], triangle[1]);
+                check_edge(triangle[1], triangle[2]);
+                check_edge(triangle[2], triangle[0]);
+            }
+        }
+
+        closest
+            .map(|(_, a, b, t)| {
+                [(a, 1.0 - t), (b, t)]
+                    .into_iter()
+                    .filter(|(_, weight)| *weight > 0.0)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl EvaluatePose for BlendSpace2D {
+    fn eval_pose(
+        &self,
+        nodes: &Pool<PoseNode>,
+        params: &ParameterContainer,
+        animations: &AnimationContainer,
+        dt: f32,
+    ) -> Ref<AnimationPose> {
+        let weights = self.weights(self.sample_position(params));
+        blend_sources(
+            &self.output_pose,
+            weights
+                .into_iter()
+                .map(|(index, weight)| (self.points[index].pose_source, weight)),
+            nodes,
+            params,
+            animations,
+            dt,
+        );
+        self.output_pose.borrow()
+    }
+
+    fn pose(&self) -> Ref<AnimationPose> {
+        self.output_pose.borrow()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::machine::node::blendspace::{
+            BlendSpace1D, BlendSpace1DPoint, BlendSpace2D, BlendSpace2DPoint,
+        },
+        core::{algebra::Vector2, pool::Handle},
+    };
+
+    fn weight_of(weights: &[(usize, f32)], index: usize) -> f32 {
+        weights
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map_or(0.0, |(_, w)| *w)
+    }
+
+    #[test]
+    fn test_blend_space_1d_weights() {
+        let blend_space = BlendSpace1D::new(
+            "Speed".to_string(),
+            [4.0, 0.0, 1.0]
+                .into_iter()
+                .map(|position| BlendSpace1DPoint {
+                    position,
+                    pose_source: Handle::NONE,
+                })
+                .collect(),
+        );
+
+        assert_eq!(blend_space.weights(-1.0), vec![(1, 1.0)]);
+        assert_eq!(blend_space.weights(5.0), vec![(0, 1.0)]);
+
+        let weights = blend_space.weights(2.5);
+        assert!((weight_of(&weights, 2) - 0.5).abs() < 1.0e-5);
+        assert!((weight_of(&weights, 0) - 0.5).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_blend_space_2d_weights() {
+        let blend_space = BlendSpace2D::new(
+            "X".to_string(),
+            "Y".to_string(),
+            [
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(0.0, 1.0),
+            ]
+            .into_iter()
+            .map(|position| BlendSpace2DPoint {
+                position,
+                pose_source: Handle::NONE,
+            })
+            .collect(),
+        );
+
+        // Exactly at a sample.
+        let weights = blend_space.weights(Vector2::new(1.0, 1.0));
+        assert!((weight_of(&weights, 2) - 1.0).abs() < 1.0e-5);
+
+        // Inside.
+        let weights = blend_space.weights(Vector2::new(0.25, 0.25));
+        let sum = weights.iter().map(|(_, w)| *w).sum::<f32>();
+        assert!((sum - 1.0).abs() < 1.0e-5);
+        assert!(weight_of(&weights, 0) > 0.0);
+
+        // Outside, projected on the bottom edge.
+        let weights = blend_space.weights(Vector2::new(0.5, -3.0));
+        assert!((weight_of(&weights, 0) - 0.5).abs() < 1.0e-5);
+        assert!((weight_of(&weights, 1) - 0.5).abs() < 1.0e-5);
+    }
+}
@@ -4,7 +4,11 @@
 use crate::{
     animation::{
         machine::{
-            node::{blend::BlendAnimations, play::PlayAnimation},
+            node::{
+                blend::BlendAnimations,
+                blendspace::{BlendSpace1D, BlendSpace1DPoint, BlendSpace2D, BlendSpace2DPoint},
+                play::PlayAnimation,
+            },
             BlendAnimationsByIndex, BlendPose, IndexedBlendInput, ParameterContainer, State,
         },
         Animation, AnimationContainer, AnimationPose,
@@ -22,6 +26,7 @@ use std::{
 };
 
 pub mod blend;
+pub mod blendspace;
 pub mod play;
 
 /// A set of common data fields that is used in every node.
@@ -46,6 +51,12 @@ pub enum PoseNode {
 
     /// See docs for [`BlendAnimationsByIndex`].
     BlendAnimationsByIndex(BlendAnimationsByIndex),
+
+    /// See docs for [`BlendSpace1D`].
+    BlendSpace1D(BlendSpace1D),
+
+    /// See docs for [`BlendSpace2D`].
+    BlendSpace2D(BlendSpace2D),
 }
 
 impl Default for PoseNode {
@@ -74,6 +85,20 @@ impl PoseNode {
         Self::BlendAnimationsByIndex(BlendAnimationsByIndex::new(index_parameter, inputs))
     }
 
+    /// Creates new node that blends animations placed on a line using a parameter.
+    pub fn make_blend_space_1d(x_parameter: String, points: Vec<BlendSpace1DPoint>) -> Self {
+        Self::BlendSpace1D(BlendSpace1D::new(x_parameter, points))
+    }
+
+    /// Creates new node that blends animations placed on a plane using two parameters.
+    pub fn make_blend_space_2d(
+        x_parameter: String,
+        y_parameter: String,
+        points: Vec<BlendSpace2DPoint>,
+    ) -> Self {
+        Self::BlendSpace2D(BlendSpace2D::new(x_parameter, y_parameter, points))
+    }
+
     /// Returns a set of handles to children pose nodes.
     pub fn children(&self) -> Vec<Handle<PoseNode>> {
         match self {
@@ -83,6 +108,8 @@ impl PoseNode {
             }
             Self::BlendAnimations(definition) => definition.children(),
             Self::BlendAnimationsByIndex(definition) => definition.children(),
+            Self::BlendSpace1D(definition) => definition.children(),
+            Self::BlendSpace2D(definition) => definition.children(),
         }
     }
 }
@@ -93,6 +120,8 @@ macro_rules! static_dispatch {
             PoseNode::PlayAnimation(v) => v.$func($($args),*),
             PoseNode::BlendAnimations(v) => v.$func($($args),*),
             PoseNode::BlendAnimationsByIndex(v) => v.$func($($args),*),
+            PoseNode::BlendSpace1D(v) => v.$func($($args),*),
+            PoseNode::BlendSpace2D(v) => v.$func($($args),*),
         }
     };
 }
@@ -2,8 +2,9 @@
 //! See [`Animation`] docs for more info.
 
 use crate::{
-    animation::track::Track,
+    animation::{track::Track, value::TrackValue, value::ValueBinding},
     core::{
+        algebra::{UnitQuaternion, Vector3},
         math::wrapf,
         pool::{Handle, Pool, Ticket},
         reflect::prelude::*,
@@ -22,11 +23,14 @@ use std::{
 };
 
 pub use pose::{AnimationPose, NodePose};
+pub use root_motion::{RootMotion, RootMotionSettings};
 pub use signal::{AnimationEvent, AnimationSignal};
 
 pub mod container;
 pub mod machine;
 pub mod pose;
+pub mod retarget;
+pub mod root_motion;
 pub mod signal;
 pub mod spritesheet;
 pub mod track;
@@ -187,6 +191,8 @@ pub struct Animation {
     looped: bool,
     enabled: bool,
     signals: Vec<AnimationSignal>,
+    #[visit(optional)] // Backward compatibility
+    root_motion_settings: Option<RootMotionSettings>,
 
     // Non-serialized
     #[reflect(hidden)]
@@ -217,6 +223,7 @@ impl Clone for Animation {
             signals: self.signals.clone(),
             events: Default::default(),
             time_slice: self.time_slice.clone(),
+            root_motion_settings: self.root_motion_settings,
         }
     }
 }
@@ -341,6 +348,25 @@ impl Animation {
         }
 
         self.set_time_position(new_time_position);
+
+        self.update_root_motion(current_time_position, new_time_position);
+    }
+
+    /// Sets new root motion settings of the animation. `None` disables root motion. See [`RootMotionSettings`] docs for
+    /// more info.
+    pub fn set_root_motion_settings(&mut self, settings: Option<RootMotionSettings>) {
+        self.root_motion_settings = settings;
+    }
+
+    /// Returns current root motion settings of the animation.
+    pub fn root_motion_settings(&self) -> Option<&RootMotionSettings> {
+        self.root_motion_settings.as_ref()
+    }
+
+    /// Returns movement of the root node in the last update tick (if root motion is enabled). See [`RootMotionSettings`]
+    /// docs for more info.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.pose.root_motion()
     }
 
     /// Extracts a first event from the events queue of the animation.
@@ -543,6 +569,121 @@ impl Animation {
                 }
             }
         }
+
+        // Strip movement of the root node, it is then extracted into root motion.
+        if let Some(settings) = self.root_motion_settings {
+            let (start_position, start_rotation) =
+                self.fetch_root_transform(settings.node, self.time_slice.start);
+            if let Some(root_pose) = self.pose.poses_mut().get_mut(&settings.node) {
+                for bound_value in root_pose.values.values.iter_mut() {
+                    match (&bound_value.binding, &mut bound_value.value) {
+                        (ValueBinding::Position, TrackValue::Vector3(position)) => {
+                            if let Some(start_position) = start_position {
+                                if !settings.ignore_x_movement {
+                                    position.x = start_position.x;
+                                }
+                                if !settings.ignore_y_movement {
+                                    position.y = start_position.y;
+                                }
+                                if !settings.ignore_z_movement {
+                                    position.z = start_position.z;
+                                }
+                            }
+                        }
+                        (ValueBinding::Rotation, TrackValue::UnitQuaternion(rotation)) => {
+                            if let Some(start_rotation) = start_rotation {
+                                if !settings.ignore_rotations {
+                                    *rotation = start_rotation;
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+
+    fn fetch_root_transform(
+        &self,
+        root: Handle<Node>,
+        time: f32,
+    ) -> (Option<Vector3<f32>>, Option<UnitQuaternion<f32>>) {
+        let mut position = None;
+        let mut rotation = None;
+        for track in self.tracks.iter() {
+            if track.is_enabled() && track.target() == root {
+                if let Some(bound_value) = track.fetch(time) {
+                    match (bound_value.binding, bound_value.value) {
+                        (ValueBinding::Position, TrackValue::Vector3(v)) => position = Some(v),
+                        (ValueBinding::Rotation, TrackValue::UnitQuaternion(v)) => {
+                            rotation = Some(v)
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        (position, rotation)
+    }
+
+    fn update_root_motion(&mut self, prev_time_position: f32, new_time_position: f32) {
+        let settings = match self.root_motion_settings {
+            Some(settings) if settings.node.is_some() => settings,
+            _ => return,
+        };
+
+        // Split the movement in two parts if the playback position was wrapped.
+        let mut segments = vec![];
+        if self.looped && new_time_position > self.time_slice.end {
+            segments.push((prev_time_position, self.time_slice.end));
+            segments.push((self.time_slice.start, self.time_position));
+        } else if self.looped && new_time_position < self.time_slice.start {
+            segments.push((prev_time_position, self.time_slice.start));
+            segments.push((self.time_slice.end, self.time_position));
+        } else {
+            segments.push((prev_time_position, self.time_position));
+        }
+
+        let (_, start_rotation) = self.fetch_root_transform(settings.node, self.time_slice.start);
+
+        let mut root_motion = RootMotion::default();
+        for (begin, end) in segments {
+            let (begin_position, begin_rotation) = self.fetch_root_transform(settings.node, begin);
+            let (end_position, end_rotation) = self.fetch_root_transform(settings.node, end);
+
+            let mut delta_position = match (begin_position, end_position) {
+                (Some(begin_position), Some(end_position)) => end_position - begin_position,
+                _ => Vector3::default(),
+            };
+            if settings.ignore_x_movement {
+                delta_position.x = 0.0;
+            }
+            if settings.ignore_y_movement {
+                delta_position.y = 0.0;
+            }
+            if settings.ignore_z_movement {
+                delta_position.z = 0.0;
+            }
+
+            let mut delta_rotation = UnitQuaternion::identity();
+            if !settings.ignore_rotations {
+                if let (Some(start_rotation), Some(begin_rotation), Some(end_rotation)) =
+                    (start_rotation, begin_rotation, end_rotation)
+                {
+                    // Root node is always in its start rotation in the pose, so the movement must be
+                    // transformed into the frame of the start rotation.
+                    let to_start = start_rotation * begin_rotation.inverse();
+                    delta_position = to_start * delta_position;
+                    delta_rotation = to_start * end_rotation * start_rotation.inverse();
+                }
+            }
+
+            root_motion.delta_position += root_motion.delta_rotation * delta_position;
+            root_motion.delta_rotation *= delta_rotation;
+        }
+
+        self.pose.set_root_motion(Some(root_motion));
     }
 
     /// Returns current pose of the animation (a final result that can be applied to a scene graph).
@@ -564,6 +705,7 @@ impl Default for Animation {
             signals: Default::default(),
             events: Default::default(),
             time_slice: Default::default(),
+            root_motion_settings: None,
         }
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    animation::{value::BoundValue, value::BoundValueCollection},
+    animation::{value::BoundValue, value::BoundValueCollection, RootMotion},
     core::pool::Handle,
     scene::{graph::Graph, graph::NodePool, node::Node},
     utils::log::{Log, MessageKind},
@@ -42,6 +42,7 @@ impl NodePose {
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct AnimationPose {
     poses: FxHashMap<Handle<Node>, NodePose>,
+    root_motion: Option<RootMotion>,
 }
 
 impl AnimationPose {
@@ -50,6 +51,7 @@ impl AnimationPose {
         for (handle, local_pose) in self.poses.iter() {
             dest.poses.insert(*handle, local_pose.clone());
         }
+        dest.root_motion = self.root_motion;
     }
 
     pub fn blend_with(&mut self, other: &AnimationPose, weight: f32) {
@@ -62,6 +64,12 @@ impl AnimationPose {
                 self.add_node_pose(other_pose.weighted_clone(weight));
             }
         }
+
+        if let Some(other_root_motion) = other.root_motion.as_ref() {
+            self.root_motion
+                .get_or_insert_with(Default::default)
+                .blend_with(other_root_motion, weight);
+        }
     }
 
     fn add_node_pose(&mut self, local_pose: NodePose) {
@@ -86,6 +94,17 @@ impl AnimationPose {
 
     pub fn reset(&mut self) {
         self.poses.clear();
+        self.root_motion = None;
+    }
+
+    /// Returns movement of a root node in the last update tick (if any). See [`crate::animation::RootMotionSettings`]
+    /// docs for more info.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.root_motion.as_ref()
+    }
+
+    pub(crate) fn set_root_motion(&mut self, root_motion: Option<RootMotion>) {
+        self.root_motion = root_motion;
     }
 
     pub fn poses(&self) -> &FxHashMap<Handle<Node>, NodePose> {
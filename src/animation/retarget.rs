@@ -0,0 +1,538 @@
+//! Animation retargeting allows you to transfer animations between skeletons with different bind poses and
+//! proportions. See [`AnimationRetargeter`] docs for more info.
+
+use crate::{
+    animation::{
+        container::{TrackFramesContainer, TrackValueKind},
+        track::Track,
+        value::{TrackValue, ValueBinding},
+        Animation, RootMotionSettings,
+    },
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        curve::{Curve, CurveKey, CurveKeyKind},
+        math::unwrap_angle,
+        pool::Handle,
+    },
+    scene::{graph::Graph, node::Node},
+    utils::log::Log,
+};
+use fxhash::FxHashMap;
+
+/// Settings of animation retargeting.
+#[derive(Clone, Debug, Default)]
+pub struct RetargetSettings {
+    /// Bone name overrides in `source name -> target name` form. Bones that are not listed here are matched
+    /// by their names.
+    pub bone_name_overrides: FxHashMap<String, String>,
+    /// Scale that will be applied to translations of bones. If not set, it will be calculated automatically
+    /// as a ratio of heights of root bones (usually hips) of target and source skeletons.
+    pub translation_scale: Option<f32>,
+}
+
+impl RetargetSettings {
+    /// Adds a new bone name override.
+    pub fn with_bone_name_override<S: AsRef<str>, T: AsRef<str>>(
+        mut self,
+        source: S,
+        target: T,
+    ) -> Self {
+        self.bone_name_overrides
+            .insert(source.as_ref().to_owned(), target.as_ref().to_owned());
+        self
+    }
+
+    /// Sets desired translation scale.
+    pub fn with_translation_scale(mut self, scale: f32) -> Self {
+        self.translation_scale = Some(scale);
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BindPose {
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+    scale: Vector3<f32>,
+    // Rotation of the parent bone relative to the root of a skeleton.
+    parent_rotation: UnitQuaternion<f32>,
+}
+
+impl BindPose {
+    fn new(graph: &Graph, root: Handle<Node>, bone: Handle<Node>) -> Self {
+        let transform = graph[bone].local_transform();
+
+        let mut parent_rotation = UnitQuaternion::identity();
+        if bone != root {
+            let mut parent = graph[bone].parent();
+            while parent.is_some() && parent != root {
+                parent_rotation = **graph[parent].local_transform().rotation() * parent_rotation;
+                parent = graph[parent].parent();
+            }
+        }
+
+        Self {
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+            scale: **transform.scale(),
+            parent_rotation,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BoneMapping {
+    target: Handle<Node>,
+    source_bind: BindPose,
+    target_bind: BindPose,
+}
+
+impl BoneMapping {
+    fn retarget_position(&self, position: Vector3<f32>, scale: f32) -> Vector3<f32> {
+        // Offset from the bind pose in the space of the root of the source skeleton.
+        let offset = self.source_bind.parent_rotation * (position - self.source_bind.position);
+        self.target_bind.position
+            + self
+                .target_bind
+                .parent_rotation
+                .inverse_transform_vector(&offset.scale(scale))
+    }
+
+    fn retarget_rotation(&self, rotation: UnitQuaternion<f32>) -> UnitQuaternion<f32> {
+        // Rotation relative to the bind pose in the space of the root of the source skeleton.
+        let source_parent = self.source_bind.parent_rotation;
+        let delta = source_parent
+            * rotation
+            * self.source_bind.rotation.inverse()
+            * source_parent.inverse();
+        let target_parent = self.target_bind.parent_rotation;
+        target_parent.inverse() * delta * target_parent * self.target_bind.rotation
+    }
+
+    fn retarget_scale(&self, scale: Vector3<f32>) -> Vector3<f32> {
+        let ratio = Vector3::new(
+            safe_ratio(scale.x, self.source_bind.scale.x),
+            safe_ratio(scale.y, self.source_bind.scale.y),
+            safe_ratio(scale.z, self.source_bind.scale.z),
+        );
+        self.target_bind.scale.component_mul(&ratio)
+    }
+}
+
+fn safe_ratio(a: f32, b: f32) -> f32 {
+    if b.abs() > f32::EPSILON {
+        a / b
+    } else {
+        1.0
+    }
+}
+
+// Height of a bone relative to the root of a skeleton in its bind pose.
+fn bind_height(graph: &Graph, root: Handle<Node>, bone: Handle<Node>) -> f32 {
+    let mut position = Vector3::default();
+    let mut current = bone;
+    while current.is_some() && current != root {
+        let transform = graph[current].local_transform();
+        position = transform.position().clone_inner()
+            + transform
+                .rotation()
+                .transform_vector(&transform.scale().component_mul(&position));
+        current = graph[current].parent();
+    }
+    position.norm()
+}
+
+fn depth(graph: &Graph, root: Handle<Node>, bone: Handle<Node>) -> usize {
+    let mut depth = 0;
+    let mut current = bone;
+    while current.is_some() && current != root {
+        depth += 1;
+        current = graph[current].parent();
+    }
+    depth
+}
+
+/// Animation retargeting allows you to transfer animations from one skeleton to another, even if they have
+/// different bind poses and proportions. It is useful to share a single animation library (for example, a motion
+/// capture library) across multiple humanoid characters.
+///
+/// # How it works
+///
+/// At first, the retargeter maps bones of the source skeleton to bones of the target skeleton. Bones are matched
+/// by their names, but you can override the mapping using [`RetargetSettings::bone_name_overrides`]. Then every
+/// track of a source animation is converted to a track of a new animation bound to a respective target bone:
+///
+/// - Rotations are converted to deltas relative to the bind pose in the space of the root of the source skeleton,
+/// and then applied to the bind pose of the target bone. This way the retargeting compensates differing bind poses
+/// and bone orientations.
+/// - Translations are converted to offsets relative to the bind pose and scaled by the ratio of heights of root
+/// bones of the skeletons (see [`RetargetSettings::translation_scale`]). This compensates differing limb lengths,
+/// because bones of the target skeleton keep their own lengths.
+/// - Scales are converted to ratios of bind scales.
+/// - Tracks bound to any other properties are copied as is.
+///
+/// Tracks of bones that have no pair in the target skeleton are discarded.
+///
+/// # Important notes
+///
+/// Both skeletons must be in a similar reference pose (for example T-pose) in their bind poses, otherwise the
+/// result will be distorted. Bind poses are taken from current local transforms of the bones, so make sure that
+/// both skeletons are not animated at the moment of creation of the retargeter.
+///
+/// Rotation tracks are resampled at key frame locations of the source track and use linear interpolation.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     animation::{
+///         retarget::{AnimationRetargeter, RetargetSettings},
+///         Animation,
+///     },
+///     core::pool::Handle,
+///     scene::{graph::Graph, node::Node},
+/// };
+///
+/// fn retarget(
+///     mocap_animation: &Animation,
+///     mocap_graph: &Graph,
+///     mocap_root: Handle<Node>,
+///     character_graph: &Graph,
+///     character_root: Handle<Node>,
+/// ) -> Animation {
+///     let retargeter = AnimationRetargeter::new(
+///         mocap_graph,
+///         mocap_root,
+///         character_graph,
+///         character_root,
+///         &RetargetSettings::default().with_bone_name_override("Hips", "mixamorig:Hips"),
+///     );
+///
+///     retargeter.retarget(mocap_animation)
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnimationRetargeter {
+    bones: FxHashMap<Handle<Node>, BoneMapping>,
+    translation_scale: f32,
+}
+
+impl AnimationRetargeter {
+    /// Creates new retargeter that maps bones of the hierarchy starting from `source_root` in `source_graph` to
+    /// bones of the hierarchy starting from `target_root` in `target_graph`.
+    pub fn new(
+        source_graph: &Graph,
+        source_root: Handle<Node>,
+        target_graph: &Graph,
+        target_root: Handle<Node>,
+        settings: &RetargetSettings,
+    ) -> Self {
+        let target_bones = target_graph
+            .traverse_handle_iter(target_root)
+            .map(|handle| (target_graph[handle].name_owned(), handle))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut bones = FxHashMap::default();
+        let mut root_bones = None;
+        for source in source_graph.traverse_handle_iter(source_root) {
+            let source_name = source_graph[source].name();
+            let target_name = settings
+                .bone_name_overrides
+                .get(source_name)
+                .map(|n| n.as_str())
+                .unwrap_or(source_name);
+
+            if let Some(&target) = target_bones.get(target_name) {
+                // Mapped bone that is closest to the root is the root bone of the skeleton.
+                if source != source_root {
+                    let depth = depth(source_graph, source_root, source);
+                    if root_bones.map_or(true, |(d, _, _)| depth < d) {
+                        root_bones = Some((depth, source, target));
+                    }
+                }
+
+                bones.insert(
+                    source,
+                    BoneMapping {
+                        target,
+                        source_bind: BindPose::new(source_graph, source_root, source),
+                        target_bind: BindPose::new(target_graph, target_root, target),
+                    },
+                );
+            }
+        }
+
+        let translation_scale = settings.translation_scale.unwrap_or_else(|| {
+            root_bones.map_or(1.0, |(_, source, target)| {
+                safe_ratio(
+                    bind_height(target_graph, target_root, target),
+                    bind_height(source_graph, source_root, source),
+                )
+            })
+        });
+
+        Self {
+            bones,
+            translation_scale,
+        }
+    }
+
+    /// Returns a handle of a target bone that corresponds to a given source bone.
+    pub fn target_of(&self, source: Handle<Node>) -> Handle<Node> {
+        self.bones
+            .get(&source)
+            .map(|mapping| mapping.target)
+            .unwrap_or_default()
+    }
+
+    /// Returns translation scale that is used for retargeting.
+    pub fn translation_scale(&self) -> f32 {
+        self.translation_scale
+    }
+
+    /// Produces new animation bound to the nodes of the target skeleton.
+    pub fn retarget(&self, animation: &Animation) -> Animation {
+        let mut result = animation.clone();
+        result.remove_tracks();
+
+        for track in animation.tracks() {
+            let mapping = match self.bones.get(&track.target()) {
+                Some(mapping) => mapping,
+                None => {
+                    Log::warn(format!(
+                        "Unable to retarget a track of {} animation, because its node {} has no pair in the target skeleton!",
+                        animation.name(),
+                        track.target()
+                    ));
+                    continue;
+                }
+            };
+
+            let mut new_track = track.clone();
+            new_track.set_target(mapping.target);
+
+            let container = track.frames_container();
+            match (track.binding(), container.value_kind()) {
+                (ValueBinding::Position, TrackValueKind::Vector3) => {
+                    new_track.set_frames_container(resample(container, |value| match value {
+                        TrackValue::Vector3(v) => TrackValue::Vector3(
+                            mapping.retarget_position(v, self.translation_scale),
+                        ),
+                        _ => value,
+                    }));
+                }
+                (ValueBinding::Rotation, TrackValueKind::UnitQuaternion) => {
+                    new_track.set_frames_container(resample(container, |value| match value {
+                        TrackValue::UnitQuaternion(q) => {
+                            TrackValue::UnitQuaternion(mapping.retarget_rotation(q))
+                        }
+                        _ => value,
+                    }));
+                }
+                (ValueBinding::Scale, TrackValueKind::Vector3) => {
+                    new_track.set_frames_container(resample(container, |value| match value {
+                        TrackValue::Vector3(v) => TrackValue::Vector3(mapping.retarget_scale(v)),
+                        _ => value,
+                    }));
+                }
+                _ => (),
+            }
+
+            result.add_track(new_track);
+        }
+
+        if let Some(settings) = animation.root_motion_settings() {
+            result.set_root_motion_settings(Some(RootMotionSettings {
+                node: self.target_of(settings.node),
+                ..*settings
+            }));
+        }
+
+        result
+    }
+}
+
+// Samples the container at every key frame location, transforms sampled values and puts them in a new
+// container of the same kind.
+fn resample<F>(container: &TrackFramesContainer, mut transform: F) -> TrackFramesContainer
+where
+    F: FnMut(TrackValue) -> TrackValue,
+{
+    let mut locations = container
+        .curves_ref()
+        .iter()
+        .flat_map(|curve| curve.keys().iter().map(|k| k.location()))
+        .collect::<Vec<_>>();
+    locations.sort_by(|a, b| a.total_cmp(b));
+    locations.dedup();
+
+    let kind = container.value_kind();
+    let mut curves = vec![Vec::new(); kind.components_count()];
+    let mut previous: Option<Vector3<f32>> = None;
+    for location in locations {
+        let value = match container.fetch(location) {
+            Some(value) => transform(value),
+            None => continue,
+        };
+
+        // Keep step interpolation if any of the source keys is using it.
+        let key_kind = if container.curves_ref().iter().any(|curve| {
+            curve
+                .keys()
+                .iter()
+                .any(|k| k.location() == location && k.kind == CurveKeyKind::Constant)
+        }) {
+            CurveKeyKind::Constant
+        } else {
+            CurveKeyKind::Linear
+        };
+
+        let components = match value {
+            TrackValue::Real(v) => vec![v],
+            TrackValue::Vector2(v) => v.as_slice().to_vec(),
+            TrackValue::Vector3(v) => v.as_slice().to_vec(),
+            TrackValue::Vector4(v) => v.as_slice().to_vec(),
+            TrackValue::UnitQuaternion(q) => {
+                let (roll, pitch, yaw) = q.euler_angles();
+                let mut euler = Vector3::new(roll, pitch, yaw);
+                if let Some(previous) = previous {
+                    euler = euler.zip_map(&previous, unwrap_angle);
+                }
+                previous = Some(euler);
+                euler.as_slice().to_vec()
+            }
+        };
+
+        for (keys, component) in curves.iter_mut().zip(components) {
+            keys.push(CurveKey::new(location, component, key_kind.clone()));
+        }
+    }
+
+    let mut result = TrackFramesContainer::new(kind);
+    for (curve, keys) in result.curves_mut().iter_mut().zip(curves) {
+        let mut new_curve = Curve::from(keys);
+        new_curve.set_name(curve.name());
+        *curve = new_curve;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            container::{TrackFramesContainer, TrackValueKind},
+            retarget::{AnimationRetargeter, RetargetSettings},
+            track::Track,
+            value::{TrackValue, ValueBinding},
+            Animation,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            curve::{Curve, CurveKey, CurveKeyKind},
+        },
+        scene::{
+            base::BaseBuilder, graph::Graph, pivot::PivotBuilder, transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_animation_retargeting() {
+        let mut source_graph = Graph::new();
+        let source_arm =
+            PivotBuilder::new(BaseBuilder::new().with_name("Arm")).build(&mut source_graph);
+        let source_hips = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Hips")
+                .with_children(&[source_arm])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                        .build(),
+                ),
+        )
+        .build(&mut source_graph);
+        let source_root = source_graph.get_root();
+
+        let target_bind_rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 1.0);
+        let mut target_graph = Graph::new();
+        let target_arm = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("RightArm")
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_rotation(target_bind_rotation)
+                        .build(),
+                ),
+        )
+        .build(&mut target_graph);
+        let target_hips = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Hips")
+                .with_children(&[target_arm])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                        .build(),
+                ),
+        )
+        .build(&mut target_graph);
+        let target_root = target_graph.get_root();
+
+        let mut position_frames = TrackFramesContainer::new(TrackValueKind::Vector3);
+        position_frames.curves_mut()[0] = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 1.0, CurveKeyKind::Linear),
+        ]);
+        position_frames.curves_mut()[1] =
+            Curve::from(vec![CurveKey::new(0.0, 1.0, CurveKeyKind::Linear)]);
+        let mut position_track = Track::new(position_frames, ValueBinding::Position);
+        position_track.set_target(source_hips);
+
+        let mut rotation_frames = TrackFramesContainer::new(TrackValueKind::UnitQuaternion);
+        rotation_frames.curves_mut()[0] = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 0.5, CurveKeyKind::Linear),
+        ]);
+        let mut rotation_track = Track::new(rotation_frames, ValueBinding::Rotation);
+        rotation_track.set_target(source_arm);
+
+        let mut animation = Animation::default();
+        animation.add_track(position_track);
+        animation.add_track(rotation_track);
+
+        let retargeter = AnimationRetargeter::new(
+            &source_graph,
+            source_root,
+            &target_graph,
+            target_root,
+            &RetargetSettings::default().with_bone_name_override("Arm", "RightArm"),
+        );
+        assert_eq!(retargeter.translation_scale(), 2.0);
+        assert_eq!(retargeter.target_of(source_hips), target_hips);
+        assert_eq!(retargeter.target_of(source_arm), target_arm);
+
+        let retargeted = retargeter.retarget(&animation);
+        assert_eq!(retargeted.tracks().len(), 2);
+
+        // Translations must be scaled relative to the bind pose of the target skeleton.
+        let position_track = &retargeted.tracks()[0];
+        assert_eq!(position_track.target(), target_hips);
+        assert_eq!(
+            position_track.frames_container().fetch(1.0),
+            Some(TrackValue::Vector3(Vector3::new(2.0, 2.0, 0.0)))
+        );
+
+        // Rotations must be applied on top of the bind pose of the target bone.
+        let rotation_track = &retargeted.tracks()[1];
+        assert_eq!(rotation_track.target(), target_arm);
+        let expected =
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.5) * target_bind_rotation;
+        match rotation_track.frames_container().fetch(1.0) {
+            Some(TrackValue::UnitQuaternion(rotation)) => {
+                assert!(rotation.angle_to(&expected) < 1.0e-4)
+            }
+            _ => panic!("Rotation must be retargeted!"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Root motion allows you to move a character using its animations. See [`RootMotionSettings`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::node::Node,
+};
+
+/// Root motion settings of an animation. Root motion is a technique that extracts movement of a root node (usually
+/// hips bone of a character) from an animation and allows you to apply it to some other object (usually a rigid body
+/// or a character controller). This way character movement will exactly match its animations and the feet won't
+/// slide on the ground.
+///
+/// When root motion is enabled, translation and rotation of the root node are stripped from the pose of the animation,
+/// so the root node will stay at its position and rotation at the beginning of the time slice of the animation. Per-frame
+/// movement of the root node is then available via [`super::Animation::root_motion`] (or [`super::AnimationPose::root_motion`]
+/// for poses produced by animation blending state machines).
+///
+/// Movement along ignored axes is kept in the pose and excluded from the root motion. It could be useful, for example,
+/// to keep vertical movement of hips of a character in a jump animation.
+#[derive(Default, Debug, Visit, Reflect, Clone, Copy, PartialEq, Eq)]
+pub struct RootMotionSettings {
+    /// A handle to a root node which movement will be extracted.
+    pub node: Handle<Node>,
+    /// Keeps movement along X axis in the pose.
+    pub ignore_x_movement: bool,
+    /// Keeps movement along Y axis in the pose.
+    pub ignore_y_movement: bool,
+    /// Keeps movement along Z axis in the pose.
+    pub ignore_z_movement: bool,
+    /// Keeps rotations in the pose.
+    pub ignore_rotations: bool,
+}
+
+/// Movement of a root node of an animation in a single update tick. The movement is defined in local coordinates of
+/// the parent of the root node (usually it is the root of a character model), so to apply it to a rigid body you need
+/// to transform the delta position by the current rotation of the model and then multiply the rotation of the body by
+/// the delta rotation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootMotion {
+    /// Position delta of the root node.
+    pub delta_position: Vector3<f32>,
+    /// Rotation delta of the root node.
+    pub delta_rotation: UnitQuaternion<f32>,
+}
+
+impl Default for RootMotion {
+    fn default() -> Self {
+        Self {
+            delta_position: Default::default(),
+            delta_rotation: UnitQuaternion::identity(),
+        }
+    }
+}
+
+impl RootMotion {
+    pub(crate) fn weighted_clone(&self, weight: f32) -> Self {
+        Self {
+            delta_position: self.delta_position.scale(weight),
+            delta_rotation: UnitQuaternion::identity().nlerp(&self.delta_rotation, weight),
+        }
+    }
+
+    /// Blends the root motion with other one using given weight. Deltas are accumulated, so the result of blending of
+    /// multiple weighted root motions is their weighted sum.
+    pub fn blend_with(&mut self, other: &RootMotion, weight: f32) {
+        let other = other.weighted_clone(weight);
+        self.delta_position += other.delta_position;
+        self.delta_rotation = other.delta_rotation * self.delta_rotation;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            container::{TrackFramesContainer, TrackValueKind},
+            track::Track,
+            value::{TrackValue, ValueBinding},
+            Animation, RootMotionSettings,
+        },
+        core::{
+            curve::{Curve, CurveKey, CurveKeyKind},
+            pool::Handle,
+        },
+    };
+
+    #[test]
+    fn test_root_motion_extraction() {
+        let root = Handle::new(1, 1);
+
+        let mut frames_container = TrackFramesContainer::new(TrackValueKind::Vector3);
+        frames_container.add_curve(Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 1.0, CurveKeyKind::Linear),
+        ]));
+        frames_container.add_curve(Curve::default());
+        frames_container.add_curve(Curve::default());
+        let mut track = Track::new(frames_container, ValueBinding::Position);
+        track.set_target(root);
+
+        let mut animation = Animation::default();
+        animation.add_track(track);
+        animation.set_time_slice(0.0..1.0);
+        animation.set_root_motion_settings(Some(RootMotionSettings {
+            node: root,
+            ..Default::default()
+        }));
+
+        animation.tick(0.5);
+        let root_motion = animation.root_motion().unwrap();
+        assert!((root_motion.delta_position.x - 0.5).abs() < 1.0e-5);
+
+        // Playback position is wrapped, the movement must be accumulated correctly.
+        animation.tick(0.75);
+        let root_motion = animation.root_motion().unwrap();
+        assert!((root_motion.delta_position.x - 0.75).abs() < 1.0e-5);
+
+        // Movement of the root node must be stripped from the pose.
+        let root_pose = &animation.pose().poses()[&root];
+        assert_eq!(
+            root_pose.values.values[0].value,
+            TrackValue::Vector3(Default::default())
+        );
+    }
+}
@@ -1,5 +1,10 @@
 //! Executor is a small wrapper that manages plugins and scripts for your game.
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    asset::pack::{self, AssetPack},
+    plugin::dynamic::DynamicPluginError,
+};
 use crate::{
     core::instant::Instant,
     engine::{resource_manager::ResourceManager, Engine, EngineInitParams, SerializationContext},
@@ -14,6 +19,8 @@ use crate::{
     window::WindowBuilder,
 };
 use clap::Parser;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
 use std::{
     ops::{Deref, DerefMut},
     sync::Arc,
@@ -24,6 +31,10 @@ use std::{
 struct Args {
     #[clap(short, long, default_value = "")]
     override_scene: String,
+
+    /// Path to an asset pack, the resources will be loaded from the pack.
+    #[clap(long, default_value = "")]
+    pack: String,
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
@@ -109,6 +120,20 @@ impl Executor {
         self.engine.add_plugin_constructor(plugin)
     }
 
+    /// Loads a plugin from a dynamic library, the plugin will be enabled only on [`Executor::run`]. See
+    /// [`Engine::add_dynamic_plugin`] for more info.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_dynamic_plugin<P>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), DynamicPluginError>
+    where
+        P: AsRef<Path>,
+    {
+        self.engine.add_dynamic_plugin(path, reload_when_changed)
+    }
+
     /// Runs the executor - starts your game. This function is never returns.
     pub fn run(mut self) -> ! {
         let mut engine = self.engine;
@@ -116,6 +141,25 @@ impl Executor {
 
         let args = Args::parse();
 
+        #[cfg(not(target_arch = "wasm32"))]
+        if !args.pack.is_empty() {
+            match AssetPack::open(&args.pack) {
+                Ok(pack) => {
+                    Log::info(format!(
+                        "Asset pack {} with {} files is mounted.",
+                        args.pack,
+                        pack.len()
+                    ));
+
+                    pack::mount(pack, 1);
+                }
+                Err(e) => Log::err(format!(
+                    "Unable to open asset pack {}! Reason: {}",
+                    args.pack, e
+                )),
+            }
+        }
+
         if !args.override_scene.is_empty() {
             // Try to load specified scene in a separate thread.
             self.loader = Some(AsyncSceneLoader::begin_loading(
@@ -176,6 +220,13 @@ impl Executor {
                         lag -= fixed_time_step;
                     }
 
+                    engine.update_frame(
+                        elapsed.as_secs_f32(),
+                        lag / fixed_time_step,
+                        control_flow,
+                        &mut lag,
+                    );
+
                     engine.get_window().request_redraw();
                 }
                 Event::RedrawRequested(_) => {
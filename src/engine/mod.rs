@@ -6,6 +6,7 @@
 pub mod error;
 pub mod executor;
 pub mod resource_manager;
+pub mod task;
 
 use crate::{
     asset::ResourceState,
@@ -13,6 +14,7 @@ use crate::{
     engine::{
         error::EngineError,
         resource_manager::{container::event::ResourceEvent, ResourceManager, ResourceWaitContext},
+        task::{TaskOwner, TaskScheduler},
     },
     event::Event,
     event_loop::{ControlFlow, EventLoop},
@@ -26,10 +28,18 @@ use crate::{
         base::ScriptMessage, node::constructor::NodeConstructorContainer, sound::SoundEngine,
         Scene, SceneContainer,
     },
-    script::{constructor::ScriptConstructorContainer, Script, ScriptContext, ScriptDeinitContext},
+    script::{
+        constructor::ScriptConstructorContainer, Script, ScriptContext, ScriptDeinitContext,
+        ScriptMessageDispatcher, ScriptMessageSender,
+    },
     utils::log::Log,
     window::{Window, WindowBuilder},
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    core::{uuid::Uuid, visitor::Visitor},
+    plugin::dynamic::{self, DynamicPlugin, DynamicPluginError},
+};
 use fxhash::FxHashSet;
 use std::{
     collections::{HashSet, VecDeque},
@@ -39,6 +49,8 @@ use std::{
     },
     time::Duration,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use std::{path::Path, time::SystemTime};
 
 /// Serialization context holds runtime type information that allows to create unknown types using
 /// their UUIDs and a respective constructors.
@@ -65,6 +77,27 @@ impl SerializationContext {
     }
 }
 
+/// Defines when [`crate::plugin::Plugin::on_fixed_update`] and [`crate::script::ScriptTrait::on_fixed_update`]
+/// are called relative to a physics step.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FixedUpdateStage {
+    /// The hook is called right before the physics step. It is the best place to apply forces or to set
+    /// velocities.
+    PrePhysics,
+    /// The hook is called right after the physics step, when rigid bodies have new positions.
+    PostPhysics,
+}
+
+fn scene_frame_size(scene: &Scene, window_size: Vector2<f32>) -> Vector2<f32> {
+    scene.render_target.as_ref().map_or(window_size, |rt| {
+        if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
+            Vector2::new(width as f32, height as f32)
+        } else {
+            panic!("only rectangle textures can be used as render target!");
+        }
+    })
+}
+
 /// See module docs.
 pub struct Engine {
     #[cfg(not(target_arch = "wasm32"))]
@@ -111,17 +144,36 @@ pub struct Engine {
     pub serialization_context: Arc<SerializationContext>,
 
     script_processor: ScriptProcessor,
+
+    /// Task scheduler runs asynchronous tasks of scripts and plugins. See [`TaskScheduler`] docs for more
+    /// info.
+    pub task_scheduler: TaskScheduler,
+
+    // A set of plugins loaded from dynamic libraries. Must be the last field, libraries must be unloaded
+    // only when nothing is referencing their code.
+    #[cfg(not(target_arch = "wasm32"))]
+    dynamic_plugins: Vec<DynamicPlugin>,
+}
+
+struct ScriptedScene {
+    handle: Handle<Scene>,
+    message_sender: ScriptMessageSender,
+    message_dispatcher: ScriptMessageDispatcher,
 }
 
 #[derive(Default)]
 struct ScriptProcessor {
     wait_list: Vec<ResourceWaitContext>,
-    scripted_scenes: FxHashSet<Handle<Scene>>,
+    scripted_scenes: Vec<ScriptedScene>,
 }
 
 impl ScriptProcessor {
     fn has_scripted_scene(&self, scene: Handle<Scene>) -> bool {
-        self.scripted_scenes.contains(&scene)
+        self.scripted_scenes.iter().any(|s| s.handle == scene)
+    }
+
+    fn scripted_scene_mut(&mut self, scene: Handle<Scene>) -> Option<&mut ScriptedScene> {
+        self.scripted_scenes.iter_mut().find(|s| s.handle == scene)
     }
 
     fn register_scripted_scene(
@@ -131,9 +183,14 @@ impl ScriptProcessor {
         resource_manager: &ResourceManager,
     ) {
         // Register the scene and ensure that it wasn't registered previously.
-        let added = self.scripted_scenes.insert(scene);
+        assert!(!self.has_scripted_scene(scene));
 
-        assert!(added);
+        let (message_sender, message_dispatcher) = ScriptMessageDispatcher::new();
+        self.scripted_scenes.push(ScriptedScene {
+            handle: scene,
+            message_sender,
+            message_dispatcher,
+        });
 
         let graph = &mut scenes[scene].graph;
 
@@ -150,11 +207,49 @@ impl ScriptProcessor {
             .push(resource_manager.state().containers_mut().get_wait_context());
     }
 
+    /// Calls the given closure for every initialized and started script of every enabled scripted scene.
+    fn process_started_scripts<F>(
+        &mut self,
+        scenes: &mut SceneContainer,
+        plugins: &mut [Box<dyn Plugin>],
+        resource_manager: &ResourceManager,
+        task_scheduler: &mut TaskScheduler,
+        dt: f32,
+        elapsed_time: f32,
+        mut func: F,
+    ) where
+        F: FnMut(&mut Script, &mut ScriptContext),
+    {
+        for scripted_scene in self.scripted_scenes.iter_mut() {
+            if let Some(scene) = scenes.try_get_mut(scripted_scene.handle) {
+                if scene.enabled {
+                    process_scripts(
+                        scene,
+                        scripted_scene.handle,
+                        plugins,
+                        resource_manager,
+                        &scripted_scene.message_sender,
+                        &mut scripted_scene.message_dispatcher,
+                        task_scheduler,
+                        dt,
+                        elapsed_time,
+                        |script, context| {
+                            if script.initialized && script.started {
+                                func(script, context)
+                            }
+                        },
+                    )
+                }
+            }
+        }
+    }
+
     fn handle_scripts(
         &mut self,
         scenes: &mut SceneContainer,
         plugins: &mut Vec<Box<dyn Plugin>>,
         resource_manager: &ResourceManager,
+        task_scheduler: &mut TaskScheduler,
         dt: f32,
         elapsed_time: f32,
     ) {
@@ -166,10 +261,10 @@ impl ScriptProcessor {
         }
 
         self.scripted_scenes
-            .retain(|handle| scenes.is_valid_handle(*handle));
+            .retain(|scripted_scene| scenes.is_valid_handle(scripted_scene.handle));
 
-        'scene_loop: for &scene_handle in self.scripted_scenes.iter() {
-            let scene = &mut scenes[scene_handle];
+        'scene_loop: for scripted_scene in self.scripted_scenes.iter_mut() {
+            let scene = &mut scenes[scripted_scene.handle];
 
             // Disabled scenes should not update their scripts.
             if !scene.enabled {
@@ -186,6 +281,84 @@ impl ScriptProcessor {
                 }
             }
 
+            // Pass collision events of the last physics step to the scripts of involved colliders
+            // and their ancestors.
+            let collision_events = scene.collision_events().cloned().collect::<Vec<_>>();
+            if !collision_events.is_empty() {
+                let mut context = ScriptContext {
+                    dt,
+                    elapsed_time,
+                    plugins,
+                    handle: Default::default(),
+                    scene,
+                    scene_handle: scripted_scene.handle,
+                    resource_manager,
+                    message_sender: &scripted_scene.message_sender,
+                    message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    task_scheduler,
+                };
+
+                for collision_event in collision_events {
+                    // A node could be an ancestor of both colliders, it must receive the event once.
+                    let mut notified = FxHashSet::default();
+                    for event in [collision_event, collision_event.swapped()] {
+                        let mut handle = event.collider1;
+                        while let Some(node) = context.scene.graph.try_get(handle) {
+                            let parent = node.parent();
+
+                            if node
+                                .script
+                                .as_ref()
+                                .map_or(false, |script| script.initialized && script.started)
+                                && notified.insert(handle)
+                            {
+                                context.handle = handle;
+
+                                process_node(&mut context, &mut |script, context| {
+                                    script.on_collision_event(&event, context);
+                                });
+                            }
+
+                            handle = parent;
+                        }
+                    }
+                }
+            }
+
+            // Pass messages, that were sent since the last update, to subscribed scripts. Messages sent
+            // while handling these ones will be delivered on the next update.
+            let messages = scripted_scene.message_dispatcher.take_messages();
+            if !messages.is_empty() {
+                let mut context = ScriptContext {
+                    dt,
+                    elapsed_time,
+                    plugins,
+                    handle: Default::default(),
+                    scene,
+                    scene_handle: scripted_scene.handle,
+                    resource_manager,
+                    message_sender: &scripted_scene.message_sender,
+                    message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    task_scheduler,
+                };
+
+                for mut message in messages {
+                    let recipients = context
+                        .message_dispatcher
+                        .recipients(&message, &context.scene.graph);
+
+                    for recipient in recipients {
+                        context.handle = recipient;
+
+                        process_node(&mut context, &mut |script, context| {
+                            if script.initialized && script.started {
+                                script.on_message(&mut *message.payload, context);
+                            }
+                        });
+                    }
+                }
+            }
+
             // We'll gather all scripts queued for destruction and destroy them all at once at the
             // end of the frame.
             let mut destruction_queue = VecDeque::new();
@@ -199,7 +372,11 @@ impl ScriptProcessor {
                     plugins,
                     handle: Default::default(),
                     scene,
+                    scene_handle: scripted_scene.handle,
                     resource_manager,
+                    message_sender: &scripted_scene.message_sender,
+                    message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    task_scheduler,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -222,7 +399,18 @@ impl ScriptProcessor {
                                     start_queue.push_back(handle);
                                 });
                             }
+                            ScriptMessage::HotReloadScript { handle } => {
+                                context.handle = handle;
+
+                                process_node(&mut context, &mut |script, context| {
+                                    if script.started {
+                                        script.on_hot_reload(context);
+                                    }
+                                });
+                            }
                             ScriptMessage::DestroyScript { handle, script } => {
+                                context.message_dispatcher.unsubscribe(handle);
+
                                 // Destruction is delayed to the end of the frame.
                                 destruction_queue.push_back((handle, script));
                             }
@@ -298,7 +486,7 @@ impl ScriptProcessor {
 
         // Process scripts from destroyed scenes.
         for (handle, mut detached_scene) in scenes.destruction_list.drain(..) {
-            if self.scripted_scenes.contains(&handle) {
+            if self.has_scripted_scene(handle) {
                 let mut context = ScriptDeinitContext {
                     elapsed_time,
                     plugins,
@@ -441,7 +629,12 @@ where
         }
     };
 
+    // Tasks spawned by the script must be dropped, if the code of the script is about to be unloaded.
+    let prev_owner = context
+        .task_scheduler
+        .set_owner(Some(TaskOwner::Script(script.id())));
     func(&mut script, context);
+    context.task_scheduler.set_owner(prev_owner);
 
     // Put the script back to the node. We must do a checked borrow, because it is possible
     // that the node is already destroyed by script logic.
@@ -452,8 +645,12 @@ where
 
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
+    scene_handle: Handle<Scene>,
     plugins: &mut [Box<dyn Plugin>],
     resource_manager: &ResourceManager,
+    message_sender: &ScriptMessageSender,
+    message_dispatcher: &mut ScriptMessageDispatcher,
+    task_scheduler: &mut TaskScheduler,
     dt: f32,
     elapsed_time: f32,
     mut func: T,
@@ -466,7 +663,11 @@ pub(crate) fn process_scripts<T>(
         plugins,
         handle: Default::default(),
         scene,
+        scene_handle,
         resource_manager,
+        message_sender,
+        message_dispatcher,
+        task_scheduler,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -619,6 +820,9 @@ impl Engine {
             plugins: Default::default(),
             serialization_context: node_constructors,
             script_processor: Default::default(),
+            task_scheduler: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dynamic_plugins: Default::default(),
             plugins_enabled: false,
             plugin_constructors: Default::default(),
             elapsed_time: 0.0,
@@ -686,24 +890,146 @@ impl Engine {
         let inner_size = self.get_window().inner_size();
         let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
 
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_dynamic_plugins_reload();
+
         self.resource_manager.state().update(dt);
         self.renderer.update_caches(dt);
         self.handle_model_events();
 
+        self.handle_fixed_update(FixedUpdateStage::PrePhysics, dt, control_flow, lag);
+
         for scene in self.scenes.iter_mut().filter(|s| s.enabled) {
-            let frame_size = scene.render_target.as_ref().map_or(window_size, |rt| {
-                if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
-                    Vector2::new(width as f32, height as f32)
-                } else {
-                    panic!("only rectangle textures can be used as render target!");
-                }
-            });
+            let frame_size = scene_frame_size(scene, window_size);
 
             scene.update(frame_size, dt);
         }
 
+        self.handle_fixed_update(FixedUpdateStage::PostPhysics, dt, control_flow, lag);
+
         self.update_plugins(dt, control_flow, lag);
         self.handle_scripts(dt);
+        self.task_scheduler.update(dt, &mut self.scenes);
+    }
+
+    /// Performs per-frame update of the engine. Unlike [`Self::update`], which is called at fixed rate, this
+    /// method must be called once per rendered frame, right before [`Self::render`].
+    ///
+    /// ## Parameters
+    ///
+    /// `dt` - real time (in seconds) that passed since the last frame.
+    ///
+    /// `interpolation_alpha` - a value in `[0; 1]` range that defines how far the current frame is between
+    /// the last fixed update and the next one, usually it is `lag / fixed_dt`. It is used to interpolate
+    /// transforms of rigid bodies (see [`crate::scene::rigidbody::RigidBody::set_interpolation`]) and it is
+    /// passed to [`crate::plugin::Plugin::on_frame`] and [`crate::script::ScriptTrait::on_frame`].
+    ///
+    /// `lag` - see [`Self::pre_update`] docs.
+    pub fn update_frame(
+        &mut self,
+        dt: f32,
+        interpolation_alpha: f32,
+        control_flow: &mut ControlFlow,
+        lag: &mut f32,
+    ) {
+        let interpolation_alpha = interpolation_alpha.clamp(0.0, 1.0);
+
+        // Hooks must see interpolated transforms of rigid bodies.
+        self.update_interpolated_transforms(interpolation_alpha);
+
+        if self.plugins_enabled {
+            let mut context = PluginContext {
+                scenes: &mut self.scenes,
+                resource_manager: &self.resource_manager,
+                renderer: &mut self.renderer,
+                dt,
+                lag,
+                user_interface: &mut self.user_interface,
+                serialization_context: &self.serialization_context,
+                task_scheduler: &mut self.task_scheduler,
+                window: get_window!(self),
+                sound_engine: SoundEngineHelper {
+                    engine: &self.sound_engine,
+                },
+            };
+
+            for (index, plugin) in self.plugins.iter_mut().enumerate() {
+                context
+                    .task_scheduler
+                    .set_owner(Some(TaskOwner::Plugin(index)));
+                plugin.on_frame(interpolation_alpha, &mut context, control_flow);
+            }
+            context.task_scheduler.set_owner(None);
+        }
+
+        self.script_processor.process_started_scripts(
+            &mut self.scenes,
+            &mut self.plugins,
+            &self.resource_manager,
+            &mut self.task_scheduler,
+            dt,
+            self.elapsed_time,
+            |script, context| script.on_frame(interpolation_alpha, context),
+        );
+
+        // Hooks could move some nodes (a camera for example), so global transforms must be updated again.
+        self.update_interpolated_transforms(interpolation_alpha);
+    }
+
+    fn update_interpolated_transforms(&mut self, interpolation_alpha: f32) {
+        let inner_size = self.get_window().inner_size();
+        let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
+
+        for scene in self.scenes.iter_mut().filter(|s| s.enabled) {
+            let frame_size = scene_frame_size(scene, window_size);
+
+            scene
+                .graph
+                .update_interpolated_transforms(interpolation_alpha, frame_size);
+        }
+    }
+
+    fn handle_fixed_update(
+        &mut self,
+        stage: FixedUpdateStage,
+        dt: f32,
+        control_flow: &mut ControlFlow,
+        lag: &mut f32,
+    ) {
+        if self.plugins_enabled {
+            let mut context = PluginContext {
+                scenes: &mut self.scenes,
+                resource_manager: &self.resource_manager,
+                renderer: &mut self.renderer,
+                dt,
+                lag,
+                user_interface: &mut self.user_interface,
+                serialization_context: &self.serialization_context,
+                task_scheduler: &mut self.task_scheduler,
+                window: get_window!(self),
+                sound_engine: SoundEngineHelper {
+                    engine: &self.sound_engine,
+                },
+            };
+
+            for (index, plugin) in self.plugins.iter_mut().enumerate() {
+                context
+                    .task_scheduler
+                    .set_owner(Some(TaskOwner::Plugin(index)));
+                plugin.on_fixed_update(stage, &mut context, control_flow);
+            }
+            context.task_scheduler.set_owner(None);
+        }
+
+        self.script_processor.process_started_scripts(
+            &mut self.scenes,
+            &mut self.plugins,
+            &self.resource_manager,
+            &mut self.task_scheduler,
+            dt,
+            self.elapsed_time,
+            |script, context| script.on_fixed_update(stage, context),
+        );
     }
 
     /// Performs post update for the engine.
@@ -739,6 +1065,7 @@ impl Engine {
             &mut self.scenes,
             &mut self.plugins,
             &self.resource_manager,
+            &mut self.task_scheduler,
             dt,
             self.elapsed_time,
         );
@@ -754,15 +1081,20 @@ impl Engine {
                 lag,
                 user_interface: &mut self.user_interface,
                 serialization_context: &self.serialization_context,
+                task_scheduler: &mut self.task_scheduler,
                 window: get_window!(self),
                 sound_engine: SoundEngineHelper {
                     engine: &self.sound_engine,
                 },
             };
 
-            for plugin in self.plugins.iter_mut() {
+            for (index, plugin) in self.plugins.iter_mut().enumerate() {
+                context
+                    .task_scheduler
+                    .set_owner(Some(TaskOwner::Plugin(index)));
                 plugin.update(&mut context, control_flow);
             }
+            context.task_scheduler.set_owner(None);
 
             while let Some(message) = self.user_interface.poll_message() {
                 let mut context = PluginContext {
@@ -773,15 +1105,20 @@ impl Engine {
                     lag,
                     user_interface: &mut self.user_interface,
                     serialization_context: &self.serialization_context,
+                    task_scheduler: &mut self.task_scheduler,
                     window: get_window!(self),
                     sound_engine: SoundEngineHelper {
                         engine: &self.sound_engine,
                     },
                 };
 
-                for plugin in self.plugins.iter_mut() {
+                for (index, plugin) in self.plugins.iter_mut().enumerate() {
+                    context
+                        .task_scheduler
+                        .set_owner(Some(TaskOwner::Plugin(index)));
                     plugin.on_ui_message(&mut context, &message, control_flow);
                 }
+                context.task_scheduler.set_owner(None);
             }
         }
     }
@@ -795,7 +1132,9 @@ impl Engine {
         lag: &mut f32,
     ) {
         if self.plugins_enabled {
-            for plugin in self.plugins.iter_mut() {
+            for (index, plugin) in self.plugins.iter_mut().enumerate() {
+                self.task_scheduler
+                    .set_owner(Some(TaskOwner::Plugin(index)));
                 plugin.on_os_event(
                     event,
                     PluginContext {
@@ -806,6 +1145,7 @@ impl Engine {
                         lag,
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
+                        task_scheduler: &mut self.task_scheduler,
                         window: get_window!(self),
                         sound_engine: SoundEngineHelper {
                             engine: &self.sound_engine,
@@ -814,6 +1154,7 @@ impl Engine {
                     control_flow,
                 );
             }
+            self.task_scheduler.set_owner(None);
         }
     }
 
@@ -830,12 +1171,23 @@ impl Engine {
         scene: Handle<Scene>,
         dt: f32,
     ) {
+        let scripted_scene = match self.script_processor.scripted_scene_mut(scene) {
+            Some(scripted_scene) => scripted_scene,
+            // Scripts of the scene are not initialized, so there is nothing to do.
+            None => return,
+        };
+
+        let scene_handle = scene;
         let scene = &mut self.scenes[scene];
         if scene.enabled {
             process_scripts(
                 scene,
+                scene_handle,
                 &mut self.plugins,
                 &self.resource_manager,
+                &scripted_scene.message_sender,
+                &mut scripted_scene.message_dispatcher,
+                &mut self.task_scheduler,
                 dt,
                 self.elapsed_time,
                 |script, context| {
@@ -913,7 +1265,9 @@ impl Engine {
 
             if self.plugins_enabled {
                 // Create and initialize instances.
-                for constructor in self.plugin_constructors.iter() {
+                for (index, constructor) in self.plugin_constructors.iter().enumerate() {
+                    self.task_scheduler
+                        .set_owner(Some(TaskOwner::Plugin(index)));
                     self.plugins.push(constructor.create_instance(
                         override_scene,
                         PluginContext {
@@ -924,6 +1278,7 @@ impl Engine {
                             lag: &mut 0.0,
                             user_interface: &mut self.user_interface,
                             serialization_context: &self.serialization_context,
+                            task_scheduler: &mut self.task_scheduler,
                             window: get_window!(self),
                             sound_engine: SoundEngineHelper {
                                 engine: &self.sound_engine,
@@ -931,10 +1286,13 @@ impl Engine {
                         },
                     ));
                 }
+                self.task_scheduler.set_owner(None);
             } else {
                 self.handle_scripts(0.0);
 
-                for mut plugin in self.plugins.drain(..) {
+                for (index, mut plugin) in self.plugins.drain(..).enumerate() {
+                    self.task_scheduler
+                        .set_owner(Some(TaskOwner::Plugin(index)));
                     // Deinit plugin first.
                     plugin.on_deinit(PluginContext {
                         scenes: &mut self.scenes,
@@ -944,12 +1302,14 @@ impl Engine {
                         lag: &mut 0.0,
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
+                        task_scheduler: &mut self.task_scheduler,
                         window: get_window!(self),
                         sound_engine: SoundEngineHelper {
                             engine: &self.sound_engine,
                         },
                     });
                 }
+                self.task_scheduler.set_owner(None);
             }
         }
     }
@@ -965,6 +1325,285 @@ impl Engine {
 
         self.plugin_constructors.push(Box::new(constructor));
     }
+
+    /// Loads a plugin from a dynamic library. The library must export its plugin constructor using
+    /// [`crate::define_dynamic_plugin`] macro and it must be built with the same compiler and engine version
+    /// as the engine, otherwise the library will be rejected. Use `cargo build` of your game with `dylib`
+    /// crate type to produce the library.
+    ///
+    /// If `reload_when_changed` is `true`, the engine will watch for changes of the library and will reload
+    /// it automatically after rebuild. On reload, the engine saves the state of the plugin (see
+    /// [`Plugin::visit_state`]) and every script instance of the plugin, unloads the old version of the
+    /// library, loads the new one and restores the state using new script constructors. Pending script
+    /// messages and async tasks are dropped on reload, because they could reference the code of the old
+    /// library. Custom scene nodes of the plugin cannot be hot reloaded, the engine will refuse to reload
+    /// the plugin if any scene contains such nodes.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_dynamic_plugin<P>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), DynamicPluginError>
+    where
+        P: AsRef<Path>,
+    {
+        let (mut dynamic_plugin, constructor) = DynamicPlugin::load(
+            path.as_ref(),
+            reload_when_changed,
+            self.plugin_constructors.len(),
+        )?;
+
+        let (script_types, node_types) = self.register_plugin_constructor(&*constructor);
+        dynamic_plugin.script_types = script_types;
+        dynamic_plugin.node_types = node_types;
+
+        self.plugin_constructors.push(constructor);
+        self.dynamic_plugins.push(dynamic_plugin);
+
+        Log::info(format!(
+            "Dynamic plugin {} was loaded successfully!",
+            path.as_ref().display()
+        ));
+
+        Ok(())
+    }
+
+    /// Registers the plugin constructor and returns a set of script and node types registered by it.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn register_plugin_constructor(
+        &self,
+        constructor: &dyn PluginConstructor,
+    ) -> (Vec<Uuid>, Vec<Uuid>) {
+        let script_constructors = &self.serialization_context.script_constructors;
+        let node_constructors = &self.serialization_context.node_constructors;
+
+        let scripts_before = script_constructors
+            .map()
+            .keys()
+            .cloned()
+            .collect::<FxHashSet<_>>();
+        let nodes_before = node_constructors
+            .map()
+            .keys()
+            .cloned()
+            .collect::<FxHashSet<_>>();
+
+        constructor.register(PluginRegistrationContext {
+            serialization_context: &self.serialization_context,
+        });
+
+        let script_types = script_constructors
+            .map()
+            .keys()
+            .filter(|type_uuid| !scripts_before.contains(type_uuid))
+            .cloned()
+            .collect();
+        let node_types = node_constructors
+            .map()
+            .keys()
+            .filter(|type_uuid| !nodes_before.contains(type_uuid))
+            .cloned()
+            .collect();
+
+        (script_types, node_types)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_dynamic_plugins_reload(&mut self) {
+        for index in 0..self.dynamic_plugins.len() {
+            if let Some(version) = self.dynamic_plugins[index].changed_version() {
+                let path = self.dynamic_plugins[index].source_path().to_owned();
+
+                match self.reload_dynamic_plugin(index, version) {
+                    Ok(_) => Log::info(format!(
+                        "Dynamic plugin {} was reloaded successfully!",
+                        path.display()
+                    )),
+                    Err(e) => {
+                        // Do not try to reload the same version again, it will be reloaded when the
+                        // library is rebuilt.
+                        self.dynamic_plugins[index].reload_failed(version);
+
+                        Log::err(format!(
+                            "Unable to reload {} dynamic plugin! Reason: {}",
+                            path.display(),
+                            e
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_dynamic_plugin(
+        &mut self,
+        index: usize,
+        version: SystemTime,
+    ) -> Result<(), DynamicPluginError> {
+        let constructor_index = self.dynamic_plugins[index].constructor_index;
+        let script_types = self.dynamic_plugins[index].script_types.clone();
+        let node_types = self.dynamic_plugins[index].node_types.clone();
+
+        // Nodes cannot be replaced in-place, so custom nodes of the plugin prevent reloading.
+        for scene in self.scenes.iter() {
+            if let Some(node) = scene
+                .graph
+                .linear_iter()
+                .find(|node| node_types.contains(&node.id()))
+            {
+                return Err(DynamicPluginError::UnsupportedNode(node.id()));
+            }
+        }
+
+        // Load the new version first, if it fails then the old version will continue to work.
+        let (loaded_path, library, constructor) = self.dynamic_plugins[index].begin_reload()?;
+
+        // Save the state of the plugin and its scripts. Everything that references the code of the old
+        // library must be destroyed before it is unloaded.
+        let plugin_state = if self.plugins_enabled && constructor_index < self.plugins.len() {
+            let mut plugin = self.plugins.remove(constructor_index);
+            let mut visitor = Visitor::new();
+            match plugin
+                .visit_state(&mut visitor)
+                .and_then(|_| visitor.save_binary_to_vec())
+            {
+                Ok(data) => Some(Some(data)),
+                Err(e) => {
+                    Log::warn(format!(
+                        "Unable to save plugin state, the state will be reset. Reason: {}",
+                        e
+                    ));
+                    Some(None)
+                }
+            }
+        } else {
+            None
+        };
+
+        let scene_scripts = self
+            .scenes
+            .pair_iter_mut()
+            .map(|(handle, scene)| {
+                (
+                    handle,
+                    dynamic::save_scripts(&mut scene.graph, &script_types),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Prefabs could also contain scripts of the plugin.
+        let models = self
+            .resource_manager
+            .state()
+            .containers()
+            .models
+            .iter()
+            .filter(|model| matches!(*model.state(), ResourceState::Ok(_)))
+            .cloned()
+            .collect::<Vec<_>>();
+        let model_scripts = models
+            .into_iter()
+            .map(|model| {
+                let scripts = dynamic::save_scripts(
+                    &mut model.data_ref().get_scene_mut().graph,
+                    &script_types,
+                );
+                (model, scripts)
+            })
+            .collect::<Vec<_>>();
+
+        // Messages and subscriptions could reference types of the old library. Restored scripts will
+        // subscribe again in `on_hot_reload`.
+        for scripted_scene in self.script_processor.scripted_scenes.iter_mut() {
+            scripted_scene.message_dispatcher.take_messages();
+        }
+        for (handle, scripts) in scene_scripts.iter() {
+            if let Some(scripted_scene) = self.script_processor.scripted_scene_mut(*handle) {
+                for script in scripts {
+                    scripted_scene.message_dispatcher.unsubscribe(script.node());
+                }
+            }
+        }
+        // Tasks of the plugin and its scripts are futures, which code belongs to the old library. Tasks
+        // of other plugins and of the game itself are kept.
+        self.task_scheduler.cancel_owned(|owner| match owner {
+            TaskOwner::Plugin(index) => index == constructor_index,
+            TaskOwner::Script(type_uuid) => script_types.contains(&type_uuid),
+        });
+
+        for type_uuid in script_types {
+            self.serialization_context
+                .script_constructors
+                .remove(type_uuid);
+        }
+        for type_uuid in node_types {
+            self.serialization_context
+                .node_constructors
+                .remove(type_uuid);
+        }
+
+        // Replace the constructor and unload the old version.
+        self.plugin_constructors[constructor_index] = constructor;
+        self.dynamic_plugins[index].finish_reload(loaded_path, library, version);
+
+        let (script_types, node_types) =
+            self.register_plugin_constructor(&*self.plugin_constructors[constructor_index]);
+        self.dynamic_plugins[index].script_types = script_types;
+        self.dynamic_plugins[index].node_types = node_types;
+
+        // Restore the state using new version of the code.
+        for (handle, scripts) in scene_scripts {
+            if let Some(scene) = self.scenes.try_get_mut(handle) {
+                dynamic::restore_scripts(&mut scene.graph, scripts, &self.serialization_context);
+            }
+        }
+        for (model, scripts) in model_scripts {
+            dynamic::restore_scripts(
+                &mut model.data_ref().get_scene_mut().graph,
+                scripts,
+                &self.serialization_context,
+            );
+        }
+
+        if let Some(plugin_state) = plugin_state {
+            self.task_scheduler
+                .set_owner(Some(TaskOwner::Plugin(constructor_index)));
+            let mut plugin = self.plugin_constructors[constructor_index]
+                .create_hot_reload_instance(PluginContext {
+                    scenes: &mut self.scenes,
+                    resource_manager: &self.resource_manager,
+                    renderer: &mut self.renderer,
+                    dt: 0.0,
+                    lag: &mut 0.0,
+                    user_interface: &mut self.user_interface,
+                    serialization_context: &self.serialization_context,
+                    task_scheduler: &mut self.task_scheduler,
+                    window: get_window!(self),
+                    sound_engine: SoundEngineHelper {
+                        engine: &self.sound_engine,
+                    },
+                });
+            self.task_scheduler.set_owner(None);
+
+            if let Some(data) = plugin_state {
+                match Visitor::load_from_memory(data) {
+                    Ok(mut visitor) => {
+                        visitor.environment = Some(self.serialization_context.clone());
+
+                        if let Err(e) = plugin.visit_state(&mut visitor) {
+                            Log::warn(format!("Unable to restore plugin state. Reason: {}", e));
+                        }
+                    }
+                    Err(e) => Log::warn(format!("Unable to restore plugin state. Reason: {}", e)),
+                }
+            }
+
+            self.plugins.insert(constructor_index, plugin);
+        }
+
+        Ok(())
+    }
 }
 
 impl Drop for Engine {
@@ -984,19 +1623,54 @@ impl Drop for Engine {
 
         // Finally disable plugins.
         self.enable_plugins(Default::default(), false);
+
+        // Serialization context could outlive the engine, so constructors of dynamic plugins must be removed
+        // from it before the libraries are unloaded.
+        #[cfg(not(target_arch = "wasm32"))]
+        for dynamic_plugin in self.dynamic_plugins.iter() {
+            for type_uuid in dynamic_plugin.script_types.iter() {
+                self.serialization_context
+                    .script_constructors
+                    .remove(*type_uuid);
+            }
+            for type_uuid in dynamic_plugin.node_types.iter() {
+                self.serialization_context
+                    .node_constructors
+                    .remove(*type_uuid);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        core::{pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
-        engine::{resource_manager::ResourceManager, ScriptProcessor},
+        core::{
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+        },
+        engine::{resource_manager::ResourceManager, ScriptProcessor, SerializationContext},
         impl_component_provider,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
-        script::{Script, ScriptContext, ScriptDeinitContext, ScriptTrait},
+        plugin::dynamic,
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::physics::{CollisionEvent, CollisionEventKind},
+            node::{Node, TypeUuidProvider},
+            pivot::PivotBuilder,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+            Scene, SceneContainer,
+        },
+        script::{Script, ScriptContext, ScriptDeinitContext, ScriptMessagePayload, ScriptTrait},
+    };
+    use std::sync::{
+        mpsc::{self, Sender, TryRecvError},
+        Arc,
     };
-    use std::sync::mpsc::{self, Sender, TryRecvError};
 
     #[derive(PartialEq, Eq, Clone, Debug)]
     enum Event {
@@ -1131,6 +1805,7 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &mut Default::default(),
                 0.0,
                 0.0,
             );
@@ -1182,4 +1857,345 @@ mod test {
             }
         }
     }
+
+    struct Ping(u32);
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct MessageReceiverScript {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(Handle<Node>, u32)>,
+    }
+
+    impl_component_provider!(MessageReceiverScript);
+
+    impl ScriptTrait for MessageReceiverScript {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            ctx.message_dispatcher.subscribe_to::<Ping>(ctx.handle);
+        }
+
+        fn on_message(&mut self, message: &mut dyn ScriptMessagePayload, ctx: &mut ScriptContext) {
+            if let Some(ping) = message.downcast_ref::<Ping>() {
+                self.sender.send((ctx.handle, ping.0)).unwrap();
+            }
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_message_passing() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let receiver = || {
+            BaseBuilder::new()
+                .with_script(Script::new(MessageReceiverScript { sender: tx.clone() }))
+        };
+
+        let child = PivotBuilder::new(receiver()).build(&mut scene.graph);
+        let parent = PivotBuilder::new(receiver().with_children(&[child])).build(&mut scene.graph);
+        let other = PivotBuilder::new(receiver()).build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let mut update = |script_processor: &mut ScriptProcessor| {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &mut Default::default(),
+                0.0,
+                0.0,
+            )
+        };
+
+        // Initialize and subscribe the scripts.
+        update(&mut script_processor);
+
+        let message_sender = script_processor.scripted_scenes[0].message_sender.clone();
+
+        let mut check = |script_processor: &mut ScriptProcessor,
+                         mut expected: Vec<(Handle<Node>, u32)>| {
+            update(script_processor);
+            let mut received = rx.try_iter().collect::<Vec<_>>();
+            received.sort_by_key(|(handle, _)| handle.index());
+            expected.sort_by_key(|(handle, _)| handle.index());
+            assert_eq!(received, expected);
+        };
+
+        message_sender.send_to_target(other, Ping(1));
+        check(&mut script_processor, vec![(other, 1)]);
+
+        message_sender.send_hierarchical(parent, Ping(2));
+        check(&mut script_processor, vec![(parent, 2), (child, 2)]);
+
+        message_sender.send_global(Ping(3));
+        check(
+            &mut script_processor,
+            vec![(parent, 3), (child, 3), (other, 3)],
+        );
+
+        // Messages of other types must be ignored.
+        message_sender.send_global(123u32);
+        check(&mut script_processor, vec![]);
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct CollisionRecorderScript {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(Handle<Node>, CollisionEvent)>,
+    }
+
+    impl_component_provider!(CollisionRecorderScript);
+
+    impl ScriptTrait for CollisionRecorderScript {
+        fn on_collision_event(&mut self, event: &CollisionEvent, ctx: &mut ScriptContext) {
+            self.sender.send((ctx.handle, *event)).unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_collision_events() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let recorder = || {
+            BaseBuilder::new()
+                .with_script(Script::new(CollisionRecorderScript { sender: tx.clone() }))
+        };
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(5.0, 0.5, 5.0))
+            .build(&mut scene.graph);
+        let ground = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground_collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut scene.graph);
+
+        // The box overlaps the ground from the very beginning.
+        let box_collider = ColliderBuilder::new(recorder())
+            .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+            .build(&mut scene.graph);
+        let box_body = RigidBodyBuilder::new(
+            recorder()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.9, 0.0))
+                        .build(),
+                )
+                .with_children(&[box_collider]),
+        )
+        .with_body_type(RigidBodyType::Dynamic)
+        .build(&mut scene.graph);
+
+        // Common ancestor of both colliders.
+        let root = PivotBuilder::new(recorder().with_children(&[ground, box_body]))
+            .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let mut update = |scene_container: &mut SceneContainer| {
+            script_processor.handle_scripts(
+                scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &mut Default::default(),
+                1.0 / 60.0,
+                0.0,
+            )
+        };
+
+        // Initialize and start the scripts.
+        update(&mut scene_container);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        // Step the simulation until the contact is detected.
+        for _ in 0..10 {
+            scene_container[scene_handle]
+                .graph
+                .update(Vector2::new(800.0, 600.0), 1.0 / 60.0);
+            if scene_container[scene_handle].collision_events().count() > 0 {
+                break;
+            }
+        }
+        update(&mut scene_container);
+
+        let received = rx.try_iter().collect::<Vec<_>>();
+        let events_of = |handle: Handle<Node>| {
+            received
+                .iter()
+                .filter(|(h, _)| *h == handle)
+                .map(|(_, e)| *e)
+                .collect::<Vec<_>>()
+        };
+
+        // The collider and its ancestors must receive the event exactly once, the event must be
+        // oriented from the point of view of the collider.
+        for handle in [box_collider, box_body] {
+            let events = events_of(handle);
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].kind, CollisionEventKind::ContactStarted);
+            assert_eq!(events[0].collider1, box_collider);
+            assert_eq!(events[0].collider2, ground_collider);
+            assert!(events[0].total_force_magnitude > 0.0);
+            assert!(events[0].max_force_magnitude > 0.0);
+        }
+
+        // The root is an ancestor of both colliders, but the event must be delivered once.
+        let events = events_of(root);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, CollisionEventKind::ContactStarted);
+
+        assert_eq!(received.len(), 3);
+    }
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct ReloadableScript {
+        starts: u32,
+        hot_reloads: u32,
+        received: u32,
+    }
+
+    impl_component_provider!(ReloadableScript);
+
+    impl TypeUuidProvider for ReloadableScript {
+        fn type_uuid() -> Uuid {
+            uuid!("4b5f8b3a-5f0e-4b8d-9a3e-2c6d7e9f1a20")
+        }
+    }
+
+    impl ScriptTrait for ReloadableScript {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            self.starts += 1;
+            ctx.message_dispatcher.subscribe_to::<Ping>(ctx.handle);
+        }
+
+        fn on_hot_reload(&mut self, ctx: &mut ScriptContext) {
+            self.hot_reloads += 1;
+            ctx.message_dispatcher.subscribe_to::<Ping>(ctx.handle);
+        }
+
+        fn on_message(&mut self, message: &mut dyn ScriptMessagePayload, _ctx: &mut ScriptContext) {
+            if let Some(ping) = message.downcast_ref::<Ping>() {
+                self.received += ping.0;
+            }
+        }
+
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_script_hot_reload() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context
+            .script_constructors
+            .add::<ReloadableScript>("ReloadableScript");
+
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(ReloadableScript::default())),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let update = |script_processor: &mut ScriptProcessor,
+                      scene_container: &mut SceneContainer| {
+            script_processor.handle_scripts(
+                scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &mut Default::default(),
+                0.0,
+                0.0,
+            )
+        };
+
+        let state = |scene_container: &SceneContainer| {
+            let script = scene_container[scene_handle].graph[node]
+                .script
+                .as_ref()
+                .and_then(|s| s.cast::<ReloadableScript>())
+                .unwrap();
+            (script.starts, script.hot_reloads, script.received)
+        };
+
+        let message_sender = script_processor.scripted_scenes[0].message_sender.clone();
+
+        update(&mut script_processor, &mut scene_container);
+        message_sender.send_global(Ping(1));
+        update(&mut script_processor, &mut scene_container);
+        assert_eq!(state(&scene_container), (1, 0, 1));
+
+        // Do the same as the engine does on hot reload.
+        let scripts = dynamic::save_scripts(
+            &mut scene_container[scene_handle].graph,
+            &[ReloadableScript::type_uuid()],
+        );
+        assert_eq!(scripts.len(), 1);
+        assert!(scene_container[scene_handle].graph[node].script.is_none());
+        for script in scripts.iter() {
+            script_processor.scripted_scenes[0]
+                .message_dispatcher
+                .unsubscribe(script.node());
+        }
+        assert!(!script_processor.scripted_scenes[0]
+            .message_dispatcher
+            .is_subscribed_to::<Ping>(node));
+        dynamic::restore_scripts(
+            &mut scene_container[scene_handle].graph,
+            scripts,
+            &serialization_context,
+        );
+
+        // The state is restored, the script is not started again, but it is able to subscribe to the
+        // messages.
+        update(&mut script_processor, &mut scene_container);
+        assert_eq!(state(&scene_container), (1, 1, 1));
+        assert!(script_processor.scripted_scenes[0]
+            .message_dispatcher
+            .is_subscribed_to::<Ping>(node));
+
+        message_sender.send_global(Ping(2));
+        update(&mut script_processor, &mut scene_container);
+        assert_eq!(state(&scene_container), (1, 1, 3));
+    }
 }
@@ -3,25 +3,72 @@
 
 use crate::{
     asset::{Resource, ResourceData, ResourceLoadError, ResourceState},
-    core::{futures::future::JoinAll, variable::InheritableVariable, VecExtensions},
+    core::{
+        futures::future::{join_all, JoinAll},
+        variable::InheritableVariable,
+        VecExtensions,
+    },
     engine::resource_manager::{
         container::{
             entry::{TimedEntry, DEFAULT_RESOURCE_LIFETIME},
             event::{ResourceEvent, ResourceEventBroadcaster},
         },
-        loader::ResourceLoader,
+        loader::{BoxedLoaderFuture, ResourceLoader},
         options::ImportOptions,
         task::TaskPool,
     },
     utils::log::Log,
 };
-use std::{future::Future, ops::Deref, path::Path, sync::Arc};
+use std::{any::Any, future::Future, ops::Deref, path::Path, sync::Arc};
 
 pub mod entry;
 pub mod event;
 
-pub(crate) trait Container {
+/// A function that checks whether every resource of some set is loaded (or failed to load).
+pub(crate) type LoadedCheck = Box<dyn Fn() -> bool + Send + Sync>;
+
+/// Containers are shared between threads along with the resource manager, except on WebAssembly,
+/// where resource loaders are not required to be `Send`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) trait ContainerBase: Any + Send {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Any + Send> ContainerBase for T {}
+
+/// Containers are shared between threads along with the resource manager, except on WebAssembly,
+/// where resource loaders are not required to be `Send`.
+#[cfg(target_arch = "wasm32")]
+pub(crate) trait ContainerBase: Any {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: Any> ContainerBase for T {}
+
+/// Type-erased resource container, it allows the resource manager to handle containers of any
+/// resource type in the same way.
+pub(crate) trait Container: ContainerBase {
     fn try_reload_resource_from_path(&mut self, path: &Path) -> bool;
+
+    fn update(&mut self, dt: f32);
+
+    fn destroy_unused(&mut self);
+
+    fn len(&self) -> usize;
+
+    fn count_pending_resources(&self) -> usize;
+
+    fn count_loaded_resources(&self) -> usize;
+
+    /// Reloads every resource in the container, the returned future completes when every resource
+    /// is reloaded (or failed to reload).
+    fn reload_resources(&mut self) -> BoxedLoaderFuture;
+
+    /// Returns a function that checks whether every resource that is currently in the container is
+    /// loaded or not.
+    fn loaded_check(&self) -> LoadedCheck;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
 /// Generic container for any resource in the engine. Main purpose of the container is to
@@ -309,7 +356,7 @@ where
     T: Deref<Target = Resource<R, E>> + Clone + Send + Future + From<Resource<R, E>>,
     R: ResourceData,
     E: ResourceLoadError,
-    O: ImportOptions,
+    O: ImportOptions + Send + 'static,
 {
     fn try_reload_resource_from_path(&mut self, path: &Path) -> bool {
         if let Some(resource) = self.find(path).cloned() {
@@ -319,4 +366,143 @@ where
             false
         }
     }
+
+    fn update(&mut self, dt: f32) {
+        ResourceContainer::update(self, dt)
+    }
+
+    fn destroy_unused(&mut self) {
+        ResourceContainer::destroy_unused(self)
+    }
+
+    fn len(&self) -> usize {
+        ResourceContainer::len(self)
+    }
+
+    fn count_pending_resources(&self) -> usize {
+        ResourceContainer::count_pending_resources(self)
+    }
+
+    fn count_loaded_resources(&self) -> usize {
+        ResourceContainer::count_loaded_resources(self)
+    }
+
+    fn reload_resources(&mut self) -> BoxedLoaderFuture {
+        // Wait for inner resources, because outputs of the new-type wrappers are not required to
+        // be `Send`.
+        let resources = ResourceContainer::reload_resources(self)
+            .iter()
+            .map(|r| r.deref().clone())
+            .collect::<Vec<_>>();
+        Box::pin(async move {
+            join_all(resources).await;
+        })
+    }
+
+    fn loaded_check(&self) -> LoadedCheck {
+        let resources = self
+            .resources
+            .iter()
+            .map(|r| r.value.deref().clone())
+            .collect::<Vec<_>>();
+        Box::new(move || resources.iter().all(|r| !r.is_loading()))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Resource container with erased type of import options, it allows to access a container of a
+/// user-defined resource type by the type of resource only.
+pub(crate) trait TypedContainer<T: Clone>: Container {
+    fn request(&mut self, path: &Path) -> T;
+
+    fn try_restore_resource(&mut self, resource: &mut T);
+
+    fn event_broadcaster(&self) -> &ResourceEventBroadcaster<T>;
+}
+
+impl<T, R, E, O> TypedContainer<T> for ResourceContainer<T, O>
+where
+    T: Deref<Target = Resource<R, E>> + Clone + Send + Future + From<Resource<R, E>>,
+    R: ResourceData,
+    E: ResourceLoadError,
+    O: ImportOptions + Send + 'static,
+{
+    fn request(&mut self, path: &Path) -> T {
+        ResourceContainer::request(self, path)
+    }
+
+    fn try_restore_resource(&mut self, resource: &mut T) {
+        ResourceContainer::try_restore_resource(self, resource)
+    }
+
+    fn event_broadcaster(&self) -> &ResourceEventBroadcaster<T> {
+        &self.event_broadcaster
+    }
+}
+
+/// Container of a user-defined resource type, see [`TypedContainer`].
+pub(crate) struct CustomContainer<T: Clone>(pub(crate) Box<dyn TypedContainer<T>>);
+
+impl<T: Clone + 'static> CustomContainer<T> {
+    /// Returns a reference to the inner container, if it uses the given type of import options.
+    pub(crate) fn get<O: ImportOptions + 'static>(&self) -> Option<&ResourceContainer<T, O>> {
+        self.0.as_any().downcast_ref()
+    }
+
+    /// Returns a mutable reference to the inner container, if it uses the given type of import
+    /// options.
+    pub(crate) fn get_mut<O: ImportOptions + 'static>(
+        &mut self,
+    ) -> Option<&mut ResourceContainer<T, O>> {
+        self.0.as_any_mut().downcast_mut()
+    }
+}
+
+impl<T: Clone + 'static> Container for CustomContainer<T> {
+    fn try_reload_resource_from_path(&mut self, path: &Path) -> bool {
+        self.0.try_reload_resource_from_path(path)
+    }
+
+    fn update(&mut self, dt: f32) {
+        self.0.update(dt)
+    }
+
+    fn destroy_unused(&mut self) {
+        self.0.destroy_unused()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn count_pending_resources(&self) -> usize {
+        self.0.count_pending_resources()
+    }
+
+    fn count_loaded_resources(&self) -> usize {
+        self.0.count_loaded_resources()
+    }
+
+    fn reload_resources(&mut self) -> BoxedLoaderFuture {
+        self.0.reload_resources()
+    }
+
+    fn loaded_check(&self) -> LoadedCheck {
+        self.0.loaded_check()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
 }
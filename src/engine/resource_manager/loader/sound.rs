@@ -1,6 +1,7 @@
 //! Sound buffer loader.
 
 use crate::{
+    asset::ResourceData,
     core::reflect::prelude::*,
     engine::resource_manager::{
         container::event::ResourceEventBroadcaster,
@@ -23,7 +24,8 @@ pub struct SoundBufferImportOptions {
 
 impl ImportOptions for SoundBufferImportOptions {}
 
-/// Default implementation for sound buffer loading.
+/// Default implementation for sound buffer loading. It supports WAV (`.wav`), OGG/Vorbis (`.ogg`),
+/// FLAC (`.flac`) and MP3 (`.mp3`) files, the format is detected by the content of a file.
 pub struct SoundBufferLoader;
 
 impl ResourceLoader<SoundBufferResource, SoundBufferImportOptions> for SoundBufferLoader {
@@ -49,7 +51,11 @@ impl ResourceLoader<SoundBufferResource, SoundBufferImportOptions> for SoundBuff
                         SoundBufferState::raw_generic(source)
                     };
                     match buffer {
-                        Ok(sound_buffer) => {
+                        Ok(mut sound_buffer) => {
+                            // A file from non-native file system is loaded in memory, which makes
+                            // the buffer lose the path, so restore it.
+                            sound_buffer.set_path(path.clone());
+
                             resource.state().commit_ok(sound_buffer);
 
                             event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
@@ -1,4 +1,7 @@
-//! Resource manager controls loading and lifetime of resource in the engine.
+//! Resource manager controls loading and lifetime of resource in the engine. Every resource is loaded
+//! through current virtual file system, see [`crate::core::vfs`] for more info.
+//! Besides built-in resource types, the manager could handle user-defined ones, see
+//! [`ContainersStorage::register`].
 
 use crate::{
     asset::{Resource, ResourceData, ResourceLoadError, ResourceState},
@@ -9,7 +12,10 @@ use crate::{
     },
     engine::{
         resource_manager::{
-            container::{Container, ResourceContainer},
+            container::{
+                event::ResourceEventBroadcaster, Container, CustomContainer, LoadedCheck,
+                ResourceContainer,
+            },
             loader::{
                 curve::CurveLoader,
                 model::ModelLoader,
@@ -18,6 +24,7 @@ use crate::{
                 texture::TextureLoader,
                 ResourceLoader,
             },
+            options::ImportOptions,
             task::TaskPool,
         },
         SerializationContext,
@@ -30,8 +37,10 @@ use crate::{
     },
     utils::{log::Log, watcher::FileSystemWatcher},
 };
+use fxhash::FxHashMap;
 use fyrox_sound::buffer::SoundBufferResource;
 use std::{
+    any::TypeId,
     fmt::{Debug, Display, Formatter},
     future::Future,
     ops::Deref,
@@ -60,6 +69,11 @@ pub struct ContainersStorage {
 
     /// Container for curve resources.
     pub curves: ResourceContainer<CurveResource, CurveImportOptions>,
+
+    /// Containers for user-defined resource types, see [`Self::register`].
+    custom: FxHashMap<TypeId, Box<dyn Container>>,
+
+    task_pool: Arc<TaskPool>,
 }
 
 impl ContainersStorage {
@@ -103,6 +117,139 @@ impl ContainersStorage {
         self.curves.set_loader(loader);
     }
 
+    /// Registers a container for a user-defined resource type `T` that will be loaded by the given
+    /// loader. Resources of the type could then be requested via [`ResourceManager::request`] (or
+    /// via the container itself), they will be reloaded on changes, destroyed when unused, etc.
+    /// exactly like built-in resources. Resources in deserialized data (for example, fields of
+    /// scripts) are "shallow" and must be restored via [`Self::try_restore_resource`]. Containers
+    /// are found by the type of resource only, so there could be only one container per type. If
+    /// the type is already registered, only the loader of its container is replaced. The method
+    /// fails if the type is already registered with different import options.
+    ///
+    /// The resource type is usually defined via [`crate::asset::define_new_resource`] macro.
+    pub fn register<T, R, E, O, L>(
+        &mut self,
+        loader: L,
+    ) -> Result<&mut ResourceContainer<T, O>, ResourceTypeRegistrationError>
+    where
+        T: Deref<Target = Resource<R, E>> + Clone + Send + Future + From<Resource<R, E>> + 'static,
+        R: ResourceData,
+        E: ResourceLoadError,
+        O: ImportOptions + Send + 'static,
+        L: 'static + ResourceLoader<T, O>,
+    {
+        if self.is_registered::<T>() {
+            self.get_mut::<T, O>()
+                .ok_or(ResourceTypeRegistrationError::ImportOptionsMismatch)?
+                .set_loader(loader);
+        } else {
+            let container =
+                ResourceContainer::<T, O>::new(self.task_pool.clone(), Box::new(loader));
+            self.custom.insert(
+                TypeId::of::<T>(),
+                Box::new(CustomContainer(Box::new(container))),
+            );
+        }
+        self.get_mut::<T, O>()
+            .ok_or(ResourceTypeRegistrationError::ImportOptionsMismatch)
+    }
+
+    /// Returns `true` if there's a container for the given user-defined resource type.
+    pub fn is_registered<T: 'static>(&self) -> bool {
+        self.custom.contains_key(&TypeId::of::<T>())
+    }
+
+    fn custom<T: Clone + 'static>(&self) -> Option<&CustomContainer<T>> {
+        self.custom
+            .get(&TypeId::of::<T>())
+            .and_then(|c| c.as_any().downcast_ref())
+    }
+
+    fn custom_mut<T: Clone + 'static>(&mut self) -> Option<&mut CustomContainer<T>> {
+        self.custom
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|c| c.as_any_mut().downcast_mut())
+    }
+
+    /// Returns a reference to the container of a user-defined resource type, if the type is
+    /// registered (see [`Self::register`]) with the given type of import options.
+    pub fn get<T, O>(&self) -> Option<&ResourceContainer<T, O>>
+    where
+        T: Clone + 'static,
+        O: ImportOptions + 'static,
+    {
+        self.custom::<T>().and_then(|c| c.get())
+    }
+
+    /// Returns a mutable reference to the container of a user-defined resource type, if the type is
+    /// registered (see [`Self::register`]) with the given type of import options.
+    pub fn get_mut<T, O>(&mut self) -> Option<&mut ResourceContainer<T, O>>
+    where
+        T: Clone + 'static,
+        O: ImportOptions + 'static,
+    {
+        self.custom_mut::<T>().and_then(|c| c.get_mut())
+    }
+
+    /// Tries to load a resource of a user-defined type from given path or get instance of existing,
+    /// if any. Returns `None` if the type is not registered (see [`Self::register`]).
+    pub fn request<T, P>(&mut self, path: P) -> Option<T>
+    where
+        T: Clone + 'static,
+        P: AsRef<Path>,
+    {
+        self.custom_mut::<T>().map(|c| c.0.request(path.as_ref()))
+    }
+
+    /// Restores a "shallow" resource of a user-defined type (for example, a deserialized field of
+    /// a script) by requesting a resource with the same path. Returns `false` if the type is not
+    /// registered (see [`Self::register`]).
+    pub fn try_restore_resource<T>(&mut self, resource: &mut T) -> bool
+    where
+        T: Clone + 'static,
+    {
+        match self.custom_mut::<T>() {
+            Some(container) => {
+                container.0.try_restore_resource(resource);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the event broadcaster of the container of a user-defined resource type, if the type
+    /// is registered (see [`Self::register`]).
+    pub fn event_broadcaster<T>(&self) -> Option<&ResourceEventBroadcaster<T>>
+    where
+        T: Clone + 'static,
+    {
+        self.custom::<T>().map(|c| c.0.event_broadcaster())
+    }
+
+    fn all(&self) -> impl Iterator<Item = &dyn Container> {
+        [
+            &self.textures as &dyn Container,
+            &self.models as &dyn Container,
+            &self.sound_buffers as &dyn Container,
+            &self.shaders as &dyn Container,
+            &self.curves as &dyn Container,
+        ]
+        .into_iter()
+        .chain(self.custom.values().map(|c| c.as_ref()))
+    }
+
+    fn all_mut(&mut self) -> impl Iterator<Item = &mut dyn Container> {
+        [
+            &mut self.textures as &mut dyn Container,
+            &mut self.models as &mut dyn Container,
+            &mut self.sound_buffers as &mut dyn Container,
+            &mut self.shaders as &mut dyn Container,
+            &mut self.curves as &mut dyn Container,
+        ]
+        .into_iter()
+        .chain(self.custom.values_mut().map(|c| c.as_mut()))
+    }
+
     /// Wait until all resources are loaded (or failed to load).
     pub fn get_wait_context(&self) -> ResourceWaitContext {
         ResourceWaitContext {
@@ -111,6 +258,7 @@ impl ContainersStorage {
             shaders: self.shaders.resources(),
             textures: self.textures.resources(),
             sound_buffers: self.sound_buffers.resources(),
+            custom: self.custom.values().map(|c| c.loaded_check()).collect(),
         }
     }
 }
@@ -123,6 +271,7 @@ pub struct ResourceWaitContext {
     shaders: Vec<Shader>,
     textures: Vec<Texture>,
     sound_buffers: Vec<SoundBufferResource>,
+    custom: Vec<LoadedCheck>,
 }
 
 impl ResourceWaitContext {
@@ -154,6 +303,7 @@ impl ResourceWaitContext {
             && check_container(&self.shaders)
             && check_container(&self.textures)
             && check_container(&self.sound_buffers)
+            && self.custom.iter().all(|check| check())
     }
 }
 /// See module docs.
@@ -199,6 +349,26 @@ impl From<TextureError> for TextureRegistrationError {
     }
 }
 
+/// An error that may occur during registration of a user-defined resource type.
+#[derive(Debug)]
+pub enum ResourceTypeRegistrationError {
+    /// Resource type is already registered with different import options.
+    ImportOptionsMismatch,
+}
+
+impl Display for ResourceTypeRegistrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceTypeRegistrationError::ImportOptionsMismatch => {
+                write!(
+                    f,
+                    "A resource type is already registered with different import options!"
+                )
+            }
+        }
+    }
+}
+
 impl ResourceManager {
     /// Creates a resource manager with default settings and loaders.
     pub fn new(serialization_context: Arc<SerializationContext>) -> Self {
@@ -219,7 +389,9 @@ impl ResourceManager {
             ),
             sound_buffers: ResourceContainer::new(task_pool.clone(), Box::new(SoundBufferLoader)),
             shaders: ResourceContainer::new(task_pool.clone(), Box::new(ShaderLoader)),
-            curves: ResourceContainer::new(task_pool, Box::new(CurveLoader)),
+            curves: ResourceContainer::new(task_pool.clone(), Box::new(CurveLoader)),
+            custom: Default::default(),
+            task_pool,
         });
 
         resource_manager
@@ -230,6 +402,42 @@ impl ResourceManager {
         self.state.lock()
     }
 
+    /// Registers a user-defined resource type, see [`ContainersStorage::register`] for more info.
+    pub fn register_resource_type<T, R, E, O, L>(
+        &self,
+        loader: L,
+    ) -> Result<(), ResourceTypeRegistrationError>
+    where
+        T: Deref<Target = Resource<R, E>> + Clone + Send + Future + From<Resource<R, E>> + 'static,
+        R: ResourceData,
+        E: ResourceLoadError,
+        O: ImportOptions + Send + 'static,
+        L: 'static + ResourceLoader<T, O>,
+    {
+        self.state().containers_mut().register(loader).map(|_| ())
+    }
+
+    /// Tries to load a resource of a user-defined type from given path or get instance of existing,
+    /// if any. The type must be registered first (see [`Self::register_resource_type`]), otherwise
+    /// the method returns `None`. Just like the other `request_*` methods, it is asynchronous and
+    /// returns a resource that could be awaited.
+    pub fn request<T, P>(&self, path: P) -> Option<T>
+    where
+        T: Clone + 'static,
+        P: AsRef<Path>,
+    {
+        self.state().containers_mut().request(path)
+    }
+
+    /// Restores a "shallow" resource of a user-defined type, see
+    /// [`ContainersStorage::try_restore_resource`] for more info.
+    pub fn try_restore_resource<T>(&self, resource: &mut T) -> bool
+    where
+        T: Clone + 'static,
+    {
+        self.state().containers_mut().try_restore_resource(resource)
+    }
+
     /// Tries to load texture from given path or get instance of existing, if any. This method is asynchronous,
     /// it immediately returns a texture which can be shared across multiple places, the loading may fail, but it is
     /// internal state of the texture. The engine does not care if texture failed to load, it just won't use
@@ -310,8 +518,8 @@ impl ResourceManager {
     ///
     /// # Supported formats
     ///
-    /// Currently only FBX (common format in game industry for storing complex 3d models)
-    /// and RGS (native Fyroxed format) formats are supported.
+    /// Currently FBX (common format in game industry for storing complex 3d models), glTF 2.0
+    /// (`.gltf` and `.glb`), Wavefront OBJ and RGS (native Fyroxed format) formats are supported.
     pub fn request_model<P: AsRef<Path>>(&self, path: P) -> Model {
         self.state().containers_mut().models.request(path)
     }
@@ -322,7 +530,7 @@ impl ResourceManager {
     ///
     /// # Supported formats
     ///
-    /// Currently only WAV and OGG are supported.
+    /// Currently WAV, OGG/Vorbis, FLAC and MP3 are supported.
     pub fn request_sound_buffer<P: AsRef<Path>>(&self, path: P) -> SoundBufferResource {
         self.state().containers_mut().sound_buffers.request(path)
     }
@@ -398,8 +606,22 @@ impl ResourceManager {
             self.reload_sound_buffers(),
             self.reload_shaders(),
             self.reload_curve_resources(),
+            self.reload_custom_resources(),
         );
     }
+
+    /// Reloads every loaded resource of user-defined types. This method is asynchronous, internally
+    /// it uses thread pool to run reload on separate thread per resource.
+    pub async fn reload_custom_resources(&self) {
+        let resources = self
+            .state()
+            .containers_mut()
+            .custom
+            .values_mut()
+            .map(|c| c.reload_resources())
+            .collect::<Vec<_>>();
+        join_all(resources).await;
+    }
 }
 
 impl ResourceManagerState {
@@ -434,32 +656,23 @@ impl ResourceManagerState {
 
     /// Returns total amount of resources in pending state.
     pub fn count_pending_resources(&self) -> usize {
-        let containers = self.containers();
-        containers.textures.count_pending_resources()
-            + containers.sound_buffers.count_pending_resources()
-            + containers.models.count_pending_resources()
-            + containers.shaders.count_pending_resources()
-            + containers.curves.count_pending_resources()
+        self.containers()
+            .all()
+            .map(|c| c.count_pending_resources())
+            .sum()
     }
 
     /// Returns total amount of loaded resources.
     pub fn count_loaded_resources(&self) -> usize {
-        let containers = self.containers();
-        containers.textures.count_loaded_resources()
-            + containers.sound_buffers.count_loaded_resources()
-            + containers.models.count_loaded_resources()
-            + containers.shaders.count_loaded_resources()
-            + containers.curves.count_loaded_resources()
+        self.containers()
+            .all()
+            .map(|c| c.count_loaded_resources())
+            .sum()
     }
 
     /// Returns total amount of registered resources.
     pub fn count_registered_resources(&self) -> usize {
-        let containers = self.containers();
-        containers.textures.len()
-            + containers.sound_buffers.len()
-            + containers.models.len()
-            + containers.shaders.len()
-            + containers.curves.len()
+        self.containers().all().map(|c| c.len()).sum()
     }
 
     /// Returns percentage of loading progress. This method is useful to show progress on
@@ -477,12 +690,9 @@ impl ResourceManagerState {
 
     /// Immediately destroys all unused resources.
     pub fn destroy_unused_resources(&mut self) {
-        let containers = self.containers_mut();
-        containers.sound_buffers.destroy_unused();
-        containers.models.destroy_unused();
-        containers.textures.destroy_unused();
-        containers.shaders.destroy_unused();
-        containers.curves.destroy_unused();
+        for container in self.containers_mut().all_mut() {
+            container.destroy_unused();
+        }
     }
 
     /// Update resource containers and do hot-reloading.
@@ -493,26 +703,16 @@ impl ResourceManagerState {
     /// Normally, this is called from `Engine::update()`.
     /// You should only call this manually if you don't use that method.
     pub fn update(&mut self, dt: f32) {
-        let containers = self.containers_mut();
-        containers.textures.update(dt);
-        containers.models.update(dt);
-        containers.sound_buffers.update(dt);
-        containers.shaders.update(dt);
-        containers.curves.update(dt);
+        for container in self.containers_mut().all_mut() {
+            container.update(dt);
+        }
 
         if let Some(watcher) = self.watcher.as_ref() {
             if let Some(evt) = watcher.try_get_event() {
                 if let notify::EventKind::Modify(_) = evt.kind {
                     for path in evt.paths {
                         if let Ok(relative_path) = make_relative_path(path) {
-                            let containers = self.containers_mut();
-                            for container in [
-                                &mut containers.textures as &mut dyn Container,
-                                &mut containers.models as &mut dyn Container,
-                                &mut containers.sound_buffers as &mut dyn Container,
-                                &mut containers.shaders as &mut dyn Container,
-                                &mut containers.curves as &mut dyn Container,
-                            ] {
+                            for container in self.containers_mut().all_mut() {
                                 if container.try_reload_resource_from_path(&relative_path) {
                                     Log::info(format!(
                                         "File {} was changed, trying to reload a respective resource...",
@@ -529,3 +729,263 @@ impl ResourceManagerState {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{define_new_resource, Resource, ResourceData, ResourceState},
+        core::{futures::executor::block_on, visitor::prelude::*},
+        engine::{
+            resource_manager::{
+                container::event::{ResourceEvent, ResourceEventBroadcaster},
+                loader::{BoxedLoaderFuture, ResourceLoader},
+                options::ImportOptions,
+                ResourceManager, ResourceTypeRegistrationError,
+            },
+            SerializationContext,
+        },
+    };
+    use serde::{Deserialize, Serialize};
+    use std::{
+        borrow::Cow,
+        path::{Path, PathBuf},
+        sync::{mpsc, Arc},
+        time::Duration,
+    };
+
+    #[derive(Debug, Default, Visit)]
+    pub struct TextData {
+        path: PathBuf,
+        text: String,
+    }
+
+    impl ResourceData for TextData {
+        fn path(&self) -> Cow<Path> {
+            Cow::Borrowed(&self.path)
+        }
+
+        fn set_path(&mut self, path: PathBuf) {
+            self.path = path;
+        }
+    }
+
+    define_new_resource!(TextResource<TextData, ()>);
+
+    #[derive(Serialize, Deserialize, Default, Clone)]
+    struct TextImportOptions {}
+
+    impl ImportOptions for TextImportOptions {}
+
+    #[derive(Serialize, Deserialize, Default, Clone)]
+    struct OtherImportOptions {}
+
+    impl ImportOptions for OtherImportOptions {}
+
+    // Produces text data from the path of a resource without touching the file system.
+    struct TextLoader;
+
+    impl ResourceLoader<TextResource, TextImportOptions> for TextLoader {
+        fn load(
+            &self,
+            resource: TextResource,
+            _default_import_options: TextImportOptions,
+            event_broadcaster: ResourceEventBroadcaster<TextResource>,
+            reload: bool,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {
+                let path = resource.state().path().to_path_buf();
+                let text = path.to_string_lossy().to_uppercase();
+                resource.state().commit_ok(TextData { path, text });
+                event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
+            })
+        }
+    }
+
+    // Never finishes loading, resources stay in pending state.
+    struct PendingLoader;
+
+    impl ResourceLoader<TextResource, TextImportOptions> for PendingLoader {
+        fn load(
+            &self,
+            _resource: TextResource,
+            _default_import_options: TextImportOptions,
+            _event_broadcaster: ResourceEventBroadcaster<TextResource>,
+            _reload: bool,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {})
+        }
+    }
+
+    struct OtherLoader;
+
+    impl ResourceLoader<TextResource, OtherImportOptions> for OtherLoader {
+        fn load(
+            &self,
+            _resource: TextResource,
+            _default_import_options: OtherImportOptions,
+            _event_broadcaster: ResourceEventBroadcaster<TextResource>,
+            _reload: bool,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {})
+        }
+    }
+
+    fn resource_manager() -> ResourceManager {
+        ResourceManager::new(Arc::new(SerializationContext::new()))
+    }
+
+    #[test]
+    fn test_register_resource_type() {
+        let resource_manager = resource_manager();
+
+        assert!(!resource_manager
+            .state()
+            .containers()
+            .is_registered::<TextResource>());
+        assert!(resource_manager
+            .request::<TextResource, _>("foo.txt")
+            .is_none());
+
+        assert!(resource_manager.register_resource_type(TextLoader).is_ok());
+        assert!(resource_manager
+            .state()
+            .containers()
+            .is_registered::<TextResource>());
+
+        // Registering the type again only replaces the loader.
+        assert!(resource_manager.register_resource_type(TextLoader).is_ok());
+
+        // Registering the type with different import options is an error.
+        assert!(matches!(
+            resource_manager.register_resource_type(OtherLoader),
+            Err(ResourceTypeRegistrationError::ImportOptionsMismatch)
+        ));
+        let state = resource_manager.state();
+        assert!(state
+            .containers()
+            .get::<TextResource, TextImportOptions>()
+            .is_some());
+        assert!(state
+            .containers()
+            .get::<TextResource, OtherImportOptions>()
+            .is_none());
+    }
+
+    #[test]
+    fn test_request_custom_resource() {
+        let resource_manager = resource_manager();
+        resource_manager.register_resource_type(TextLoader).unwrap();
+
+        let resource = resource_manager
+            .request::<TextResource, _>("foo.txt")
+            .unwrap();
+        let resource = block_on(resource).unwrap();
+        assert_eq!(resource.data_ref().text, "FOO.TXT");
+
+        // Existing resource must be reused.
+        let same = resource_manager
+            .request::<TextResource, _>("foo.txt")
+            .unwrap();
+        assert_eq!(same, resource);
+        assert_eq!(resource_manager.state().count_registered_resources(), 1);
+        assert_eq!(resource_manager.state().count_loaded_resources(), 1);
+    }
+
+    #[test]
+    fn test_restore_custom_resource() {
+        let resource_manager = resource_manager();
+
+        // Deserialized resources have only a path.
+        let mut shallow = TextResource(Resource::new(ResourceState::new_pending("foo.txt".into())));
+        assert!(!resource_manager.try_restore_resource(&mut shallow));
+
+        resource_manager.register_resource_type(TextLoader).unwrap();
+        assert!(resource_manager.try_restore_resource(&mut shallow));
+
+        let resource = block_on(shallow.clone()).unwrap();
+        assert_eq!(resource.data_ref().text, "FOO.TXT");
+        assert_eq!(
+            resource_manager
+                .request::<TextResource, _>("foo.txt")
+                .unwrap(),
+            shallow
+        );
+    }
+
+    #[test]
+    fn test_custom_resource_events_and_reload() {
+        let resource_manager = resource_manager();
+        resource_manager.register_resource_type(TextLoader).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        resource_manager
+            .state()
+            .containers()
+            .event_broadcaster::<TextResource>()
+            .unwrap()
+            .add(sender);
+        let next_event = || receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let resource = resource_manager
+            .request::<TextResource, _>("foo.txt")
+            .unwrap();
+        assert!(matches!(next_event(), ResourceEvent::Added(r) if r == resource));
+        assert!(matches!(next_event(), ResourceEvent::Loaded(r) if r == resource));
+
+        block_on(resource_manager.reload_custom_resources());
+        assert!(matches!(next_event(), ResourceEvent::Reloaded(r) if r == resource));
+
+        // This is what the file system watcher does when a file is changed.
+        assert!(resource_manager
+            .state()
+            .containers_mut()
+            .all_mut()
+            .any(|c| c.try_reload_resource_from_path(Path::new("foo.txt"))));
+        assert!(matches!(next_event(), ResourceEvent::Reloaded(r) if r == resource));
+        assert_eq!(block_on(resource).unwrap().data_ref().text, "FOO.TXT");
+    }
+
+    #[test]
+    fn test_destroy_unused_custom_resources() {
+        let resource_manager = resource_manager();
+        resource_manager
+            .register_resource_type(PendingLoader)
+            .unwrap();
+
+        let _used = resource_manager
+            .request::<TextResource, _>("used.txt")
+            .unwrap();
+        let unused = resource_manager
+            .request::<TextResource, _>("unused.txt")
+            .unwrap();
+        drop(unused);
+
+        let mut state = resource_manager.state();
+        state.destroy_unused_resources();
+        let container = state
+            .containers()
+            .get::<TextResource, TextImportOptions>()
+            .unwrap();
+        assert_eq!(container.len(), 1);
+        assert!(container.find("used.txt").is_some());
+        assert!(container.find("unused.txt").is_none());
+    }
+
+    #[test]
+    fn test_wait_context_with_custom_resources() {
+        let resource_manager = resource_manager();
+        resource_manager
+            .register_resource_type(PendingLoader)
+            .unwrap();
+
+        let resource = resource_manager
+            .request::<TextResource, _>("foo.txt")
+            .unwrap();
+
+        let wait_context = resource_manager.state().containers().get_wait_context();
+        assert!(!wait_context.is_all_loaded());
+
+        resource.state().commit_ok(TextData::default());
+        assert!(wait_context.is_all_loaded());
+    }
+}
@@ -0,0 +1,646 @@
+//! Task scheduler allows you to run asynchronous tasks (futures) driven by the engine loop. It is useful for
+//! game logic that spans multiple frames, for example "wait 2 seconds, then open the door, then wait until the
+//! animation ends". See [`TaskScheduler`] docs for more info.
+
+use crate::{
+    animation::Animation,
+    core::{futures::task::noop_waker_ref, pool::Handle, uuid::Uuid},
+    engine::resource_manager::ResourceWaitContext,
+    scene::{animation::AnimationPlayer, node::Node, Scene, SceneContainer},
+};
+use fxhash::FxHashSet;
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+/// A handle of a task, it can be used to cancel the task.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TaskHandle(u64);
+
+/// An owner of a task. It is used to drop every task of a dynamic plugin, when it is about to be reloaded,
+/// because the futures reference the code of the old version of the plugin.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TaskOwner {
+    /// The task was spawned by a script of the given type.
+    Script(Uuid),
+    /// The task was spawned by a plugin with the given index.
+    Plugin(usize),
+}
+
+struct Task {
+    handle: TaskHandle,
+    scene: Handle<Scene>,
+    owner: Option<TaskOwner>,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+#[derive(Default)]
+struct SharedState {
+    elapsed_time: Cell<f32>,
+    frame: Cell<u64>,
+    next_handle: Cell<u64>,
+    // A handle and a pointer to the scene of the task that is being polled right now. It is set only
+    // while a task is polled by the scheduler.
+    scene: Cell<Option<(Handle<Scene>, *mut Scene)>>,
+    scene_borrowed: Cell<bool>,
+    spawn_queue: RefCell<Vec<Task>>,
+    cancelled: RefCell<FxHashSet<TaskHandle>>,
+}
+
+impl SharedState {
+    fn make_handle(&self) -> TaskHandle {
+        let handle = TaskHandle(self.next_handle.get());
+        self.next_handle.set(handle.0 + 1);
+        handle
+    }
+}
+
+// Resets the value of the cell to default on drop, it is used to keep the shared state consistent even
+// if a task panics.
+struct ResetGuard<'a, T: Default>(&'a Cell<T>);
+
+impl<'a, T: Default> Drop for ResetGuard<'a, T> {
+    fn drop(&mut self) {
+        self.0.take();
+    }
+}
+
+/// Task context is passed to every task on spawn. It provides awaitable primitives (timers, next frame, etc.)
+/// and access to the scene of the task. It is cheap to clone.
+#[derive(Clone)]
+pub struct TaskContext {
+    state: Rc<SharedState>,
+    scene: Handle<Scene>,
+    owner: Option<TaskOwner>,
+}
+
+impl Debug for TaskContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TaskContext")
+    }
+}
+
+impl TaskContext {
+    /// Returns a handle of the scene to which the task belongs to.
+    pub fn scene_handle(&self) -> Handle<Scene> {
+        self.scene
+    }
+
+    /// Returns amount of time (in seconds) that passed since the scheduler was created. It is advanced
+    /// at fixed rate by the engine.
+    pub fn elapsed_time(&self) -> f32 {
+        self.state.elapsed_time.get()
+    }
+
+    /// Gives mutable access to the scene of the task that is currently running. The scene is borrowed
+    /// only for the duration of the closure, so it is impossible to hold it across `.await` points.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if it is called outside of a task (for example if the context was moved somewhere
+    /// else), if it is called from a task of another scene or if it is called from the closure of another
+    /// `with_scene` call.
+    pub fn with_scene<R, F>(&self, func: F) -> R
+    where
+        F: FnOnce(&mut Scene) -> R,
+    {
+        let (scene_handle, scene) = self
+            .state
+            .scene
+            .get()
+            .expect("The scene can be accessed only from a running task!");
+
+        assert_eq!(
+            scene_handle, self.scene,
+            "The scene of the context can be accessed only from a task of the same scene!"
+        );
+
+        assert!(
+            !self.state.scene_borrowed.replace(true),
+            "The scene is already borrowed!"
+        );
+
+        let _guard = ResetGuard(&self.state.scene_borrowed);
+
+        // SAFETY: The pointer is set by the scheduler right before polling a task and it is reset
+        // right after, while the scheduler holds mutable borrow of the scene container. The pointer
+        // is used only by the contexts of the scene, it belongs to. The task context is not `Send`,
+        // so it cannot be used from other threads, and the "borrowed" flag above prevents creating
+        // two mutable references at the same time.
+        func(unsafe { &mut *scene })
+    }
+
+    /// Spawns a new task in the same scene. The task will be polled for the first time on next update
+    /// of the scheduler.
+    pub fn spawn<F, Fut>(&self, func: F) -> TaskHandle
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let handle = self.state.make_handle();
+        let future = func(self.clone());
+        self.state.spawn_queue.borrow_mut().push(Task {
+            handle,
+            scene: self.scene,
+            owner: self.owner,
+            future: Box::pin(future),
+        });
+        handle
+    }
+
+    /// Cancels a task. The task will be dropped on next update of the scheduler.
+    pub fn cancel(&self, task: TaskHandle) {
+        self.state.cancelled.borrow_mut().insert(task);
+    }
+
+    /// Returns a future that will be resolved after the given amount of time (in seconds).
+    pub fn sleep(&self, seconds: f32) -> Sleep {
+        Sleep {
+            state: self.state.clone(),
+            until: self.elapsed_time() + seconds,
+        }
+    }
+
+    /// Returns a future that will be resolved on next update of the scheduler.
+    pub fn next_frame(&self) -> NextFrame {
+        NextFrame {
+            state: self.state.clone(),
+            frame: self.state.frame.get(),
+        }
+    }
+
+    /// Returns a future that will be resolved when the given predicate returns `true`. The predicate is
+    /// checked once per update of the scheduler and it has mutable access to the scene of the task.
+    pub fn wait_until<P>(&self, predicate: P) -> WaitUntil<P>
+    where
+        P: FnMut(&mut Scene) -> bool,
+    {
+        WaitUntil {
+            context: self.clone(),
+            predicate,
+        }
+    }
+
+    /// Returns a future that will be resolved when the given animation of the given animation player ends.
+    /// The future is also resolved if the animation or the player does not exist (anymore). Keep in mind,
+    /// that looped animations never ends.
+    pub fn wait_for_animation(
+        &self,
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+    ) -> WaitUntil<impl FnMut(&mut Scene) -> bool> {
+        self.wait_until(move |scene| {
+            scene
+                .graph
+                .try_get(animation_player)
+                .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                .and_then(|p| p.animations().try_get(animation))
+                .map_or(true, |a| a.has_ended())
+        })
+    }
+
+    /// Returns a future that will be resolved when every resource of the given wait context is loaded
+    /// (or failed to load). Use [`crate::engine::resource_manager::ResourceManager::state`] and
+    /// `containers_mut().get_wait_context()` to obtain the wait context. To wait for a single resource,
+    /// simply `.await` the resource itself.
+    pub fn wait_for_resources(&self, wait_context: ResourceWaitContext) -> WaitForResources {
+        WaitForResources { wait_context }
+    }
+}
+
+/// A future that will be resolved after some amount of time. See [`TaskContext::sleep`].
+pub struct Sleep {
+    state: Rc<SharedState>,
+    until: f32,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.elapsed_time.get() >= self.until {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// A future that will be resolved on next update of the scheduler. See [`TaskContext::next_frame`].
+pub struct NextFrame {
+    state: Rc<SharedState>,
+    frame: u64,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.frame.get() > self.frame {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// A future that will be resolved when a predicate returns `true`. See [`TaskContext::wait_until`].
+pub struct WaitUntil<P> {
+    context: TaskContext,
+    predicate: P,
+}
+
+impl<P> Future for WaitUntil<P>
+where
+    P: FnMut(&mut Scene) -> bool + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let predicate = &mut this.predicate;
+        if this.context.with_scene(|scene| predicate(scene)) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// A future that will be resolved when a set of resources is loaded. See [`TaskContext::wait_for_resources`].
+pub struct WaitForResources {
+    wait_context: ResourceWaitContext,
+}
+
+impl Future for WaitForResources {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.wait_context.is_all_loaded() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Task scheduler runs asynchronous tasks (futures) driven by the engine loop. Every task belongs to a
+/// scene and it is polled once per update of the engine (at fixed rate) after scripts and plugins. A task
+/// is dropped when it is finished, cancelled or when its scene is destroyed.
+///
+/// Tasks are `'static` futures, so they cannot hold references to the scene. Instead, every task receives
+/// [`TaskContext`] that gives temporary access to the scene (see [`TaskContext::with_scene`]) and provides
+/// awaitable primitives.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::pool::Handle,
+///     engine::task::TaskScheduler,
+///     scene::{node::Node, Scene},
+/// };
+///
+/// fn open_door(scheduler: &mut TaskScheduler, scene: Handle<Scene>, door: Handle<Node>) {
+///     scheduler.spawn(scene, move |ctx| async move {
+///         // Wait a bit before opening the door.
+///         ctx.sleep(2.0).await;
+///
+///         ctx.with_scene(|scene| scene.graph[door].set_visibility(false));
+///     });
+/// }
+/// ```
+pub struct TaskScheduler {
+    state: Rc<SharedState>,
+    tasks: Vec<Task>,
+    owner: Option<TaskOwner>,
+}
+
+impl Default for TaskScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for TaskScheduler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TaskScheduler - {} tasks", self.tasks.len())
+    }
+}
+
+impl TaskScheduler {
+    /// Creates new empty task scheduler.
+    pub fn new() -> Self {
+        Self {
+            state: Default::default(),
+            tasks: Default::default(),
+            owner: None,
+        }
+    }
+
+    /// Spawns a new task in the given scene. The closure receives task context and must return a future,
+    /// usually it is an `async move` block. The task will be polled for the first time on next update of
+    /// the scheduler.
+    pub fn spawn<F, Fut>(&mut self, scene: Handle<Scene>, func: F) -> TaskHandle
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let handle = self.state.make_handle();
+        let future = func(TaskContext {
+            state: self.state.clone(),
+            scene,
+            owner: self.owner,
+        });
+        self.tasks.push(Task {
+            handle,
+            scene,
+            owner: self.owner,
+            future: Box::pin(future),
+        });
+        handle
+    }
+
+    /// Cancels a task. The task will be dropped immediately.
+    pub fn cancel(&mut self, task: TaskHandle) {
+        self.state.cancelled.borrow_mut().remove(&task);
+        self.tasks.retain(|t| t.handle != task);
+        self.state
+            .spawn_queue
+            .borrow_mut()
+            .retain(|t| t.handle != task);
+    }
+
+    /// Returns `true` if the task is still running, `false` - otherwise.
+    pub fn is_running(&self, task: TaskHandle) -> bool {
+        self.tasks.iter().any(|t| t.handle == task)
+            || self
+                .state
+                .spawn_queue
+                .borrow()
+                .iter()
+                .any(|t| t.handle == task)
+    }
+
+    /// Drops every task.
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+        self.state.spawn_queue.borrow_mut().clear();
+        self.state.cancelled.borrow_mut().clear();
+    }
+
+    /// Sets an owner of every task that will be spawned by the scheduler (and by the spawned tasks), returns
+    /// previous owner.
+    pub(crate) fn set_owner(&mut self, owner: Option<TaskOwner>) -> Option<TaskOwner> {
+        std::mem::replace(&mut self.owner, owner)
+    }
+
+    /// Drops every task, which owner satisfies the given predicate. Tasks without an owner are kept.
+    pub(crate) fn cancel_owned<P>(&mut self, mut predicate: P)
+    where
+        P: FnMut(TaskOwner) -> bool,
+    {
+        let mut is_owned = |task: &Task| task.owner.map_or(false, &mut predicate);
+        self.tasks.retain(|t| !is_owned(t));
+        self.state.spawn_queue.borrow_mut().retain(|t| !is_owned(t));
+    }
+
+    /// Returns total amount of running tasks.
+    pub fn task_count(&self) -> usize {
+        self.tasks.len() + self.state.spawn_queue.borrow().len()
+    }
+
+    /// Advances time of the scheduler and polls every task once. Normally, it is called by the engine and
+    /// there is no need to call it manually.
+    pub fn update(&mut self, dt: f32, scenes: &mut SceneContainer) {
+        self.state
+            .elapsed_time
+            .set(self.state.elapsed_time.get() + dt);
+        self.state.frame.set(self.state.frame.get() + 1);
+
+        self.tasks
+            .extend(self.state.spawn_queue.borrow_mut().drain(..));
+
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        let mut i = 0;
+        while i < self.tasks.len() {
+            let task = &mut self.tasks[i];
+
+            let finished = if self.state.cancelled.borrow_mut().remove(&task.handle) {
+                true
+            } else if let Some(scene) = scenes.try_get_mut(task.scene) {
+                self.state.scene.set(Some((task.scene, scene as *mut _)));
+                let _guard = ResetGuard(&self.state.scene);
+                task.future.as_mut().poll(&mut cx).is_ready()
+            } else {
+                // The scene was destroyed.
+                true
+            };
+
+            if finished {
+                self.tasks.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        // Forget cancellations of tasks that are finished already, otherwise the set would grow forever.
+        let spawn_queue = self.state.spawn_queue.borrow();
+        let tasks = &self.tasks;
+        self.state.cancelled.borrow_mut().retain(|handle| {
+            tasks.iter().any(|t| t.handle == *handle)
+                || spawn_queue.iter().any(|t| t.handle == *handle)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::uuid::Uuid,
+        engine::task::{TaskContext, TaskOwner, TaskScheduler},
+        scene::{Scene, SceneContainer},
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        panic::{self, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    #[test]
+    fn test_task_scheduler() {
+        let mut scenes = SceneContainer::new(Default::default());
+        let scene = scenes.add(Scene::new());
+
+        let mut scheduler = TaskScheduler::new();
+
+        let progress = Rc::new(Cell::new(0));
+        let task_progress = progress.clone();
+        let task = scheduler.spawn(scene, move |ctx| async move {
+            ctx.next_frame().await;
+            task_progress.set(1);
+            ctx.sleep(0.5).await;
+            ctx.with_scene(|scene| scene.enabled = false);
+            task_progress.set(2);
+        });
+
+        scheduler.update(0.1, &mut scenes);
+        assert_eq!(progress.get(), 0);
+        scheduler.update(0.1, &mut scenes);
+        assert_eq!(progress.get(), 1);
+        for _ in 0..4 {
+            scheduler.update(0.1, &mut scenes);
+        }
+        assert_eq!(progress.get(), 1);
+        assert!(scheduler.is_running(task));
+        scheduler.update(0.1, &mut scenes);
+        assert_eq!(progress.get(), 2);
+        assert!(!scenes[scene].enabled);
+        assert!(!scheduler.is_running(task));
+    }
+
+    #[test]
+    fn test_with_scene_after_panic() {
+        let mut scenes = SceneContainer::new(Default::default());
+        let scene = scenes.add(Scene::new());
+
+        let mut scheduler = TaskScheduler::new();
+
+        scheduler.spawn(scene, move |ctx| async move {
+            ctx.with_scene(|_| panic!("Task panic"));
+        });
+        assert!(
+            panic::catch_unwind(AssertUnwindSafe(|| scheduler.update(0.1, &mut scenes))).is_err()
+        );
+
+        // The panicked task cannot be polled anymore, but the scene must not be left borrowed by it.
+        scheduler.clear();
+        scheduler.spawn(scene, move |ctx| async move {
+            ctx.with_scene(|scene| scene.enabled = false);
+        });
+        scheduler.update(0.1, &mut scenes);
+        assert!(!scenes[scene].enabled);
+    }
+
+    #[test]
+    fn test_context_of_another_scene() {
+        let mut scenes = SceneContainer::new(Default::default());
+        let scene_a = scenes.add(Scene::new());
+        let scene_b = scenes.add(Scene::new());
+
+        let mut scheduler = TaskScheduler::new();
+
+        let stolen_context = Rc::new(RefCell::new(None::<TaskContext>));
+        let task_stolen_context = stolen_context.clone();
+        scheduler.spawn(scene_a, move |ctx| async move {
+            *task_stolen_context.borrow_mut() = Some(ctx);
+        });
+        let task_stolen_context = stolen_context.clone();
+        scheduler.spawn(scene_b, move |_| async move {
+            if let Some(ctx) = task_stolen_context.borrow().as_ref() {
+                ctx.with_scene(|scene| scene.enabled = false);
+            }
+        });
+
+        // A context of the scene A must not give access to the scene B.
+        assert!(
+            panic::catch_unwind(AssertUnwindSafe(|| scheduler.update(0.1, &mut scenes))).is_err()
+        );
+        assert!(scenes[scene_a].enabled);
+        assert!(scenes[scene_b].enabled);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let mut scenes = SceneContainer::new(Default::default());
+        let scene = scenes.add(Scene::new());
+
+        let mut scheduler = TaskScheduler::new();
+
+        let long_task = scheduler.spawn(scene, move |ctx| async move {
+            ctx.sleep(100.0).await;
+        });
+        let finished_task = scheduler.spawn(scene, move |_| async move {});
+        scheduler.spawn(scene, move |ctx| async move {
+            ctx.next_frame().await;
+            ctx.cancel(long_task);
+            ctx.cancel(finished_task);
+        });
+
+        scheduler.update(0.1, &mut scenes);
+        assert!(scheduler.is_running(long_task));
+        assert!(!scheduler.is_running(finished_task));
+
+        scheduler.update(0.1, &mut scenes);
+        assert!(scheduler.is_running(long_task));
+        // Cancellation of the finished task must be forgotten.
+        assert_eq!(scheduler.state.cancelled.borrow().len(), 1);
+
+        scheduler.update(0.1, &mut scenes);
+        assert!(!scheduler.is_running(long_task));
+        assert!(scheduler.state.cancelled.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_cancel_owned() {
+        let mut scenes = SceneContainer::new(Default::default());
+        let scene = scenes.add(Scene::new());
+
+        let mut scheduler = TaskScheduler::new();
+
+        let script_type = Uuid::new_v4();
+        let other_script_type = Uuid::new_v4();
+
+        let game_task = scheduler.spawn(scene, move |ctx| async move {
+            ctx.sleep(100.0).await;
+        });
+        scheduler.set_owner(Some(TaskOwner::Plugin(0)));
+        let plugin_task = scheduler.spawn(scene, move |ctx| async move {
+            ctx.sleep(100.0).await;
+        });
+        scheduler.set_owner(Some(TaskOwner::Plugin(1)));
+        let other_plugin_task = scheduler.spawn(scene, move |ctx| async move {
+            ctx.sleep(100.0).await;
+        });
+        scheduler.set_owner(Some(TaskOwner::Script(script_type)));
+        let nested_task = Rc::new(Cell::new(None));
+        let task_nested_task = nested_task.clone();
+        let script_task = scheduler.spawn(scene, move |ctx| async move {
+            // Nested tasks must inherit the owner.
+            task_nested_task.set(Some(ctx.spawn(|ctx| async move {
+                ctx.sleep(100.0).await;
+            })));
+            ctx.sleep(100.0).await;
+        });
+        scheduler.set_owner(Some(TaskOwner::Script(other_script_type)));
+        let other_script_task = scheduler.spawn(scene, move |ctx| async move {
+            ctx.sleep(100.0).await;
+        });
+        assert_eq!(
+            scheduler.set_owner(None),
+            Some(TaskOwner::Script(other_script_type))
+        );
+
+        scheduler.update(0.1, &mut scenes);
+        let nested_task = nested_task.get().unwrap();
+        assert!(scheduler.is_running(nested_task));
+        assert_eq!(scheduler.task_count(), 6);
+
+        scheduler.cancel_owned(|owner| {
+            owner == TaskOwner::Plugin(0) || owner == TaskOwner::Script(script_type)
+        });
+
+        assert!(scheduler.is_running(game_task));
+        assert!(!scheduler.is_running(plugin_task));
+        assert!(scheduler.is_running(other_plugin_task));
+        assert!(!scheduler.is_running(script_task));
+        assert!(!scheduler.is_running(nested_task));
+        assert!(scheduler.is_running(other_script_task));
+    }
+}
@@ -0,0 +1,523 @@
+//! Dynamic plugins are plugins that are loaded from a dynamic library (`.dll`, `.so`, `.dylib`). They can be
+//! reloaded while the engine is running (hot reloading). See [`define_dynamic_plugin`](crate::define_dynamic_plugin)
+//! docs for more info.
+
+use crate::{
+    core::{
+        pool::Handle,
+        uuid::Uuid,
+        visitor::{Visit, VisitError, Visitor},
+    },
+    engine::SerializationContext,
+    plugin::PluginConstructor,
+    scene::{base::ScriptMessage, graph::Graph, node::Node},
+    utils::log::Log,
+};
+use libloading::{Library, Symbol};
+use std::{
+    ffi::CStr,
+    fmt::{Display, Formatter},
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+/// A string that contains versions of the engine and the compiler that was used to build the engine. Dynamic
+/// plugin can be loaded only if its ABI info matches the ABI info of the engine that loads it. Rust does not
+/// have stable ABI, so this is the only way to ensure that both sides have compatible layout of the types.
+pub const ABI_INFO: &str = concat!(
+    "fyrox ",
+    env!("CARGO_PKG_VERSION"),
+    "; ",
+    env!("FYROX_RUSTC_VERSION"),
+    "\0"
+);
+
+/// Name of the exported function that returns ABI info of a dynamic plugin.
+pub const ABI_INFO_SYMBOL: &[u8] = b"fyrox_plugin_abi_info";
+
+/// Name of the exported function that creates plugin constructor of a dynamic plugin.
+pub const CONSTRUCTOR_SYMBOL: &[u8] = b"fyrox_plugin_constructor";
+
+type AbiInfoFn = unsafe extern "C" fn() -> *const c_char;
+type ConstructorFn = unsafe fn() -> Box<dyn PluginConstructor>;
+
+/// Exports the given plugin constructor from a dynamic library, so the library could be loaded by
+/// [`crate::engine::Engine::add_dynamic_plugin`]. The crate of the game must have `dylib` crate type:
+///
+/// ```toml
+/// [lib]
+/// crate-type = ["rlib", "dylib"]
+/// ```
+///
+/// The game and the editor (or the executor) that loads it must be built with the same version of the
+/// compiler and the engine, otherwise the library will be rejected on load.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::pool::Handle,
+///     define_dynamic_plugin,
+///     plugin::{Plugin, PluginConstructor, PluginContext},
+///     scene::Scene,
+/// };
+///
+/// struct Game;
+///
+/// impl Plugin for Game {}
+///
+/// struct GameConstructor;
+///
+/// impl PluginConstructor for GameConstructor {
+///     fn create_instance(
+///         &self,
+///         _override_scene: Handle<Scene>,
+///         _context: PluginContext,
+///     ) -> Box<dyn Plugin> {
+///         Box::new(Game)
+///     }
+/// }
+///
+/// define_dynamic_plugin!(GameConstructor);
+/// ```
+#[macro_export]
+macro_rules! define_dynamic_plugin {
+    ($constructor:expr) => {
+        #[no_mangle]
+        pub extern "C" fn fyrox_plugin_abi_info() -> *const std::os::raw::c_char {
+            $crate::plugin::dynamic::ABI_INFO.as_ptr() as *const std::os::raw::c_char
+        }
+
+        #[no_mangle]
+        pub fn fyrox_plugin_constructor() -> Box<dyn $crate::plugin::PluginConstructor> {
+            Box::new($constructor)
+        }
+    };
+}
+
+/// An error that may occur during loading of a dynamic plugin.
+#[derive(Debug)]
+pub enum DynamicPluginError {
+    /// An IO error.
+    Io(std::io::Error),
+    /// Unable to load the library.
+    Library(libloading::Error),
+    /// The library does not export required symbol. Most likely, [`define_dynamic_plugin`](crate::define_dynamic_plugin)
+    /// was not used.
+    MissingSymbol(String),
+    /// The library was built using different version of the engine or the compiler.
+    IncompatibleAbi {
+        /// ABI info of the engine.
+        expected: String,
+        /// ABI info of the library.
+        actual: String,
+    },
+    /// A scene contains a node of a type that is defined in the plugin. Such nodes cannot be hot reloaded.
+    UnsupportedNode(Uuid),
+    /// Unable to save or restore plugin state.
+    Visit(VisitError),
+}
+
+impl Display for DynamicPluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DynamicPluginError::Io(v) => Display::fmt(v, f),
+            DynamicPluginError::Library(v) => Display::fmt(v, f),
+            DynamicPluginError::MissingSymbol(v) => {
+                write!(f, "Dynamic plugin does not export {} symbol!", v)
+            }
+            DynamicPluginError::IncompatibleAbi { expected, actual } => {
+                write!(
+                    f,
+                    "Dynamic plugin has incompatible ABI! Expected: {}, got: {}. \
+                    Rebuild the plugin with the same compiler and engine version.",
+                    expected, actual
+                )
+            }
+            DynamicPluginError::UnsupportedNode(v) => {
+                write!(
+                    f,
+                    "A scene contains a node of {} type, that is defined in the dynamic plugin. \
+                    Such nodes cannot be hot reloaded.",
+                    v
+                )
+            }
+            DynamicPluginError::Visit(v) => Display::fmt(v, f),
+        }
+    }
+}
+
+impl From<std::io::Error> for DynamicPluginError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for DynamicPluginError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+impl From<libloading::Error> for DynamicPluginError {
+    fn from(e: libloading::Error) -> Self {
+        Self::Library(e)
+    }
+}
+
+/// A plugin loaded from a dynamic library.
+pub(crate) struct DynamicPlugin {
+    /// Path to the library produced by the compiler.
+    source_path: PathBuf,
+    /// Path to the copy of the library that is actually loaded. The copy is required, because some
+    /// OSes lock loaded libraries and the compiler won't be able to overwrite it.
+    loaded_path: PathBuf,
+    /// Modification time of the loaded version of the library.
+    modified: Option<SystemTime>,
+    /// Modification time of the version of the library that failed to reload.
+    failed: Option<SystemTime>,
+    reload_when_changed: bool,
+    generation: u32,
+    library: Option<Library>,
+    /// Index of the plugin constructor in the engine.
+    pub(crate) constructor_index: usize,
+    /// A set of script types registered by the plugin.
+    pub(crate) script_types: Vec<Uuid>,
+    /// A set of node types registered by the plugin.
+    pub(crate) node_types: Vec<Uuid>,
+}
+
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn load_library(
+    source_path: &Path,
+    generation: u32,
+) -> Result<(PathBuf, Library, Box<dyn PluginConstructor>), DynamicPluginError> {
+    let file_name = source_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let loaded_path = std::env::temp_dir().join(format!(
+        "fyrox_{}_{}_{}",
+        std::process::id(),
+        generation,
+        file_name
+    ));
+    std::fs::copy(source_path, &loaded_path)?;
+
+    match load_copied_library(&loaded_path) {
+        Ok((library, constructor)) => Ok((loaded_path, library, constructor)),
+        Err(e) => {
+            let _ = std::fs::remove_file(&loaded_path);
+            Err(e)
+        }
+    }
+}
+
+fn load_copied_library(
+    path: &Path,
+) -> Result<(Library, Box<dyn PluginConstructor>), DynamicPluginError> {
+    // SAFETY: Loading a library executes its initialization code, there is no way to check it. ABI
+    // compatibility is checked below using C interface, which is stable.
+    let library = unsafe { Library::new(path)? };
+
+    let actual = unsafe {
+        let abi_info: Symbol<AbiInfoFn> = library.get(ABI_INFO_SYMBOL).map_err(|_| {
+            DynamicPluginError::MissingSymbol(String::from_utf8_lossy(ABI_INFO_SYMBOL).to_string())
+        })?;
+        CStr::from_ptr(abi_info()).to_string_lossy().to_string()
+    };
+
+    let expected = ABI_INFO.trim_end_matches('\0');
+    if actual != expected {
+        return Err(DynamicPluginError::IncompatibleAbi {
+            expected: expected.to_owned(),
+            actual,
+        });
+    }
+
+    // SAFETY: ABI is the same on both sides, so it is safe to call Rust functions of the library.
+    let constructor = unsafe {
+        let constructor: Symbol<ConstructorFn> = library.get(CONSTRUCTOR_SYMBOL).map_err(|_| {
+            DynamicPluginError::MissingSymbol(
+                String::from_utf8_lossy(CONSTRUCTOR_SYMBOL).to_string(),
+            )
+        })?;
+        constructor()
+    };
+
+    Ok((library, constructor))
+}
+
+impl DynamicPlugin {
+    /// Loads a library and creates plugin constructor from it.
+    pub(crate) fn load(
+        source_path: &Path,
+        reload_when_changed: bool,
+        constructor_index: usize,
+    ) -> Result<(Self, Box<dyn PluginConstructor>), DynamicPluginError> {
+        let modified = modification_time(source_path);
+        let (loaded_path, library, constructor) = load_library(source_path, 0)?;
+
+        Ok((
+            Self {
+                source_path: source_path.to_owned(),
+                loaded_path,
+                modified,
+                failed: None,
+                reload_when_changed,
+                generation: 0,
+                library: Some(library),
+                constructor_index,
+                script_types: Default::default(),
+                node_types: Default::default(),
+            },
+            constructor,
+        ))
+    }
+
+    /// Returns path of the library.
+    pub(crate) fn source_path(&self) -> &Path {
+        &self.source_path
+    }
+
+    /// Returns modification time of the library, if it was changed (rebuilt) since it was loaded and
+    /// this version of the library did not fail to reload before.
+    pub(crate) fn changed_version(&self) -> Option<SystemTime> {
+        if !self.reload_when_changed {
+            return None;
+        }
+
+        let modified = modification_time(&self.source_path)?;
+        if Some(modified) != self.modified && Some(modified) != self.failed {
+            Some(modified)
+        } else {
+            None
+        }
+    }
+
+    /// Remembers the version of the library that failed to reload, so it won't be reloaded again until
+    /// the library is changed.
+    pub(crate) fn reload_failed(&mut self, version: SystemTime) {
+        self.failed = Some(version);
+    }
+
+    /// Loads new version of the library. The old version is kept loaded until [`Self::finish_reload`]
+    /// is called, so the caller is able to destroy everything that references code of the old version.
+    pub(crate) fn begin_reload(
+        &mut self,
+    ) -> Result<(PathBuf, Library, Box<dyn PluginConstructor>), DynamicPluginError> {
+        self.generation += 1;
+        load_library(&self.source_path, self.generation)
+    }
+
+    /// Unloads old version of the library and replaces it with the new one. `version` is the modification
+    /// time of the new version (see [`Self::changed_version`]).
+    pub(crate) fn finish_reload(
+        &mut self,
+        loaded_path: PathBuf,
+        library: Library,
+        version: SystemTime,
+    ) {
+        self.unload();
+        self.loaded_path = loaded_path;
+        self.library = Some(library);
+        self.modified = Some(version);
+        self.failed = None;
+    }
+
+    fn unload(&mut self) {
+        if let Some(library) = self.library.take() {
+            drop(library);
+            let _ = std::fs::remove_file(&self.loaded_path);
+        }
+    }
+}
+
+impl Drop for DynamicPlugin {
+    fn drop(&mut self) {
+        self.unload();
+    }
+}
+
+/// Saved state of a script instance.
+pub(crate) struct SavedScript {
+    node: Handle<Node>,
+    type_uuid: Uuid,
+    initialized: bool,
+    started: bool,
+    data: Option<Vec<u8>>,
+}
+
+impl SavedScript {
+    /// Returns a handle of the node, that had the script.
+    pub(crate) fn node(&self) -> Handle<Node> {
+        self.node
+    }
+}
+
+/// Takes every script of the given types out of the graph and saves their state.
+pub(crate) fn save_scripts(graph: &mut Graph, script_types: &[Uuid]) -> Vec<SavedScript> {
+    let mut scripts = Vec::new();
+
+    for node_index in 0..graph.capacity() {
+        let handle = graph.handle_from_index(node_index);
+
+        if let Some(node) = graph.try_get_mut(handle) {
+            let is_plugin_script = node
+                .script
+                .as_ref()
+                .map_or(false, |script| script_types.contains(&script.id()));
+
+            if is_plugin_script {
+                if let Some(mut script) = node.script.take() {
+                    let mut visitor = Visitor::new();
+                    let data = match script
+                        .visit("Script", &mut visitor)
+                        .and_then(|_| visitor.save_binary_to_vec())
+                    {
+                        Ok(data) => Some(data),
+                        Err(e) => {
+                            Log::warn(format!(
+                                "Unable to save state of {} script of {} node, the state will be reset. Reason: {}",
+                                script.id(),
+                                node.name(),
+                                e
+                            ));
+                            None
+                        }
+                    };
+
+                    scripts.push(SavedScript {
+                        node: handle,
+                        type_uuid: script.id(),
+                        initialized: script.initialized,
+                        started: script.started,
+                        data,
+                    });
+                }
+            }
+        }
+    }
+
+    scripts
+}
+
+/// Creates new instances of saved scripts using script constructors and restores their state.
+/// [`crate::script::ScriptTrait::on_hot_reload`] of started scripts will be called on next update, so they
+/// could subscribe to the messages of the new version of the code.
+pub(crate) fn restore_scripts(
+    graph: &mut Graph,
+    scripts: Vec<SavedScript>,
+    serialization_context: &Arc<SerializationContext>,
+) {
+    for saved in scripts {
+        let node = match graph.try_get_mut(saved.node) {
+            Some(node) => node,
+            None => continue,
+        };
+
+        let mut script = match serialization_context
+            .script_constructors
+            .try_create(&saved.type_uuid)
+        {
+            Some(script) => script,
+            None => {
+                Log::warn(format!(
+                    "There is no script of {} type in reloaded plugin, the script is removed from {} node.",
+                    saved.type_uuid,
+                    node.name()
+                ));
+                continue;
+            }
+        };
+
+        if let Some(data) = saved.data {
+            match Visitor::load_from_memory(data) {
+                Ok(mut visitor) => {
+                    visitor.environment = Some(serialization_context.clone());
+
+                    if let Err(e) = script.visit("Script", &mut visitor) {
+                        Log::warn(format!(
+                            "Unable to restore state of {} script of {} node. Reason: {}",
+                            saved.type_uuid,
+                            node.name(),
+                            e
+                        ));
+                    }
+                }
+                Err(e) => Log::warn(format!(
+                    "Unable to restore state of {} script of {} node. Reason: {}",
+                    saved.type_uuid,
+                    node.name(),
+                    e
+                )),
+            }
+        }
+
+        script.initialized = saved.initialized;
+        script.started = saved.started;
+
+        node.script = Some(script);
+
+        if saved.started {
+            // Message subscriptions of the script were dropped, it must be able to subscribe again.
+            graph
+                .script_message_sender
+                .send(ScriptMessage::HotReloadScript { handle: saved.node })
+                .unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::plugin::dynamic::{modification_time, DynamicPlugin};
+    use std::{
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn test_failed_reload_is_retried_after_change() {
+        let source_path = std::env::temp_dir().join(format!(
+            "fyrox_test_dynamic_plugin_{}.so",
+            std::process::id()
+        ));
+        std::fs::write(&source_path, b"library").unwrap();
+        let version = modification_time(&source_path).unwrap();
+
+        let mut plugin = DynamicPlugin {
+            source_path: source_path.clone(),
+            loaded_path: PathBuf::new(),
+            modified: Some(SystemTime::UNIX_EPOCH),
+            failed: None,
+            reload_when_changed: true,
+            generation: 0,
+            library: None,
+            constructor_index: 0,
+            script_types: Default::default(),
+            node_types: Default::default(),
+        };
+
+        assert_eq!(plugin.changed_version(), Some(version));
+
+        // Failed version must not be reloaded again, but it must not be treated as loaded as well.
+        plugin.reload_failed(version);
+        assert_eq!(plugin.changed_version(), None);
+        assert_eq!(plugin.modified, Some(SystemTime::UNIX_EPOCH));
+
+        // Rebuilt library (the failure belongs to some older version).
+        plugin.failed = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1));
+        assert_eq!(plugin.changed_version(), Some(version));
+
+        plugin.reload_when_changed = false;
+        assert_eq!(plugin.changed_version(), None);
+
+        std::fs::remove_file(source_path).unwrap();
+    }
+}
@@ -2,9 +2,18 @@
 
 #![warn(missing_docs)]
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dynamic;
+
 use crate::{
-    core::pool::Handle,
-    engine::{resource_manager::ResourceManager, SerializationContext},
+    core::{
+        pool::Handle,
+        visitor::{VisitResult, Visitor},
+    },
+    engine::{
+        resource_manager::ResourceManager, task::TaskScheduler, FixedUpdateStage,
+        SerializationContext,
+    },
     event::Event,
     event_loop::ControlFlow,
     gui::{message::UiMessage, UserInterface},
@@ -41,6 +50,14 @@ pub trait PluginConstructor {
         #[allow(unused_variables)] override_scene: Handle<Scene>,
         context: PluginContext,
     ) -> Box<dyn Plugin>;
+
+    /// The method is called when a dynamic plugin is hot reloaded, it must create new plugin instance. The state
+    /// of the old instance is restored right after using [`Plugin::visit_state`]. Default implementation calls
+    /// [`Self::create_instance`], override the method if `create_instance` does some one-time initialization
+    /// (for example loads a scene).
+    fn create_hot_reload_instance(&self, context: PluginContext) -> Box<dyn Plugin> {
+        self.create_instance(Handle::NONE, context)
+    }
 }
 
 /// Contains plugin environment for the registration stage.
@@ -66,6 +83,28 @@ impl<'a> SoundEngineHelper<'a> {
     pub fn sound_gain(&self) -> f32 {
         self.engine.lock().unwrap().master_gain()
     }
+
+    /// Sets gain multiplier of audio buses with the given name in every sound scene. It is not
+    /// serialized and intended to be used for volume settings in options menu (music volume,
+    /// sound effects volume, etc.).
+    pub fn set_bus_gain<S: AsRef<str>>(&mut self, bus: S, gain: f32) {
+        self.engine.lock().unwrap().set_bus_gain(bus, gain);
+    }
+
+    /// Returns gain multiplier of audio buses with the given name.
+    pub fn bus_gain(&self, bus: &str) -> f32 {
+        self.engine.lock().unwrap().bus_gain(bus)
+    }
+
+    /// Mutes or unmutes audio buses with the given name in every sound scene.
+    pub fn set_bus_muted<S: AsRef<str>>(&mut self, bus: S, muted: bool) {
+        self.engine.lock().unwrap().set_bus_muted(bus, muted);
+    }
+
+    /// Returns true if audio buses with the given name are muted.
+    pub fn is_bus_muted(&self, bus: &str) -> bool {
+        self.engine.lock().unwrap().is_bus_muted(bus)
+    }
 }
 
 /// Contains plugin environment.
@@ -100,6 +139,10 @@ pub struct PluginContext<'a, 'b> {
     /// info.
     pub serialization_context: &'a Arc<SerializationContext>,
 
+    /// A reference to the task scheduler, it can be used to spawn asynchronous tasks that will be driven by
+    /// the engine loop. See [`TaskScheduler`] docs for more info.
+    pub task_scheduler: &'a mut TaskScheduler,
+
     /// A reference to the main application window.
     pub window: &'a Window,
 
@@ -145,17 +188,18 @@ impl dyn Plugin {
 ///
 /// # Static vs dynamic plugins
 ///
-/// Every plugin must be linked statically to ensure that everything is memory safe. There was some
-/// long research about hot reloading and dynamic plugins (in DLLs) and it turned out that they're
-/// not guaranteed to be memory safe because Rust does not have stable ABI. When a plugin compiled
-/// into DLL, Rust compiler is free to reorder struct members in any way it needs to. It is not
-/// guaranteed that two projects that uses the same library will have compatible ABI. This fact
-/// indicates that you either have to use static linking of your plugins or provide C interface
-/// to every part of the engine and "communicate" with plugin using C interface with C ABI (which
-/// is standardized and guaranteed to be compatible). The main problem with C interface is
-/// boilerplate code and the need to mark every structure "visible" through C interface with
-/// `#[repr(C)]` attribute which is not always easy and even possible (because some structures could
-/// be re-exported from dependencies). These are the main reasons why the engine uses static plugins.
+/// By default, plugins are linked statically, it is the most reliable way, because Rust does not have stable
+/// ABI. When a plugin compiled into DLL, Rust compiler is free to reorder struct members in any way it needs
+/// to, so it is not guaranteed that two projects that uses the same library will have compatible ABI.
+///
+/// However, it is possible to compile game code as a dynamic library and load it using
+/// [`crate::engine::Engine::add_dynamic_plugin`]. This allows to reload game code while the engine (or the
+/// editor) is running. To mitigate the ABI issue, the engine checks that the library was built with the
+/// same compiler and engine version and refuses to load it otherwise. See [`dynamic`] module docs for more
+/// info. On reload, plugin state is saved and restored using [`Plugin::visit_state`], scripts are saved and
+/// restored using their [`crate::core::visitor::Visit`] implementation. Message subscriptions and tasks of
+/// the scripts are dropped on reload, and [`crate::script::ScriptTrait::on_hot_reload`] of every started
+/// script is called, so the scripts could subscribe to the messages of the new version of the code.
 ///
 /// # Example
 ///
@@ -189,6 +233,14 @@ impl dyn Plugin {
 /// }
 /// ```
 pub trait Plugin: BasePlugin {
+    /// The method is used to save and restore plugin state when a dynamic plugin is hot reloaded. The easiest
+    /// way to implement it is to derive [`crate::core::visitor::Visit`] for the plugin and call
+    /// `self.visit("Plugin", visitor)`. Default implementation does nothing, so the state of a reloaded
+    /// plugin will be reset.
+    fn visit_state(&mut self, #[allow(unused_variables)] visitor: &mut Visitor) -> VisitResult {
+        Ok(())
+    }
+
     /// The method is called before plugin will be disabled. It should be used for clean up, or some
     /// additional actions.
     fn on_deinit(&mut self, #[allow(unused_variables)] context: PluginContext) {}
@@ -202,6 +254,27 @@ pub trait Plugin: BasePlugin {
     ) {
     }
 
+    /// The method is called at fixed rate right before and right after each physics step, `stage` tells
+    /// which one.
+    fn on_fixed_update(
+        &mut self,
+        #[allow(unused_variables)] stage: FixedUpdateStage,
+        #[allow(unused_variables)] context: &mut PluginContext,
+        #[allow(unused_variables)] control_flow: &mut ControlFlow,
+    ) {
+    }
+
+    /// The method is called once per rendered frame, [`PluginContext::dt`] contains real time that passed
+    /// since the last frame. `interpolation_alpha` (in `[0; 1]` range) defines how far the frame is between
+    /// the last fixed update and the next one.
+    fn on_frame(
+        &mut self,
+        #[allow(unused_variables)] interpolation_alpha: f32,
+        #[allow(unused_variables)] context: &mut PluginContext,
+        #[allow(unused_variables)] control_flow: &mut ControlFlow,
+    ) {
+    }
+
     /// The method is called when the main window receives an event from the OS. The main use of
     /// the method is to respond to some external events, for example an event from keyboard or
     /// gamepad. See [`Event`] docs for more info.
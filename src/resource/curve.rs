@@ -72,7 +72,7 @@ impl ResourceData for CurveResourceState {
 impl CurveResourceState {
     /// Load a curve resource from the specific file path.
     pub async fn from_file(path: &Path) -> Result<Self, CurveResourceError> {
-        let mut visitor = Visitor::load_binary(path).await?;
+        let mut visitor = Visitor::load_from_file(path).await?;
         let mut curve = Curve::default();
         curve.visit("Curve", &mut visitor)?;
         Ok(Self {
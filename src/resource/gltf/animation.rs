@@ -0,0 +1,126 @@
+//! Converts glTF animations into engine animations.
+
+use crate::{
+    animation::{track::Track, Animation},
+    core::{
+        algebra::{Quaternion, UnitQuaternion, Vector3},
+        curve::{CurveKey, CurveKeyKind},
+        math::unwrap_angle,
+        pool::Handle,
+    },
+    scene::node::Node,
+    utils::log::Log,
+};
+use gltf::animation::{util::ReadOutputs, Interpolation};
+
+fn fill_vec3_track(track: &mut Track, times: &[f32], values: &[[f32; 3]], kind: CurveKeyKind) {
+    let curves = track.frames_container_mut().curves_mut();
+    for (&time, value) in times.iter().zip(values) {
+        for (curve, &component) in curves.iter_mut().zip(value) {
+            curve.add_key(CurveKey::new(time, component, kind.clone()));
+        }
+    }
+}
+
+fn fill_rotation_track(track: &mut Track, times: &[f32], values: &[[f32; 4]], kind: CurveKeyKind) {
+    let curves = track.frames_container_mut().curves_mut();
+    let mut previous: Option<Vector3<f32>> = None;
+    for (&time, &[x, y, z, w]) in times.iter().zip(values) {
+        let (roll, pitch, yaw) =
+            UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z)).euler_angles();
+        let mut euler = Vector3::new(roll, pitch, yaw);
+        if let Some(previous) = previous {
+            for i in 0..3 {
+                euler[i] = unwrap_angle(euler[i], previous[i]);
+            }
+        }
+        previous = Some(euler);
+        for (curve, &component) in curves.iter_mut().zip(euler.iter()) {
+            curve.add_key(CurveKey::new(time, component, kind.clone()));
+        }
+    }
+}
+
+/// Cubic spline samplers store triplets of (in-tangent, value, out-tangent) per key frame. Tangent
+/// semantics of engine curves differ from glTF, so only values are taken and interpolated linearly.
+fn take_spline_values<T: Copy>(values: Vec<T>, interpolation: Interpolation) -> Vec<T> {
+    match interpolation {
+        Interpolation::CubicSpline => values.chunks(3).filter_map(|c| c.get(1).copied()).collect(),
+        _ => values,
+    }
+}
+
+pub(super) fn convert_animations(
+    document: &gltf::Document,
+    buffers: &[Vec<u8>],
+    node_map: &[Handle<Node>],
+) -> Vec<Animation> {
+    let mut animations = Vec::new();
+
+    for gltf_animation in document.animations() {
+        let mut animation = Animation::default();
+        animation.set_name(
+            gltf_animation
+                .name()
+                .map(|n| n.to_owned())
+                .unwrap_or_else(|| format!("Animation{}", gltf_animation.index())),
+        );
+
+        for channel in gltf_animation.channels() {
+            let target = match node_map.get(channel.target().node().index()) {
+                Some(target) => *target,
+                None => continue,
+            };
+
+            let interpolation = channel.sampler().interpolation();
+            let kind = match interpolation {
+                Interpolation::Step => CurveKeyKind::Constant,
+                Interpolation::Linear | Interpolation::CubicSpline => CurveKeyKind::Linear,
+            };
+
+            let reader = channel.reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()));
+            let times = match reader.read_inputs() {
+                Some(inputs) => inputs.collect::<Vec<_>>(),
+                None => continue,
+            };
+
+            let mut track = match reader.read_outputs() {
+                Some(ReadOutputs::Translations(translations)) => {
+                    let mut track = Track::new_position();
+                    let values = take_spline_values(translations.collect(), interpolation);
+                    fill_vec3_track(&mut track, &times, &values, kind);
+                    track
+                }
+                Some(ReadOutputs::Scales(scales)) => {
+                    let mut track = Track::new_scale();
+                    let values = take_spline_values(scales.collect(), interpolation);
+                    fill_vec3_track(&mut track, &times, &values, kind);
+                    track
+                }
+                Some(ReadOutputs::Rotations(rotations)) => {
+                    let mut track = Track::new_rotation();
+                    let values = take_spline_values(rotations.into_f32().collect(), interpolation);
+                    fill_rotation_track(&mut track, &times, &values, kind);
+                    track
+                }
+                Some(ReadOutputs::MorphTargetWeights(_)) => {
+                    Log::warn(format!(
+                        "Morph target animations are not supported, channel of {} animation is ignored.",
+                        animation.name()
+                    ));
+                    continue;
+                }
+                None => continue,
+            };
+
+            track.set_target(target);
+            animation.add_track(track);
+        }
+
+        animation.fit_length_to_content();
+
+        animations.push(animation);
+    }
+
+    animations
+}
@@ -0,0 +1,97 @@
+//! Contains all possible errors that can occur during glTF loading and conversion.
+
+use crate::core::io::FileLoadError;
+use std::fmt::{Display, Formatter};
+
+/// See module docs.
+#[derive(Debug)]
+pub enum GltfError {
+    /// An error occurred while parsing glTF document (either JSON or binary container).
+    Gltf(gltf::Error),
+
+    /// An error occurred during file loading.
+    FileLoadError(FileLoadError),
+
+    /// Unable to decode base64-encoded data URI.
+    Base64(base64::DecodeError),
+
+    /// Binary glTF references `BIN` chunk, but it is missing.
+    MissingBlob,
+
+    /// Data URI has unsupported encoding (only base64 is supported).
+    UnsupportedUri(String),
+
+    /// A primitive does not have mandatory `POSITION` attribute.
+    MissingPositions,
+
+    /// Internal error that means some index was out of bounds. Probably malformed document.
+    IndexOutOfBounds,
+
+    /// A vertex references a joint with an index that does not fit into bone indices of vertices
+    /// (which are 8-bit), it means that a skin has more than 256 joints.
+    TooManyJoints(usize),
+
+    /// Arbitrary error that can have any meaning.
+    Custom(Box<String>),
+}
+
+impl Display for GltfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GltfError::Gltf(v) => {
+                write!(f, "glTF: Unable to parse document: {v}")
+            }
+            GltfError::FileLoadError(v) => {
+                write!(f, "glTF: File load error {v:?}.")
+            }
+            GltfError::Base64(v) => {
+                write!(f, "glTF: Unable to decode data URI: {v}")
+            }
+            GltfError::MissingBlob => {
+                write!(f, "glTF: Binary chunk is referenced, but it is missing.")
+            }
+            GltfError::UnsupportedUri(v) => {
+                write!(f, "glTF: Unsupported URI {v}")
+            }
+            GltfError::MissingPositions => {
+                write!(f, "glTF: A primitive does not have POSITION attribute.")
+            }
+            GltfError::IndexOutOfBounds => {
+                write!(f, "glTF: Index is out-of-bounds.")
+            }
+            GltfError::TooManyJoints(v) => {
+                write!(
+                    f,
+                    "glTF: A vertex references joint {v}, but only 256 joints per skin are supported."
+                )
+            }
+            GltfError::Custom(v) => {
+                write!(f, "glTF: An error has occurred: {v}")
+            }
+        }
+    }
+}
+
+impl From<gltf::Error> for GltfError {
+    fn from(err: gltf::Error) -> Self {
+        GltfError::Gltf(err)
+    }
+}
+
+impl From<FileLoadError> for GltfError {
+    fn from(err: FileLoadError) -> Self {
+        GltfError::FileLoadError(err)
+    }
+}
+
+impl From<base64::DecodeError> for GltfError {
+    fn from(err: base64::DecodeError) -> Self {
+        GltfError::Base64(err)
+    }
+}
+
+impl From<String> for GltfError {
+    fn from(err: String) -> Self {
+        GltfError::Custom(Box::new(err))
+    }
+}
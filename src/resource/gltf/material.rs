@@ -0,0 +1,334 @@
+//! Converts glTF PBR materials into instances of the standard material.
+
+use crate::{
+    core::{algebra::Vector3, color::Color, io, sstorage::ImmutableString},
+    engine::resource_manager::ResourceManager,
+    material::{
+        shader::{SamplerFallback, Shader},
+        Material, PropertyValue, SharedMaterial,
+    },
+    resource::{
+        gltf::{decode_uri, load_uri},
+        model::ModelImportOptions,
+        texture::{CompressionOptions, Texture, TextureKind, TexturePixelKind},
+    },
+    utils::log::Log,
+};
+use fxhash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+/// Location of pixels of a glTF image.
+enum ImageSource {
+    /// Image is stored in a separate file and can be requested from the resource manager.
+    File(PathBuf),
+    /// Image is embedded in the document (either in a buffer view or in a data URI).
+    Embedded(Vec<u8>),
+}
+
+/// Converts materials of a glTF document and caches the results, so primitives that share the
+/// same material will share the same [`SharedMaterial`] instance.
+pub(super) struct MaterialConverter<'a> {
+    buffers: &'a [Vec<u8>],
+    resource_manager: ResourceManager,
+    model_path: &'a Path,
+    model_import_options: &'a ModelImportOptions,
+    textures: FxHashMap<usize, Texture>,
+    materials: FxHashMap<Option<usize>, SharedMaterial>,
+}
+
+impl<'a> MaterialConverter<'a> {
+    pub(super) fn new(
+        buffers: &'a [Vec<u8>],
+        resource_manager: ResourceManager,
+        model_path: &'a Path,
+        model_import_options: &'a ModelImportOptions,
+    ) -> Self {
+        Self {
+            buffers,
+            resource_manager,
+            model_path,
+            model_import_options,
+            textures: Default::default(),
+            materials: Default::default(),
+        }
+    }
+
+    async fn image_source(&self, image: &gltf::Image<'_>) -> Option<ImageSource> {
+        match image.source() {
+            gltf::image::Source::View { view, .. } => {
+                let buffer = self.buffers.get(view.buffer().index())?;
+                let begin = view.offset();
+                let end = begin + view.length();
+                buffer
+                    .get(begin..end)
+                    .map(|d| ImageSource::Embedded(d.to_vec()))
+            }
+            gltf::image::Source::Uri { uri, .. } => {
+                if uri.starts_with("data:") {
+                    match load_uri(uri, self.model_path).await {
+                        Ok(data) => Some(ImageSource::Embedded(data)),
+                        Err(e) => {
+                            Log::err(format!(
+                                "Unable to decode embedded image of {:?}. Reason: {}",
+                                self.model_path, e
+                            ));
+                            None
+                        }
+                    }
+                } else {
+                    // glTF stores paths relative to the document, try it first and only then
+                    // fall back to the search options.
+                    let relative = decode_uri(uri);
+                    let candidate = self
+                        .model_path
+                        .parent()
+                        .map(|p| p.join(&relative))
+                        .unwrap_or_else(|| PathBuf::from(&relative));
+                    if io::exists(&candidate).await {
+                        Some(ImageSource::File(candidate))
+                    } else {
+                        let path = self
+                            .model_import_options
+                            .material_search_options
+                            .find_resource_path(Path::new(&relative), self.model_path)
+                            .await;
+                        if path.is_none() {
+                            Log::warn(format!(
+                                "Unable to find a texture {:?} for 3D model {:?} using {:?} option!",
+                                relative, self.model_path, self.model_import_options
+                            ));
+                        }
+                        path.map(ImageSource::File)
+                    }
+                }
+            }
+        }
+    }
+
+    async fn texture(&mut self, image: gltf::Image<'_>) -> Option<Texture> {
+        if let Some(texture) = self.textures.get(&image.index()) {
+            return Some(texture.clone());
+        }
+
+        let texture = match self.image_source(&image).await? {
+            ImageSource::File(path) => self.resource_manager.request_texture(path),
+            ImageSource::Embedded(data) => {
+                match Texture::load_from_memory(&data, CompressionOptions::NoCompression, true) {
+                    Ok(texture) => texture,
+                    Err(e) => {
+                        Log::err(format!(
+                            "Unable to load embedded texture of {:?}. Reason: {:?}",
+                            self.model_path, e
+                        ));
+                        return None;
+                    }
+                }
+            }
+        };
+
+        self.textures.insert(image.index(), texture.clone());
+
+        Some(texture)
+    }
+
+    /// glTF packs roughness into green channel and metalness into blue channel of a single
+    /// texture, while the standard shader expects them in red channels of separate textures.
+    /// This method splits the texture and scales each channel by its factor.
+    async fn metallic_roughness(
+        &self,
+        image: gltf::Image<'_>,
+        metallic_factor: f32,
+        roughness_factor: f32,
+    ) -> Option<(Texture, Texture)> {
+        let data = match self.image_source(&image).await? {
+            ImageSource::File(path) => match io::load_file(&path).await {
+                Ok(data) => data,
+                Err(e) => {
+                    Log::err(format!(
+                        "Unable to load metallic-roughness texture {:?}. Reason: {:?}",
+                        path, e
+                    ));
+                    return None;
+                }
+            },
+            ImageSource::Embedded(data) => data,
+        };
+
+        let image = match image::load_from_memory(&data) {
+            Ok(image) => image.to_rgba8(),
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to decode metallic-roughness texture of {:?}. Reason: {:?}",
+                    self.model_path, e
+                ));
+                return None;
+            }
+        };
+
+        let (width, height) = image.dimensions();
+        let mut metallic = Vec::with_capacity((width * height) as usize);
+        let mut roughness = Vec::with_capacity((width * height) as usize);
+        for pixel in image.pixels() {
+            roughness.push((pixel[1] as f32 * roughness_factor).min(255.0) as u8);
+            metallic.push((pixel[2] as f32 * metallic_factor).min(255.0) as u8);
+        }
+
+        let kind = TextureKind::Rectangle { width, height };
+        Some((
+            Texture::from_bytes(kind, TexturePixelKind::R8, metallic, false)?,
+            Texture::from_bytes(kind, TexturePixelKind::R8, roughness, false)?,
+        ))
+    }
+
+    /// Converts given glTF material to the standard material or returns existing one, if it
+    /// was already converted.
+    pub(super) async fn convert(&mut self, material: gltf::Material<'_>) -> SharedMaterial {
+        if let Some(shared) = self.materials.get(&material.index()) {
+            return shared.clone();
+        }
+
+        let mut result = if material.double_sided() {
+            Material::from_shader(Shader::standard_twosides(), None)
+        } else {
+            Material::standard()
+        };
+
+        let pbr = material.pbr_metallic_roughness();
+
+        let [r, g, b, a] = pbr.base_color_factor();
+        set_property(
+            &mut result,
+            "diffuseColor",
+            PropertyValue::Color(Color::from_rgba(
+                (r * 255.0) as u8,
+                (g * 255.0) as u8,
+                (b * 255.0) as u8,
+                (a * 255.0) as u8,
+            )),
+        );
+
+        if let Some(info) = pbr.base_color_texture() {
+            if let Some(texture) = self.texture(info.texture().source()).await {
+                set_sampler(
+                    &mut result,
+                    "diffuseTexture",
+                    texture,
+                    SamplerFallback::White,
+                );
+            }
+        }
+
+        if let Some(info) = material.normal_texture() {
+            if let Some(texture) = self.texture(info.texture().source()).await {
+                set_sampler(
+                    &mut result,
+                    "normalTexture",
+                    texture,
+                    SamplerFallback::Normal,
+                );
+            }
+        }
+
+        if let Some(info) = material.occlusion_texture() {
+            // Occlusion is stored in red channel, exactly as the standard shader expects.
+            if let Some(texture) = self.texture(info.texture().source()).await {
+                set_sampler(&mut result, "aoTexture", texture, SamplerFallback::White);
+            }
+        }
+
+        let metallic_factor = pbr.metallic_factor();
+        let roughness_factor = pbr.roughness_factor();
+        let metallic_roughness = match pbr.metallic_roughness_texture() {
+            Some(info) => {
+                self.metallic_roughness(info.texture().source(), metallic_factor, roughness_factor)
+                    .await
+            }
+            None => {
+                // Bake the factors into 1x1 textures, otherwise fallback values (fully
+                // dielectric and fully rough) will be used.
+                let kind = TextureKind::Rectangle {
+                    width: 1,
+                    height: 1,
+                };
+                Texture::from_bytes(
+                    kind,
+                    TexturePixelKind::R8,
+                    vec![(metallic_factor * 255.0) as u8],
+                    false,
+                )
+                .zip(Texture::from_bytes(
+                    kind,
+                    TexturePixelKind::R8,
+                    vec![(roughness_factor * 255.0) as u8],
+                    false,
+                ))
+            }
+        };
+        if let Some((metallic, roughness)) = metallic_roughness {
+            set_sampler(
+                &mut result,
+                "metallicTexture",
+                metallic,
+                SamplerFallback::Black,
+            );
+            set_sampler(
+                &mut result,
+                "roughnessTexture",
+                roughness,
+                SamplerFallback::White,
+            );
+        }
+
+        let emissive_factor = Vector3::from(material.emissive_factor());
+        let emission = match material.emissive_texture() {
+            Some(info) => self.texture(info.texture().source()).await,
+            None if emissive_factor != Vector3::default() => Texture::from_bytes(
+                TextureKind::Rectangle {
+                    width: 1,
+                    height: 1,
+                },
+                TexturePixelKind::RGBA8,
+                vec![255; 4],
+                false,
+            ),
+            None => None,
+        };
+        if let Some(emission) = emission {
+            set_sampler(
+                &mut result,
+                "emissionTexture",
+                emission,
+                SamplerFallback::Black,
+            );
+            set_property(
+                &mut result,
+                "emissionStrength",
+                PropertyValue::Vector3(emissive_factor),
+            );
+        }
+
+        let shared = SharedMaterial::new(result);
+        self.materials.insert(material.index(), shared.clone());
+        shared
+    }
+}
+
+fn set_property(material: &mut Material, name: &str, value: PropertyValue) {
+    if let Err(e) = material.set_property(&ImmutableString::new(name), value) {
+        Log::err(format!(
+            "Unable to set material property {} for glTF material! Reason: {:?}",
+            name, e
+        ));
+    }
+}
+
+fn set_sampler(material: &mut Material, name: &str, texture: Texture, fallback: SamplerFallback) {
+    set_property(
+        material,
+        name,
+        PropertyValue::Sampler {
+            value: Some(texture),
+            fallback,
+        },
+    )
+}
@@ -0,0 +1,634 @@
+//! Contains all methods to load and convert glTF 2.0 model format.
+//!
+//! glTF is an open format for transmission of 3D scenes, it is supported by pretty much every
+//! modern 3D modelling software (including Blender). Both text (`.gltf`) and binary (`.glb`)
+//! containers are supported, with external or embedded buffers and images. The loader converts
+//! node hierarchy, meshes (including skinning), PBR materials, cameras, punctual lights
+//! (`KHR_lights_punctual`) and animations.
+//!
+//! Normally you should never use methods from this module directly, use resource manager to load
+//! models and create their instances.
+
+mod animation;
+pub mod error;
+mod material;
+
+use crate::{
+    animation::AnimationContainer,
+    core::{
+        algebra::{Matrix4, Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        instant::Instant,
+        io,
+        math::TriangleDefinition,
+        pool::Handle,
+        uuid::Uuid,
+    },
+    engine::resource_manager::ResourceManager,
+    resource::{
+        gltf::{animation::convert_animations, error::GltfError, material::MaterialConverter},
+        model::ModelImportOptions,
+    },
+    scene::{
+        animation::AnimationPlayerBuilder,
+        base::{BaseBuilder, InstanceId},
+        camera::{CameraBuilder, OrthographicProjection, PerspectiveProjection, Projection},
+        graph::Graph,
+        light::{
+            directional::DirectionalLightBuilder, point::PointLightBuilder, spot::SpotLightBuilder,
+            BaseLightBuilder,
+        },
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::{Surface, SurfaceData, SurfaceSharedData},
+            vertex::{AnimatedVertex, StaticVertex},
+            Mesh, MeshBuilder,
+        },
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+        Scene,
+    },
+    utils::log::Log,
+};
+use fxhash::FxHashMap;
+use std::{
+    collections::hash_map::DefaultHasher,
+    f32::consts::{FRAC_PI_2, PI},
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+/// Decodes percent-encoded characters of a relative URI (`my%20texture.png` -> `my texture.png`).
+fn decode_uri(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = uri
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Loads the content of a URI, which could be either a base64 data URI or a path relative to
+/// the model file.
+async fn load_uri(uri: &str, model_path: &Path) -> Result<Vec<u8>, GltfError> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        match data.split_once(";base64,") {
+            Some((_, encoded)) => Ok(base64::decode(encoded)?),
+            None => Err(GltfError::UnsupportedUri(uri.to_owned())),
+        }
+    } else {
+        let relative = decode_uri(uri);
+        let path = match model_path.parent() {
+            Some(parent) => parent.join(relative),
+            None => relative.into(),
+        };
+        Ok(io::load_file(path).await?)
+    }
+}
+
+async fn load_buffers(gltf: &gltf::Gltf, model_path: &Path) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffers = Vec::new();
+    for buffer in gltf.buffers() {
+        let data = match buffer.source() {
+            gltf::buffer::Source::Bin => gltf.blob.clone().ok_or(GltfError::MissingBlob)?,
+            gltf::buffer::Source::Uri(uri) => load_uri(uri, model_path).await?,
+        };
+        if data.len() < buffer.length() {
+            return Err(GltfError::IndexOutOfBounds);
+        }
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+fn node_name(node: &gltf::Node) -> String {
+    node.name()
+        .map(|n| n.to_owned())
+        .unwrap_or_else(|| format!("Node{}", node.index()))
+}
+
+fn convert_node_to_base(node: &gltf::Node, inv_bind_pose: Matrix4<f32>) -> BaseBuilder {
+    let name = node_name(node);
+
+    // glTF does not have persistent unique ids for nodes, so use the same approach as FBX importer
+    // does - generate instance id from the name of the node.
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let hash = hasher.finish();
+    let instance_id = InstanceId(Uuid::from_u64_pair(hash, hash));
+
+    let (translation, [x, y, z, w], scale) = node.transform().decomposed();
+
+    BaseBuilder::new()
+        .with_inv_bind_pose_transform(inv_bind_pose)
+        .with_name(name)
+        .with_instance_id(instance_id)
+        .with_local_transform(
+            TransformBuilder::new()
+                .with_local_position(Vector3::from(translation))
+                .with_local_rotation(UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z)))
+                .with_local_scale(Vector3::from(scale))
+                .build(),
+        )
+}
+
+fn convert_bone_indices(joints: [u16; 4]) -> Result<[u8; 4], GltfError> {
+    let mut bone_indices = [0; 4];
+    for (bone_index, &joint) in bone_indices.iter_mut().zip(joints.iter()) {
+        *bone_index = u8::try_from(joint).map_err(|_| GltfError::TooManyJoints(joint as usize))?;
+    }
+    Ok(bone_indices)
+}
+
+fn convert_primitive(
+    primitive: &gltf::Primitive,
+    buffers: &[Vec<u8>],
+    skinned: bool,
+) -> Result<Option<SurfaceData>, GltfError> {
+    if primitive.mode() != gltf::mesh::Mode::Triangles {
+        Log::warn(format!(
+            "glTF: Primitive {} has unsupported mode {:?}, it is ignored.",
+            primitive.index(),
+            primitive.mode()
+        ));
+        return Ok(None);
+    }
+
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()));
+
+    let positions = reader
+        .read_positions()
+        .ok_or(GltfError::MissingPositions)?
+        .map(Vector3::from)
+        .collect::<Vec<_>>();
+    let normals = reader.read_normals().map(|n| n.collect::<Vec<_>>());
+    let tangents = reader.read_tangents().map(|t| t.collect::<Vec<_>>());
+    let uvs = reader
+        .read_tex_coords(0)
+        .map(|uv| uv.into_f32().collect::<Vec<_>>());
+    let joints = reader
+        .read_joints(0)
+        .map(|j| j.into_u16().collect::<Vec<_>>());
+    let weights = reader
+        .read_weights(0)
+        .map(|w| w.into_f32().collect::<Vec<_>>());
+
+    let indices = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect::<Vec<_>>(),
+        None => (0..positions.len() as u32).collect(),
+    };
+    if indices.iter().any(|&i| i as usize >= positions.len()) {
+        return Err(GltfError::IndexOutOfBounds);
+    }
+    let triangles = indices
+        .chunks_exact(3)
+        .map(|t| TriangleDefinition([t[0], t[1], t[2]]))
+        .collect::<Vec<_>>();
+
+    let normal = |i: usize| {
+        normals
+            .as_ref()
+            .and_then(|n| n.get(i))
+            .map(|n| Vector3::from(*n))
+            .unwrap_or_else(Vector3::y)
+    };
+    let tangent = |i: usize| {
+        tangents
+            .as_ref()
+            .and_then(|t| t.get(i))
+            .map(|t| Vector4::from(*t))
+            .unwrap_or_else(|| Vector4::new(1.0, 0.0, 0.0, 1.0))
+    };
+    // glTF has origin of texture coordinates at top-left corner, which matches the engine.
+    let uv = |i: usize| {
+        uvs.as_ref()
+            .and_then(|uv| uv.get(i))
+            .map(|uv| Vector2::from(*uv))
+            .unwrap_or_default()
+    };
+
+    let vertex_buffer = match (joints, weights) {
+        (Some(joints), Some(weights)) if skinned => {
+            let vertices = positions
+                .iter()
+                .enumerate()
+                .map(|(i, position)| {
+                    Ok(AnimatedVertex {
+                        position: *position,
+                        tex_coord: uv(i),
+                        normal: normal(i),
+                        tangent: tangent(i),
+                        bone_weights: weights.get(i).copied().unwrap_or_default(),
+                        bone_indices: convert_bone_indices(
+                            joints.get(i).copied().unwrap_or_default(),
+                        )?,
+                    })
+                })
+                .collect::<Result<Vec<_>, GltfError>>()?;
+            VertexBuffer::new(vertices.len(), AnimatedVertex::layout(), vertices)
+        }
+        _ => {
+            let vertices = positions
+                .iter()
+                .enumerate()
+                .map(|(i, position)| StaticVertex {
+                    position: *position,
+                    tex_coord: uv(i),
+                    normal: normal(i),
+                    tangent: tangent(i),
+                })
+                .collect::<Vec<_>>();
+            VertexBuffer::new(vertices.len(), StaticVertex::layout(), vertices)
+        }
+    }
+    .map_err(|e| format!("Unable to create vertex buffer. Reason: {:?}", e))?;
+
+    let mut data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(triangles), false);
+
+    if normals.is_none() {
+        data.calculate_normals()
+            .map_err(|e| format!("Unable to calculate normals. Reason: {:?}", e))?;
+    }
+    if tangents.is_none() {
+        data.calculate_tangents()
+            .map_err(|e| format!("Unable to calculate tangents. Reason: {:?}", e))?;
+    }
+
+    Ok(Some(data))
+}
+
+async fn convert_mesh(
+    mesh: gltf::Mesh<'_>,
+    buffers: &[Vec<u8>],
+    skinned: bool,
+    materials: &mut MaterialConverter<'_>,
+) -> Result<Vec<Surface>, GltfError> {
+    let mut surfaces = Vec::new();
+    for primitive in mesh.primitives() {
+        if let Some(data) = convert_primitive(&primitive, buffers, skinned)? {
+            let mut surface = Surface::new(SurfaceSharedData::new(data));
+            surface.set_material(materials.convert(primitive.material()).await);
+            surfaces.push(surface);
+        }
+    }
+    Ok(surfaces)
+}
+
+/// Creates a camera that is attached to a glTF node. glTF cameras look along -Z axis, while the
+/// engine cameras look along +Z axis, so the camera is rotated around Y axis.
+fn convert_camera(camera: gltf::Camera, name: &str, graph: &mut Graph) -> Handle<Node> {
+    let projection = match camera.projection() {
+        gltf::camera::Projection::Perspective(perspective) => {
+            Projection::Perspective(PerspectiveProjection {
+                fov: perspective.yfov(),
+                z_near: perspective.znear(),
+                z_far: perspective.zfar().unwrap_or(2048.0),
+            })
+        }
+        gltf::camera::Projection::Orthographic(orthographic) => {
+            Projection::Orthographic(OrthographicProjection {
+                z_near: orthographic.znear(),
+                z_far: orthographic.zfar(),
+                vertical_size: orthographic.ymag(),
+            })
+        }
+    };
+
+    CameraBuilder::new(
+        BaseBuilder::new()
+            .with_name(format!("{}_Camera", name))
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_rotation(UnitQuaternion::from_axis_angle(&Vector3::y_axis(), PI))
+                    .build(),
+            ),
+    )
+    .with_projection(projection)
+    .build(graph)
+}
+
+/// Creates a light that is attached to a glTF node. glTF lights shine along -Z axis, while the
+/// engine lights shine along -Y axis, so the light is rotated around X axis.
+fn convert_light(
+    light: gltf::khr_lights_punctual::Light,
+    name: &str,
+    graph: &mut Graph,
+) -> Handle<Node> {
+    let [r, g, b] = light.color();
+    let base_light = BaseLightBuilder::new(
+        BaseBuilder::new()
+            .with_name(format!("{}_Light", name))
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_rotation(UnitQuaternion::from_axis_angle(
+                        &Vector3::x_axis(),
+                        FRAC_PI_2,
+                    ))
+                    .build(),
+            ),
+    )
+    .with_color(Color::opaque(
+        (r * 255.0) as u8,
+        (g * 255.0) as u8,
+        (b * 255.0) as u8,
+    ));
+
+    // glTF lights have infinite range by default, use some reasonable distance instead.
+    let range = light.range().unwrap_or(10.0);
+
+    match light.kind() {
+        gltf::khr_lights_punctual::Kind::Directional => {
+            DirectionalLightBuilder::new(base_light).build(graph)
+        }
+        gltf::khr_lights_punctual::Kind::Point => PointLightBuilder::new(base_light)
+            .with_radius(range)
+            .build(graph),
+        gltf::khr_lights_punctual::Kind::Spot {
+            inner_cone_angle,
+            outer_cone_angle,
+        } => SpotLightBuilder::new(base_light)
+            .with_distance(range)
+            // glTF stores half-angles.
+            .with_hotspot_cone_angle(2.0 * inner_cone_angle)
+            .with_falloff_angle_delta(2.0 * (outer_cone_angle - inner_cone_angle))
+            .build(graph),
+    }
+}
+
+///
+/// Converts glTF document to native engine representation.
+///
+async fn convert(
+    gltf: &gltf::Gltf,
+    buffers: &[Vec<u8>],
+    resource_manager: ResourceManager,
+    scene: &mut Scene,
+    model_path: &Path,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let root = scene.graph.get_root();
+
+    // Inverse bind pose matrices are stored per-bone in the engine.
+    let mut inv_bind_poses = FxHashMap::default();
+    for skin in gltf.skins() {
+        let reader = skin.reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()));
+        match reader.read_inverse_bind_matrices() {
+            Some(matrices) => {
+                for (joint, matrix) in skin.joints().zip(matrices) {
+                    inv_bind_poses.insert(joint.index(), Matrix4::from(matrix));
+                }
+            }
+            None => {
+                for joint in skin.joints() {
+                    inv_bind_poses.insert(joint.index(), Matrix4::identity());
+                }
+            }
+        }
+    }
+
+    let mut materials =
+        MaterialConverter::new(buffers, resource_manager, model_path, model_import_options);
+
+    let mut node_map = Vec::new();
+    for node in gltf.nodes() {
+        let base = convert_node_to_base(
+            &node,
+            inv_bind_poses
+                .get(&node.index())
+                .cloned()
+                .unwrap_or_else(Matrix4::identity),
+        );
+
+        let handle = if let Some(mesh) = node.mesh() {
+            let surfaces =
+                convert_mesh(mesh, buffers, node.skin().is_some(), &mut materials).await?;
+            MeshBuilder::new(base)
+                .with_surfaces(surfaces)
+                .build(&mut scene.graph)
+        } else {
+            PivotBuilder::new(base).build(&mut scene.graph)
+        };
+
+        let name = node_name(&node);
+        if let Some(camera) = node.camera() {
+            let camera = convert_camera(camera, &name, &mut scene.graph);
+            scene.graph.link_nodes(camera, handle);
+        }
+        if let Some(light) = node.light() {
+            let light = convert_light(light, &name, &mut scene.graph);
+            scene.graph.link_nodes(light, handle);
+        }
+
+        node_map.push(handle);
+    }
+
+    // Link according to hierarchy, every node without a parent is attached to the root.
+    let mut has_parent = vec![false; node_map.len()];
+    for node in gltf.nodes() {
+        for child in node.children() {
+            let child_handle = *node_map
+                .get(child.index())
+                .ok_or(GltfError::IndexOutOfBounds)?;
+            scene.graph.link_nodes(child_handle, node_map[node.index()]);
+            has_parent[child.index()] = true;
+        }
+    }
+    for (&handle, has_parent) in node_map.iter().zip(has_parent) {
+        if !has_parent {
+            scene.graph.link_nodes(handle, root);
+        }
+    }
+
+    // Assign bones to skinned meshes. Joint indices of vertices are indices in the joints array
+    // of a skin, so the array can be used as is.
+    for node in gltf.nodes() {
+        if let Some(skin) = node.skin() {
+            let bones = skin
+                .joints()
+                .map(|joint| node_map[joint.index()])
+                .collect::<Vec<_>>();
+            if let Some(mesh) = scene.graph[node_map[node.index()]].cast_mut::<Mesh>() {
+                for surface in mesh.surfaces_mut() {
+                    surface.bones.set_value_silent(bones.clone());
+                }
+            }
+        }
+    }
+
+    let animations = convert_animations(gltf, buffers, &node_map);
+    // Do not create animation player if there's no animation content.
+    if !animations.is_empty() {
+        let mut animations_container = AnimationContainer::new();
+        for animation in animations {
+            animations_container.add(animation);
+        }
+        AnimationPlayerBuilder::new(BaseBuilder::new().with_name("AnimationPlayer"))
+            .with_animations(animations_container)
+            .build(&mut scene.graph);
+    }
+
+    scene.graph.update_hierarchical_data();
+
+    Ok(())
+}
+
+/// Tries to load and convert glTF (`.gltf` or `.glb`) from given path.
+///
+/// Normally you should never use this method, use resource manager to load models.
+pub async fn load_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    path: P,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let start_time = Instant::now();
+
+    Log::info(format!("Trying to load {:?}", path.as_ref()));
+
+    let data = io::load_file(path.as_ref()).await?;
+    let gltf = gltf::Gltf::from_slice(&data)?;
+    let buffers = load_buffers(&gltf, path.as_ref()).await?;
+
+    convert(
+        &gltf,
+        &buffers,
+        resource_manager,
+        scene,
+        path.as_ref(),
+        model_import_options,
+    )
+    .await?;
+
+    Log::info(format!(
+        "glTF {:?} loaded in {} ms",
+        path.as_ref(),
+        start_time.elapsed().as_millis()
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::futures::executor::block_on,
+        engine::resource_manager::ResourceManager,
+        resource::{
+            gltf::{convert_bone_indices, error::GltfError, load_to_scene},
+            model::ModelImportOptions,
+        },
+        scene::{
+            animation::AnimationPlayer,
+            camera::{Camera, Projection},
+            light::{directional::DirectionalLight, spot::SpotLight},
+            mesh::Mesh,
+            Scene,
+        },
+    };
+
+    fn load(path: &str) -> Scene {
+        let mut scene = Scene::new();
+        block_on(load_to_scene(
+            &mut scene,
+            ResourceManager::new(Default::default()),
+            path,
+            &ModelImportOptions::default(),
+        ))
+        .unwrap();
+        scene
+    }
+
+    #[test]
+    fn test_load_scene() {
+        let scene = load("test_data/gltf/scene.gltf");
+        let graph = &scene.graph;
+
+        let triangle = graph.find_by_name_from_root("Triangle");
+        assert!(triangle.is_some());
+        assert_eq!(graph[triangle].parent(), graph.get_root());
+        assert_eq!(
+            graph[triangle].global_position(),
+            crate::core::algebra::Vector3::new(1.0, 2.0, 3.0)
+        );
+        let mesh = graph[triangle].cast::<Mesh>().unwrap();
+        assert_eq!(mesh.surfaces().len(), 1);
+        let data = mesh.surfaces()[0].data();
+        let data = data.lock();
+        assert_eq!(data.vertex_buffer.vertex_count(), 3);
+        assert_eq!(data.geometry_buffer.len(), 1);
+
+        // Spot lamp is a child of the triangle.
+        let spot_lamp = graph.find_by_name_from_root("SpotLamp");
+        assert_eq!(graph[spot_lamp].parent(), triangle);
+        let spot = graph.find_by_name_from_root("SpotLamp_Light");
+        assert_eq!(graph[spot].parent(), spot_lamp);
+        let spot = graph[spot].cast::<SpotLight>().unwrap();
+        assert_eq!(spot.distance(), 5.0);
+        assert!((spot.hotspot_cone_angle() - 0.4).abs() < 0.0001);
+
+        let sun = graph.find_by_name_from_root("Sun_Light");
+        assert!(graph[sun].cast::<DirectionalLight>().is_some());
+
+        let camera = graph.find_by_name_from_root("MainCamera_Camera");
+        let camera = graph[camera].cast::<Camera>().unwrap();
+        match camera.projection() {
+            Projection::Perspective(perspective) => {
+                assert_eq!(perspective.fov, 1.0);
+                assert_eq!(perspective.z_near, 0.1);
+                assert_eq!(perspective.z_far, 100.0);
+            }
+            Projection::Orthographic(_) => panic!("Must be perspective projection!"),
+        }
+    }
+
+    #[test]
+    fn test_convert_bone_indices() {
+        assert_eq!(
+            convert_bone_indices([0, 1, 2, 255]).unwrap(),
+            [0u8, 1, 2, 255]
+        );
+        assert!(matches!(
+            convert_bone_indices([0, 256, 0, 0]),
+            Err(GltfError::TooManyJoints(256))
+        ));
+    }
+
+    #[test]
+    fn test_load_skinned_mesh_with_animation() {
+        let scene = load("test_data/gltf/skinned.gltf");
+        let graph = &scene.graph;
+
+        let root = graph.find_by_name_from_root("Root");
+        let bone = graph.find_by_name_from_root("Bone");
+        assert_eq!(graph[bone].parent(), root);
+
+        let body = graph.find_by_name_from_root("Body");
+        let mesh = graph[body].cast::<Mesh>().unwrap();
+        assert_eq!(mesh.surfaces()[0].bones(), &[root, bone]);
+        assert_eq!(
+            graph[bone].inv_bind_pose_transform().m24,
+            -0.5,
+            "Inverse bind pose must be taken from the skin"
+        );
+
+        let player = graph.find_by_name_from_root("AnimationPlayer");
+        let player = graph[player].cast::<AnimationPlayer>().unwrap();
+        let (_, animation) = player.animations().find_by_name_ref("Bend").unwrap();
+        assert_eq!(animation.tracks().len(), 1);
+        assert_eq!(animation.tracks()[0].target(), bone);
+        assert_eq!(animation.length(), 1.0);
+    }
+}
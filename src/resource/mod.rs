@@ -4,5 +4,7 @@
 
 pub mod curve;
 pub mod fbx;
+pub mod gltf;
 pub mod model;
+pub mod obj;
 pub mod texture;
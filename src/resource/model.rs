@@ -15,13 +15,18 @@
 //!
 //! # Supported formats
 //!
-//! Currently only FBX (common format in game industry for storing complex 3d models)
-//! and RGS (native Fyroxed format) formats are supported.
+//! Currently FBX (common format in game industry for storing complex 3d models), glTF 2.0
+//! (both `.gltf` and `.glb` containers), Wavefront OBJ (static meshes with MTL materials) and
+//! RGS (native Fyroxed format) formats are supported.
 
-use crate::animation::Animation;
+use crate::animation::{
+    retarget::{AnimationRetargeter, RetargetSettings},
+    Animation,
+};
 use crate::{
     asset::{define_new_resource, Resource, ResourceData},
     core::{
+        io,
         pool::Handle,
         reflect::prelude::*,
         variable::reset_inheritable_properties,
@@ -31,7 +36,11 @@ use crate::{
         resource_manager::{options::ImportOptions, ResourceManager},
         SerializationContext,
     },
-    resource::fbx::{self, error::FbxError},
+    resource::{
+        fbx::{self, error::FbxError},
+        gltf::{self, error::GltfError},
+        obj::{self, error::ObjError},
+    },
     scene::{
         animation::AnimationPlayer,
         graph::{map::NodeHandleMap, Graph},
@@ -48,6 +57,7 @@ use std::{
     sync::Arc,
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+use walkdir::WalkDir;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[repr(u32)]
@@ -203,6 +213,35 @@ impl Model {
         retargetted_animations
     }
 
+    /// Retargets animations from given model resource to a node hierarchy starting from `root` on a
+    /// given scene. Unlike [`Self::retarget_animations_directly`], it allows you to retarget animations
+    /// to skeletons with different bind poses, proportions and bone names. See
+    /// [`AnimationRetargeter`] docs for more info.
+    pub fn retarget_animations_with_settings(
+        &self,
+        root: Handle<Node>,
+        graph: &Graph,
+        settings: &RetargetSettings,
+    ) -> Vec<Animation> {
+        let data = self.data_ref();
+
+        let retargeter = AnimationRetargeter::new(
+            &data.scene.graph,
+            data.scene.graph.get_root(),
+            graph,
+            root,
+            settings,
+        );
+
+        data.scene
+            .graph
+            .linear_iter()
+            .filter_map(|node| node.query_component_ref::<AnimationPlayer>())
+            .flat_map(|player| player.animations().iter())
+            .map(|animation| retargeter.retarget(animation))
+            .collect()
+    }
+
     /// Tries to retarget animations from given model resource to a node hierarchy starting
     /// from `root` on a given scene. Unlike [`Self::retarget_animations_directly`], it automatically
     /// adds retargetted animations to the specified animation player in the hierarchy of given `root`.
@@ -343,6 +382,46 @@ impl MaterialSearchOptions {
     pub fn materials_directory<P: AsRef<Path>>(path: P) -> Self {
         Self::MaterialsDirectory(path.as_ref().to_path_buf())
     }
+
+    /// Tries to find a path to an external resource (usually a texture) referenced by a model
+    /// at `model_path`. `resource_path` is a path as it is stored in the model file. Returns
+    /// [`None`] if there is no such file.
+    pub(crate) async fn find_resource_path(
+        &self,
+        resource_path: &Path,
+        model_path: &Path,
+    ) -> Option<PathBuf> {
+        let filename = resource_path.file_name()?;
+
+        match self {
+            MaterialSearchOptions::MaterialsDirectory(ref directory) => {
+                Some(directory.join(filename))
+            }
+            MaterialSearchOptions::RecursiveUp => {
+                let mut path = model_path.to_owned();
+                while let Some(parent) = path.parent() {
+                    let candidate = parent.join(filename);
+                    if io::exists(&candidate).await {
+                        return Some(candidate);
+                    }
+                    path.pop();
+                }
+                None
+            }
+            MaterialSearchOptions::WorkingDirectory => {
+                for dir in WalkDir::new(".").into_iter().flatten() {
+                    if dir.path().is_dir() {
+                        let candidate = dir.path().join(filename);
+                        if candidate.exists() {
+                            return Some(candidate);
+                        }
+                    }
+                }
+                None
+            }
+            MaterialSearchOptions::UsePathDirectly => Some(resource_path.to_owned()),
+        }
+    }
 }
 
 /// A set of options that will be applied to a model resource when loading it from external source.
@@ -379,6 +458,10 @@ pub enum ModelLoadError {
     NotSupported(String),
     /// An error occurred while loading FBX file.
     Fbx(FbxError),
+    /// An error occurred while loading glTF file.
+    Gltf(GltfError),
+    /// An error occurred while loading OBJ file.
+    Obj(ObjError),
 }
 
 impl Display for ModelLoadError {
@@ -391,6 +474,8 @@ impl Display for ModelLoadError {
                 write!(f, "Model format is not supported: {v}")
             }
             ModelLoadError::Fbx(v) => v.fmt(f),
+            ModelLoadError::Gltf(v) => v.fmt(f),
+            ModelLoadError::Obj(v) => v.fmt(f),
         }
     }
 }
@@ -401,6 +486,18 @@ impl From<FbxError> for ModelLoadError {
     }
 }
 
+impl From<GltfError> for ModelLoadError {
+    fn from(gltf: GltfError) -> Self {
+        ModelLoadError::Gltf(gltf)
+    }
+}
+
+impl From<ObjError> for ModelLoadError {
+    fn from(obj: ObjError) -> Self {
+        ModelLoadError::Obj(obj)
+    }
+}
+
 impl From<VisitError> for ModelLoadError {
     fn from(e: VisitError) -> Self {
         ModelLoadError::Visit(e)
@@ -439,6 +536,38 @@ impl ModelData {
                 // any persistent unique ids, and we have to use names.
                 (scene, NodeMapping::UseNames)
             }
+            "gltf" | "glb" => {
+                let mut scene = Scene::new();
+                if let Some(filename) = path.as_ref().file_name() {
+                    let root = scene.graph.get_root();
+                    scene.graph[root].set_name(&filename.to_string_lossy());
+                }
+                gltf::load_to_scene(
+                    &mut scene,
+                    resource_manager,
+                    path.as_ref(),
+                    &model_import_options,
+                )
+                .await?;
+                // glTF nodes do not have persistent unique ids either, so names are used.
+                (scene, NodeMapping::UseNames)
+            }
+            "obj" => {
+                let mut scene = Scene::new();
+                if let Some(filename) = path.as_ref().file_name() {
+                    let root = scene.graph.get_root();
+                    scene.graph[root].set_name(&filename.to_string_lossy());
+                }
+                obj::load_to_scene(
+                    &mut scene,
+                    resource_manager,
+                    path.as_ref(),
+                    &model_import_options,
+                )
+                .await?;
+                // OBJ groups are identified by names only.
+                (scene, NodeMapping::UseNames)
+            }
             // Scene can be used directly as model resource. Such scenes can be created in
             // Fyroxed.
             "rgs" => (
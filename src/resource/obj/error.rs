@@ -0,0 +1,51 @@
+//! Contains all possible errors that can occur during OBJ loading and conversion.
+
+use crate::core::io::FileLoadError;
+use std::fmt::{Display, Formatter};
+
+/// See module docs.
+#[derive(Debug)]
+pub enum ObjError {
+    /// An error occurred while parsing OBJ document.
+    Obj(tobj::LoadError),
+
+    /// An error occurred during file loading.
+    FileLoadError(FileLoadError),
+
+    /// Arbitrary error that can have any meaning.
+    Custom(Box<String>),
+}
+
+impl Display for ObjError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::Obj(v) => {
+                write!(f, "OBJ: Unable to parse document: {v}")
+            }
+            ObjError::FileLoadError(v) => {
+                write!(f, "OBJ: File load error {v:?}.")
+            }
+            ObjError::Custom(v) => {
+                write!(f, "OBJ: An error has occurred: {v}")
+            }
+        }
+    }
+}
+
+impl From<tobj::LoadError> for ObjError {
+    fn from(err: tobj::LoadError) -> Self {
+        ObjError::Obj(err)
+    }
+}
+
+impl From<FileLoadError> for ObjError {
+    fn from(err: FileLoadError) -> Self {
+        ObjError::FileLoadError(err)
+    }
+}
+
+impl From<String> for ObjError {
+    fn from(err: String) -> Self {
+        ObjError::Custom(Box::new(err))
+    }
+}
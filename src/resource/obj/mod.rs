@@ -0,0 +1,343 @@
+//! Contains all methods to load and convert Wavefront OBJ model format.
+//!
+//! OBJ is a simple text format that stores static geometry only, materials are stored in
+//! companion `.mtl` files. Every object (`o`) or group (`g`) of the document becomes a separate
+//! mesh node, faces of the same group that use different materials become separate surfaces.
+//!
+//! Normally you should never use methods from this module directly, use resource manager to load
+//! models and create their instances.
+
+pub mod error;
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3, Vector4},
+        color::Color,
+        instant::Instant,
+        io,
+        math::TriangleDefinition,
+        sstorage::ImmutableString,
+        uuid::Uuid,
+    },
+    engine::resource_manager::ResourceManager,
+    material::{shader::SamplerFallback, Material, PropertyValue, SharedMaterial},
+    resource::{model::ModelImportOptions, obj::error::ObjError},
+    scene::{
+        base::{BaseBuilder, InstanceId},
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::{Surface, SurfaceData, SurfaceSharedData},
+            vertex::StaticVertex,
+            MeshBuilder,
+        },
+        Scene,
+    },
+    utils::log::Log,
+};
+use fxhash::FxHashMap;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+/// The parser requests material libraries synchronously, so they're loaded in advance.
+async fn load_material_libraries(document: &[u8], directory: &Path) -> FxHashMap<PathBuf, Vec<u8>> {
+    let mut libraries = FxHashMap::default();
+    for line in String::from_utf8_lossy(document).lines() {
+        let mut words = line.split_whitespace();
+        if let (Some("mtllib"), Some(library)) = (words.next(), words.next()) {
+            let library = PathBuf::from(library);
+            match io::load_file(directory.join(&library)).await {
+                Ok(data) => {
+                    libraries.insert(library, data);
+                }
+                Err(e) => Log::err(format!(
+                    "Unable to load material library {:?}. Reason: {:?}",
+                    library, e
+                )),
+            }
+        }
+    }
+    libraries
+}
+
+async fn find_texture_path(
+    texture: &str,
+    model_path: &Path,
+    model_import_options: &ModelImportOptions,
+) -> Option<PathBuf> {
+    // MTL stores paths relative to the document, try it first and only then fall back to the
+    // search options.
+    let candidate = model_path
+        .parent()
+        .map(|p| p.join(texture))
+        .unwrap_or_else(|| PathBuf::from(texture));
+    if io::exists(&candidate).await {
+        return Some(candidate);
+    }
+
+    let path = model_import_options
+        .material_search_options
+        .find_resource_path(Path::new(texture), model_path)
+        .await;
+    if path.is_none() {
+        Log::warn(format!(
+            "Unable to find a texture {:?} for 3D model {:?} using {:?} option!",
+            texture, model_path, model_import_options
+        ));
+    }
+    path
+}
+
+fn set_property(material: &mut Material, name: &str, value: PropertyValue) {
+    if let Err(e) = material.set_property(&ImmutableString::new(name), value) {
+        Log::err(format!(
+            "Unable to set material property {} for OBJ material! Reason: {:?}",
+            name, e
+        ));
+    }
+}
+
+async fn convert_material(
+    obj_material: &tobj::Material,
+    resource_manager: &ResourceManager,
+    model_path: &Path,
+    model_import_options: &ModelImportOptions,
+) -> SharedMaterial {
+    let mut material = Material::standard();
+
+    if let Some([r, g, b]) = obj_material.diffuse {
+        let a = obj_material.dissolve.unwrap_or(1.0);
+        set_property(
+            &mut material,
+            "diffuseColor",
+            PropertyValue::Color(Color::from_rgba(
+                (r * 255.0) as u8,
+                (g * 255.0) as u8,
+                (b * 255.0) as u8,
+                (a * 255.0) as u8,
+            )),
+        );
+    }
+
+    for (texture, property, fallback) in [
+        (
+            &obj_material.diffuse_texture,
+            "diffuseTexture",
+            SamplerFallback::White,
+        ),
+        (
+            &obj_material.normal_texture,
+            "normalTexture",
+            SamplerFallback::Normal,
+        ),
+    ] {
+        if let Some(texture) = texture {
+            if let Some(path) = find_texture_path(texture, model_path, model_import_options).await {
+                set_property(
+                    &mut material,
+                    property,
+                    PropertyValue::Sampler {
+                        value: Some(resource_manager.request_texture(path)),
+                        fallback,
+                    },
+                );
+            }
+        }
+    }
+
+    SharedMaterial::new(material)
+}
+
+fn convert_mesh(mesh: &tobj::Mesh) -> Result<SurfaceData, ObjError> {
+    let vertex_count = mesh.positions.len() / 3;
+    let has_normals = mesh.normals.len() == mesh.positions.len();
+    let has_tex_coords = mesh.texcoords.len() / 2 == vertex_count;
+
+    let vertices = (0..vertex_count)
+        .map(|i| StaticVertex {
+            position: Vector3::new(
+                mesh.positions[i * 3],
+                mesh.positions[i * 3 + 1],
+                mesh.positions[i * 3 + 2],
+            ),
+            // OBJ has origin of texture coordinates at bottom-left corner, flip it to match
+            // the engine.
+            tex_coord: if has_tex_coords {
+                Vector2::new(mesh.texcoords[i * 2], 1.0 - mesh.texcoords[i * 2 + 1])
+            } else {
+                Vector2::default()
+            },
+            normal: if has_normals {
+                Vector3::new(
+                    mesh.normals[i * 3],
+                    mesh.normals[i * 3 + 1],
+                    mesh.normals[i * 3 + 2],
+                )
+            } else {
+                Vector3::y()
+            },
+            tangent: Vector4::new(1.0, 0.0, 0.0, 1.0),
+        })
+        .collect::<Vec<_>>();
+
+    let triangles = mesh
+        .indices
+        .chunks_exact(3)
+        .map(|t| TriangleDefinition([t[0], t[1], t[2]]))
+        .collect::<Vec<_>>();
+
+    let vertex_buffer = VertexBuffer::new(vertices.len(), StaticVertex::layout(), vertices)
+        .map_err(|e| format!("Unable to create vertex buffer. Reason: {:?}", e))?;
+
+    let mut data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(triangles), false);
+
+    if !has_normals {
+        data.calculate_normals()
+            .map_err(|e| format!("Unable to calculate normals. Reason: {:?}", e))?;
+    }
+    data.calculate_tangents()
+        .map_err(|e| format!("Unable to calculate tangents. Reason: {:?}", e))?;
+
+    Ok(data)
+}
+
+/// Tries to load and convert OBJ (and its material libraries) from given path.
+///
+/// Normally you should never use this method, use resource manager to load models.
+pub async fn load_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    path: P,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), ObjError> {
+    let start_time = Instant::now();
+    let path = path.as_ref();
+
+    Log::info(format!("Trying to load {:?}", path));
+
+    let data = io::load_file(path).await?;
+    let libraries =
+        load_material_libraries(&data, path.parent().unwrap_or_else(|| Path::new(""))).await;
+    let (models, materials) =
+        tobj::load_obj_buf(&mut Cursor::new(data), &tobj::GPU_LOAD_OPTIONS, |library| {
+            match libraries.get(library) {
+                Some(data) => tobj::load_mtl_buf(&mut Cursor::new(data)),
+                None => Err(tobj::LoadError::OpenFileFailed),
+            }
+        })?;
+
+    let materials = match materials {
+        Ok(materials) => materials,
+        Err(e) => {
+            Log::warn(format!(
+                "Unable to load materials of {:?}, default material will be used. Reason: {}",
+                path, e
+            ));
+            Vec::new()
+        }
+    };
+
+    let mut shared_materials = Vec::with_capacity(materials.len());
+    for material in materials.iter() {
+        shared_materials
+            .push(convert_material(material, &resource_manager, path, model_import_options).await);
+    }
+
+    // A group is split into multiple models by the parser when it uses multiple materials,
+    // gather them back so every group will become a single node.
+    let mut groups: Vec<(String, Vec<&tobj::Model>)> = Vec::new();
+    for model in models.iter() {
+        match groups.iter_mut().find(|(name, _)| name == &model.name) {
+            Some((_, group)) => group.push(model),
+            None => groups.push((model.name.clone(), vec![model])),
+        }
+    }
+
+    let root = scene.graph.get_root();
+    for (name, group) in groups {
+        let mut surfaces = Vec::new();
+        for model in group {
+            let mut surface = Surface::new(SurfaceSharedData::new(convert_mesh(&model.mesh)?));
+            if let Some(material) = model
+                .mesh
+                .material_id
+                .and_then(|id| shared_materials.get(id))
+            {
+                surface.set_material(material.clone());
+            }
+            surfaces.push(surface);
+        }
+
+        // OBJ does not have persistent unique ids for groups, so use the same approach as FBX
+        // importer does - generate instance id from the name of the group.
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let mesh = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_name(name)
+                .with_instance_id(InstanceId(Uuid::from_u64_pair(hash, hash))),
+        )
+        .with_surfaces(surfaces)
+        .build(&mut scene.graph);
+        scene.graph.link_nodes(mesh, root);
+    }
+
+    scene.graph.update_hierarchical_data();
+
+    Log::info(format!(
+        "OBJ {:?} loaded in {} ms",
+        path,
+        start_time.elapsed().as_millis()
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{futures::executor::block_on, sstorage::ImmutableString},
+        engine::resource_manager::ResourceManager,
+        material::PropertyValue,
+        resource::{model::ModelImportOptions, obj::load_to_scene},
+        scene::{mesh::Mesh, Scene},
+    };
+
+    #[test]
+    fn test_load_groups_and_materials() {
+        let mut scene = Scene::new();
+        block_on(load_to_scene(
+            &mut scene,
+            ResourceManager::new(Default::default()),
+            "test_data/obj/groups.obj",
+            &ModelImportOptions::default(),
+        ))
+        .unwrap();
+        let graph = &scene.graph;
+
+        let floor = graph.find_by_name_from_root("Floor");
+        assert_eq!(graph[floor].parent(), graph.get_root());
+        let floor = graph[floor].cast::<Mesh>().unwrap();
+        assert_eq!(floor.surfaces().len(), 1);
+        assert_eq!(floor.surfaces()[0].data().lock().geometry_buffer.len(), 2);
+
+        // Box uses two materials, so it must have two surfaces.
+        let cube = graph.find_by_name_from_root("Box");
+        assert_eq!(graph[cube].parent(), graph.get_root());
+        let cube = graph[cube].cast::<Mesh>().unwrap();
+        assert_eq!(cube.surfaces().len(), 2);
+
+        let material = cube.surfaces()[0].material().lock();
+        match material.property_ref(&ImmutableString::new("diffuseColor")) {
+            Some(PropertyValue::Color(color)) => {
+                assert_eq!((color.r, color.g, color.b), (255, 0, 0));
+            }
+            _ => panic!("Diffuse color must be set!"),
+        }
+    }
+}
@@ -91,21 +91,36 @@ impl NodeTrait for AnimationBlendingStateMachine {
     }
 
     fn update(&mut self, context: &mut UpdateContext) -> bool {
-        if let Some(animation_player) = context
+        // Animations are temporarily moved out of the animation player, so IK solvers will be able
+        // to read the scene nodes.
+        let animations = context
             .nodes
             .try_borrow_mut(*self.animation_player)
             .and_then(|n| n.query_component_mut::<AnimationPlayer>())
-        {
-            // Prevent animation player to apply animation to scene nodes. The animation will
-            // do than instead.
-            animation_player.set_auto_apply(false);
+            .map(|animation_player| {
+                // Prevent animation player to apply animation to scene nodes. The animation will
+                // do than instead.
+                animation_player.set_auto_apply(false);
+
+                std::mem::take(animation_player.animations.get_value_mut_silent())
+            });
 
-            let pose = self
-                .machine
-                .get_value_mut_silent()
-                .evaluate_pose(&animation_player.animations, context.dt);
+        if let Some(animations) = animations {
+            let pose = self.machine.get_value_mut_silent().evaluate_pose_with_ik(
+                &animations,
+                context.nodes,
+                context.dt,
+            );
 
             pose.apply_internal(context.nodes);
+
+            if let Some(animation_player) = context
+                .nodes
+                .try_borrow_mut(*self.animation_player)
+                .and_then(|n| n.query_component_mut::<AnimationPlayer>())
+            {
+                *animation_player.animations.get_value_mut_silent() = animations;
+            }
         }
         self.base.update_lifetime(context.dt)
     }
@@ -263,6 +263,11 @@ pub enum ScriptMessage {
         /// Node handle.
         handle: Handle<Node>,
     },
+    /// A script of a dynamic plugin was hot reloaded.
+    HotReloadScript {
+        /// Node handle.
+        handle: Handle<Node>,
+    },
     /// A node script must be destroyed. It can happen if the script was replaced with some other
     /// or a node was destroyed.
     DestroyScript {
@@ -0,0 +1,719 @@
+//! Character controller is a kinematic rigid body that performs collide-and-slide movement of a character.
+//! See [`CharacterController`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    scene::{
+        base::Base,
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext, TypeUuidProvider, UpdateContext},
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+        Scene,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Step climbing settings of a character controller.
+#[derive(Copy, Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct CharacterAutostep {
+    /// Maximum height of a step that the character can climb.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub max_height: f32,
+    /// Minimum free width that must be available on top of a step to climb it.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub min_width: f32,
+    /// Whether the character can climb on dynamic rigid bodies or not.
+    pub include_dynamic_bodies: bool,
+}
+
+impl Default for CharacterAutostep {
+    fn default() -> Self {
+        Self {
+            max_height: 0.25,
+            min_width: 0.2,
+            include_dynamic_bodies: true,
+        }
+    }
+}
+
+/// A collision that was detected during the movement of a character controller.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharacterCollision {
+    /// A handle of the collider with which the character collided.
+    pub collider: Handle<Node>,
+    /// A part of the desired translation that was applied before the collision.
+    pub translation_applied: Vector3<f32>,
+    /// A part of the desired translation that remained after the collision.
+    pub translation_remaining: Vector3<f32>,
+    /// Time of impact of the collision.
+    pub toi: f32,
+    /// A point of contact on the collider in world coordinates.
+    pub position: Point3<f32>,
+    /// Normal of the collider at the contact point in world coordinates.
+    pub normal: Vector3<f32>,
+}
+
+/// Character controller is a kinematic rigid body that moves a character in a "collide-and-slide" manner. It is
+/// able to slide along walls, climb stairs, limit the slopes on which the character can walk and snap the
+/// character to the ground when it goes down the stairs or slopes. Character controller is a wrapper over the
+/// [`RigidBody`] with [`RigidBodyType::KinematicPositionBased`] type, so it has all the features of the rigid body
+/// and uses its collider(s) to perform collision detection. Shape of the character is defined by the first
+/// collider of the controller, usually it is a capsule.
+///
+/// # Movement
+///
+/// Character controller does not have any dynamic, it moves only when you ask it to. To move the controller, use
+/// [`CharacterController::move_by`] with a desired translation (in world coordinates) for the current frame, the
+/// controller will then find a corrected translation that does not penetrate other colliders during next update
+/// of the scene graph and move itself. It is up to you to apply gravity, jumping, etc.
+///
+/// After the update, you can check whether the character is on the ground or not using
+/// [`CharacterController::is_grounded`] and fetch the colliders with which the character collided during the
+/// movement using [`CharacterController::collisions`].
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector3, pool::Handle},
+///     scene::{
+///         base::BaseBuilder,
+///         character_controller::{CharacterController, CharacterControllerBuilder},
+///         collider::{ColliderBuilder, ColliderShape},
+///         graph::Graph,
+///         node::Node,
+///         rigidbody::RigidBodyBuilder,
+///     },
+/// };
+///
+/// fn create_character(graph: &mut Graph) -> Handle<Node> {
+///     CharacterControllerBuilder::new(RigidBodyBuilder::new(
+///         BaseBuilder::new().with_children(&[ColliderBuilder::new(BaseBuilder::new())
+///             .with_shape(ColliderShape::capsule_y(0.5, 0.3))
+///             .build(graph)]),
+///     ))
+///     .with_max_slope_climb_angle(50.0f32.to_radians())
+///     .build(graph)
+/// }
+///
+/// fn walk(character: &mut CharacterController, velocity: Vector3<f32>, dt: f32) {
+///     // Apply some gravity if the character is in the air.
+///     let gravity = if character.is_grounded() {
+///         Vector3::default()
+///     } else {
+///         Vector3::new(0.0, -9.81, 0.0)
+///     };
+///
+///     character.move_by((velocity + gravity).scale(dt));
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct CharacterController {
+    rigid_body: RigidBody,
+
+    #[reflect(setter = "set_up_vector")]
+    up: InheritableVariable<Vector3<f32>>,
+
+    #[reflect(min_value = 0.0, step = 0.001)]
+    #[reflect(setter = "set_offset")]
+    offset: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_slide")]
+    slide: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_autostep")]
+    autostep: InheritableVariable<Option<CharacterAutostep>>,
+
+    #[reflect(min_value = 0.0, max_value = 1.5708, step = 0.01)]
+    #[reflect(setter = "set_max_slope_climb_angle")]
+    max_slope_climb_angle: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, max_value = 1.5708, step = 0.01)]
+    #[reflect(setter = "set_min_slope_slide_angle")]
+    min_slope_slide_angle: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_snap_to_ground")]
+    snap_to_ground: InheritableVariable<Option<f32>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    desired_translation: Vector3<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    grounded: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collisions: Vec<CharacterCollision>,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterControllerBuilder::new(RigidBodyBuilder::new(Default::default()))
+            .build_character_controller()
+    }
+}
+
+impl Deref for CharacterController {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rigid_body
+    }
+}
+
+impl DerefMut for CharacterController {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rigid_body
+    }
+}
+
+impl TypeUuidProvider for CharacterController {
+    fn type_uuid() -> Uuid {
+        uuid!("8a9e5c02-6b4f-4d36-9f0e-0b27d0c6b1a4")
+    }
+}
+
+impl CharacterController {
+    /// Returns a reference to the rigid body of the controller.
+    pub fn rigid_body_ref(&self) -> &RigidBody {
+        &self.rigid_body
+    }
+
+    /// Returns a reference to the rigid body of the controller.
+    pub fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        &mut self.rigid_body
+    }
+
+    /// Sets new up vector of the character, it is used to detect ground and to climb steps. Default is Y axis.
+    pub fn set_up_vector(&mut self, up: Vector3<f32>) -> Vector3<f32> {
+        self.up
+            .set_value_and_mark_modified(up.try_normalize(f32::EPSILON).unwrap_or_else(Vector3::y))
+    }
+
+    /// Returns current up vector of the character.
+    pub fn up_vector(&self) -> Vector3<f32> {
+        *self.up
+    }
+
+    /// Sets a small gap that will be preserved between the character and other colliders. It is needed to
+    /// prevent numerical issues, it should be small but not zero.
+    pub fn set_offset(&mut self, offset: f32) -> f32 {
+        self.offset.set_value_and_mark_modified(offset.max(0.0))
+    }
+
+    /// Returns current offset of the character.
+    pub fn offset(&self) -> f32 {
+        *self.offset
+    }
+
+    /// Sets whether the character should slide along obstacles or stop when collides with them.
+    pub fn set_slide(&mut self, slide: bool) -> bool {
+        self.slide.set_value_and_mark_modified(slide)
+    }
+
+    /// Returns `true` if the character slides along obstacles, `false` - otherwise.
+    pub fn is_slide(&self) -> bool {
+        *self.slide
+    }
+
+    /// Sets step climbing settings. `None` disables step climbing.
+    pub fn set_autostep(
+        &mut self,
+        autostep: Option<CharacterAutostep>,
+    ) -> Option<CharacterAutostep> {
+        self.autostep.set_value_and_mark_modified(autostep)
+    }
+
+    /// Returns current step climbing settings.
+    pub fn autostep(&self) -> Option<CharacterAutostep> {
+        *self.autostep
+    }
+
+    /// Sets maximum angle (in radians) of slopes that the character can climb.
+    pub fn set_max_slope_climb_angle(&mut self, angle: f32) -> f32 {
+        self.max_slope_climb_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns maximum angle (in radians) of slopes that the character can climb.
+    pub fn max_slope_climb_angle(&self) -> f32 {
+        *self.max_slope_climb_angle
+    }
+
+    /// Sets minimum angle (in radians) of slopes on which the character will slide down automatically.
+    pub fn set_min_slope_slide_angle(&mut self, angle: f32) -> f32 {
+        self.min_slope_slide_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns minimum angle (in radians) of slopes on which the character will slide down automatically.
+    pub fn min_slope_slide_angle(&self) -> f32 {
+        *self.min_slope_slide_angle
+    }
+
+    /// Sets maximum distance to the ground at which the character will be snapped to it. It is useful to keep
+    /// the character on the ground when it goes down the stairs or slopes. `None` disables snapping.
+    pub fn set_snap_to_ground(&mut self, distance: Option<f32>) -> Option<f32> {
+        self.snap_to_ground.set_value_and_mark_modified(distance)
+    }
+
+    /// Returns maximum distance to the ground at which the character will be snapped to it.
+    pub fn snap_to_ground(&self) -> Option<f32> {
+        *self.snap_to_ground
+    }
+
+    /// Adds a desired translation (in world coordinates) to the character. The character will be moved during
+    /// next update of the scene graph. Desired translations are accumulated until the update.
+    pub fn move_by(&mut self, translation: Vector3<f32>) {
+        self.desired_translation += translation;
+    }
+
+    /// Returns desired translation that will be applied on next update.
+    pub fn desired_translation(&self) -> Vector3<f32> {
+        self.desired_translation
+    }
+
+    /// Returns `true` if the character was on the ground after the last movement.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Returns a list of collisions that were detected during the last movement.
+    pub fn collisions(&self) -> &[CharacterCollision] {
+        &self.collisions
+    }
+}
+
+impl NodeTrait for CharacterController {
+    crate::impl_query_component!(rigid_body: RigidBody);
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.rigid_body.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.rigid_body.world_bounding_box()
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.rigid_body.restore_resources(resource_manager);
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn clean_up(&mut self, graph: &mut Graph) {
+        self.rigid_body.clean_up(graph)
+    }
+
+    fn sync_native(&self, self_handle: Handle<Node>, context: &mut SyncContext) {
+        self.rigid_body.sync_native(self_handle, context)
+    }
+
+    fn sync_transform(&self, new_global_transform: &Matrix4<f32>, context: &mut SyncContext) {
+        self.rigid_body
+            .sync_transform(new_global_transform, context)
+    }
+
+    fn validate(&self, _scene: &Scene) -> Result<(), String> {
+        if self.rigid_body.body_type() != RigidBodyType::KinematicPositionBased {
+            Err(
+                "Body type of a character controller must be kinematic position-based, \
+            otherwise it will not move correctly!"
+                    .to_string(),
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) -> bool {
+        let desired_translation = std::mem::take(&mut self.desired_translation);
+
+        let mut collisions = std::mem::take(&mut self.collisions);
+        collisions.clear();
+        let movement =
+            context
+                .physics
+                .move_character(self, desired_translation, context.dt, &mut collisions);
+        self.collisions = collisions;
+
+        if let Some(movement) = movement {
+            self.grounded = movement.grounded;
+
+            // Translation is in world coordinates, so it must be transformed to local coordinates of the
+            // parent node.
+            let local_translation = context
+                .nodes
+                .try_borrow(self.parent())
+                .and_then(|p| p.global_transform().try_inverse())
+                .map(|m| m.transform_vector(&movement.translation))
+                .unwrap_or(movement.translation);
+
+            let position = **self.local_transform().position();
+            self.local_transform_mut()
+                .set_position(position + local_translation);
+        }
+
+        self.rigid_body.update(context)
+    }
+}
+
+/// Allows you to create character controller in declarative manner.
+pub struct CharacterControllerBuilder {
+    rigid_body_builder: RigidBodyBuilder,
+    up: Vector3<f32>,
+    offset: f32,
+    slide: bool,
+    autostep: Option<CharacterAutostep>,
+    max_slope_climb_angle: f32,
+    min_slope_slide_angle: f32,
+    snap_to_ground: Option<f32>,
+}
+
+impl CharacterControllerBuilder {
+    /// Creates new character controller builder. Body type of the rigid body will be forced to
+    /// [`RigidBodyType::KinematicPositionBased`].
+    pub fn new(rigid_body_builder: RigidBodyBuilder) -> Self {
+        Self {
+            rigid_body_builder,
+            up: Vector3::y(),
+            offset: 0.01,
+            slide: true,
+            autostep: Some(Default::default()),
+            max_slope_climb_angle: 45.0f32.to_radians(),
+            min_slope_slide_angle: 30.0f32.to_radians(),
+            snap_to_ground: Some(0.2),
+        }
+    }
+
+    /// Sets desired up vector.
+    pub fn with_up_vector(mut self, up: Vector3<f32>) -> Self {
+        self.up = up.try_normalize(f32::EPSILON).unwrap_or_else(Vector3::y);
+        self
+    }
+
+    /// Sets desired offset.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset.max(0.0);
+        self
+    }
+
+    /// Sets whether the character should slide along obstacles or not.
+    pub fn with_slide(mut self, slide: bool) -> Self {
+        self.slide = slide;
+        self
+    }
+
+    /// Sets desired step climbing settings.
+    pub fn with_autostep(mut self, autostep: Option<CharacterAutostep>) -> Self {
+        self.autostep = autostep;
+        self
+    }
+
+    /// Sets desired maximum angle (in radians) of slopes that the character can climb.
+    pub fn with_max_slope_climb_angle(mut self, angle: f32) -> Self {
+        self.max_slope_climb_angle = angle;
+        self
+    }
+
+    /// Sets desired minimum angle (in radians) of slopes on which the character will slide down.
+    pub fn with_min_slope_slide_angle(mut self, angle: f32) -> Self {
+        self.min_slope_slide_angle = angle;
+        self
+    }
+
+    /// Sets desired snap-to-ground distance.
+    pub fn with_snap_to_ground(mut self, distance: Option<f32>) -> Self {
+        self.snap_to_ground = distance;
+        self
+    }
+
+    /// Creates CharacterController node but does not add it to the graph.
+    pub fn build_character_controller(self) -> CharacterController {
+        CharacterController {
+            rigid_body: self
+                .rigid_body_builder
+                .with_body_type(RigidBodyType::KinematicPositionBased)
+                .build_rigid_body(),
+            up: self.up.into(),
+            offset: self.offset.into(),
+            slide: self.slide.into(),
+            autostep: self.autostep.into(),
+            max_slope_climb_angle: self.max_slope_climb_angle.into(),
+            min_slope_slide_angle: self.min_slope_slide_angle.into(),
+            snap_to_ground: self.snap_to_ground.into(),
+            desired_translation: Default::default(),
+            grounded: false,
+            collisions: Default::default(),
+        }
+    }
+
+    /// Creates CharacterController node but does not add it to the graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_character_controller())
+    }
+
+    /// Creates CharacterController node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::reflect::Reflect;
+    use crate::core::variable::try_inherit_properties;
+    use crate::{
+        core::{
+            algebra::{UnitQuaternion, Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            character_controller::{
+                CharacterAutostep, CharacterController, CharacterControllerBuilder,
+            },
+            collider::{ColliderBuilder, ColliderShape},
+            graph::Graph,
+            node::Node,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_character_controller_inheritance() {
+        let parent = CharacterControllerBuilder::new(RigidBodyBuilder::new(BaseBuilder::new()))
+            .with_up_vector(Vector3::x())
+            .with_offset(0.1)
+            .with_slide(false)
+            .with_autostep(Some(CharacterAutostep {
+                max_height: 0.5,
+                min_width: 0.1,
+                include_dynamic_bodies: false,
+            }))
+            .with_max_slope_climb_angle(1.0)
+            .with_min_slope_slide_angle(0.5)
+            .with_snap_to_ground(None)
+            .build_node();
+
+        let mut child = CharacterControllerBuilder::new(RigidBodyBuilder::new(BaseBuilder::new()))
+            .build_character_controller();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<CharacterController>().unwrap();
+
+        check_inheritable_properties_equality(&child, parent);
+    }
+
+    fn add_box(
+        graph: &mut Graph,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+        half_extents: Vector3<f32>,
+    ) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(
+                half_extents.x,
+                half_extents.y,
+                half_extents.z,
+            ))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .with_local_rotation(rotation)
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(graph)
+    }
+
+    // Creates a graph with flat ground, top of the ground is at zero height.
+    fn create_ground() -> Graph {
+        let mut graph = Graph::new();
+        add_box(
+            &mut graph,
+            Vector3::new(0.0, -0.5, 0.0),
+            UnitQuaternion::identity(),
+            Vector3::new(50.0, 0.5, 50.0),
+        );
+        graph
+    }
+
+    // Creates a box-shaped character (1.6 m tall) slightly above the ground at the origin.
+    fn add_character(graph: &mut Graph, autostep: Option<CharacterAutostep>) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.3, 0.8, 0.3))
+            .build(graph);
+        let character = CharacterControllerBuilder::new(RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.85, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        ))
+        .with_autostep(autostep)
+        .build(graph);
+
+        // Native bodies and colliders are created on the first update.
+        graph.update(Vector2::new(1.0, 1.0), 1.0 / 60.0);
+
+        character
+    }
+
+    // Moves the character by the given translation every frame.
+    fn walk(graph: &mut Graph, character: Handle<Node>, translation: Vector3<f32>, frames: usize) {
+        for _ in 0..frames {
+            graph[character]
+                .cast_mut::<CharacterController>()
+                .unwrap()
+                .move_by(translation);
+            graph.update(Vector2::new(1.0, 1.0), 1.0 / 60.0);
+        }
+    }
+
+    fn position(graph: &Graph, character: Handle<Node>) -> Vector3<f32> {
+        **graph[character].local_transform().position()
+    }
+
+    fn is_grounded(graph: &Graph, character: Handle<Node>) -> bool {
+        graph[character]
+            .cast::<CharacterController>()
+            .unwrap()
+            .is_grounded()
+    }
+
+    #[test]
+    fn test_character_controller_grounded() {
+        let mut graph = create_ground();
+        let character = add_character(&mut graph, None);
+
+        graph[character]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 2.0, 0.0));
+        walk(&mut graph, character, Vector3::new(0.0, -0.1, 0.0), 2);
+        assert!(!is_grounded(&graph, character));
+
+        // Fall on the ground, the character must stop on it.
+        walk(&mut graph, character, Vector3::new(0.0, -0.1, 0.0), 30);
+        assert!(is_grounded(&graph, character));
+        assert!((position(&graph, character).y - 0.8).abs() < 0.05);
+
+        // Jump.
+        walk(&mut graph, character, Vector3::new(0.0, 0.25, 0.0), 2);
+        assert!(!is_grounded(&graph, character));
+        assert!(position(&graph, character).y > 1.2);
+    }
+
+    #[test]
+    fn test_character_controller_wall_sliding() {
+        let mut graph = create_ground();
+        // The wall faces the character at x = 1.5.
+        let wall = add_box(
+            &mut graph,
+            Vector3::new(2.0, 5.0, 0.0),
+            UnitQuaternion::identity(),
+            Vector3::new(0.5, 5.0, 50.0),
+        );
+        let character = add_character(&mut graph, None);
+
+        // Move diagonally into the wall, the character must stop at the wall and slide along it.
+        let mut collided = false;
+        for _ in 0..30 {
+            walk(&mut graph, character, Vector3::new(0.1, 0.0, 0.1), 1);
+            collided |= graph[character]
+                .cast::<CharacterController>()
+                .unwrap()
+                .collisions()
+                .iter()
+                .any(|c| graph[c.collider].parent() == wall);
+        }
+
+        let position = position(&graph, character);
+        assert!(collided);
+        assert!(position.x > 1.1 && position.x < 1.2, "{}", position);
+        assert!((position.z - 3.0).abs() < 0.05, "{}", position);
+    }
+
+    fn walk_to_step(step_height: f32, autostep: Option<CharacterAutostep>) -> Vector3<f32> {
+        let mut graph = create_ground();
+        // Front face of the step is at x = 1.0.
+        add_box(
+            &mut graph,
+            Vector3::new(6.0, step_height * 0.5, 0.0),
+            UnitQuaternion::identity(),
+            Vector3::new(5.0, step_height * 0.5, 50.0),
+        );
+        let character = add_character(&mut graph, autostep);
+        walk(&mut graph, character, Vector3::new(0.05, -0.02, 0.0), 60);
+        position(&graph, character)
+    }
+
+    #[test]
+    fn test_character_controller_step_climbing() {
+        let autostep = Some(CharacterAutostep::default());
+
+        // Low step must be climbed.
+        let position = walk_to_step(0.2, autostep);
+        assert!(position.x > 1.5, "{}", position);
+        assert!(position.y > 0.95, "{}", position);
+
+        // But not without autostep.
+        let position = walk_to_step(0.2, None);
+        assert!(position.x < 0.75, "{}", position);
+        assert!(position.y < 0.85, "{}", position);
+
+        // High step blocks the character.
+        let position = walk_to_step(0.5, autostep);
+        assert!(position.x < 0.75, "{}", position);
+        assert!(position.y < 0.85, "{}", position);
+    }
+
+    fn walk_to_slope(angle: f32) -> Vector3<f32> {
+        let mut graph = create_ground();
+        // The slope starts at x = 1.0 and goes up along X axis.
+        let (sin, cos) = angle.sin_cos();
+        add_box(
+            &mut graph,
+            Vector3::new(1.0 + sin * 0.5, -cos * 0.5, 0.0),
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), angle),
+            Vector3::new(5.0, 0.5, 50.0),
+        );
+        let character = add_character(&mut graph, None);
+        walk(&mut graph, character, Vector3::new(0.05, 0.0, 0.0), 60);
+        position(&graph, character)
+    }
+
+    #[test]
+    fn test_character_controller_slope_limit() {
+        // Default maximum slope climb angle is 45 degrees.
+        let position = walk_to_slope(30.0f32.to_radians());
+        assert!(position.x > 1.5, "{}", position);
+        assert!(position.y > 1.2, "{}", position);
+
+        let position = walk_to_slope(60.0f32.to_radians());
+        assert!(position.x < 0.8, "{}", position);
+        assert!(position.y < 0.9, "{}", position);
+    }
+}
@@ -15,6 +15,7 @@ use crate::{
         base::{Base, BaseBuilder},
         dim2::rigidbody::RigidBody,
         graph::Graph,
+        joint::{JointAxisParams, JointMotor},
         node::{Node, NodeTrait, SyncContext, TypeUuidProvider},
         Scene,
     },
@@ -41,6 +42,13 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angles range for the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits_angles: Range<f32>,
+
+    /// Optional motor, that drives relative rotation of the attached bodies.
+    #[reflect(
+        description = "Optional motor, that drives relative rotation of the attached bodies."
+    )]
+    #[visit(optional)] // Backward compatibility
+    pub motor: Option<JointMotor>,
 }
 
 impl Default for BallJoint {
@@ -48,6 +56,7 @@ impl Default for BallJoint {
         Self {
             limits_enabled: false,
             limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: None,
         }
     }
 }
@@ -72,6 +81,13 @@ pub struct PrismaticJoint {
     #[reflect(description = "Allowed linear distance range along local X axis of the joint.")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Optional motor, that drives relative movement of the attached bodies along local X axis of the joint.
+    #[reflect(
+        description = "Optional motor, that drives relative movement of the attached bodies along local X axis of the joint."
+    )]
+    #[visit(optional)] // Backward compatibility
+    pub motor: Option<JointMotor>,
 }
 
 impl Default for PrismaticJoint {
@@ -79,10 +95,45 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: None,
         }
     }
 }
 
+/// Rope joint limits maximum distance between anchors of two rigid bodies, but allows any other relative
+/// movement. The bodies can move closer to each other freely, but can't move apart further than the given
+/// distance. The real world example is a rope, a chain, etc.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between anchors of the attached bodies.
+    #[reflect(description = "Maximum distance between anchors of the attached bodies.")]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
+/// Generic joint allows you to configure every degree of freedom (two linear axes and one angular axis)
+/// of relative movement of two rigid bodies separately. Every axis could be locked, limited and driven
+/// by a motor.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect, Default)]
+pub struct GenericJoint {
+    /// Motion settings of local X axis of the joint.
+    #[reflect(description = "Motion settings of local X axis of the joint.")]
+    pub x: JointAxisParams,
+
+    /// Motion settings of local Y axis of the joint.
+    #[reflect(description = "Motion settings of local Y axis of the joint.")]
+    pub y: JointAxisParams,
+
+    /// Motion settings of relative rotation of the attached bodies.
+    #[reflect(description = "Motion settings of relative rotation of the attached bodies.")]
+    pub angular: JointAxisParams,
+}
+
 /// The exact kind of the joint.
 #[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
 pub enum JointParams {
@@ -92,6 +143,10 @@ pub enum JointParams {
     FixedJoint(FixedJoint),
     /// See [`PrismaticJoint`] for more info.
     PrismaticJoint(PrismaticJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`GenericJoint`] for more info.
+    GenericJoint(GenericJoint),
 }
 
 impl Default for JointParams {
@@ -372,9 +427,13 @@ impl JointBuilder {
 mod test {
     use crate::core::reflect::Reflect;
     use crate::core::variable::try_inherit_properties;
+    use crate::core::visitor::prelude::*;
     use crate::scene::{
         base::{test::check_inheritable_properties_equality, BaseBuilder},
-        dim2::joint::{BallJoint, Joint, JointBuilder, JointParams},
+        dim2::joint::{
+            BallJoint, GenericJoint, Joint, JointBuilder, JointParams, PrismaticJoint, RopeJoint,
+        },
+        joint::{JointAxisParams, JointMotor},
     };
 
     #[test]
@@ -392,4 +451,52 @@ mod test {
         check_inheritable_properties_equality(&child.base, &parent.base);
         check_inheritable_properties_equality(&child, parent);
     }
+
+    fn check_visit_round_trip(params: JointParams) {
+        let mut visitor = Visitor::new();
+        params.clone().visit("Params", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = JointParams::default();
+        loaded.visit("Params", &mut visitor).unwrap();
+
+        assert_eq!(loaded, params);
+    }
+
+    #[test]
+    fn test_joint_2d_params_visit() {
+        let motor = JointMotor {
+            target_velocity: 1.0,
+            target_position: 2.0,
+            stiffness: 3.0,
+            damping: 4.0,
+            max_force: 5.0,
+        };
+
+        check_visit_round_trip(JointParams::BallJoint(BallJoint {
+            limits_enabled: true,
+            limits_angles: -1.0..1.0,
+            motor: Some(motor.clone()),
+        }));
+        check_visit_round_trip(JointParams::PrismaticJoint(PrismaticJoint {
+            limits_enabled: true,
+            limits: -1.0..1.0,
+            motor: Some(motor.clone()),
+        }));
+        check_visit_round_trip(JointParams::RopeJoint(RopeJoint { max_distance: 2.5 }));
+        check_visit_round_trip(JointParams::GenericJoint(GenericJoint {
+            x: JointAxisParams::locked(),
+            y: JointAxisParams {
+                locked: false,
+                limits_enabled: true,
+                limits: -2.0..3.0,
+                motor: Some(motor.clone()),
+            },
+            angular: JointAxisParams {
+                motor: Some(motor),
+                ..Default::default()
+            },
+        }));
+    }
 }
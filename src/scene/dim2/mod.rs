@@ -1,6 +1,7 @@
 //! The module contains 2D scene nodes and physics. Despite the naming, scene nodes are still 3D
 //! but physics simulation is in true 2D.
 
+pub mod character_controller;
 pub mod collider;
 pub mod joint;
 pub mod physics;
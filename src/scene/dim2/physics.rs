@@ -4,7 +4,7 @@ use crate::{
     core::{
         algebra::{
             Isometry2, Isometry3, Matrix4, Point2, Rotation3, Translation2, Translation3,
-            UnitComplex, UnitQuaternion, Vector2, Vector3,
+            UnitComplex, UnitQuaternion, UnitVector2, Vector2, Vector3,
         },
         arrayvec::ArrayVec,
         instant,
@@ -22,7 +22,10 @@ use crate::{
         debug::SceneDrawingContext,
         dim2::{self, collider::ColliderShape, joint::JointParams, rigidbody::ApplyAction},
         graph::{
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                CollisionEvent, CollisionEventKind, FeatureId, IntegrationParameters,
+                PhysicsPerformanceStatistics, ShapeCastStatus,
+            },
             NodePool,
         },
         node::{Node, NodeTrait},
@@ -30,6 +33,10 @@ use crate::{
     utils::log::{Log, MessageKind},
 };
 use rapier2d::{
+    control::{
+        CharacterAutostep, CharacterLength, EffectiveCharacterMovement,
+        KinematicCharacterController,
+    },
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
         IslandManager, JointAxesMask, JointAxis, MultibodyJointHandle, MultibodyJointSet,
@@ -40,10 +47,13 @@ use rapier2d::{
         BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
+    pipeline::{
+        ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter,
+        QueryPipeline,
+    },
 };
 use std::{
-    cell::RefCell,
+    cell::{RefCell, RefMut},
     cmp::Ordering,
     fmt::{Debug, Formatter},
     hash::Hash,
@@ -145,6 +155,108 @@ pub struct RayCastOptions {
     pub sort_results: bool,
 }
 
+/// A set of options for the shape cast.
+pub struct ShapeCastOptions {
+    /// A shape to cast. Triangle meshes and height fields are not supported.
+    pub shape: ColliderShape,
+
+    /// Initial position of the shape in world coordinates.
+    pub shape_position: Isometry2<f32>,
+
+    /// Linear velocity of the shape. Can be non-normalized, time of impact is measured in units of the
+    /// length of the velocity.
+    pub shape_velocity: Vector2<f32>,
+
+    /// Maximum time of impact.
+    pub max_toi: f32,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// If `true`, the cast will stop immediately if the shape is penetrating a collider at its initial
+    /// position. If `false`, penetrating colliders will be ignored if the shape is moving away from them.
+    pub stop_at_penetration: bool,
+}
+
+// Collects collision events during a simulation step. The events are converted to the engine
+// representation right after the step.
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<rapier2d::geometry::CollisionEvent>>,
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: rapier2d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier2d::geometry::ContactPair>,
+    ) {
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier2d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
+impl From<rapier2d::parry::query::TOIStatus> for ShapeCastStatus {
+    fn from(v: rapier2d::parry::query::TOIStatus) -> Self {
+        match v {
+            rapier2d::parry::query::TOIStatus::OutOfIterations => Self::OutOfIterations,
+            rapier2d::parry::query::TOIStatus::Converged => Self::Converged,
+            rapier2d::parry::query::TOIStatus::Failed => Self::Failed,
+            rapier2d::parry::query::TOIStatus::Penetrating => Self::Penetrating,
+        }
+    }
+}
+
+/// A shape cast result.
+#[derive(Debug, Clone)]
+pub struct ShapeCastResult {
+    /// A handle of the first collider hit by the shape.
+    pub collider: Handle<Node>,
+
+    /// Time of impact. Position of the shape at the time of impact is `shape_position.translation +
+    /// shape_velocity * toi`.
+    pub toi: f32,
+
+    /// Closest point on the collider at the time of impact in world coordinates.
+    pub witness1: Point2<f32>,
+
+    /// Closest point on the cast shape at the time of impact in world coordinates.
+    pub witness2: Point2<f32>,
+
+    /// Normal of the collider at `witness1` in world coordinates.
+    pub normal1: Vector2<f32>,
+
+    /// Normal of the cast shape at `witness2` in world coordinates.
+    pub normal2: Vector2<f32>,
+
+    /// Status of the computation.
+    pub status: ShapeCastStatus,
+}
+
+/// A point projection result.
+#[derive(Debug, Clone)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// Whether the point is inside the collider or not.
+    pub is_inside: bool,
+
+    /// Projection of the point on the collider in world coordinates.
+    pub point: Point2<f32>,
+}
+
 /// Data of the contact.
 pub struct ContactData {
     /// The contact point in the local-space of the first shape.
@@ -208,6 +320,31 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &scene::joint::JointMotor) {
+    joint
+        .set_motor(
+            axis,
+            motor.target_position,
+            motor.target_velocity,
+            motor.stiffness,
+            motor.damping,
+        )
+        .set_motor_max_force(axis, motor.max_force);
+}
+
+fn set_joint_axis_params(
+    joint: &mut GenericJoint,
+    axis: JointAxis,
+    params: &scene::joint::JointAxisParams,
+) {
+    if params.limits_enabled {
+        joint.set_limits(axis, [params.limits.start, params.limits.end]);
+    }
+    if let Some(motor) = params.motor.as_ref() {
+        set_joint_motor(joint, axis, motor);
+    }
+}
+
 fn convert_joint_params(
     params: scene::dim2::joint::JointParams,
     local_frame1: Isometry2<f32>,
@@ -217,12 +354,27 @@ fn convert_joint_params(
         JointParams::BallJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
+        JointParams::RopeJoint(_) => JointAxesMask::empty(),
+        JointParams::GenericJoint(ref v) => [
+            (&v.x, JointAxesMask::X),
+            (&v.y, JointAxesMask::Y),
+            (&v.angular, JointAxesMask::ANG_X),
+        ]
+        .into_iter()
+        .filter(|(axis, _)| axis.locked)
+        .fold(JointAxesMask::empty(), |mask, (_, axis_mask)| {
+            mask | axis_mask
+        }),
     };
 
-    let mut joint = GenericJointBuilder::new(locked_axis)
+    let mut builder = GenericJointBuilder::new(locked_axis)
         .local_frame1(local_frame1)
-        .local_frame2(local_frame2)
-        .build();
+        .local_frame2(local_frame2);
+    if let JointParams::RopeJoint(_) = params {
+        // Distance between anchors is limited along a coupled linear axis.
+        builder = builder.coupled_axes(JointAxesMask::LIN_AXES);
+    }
+    let mut joint = builder.build();
 
     match params {
         scene::dim2::joint::JointParams::BallJoint(v) => {
@@ -232,12 +384,32 @@ fn convert_joint_params(
                     [v.limits_angles.start, v.limits_angles.end],
                 );
             }
+            if let Some(motor) = v.motor.as_ref() {
+                set_joint_motor(&mut joint, JointAxis::AngX, motor);
+            }
         }
         scene::dim2::joint::JointParams::FixedJoint(_) => {}
         scene::dim2::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            if let Some(motor) = v.motor.as_ref() {
+                set_joint_motor(&mut joint, JointAxis::X, motor);
+            }
+        }
+        scene::dim2::joint::JointParams::RopeJoint(v) => {
+            joint.set_limits(JointAxis::X, [0.0, v.max_distance]);
+        }
+        scene::dim2::joint::JointParams::GenericJoint(v) => {
+            for (params, axis) in [
+                (&v.x, JointAxis::X),
+                (&v.y, JointAxis::Y),
+                (&v.angular, JointAxis::AngX),
+            ] {
+                if !params.locked {
+                    set_joint_axis_params(&mut joint, axis, params);
+                }
+            }
         }
     }
 
@@ -343,7 +515,11 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
+    // Collision events of the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
@@ -380,6 +556,13 @@ fn u32_to_group(v: u32) -> rapier2d::geometry::Group {
     rapier2d::geometry::Group::from_bits(v).unwrap_or_else(rapier2d::geometry::Group::all)
 }
 
+fn query_filter<'a>(groups: collider::InteractionGroups) -> QueryFilter<'a> {
+    QueryFilter::new().groups(InteractionGroups::new(
+        u32_to_group(groups.memberships.0),
+        u32_to_group(groups.filter.0),
+    ))
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(crate) fn new() -> Self {
@@ -408,7 +591,8 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -418,6 +602,8 @@ impl PhysicsWorld {
     pub(crate) fn update(&mut self, dt: f32) {
         let time = instant::Instant::now();
 
+        self.collision_events.clear();
+
         if self.enabled {
             let integration_parameters = rapier2d::dynamics::IntegrationParameters {
                 dt: self.integration_parameters.dt.unwrap_or(dt),
@@ -458,13 +644,72 @@ impl PhysicsWorld {
                 &mut self.multibody_joints.set,
                 &mut self.ccd_solver,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
+
+            let events = std::mem::take(&mut *self.event_handler.events.lock());
+            self.collision_events = events
+                .into_iter()
+                .map(|event| self.convert_collision_event(event, integration_parameters.dt))
+                .collect();
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    fn convert_collision_event(
+        &self,
+        event: rapier2d::geometry::CollisionEvent,
+        dt: f32,
+    ) -> CollisionEvent {
+        let kind = match (event.started(), event.sensor()) {
+            (true, false) => CollisionEventKind::ContactStarted,
+            (false, false) => CollisionEventKind::ContactStopped,
+            (true, true) => CollisionEventKind::IntersectionStarted,
+            (false, true) => CollisionEventKind::IntersectionStopped,
+        };
+
+        let mut total_force = Vector2::default();
+        let mut max_force_magnitude = 0.0f32;
+        if kind == CollisionEventKind::ContactStarted && dt > 0.0 {
+            if let Some(pair) = self
+                .narrow_phase
+                .contact_pair(event.collider1(), event.collider2())
+            {
+                for manifold in pair.manifolds.iter() {
+                    for point in manifold.points.iter() {
+                        total_force += manifold.data.normal.scale(point.data.impulse / dt);
+                        max_force_magnitude = max_force_magnitude.max(point.data.impulse / dt);
+                    }
+                }
+            }
+        }
+
+        CollisionEvent {
+            kind,
+            collider1: self
+                .colliders
+                .map
+                .value_of(&event.collider1())
+                .cloned()
+                .unwrap_or_default(),
+            collider2: self
+                .colliders
+                .map
+                .value_of(&event.collider2())
+                .cloned()
+                .unwrap_or_default(),
+            total_force_magnitude: total_force.norm(),
+            max_force_magnitude,
+        }
+    }
+
+    /// Returns collision events produced during the last simulation step. The list is cleared
+    /// at the beginning of every step.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     pub(crate) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
@@ -541,10 +786,7 @@ impl PhysicsWorld {
         );
     }
 
-    /// Casts a ray with given options.
-    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
-        let time = instant::Instant::now();
-
+    fn updated_query(&self) -> RefMut<QueryPipeline> {
         let mut query = self.query.borrow_mut();
 
         // TODO: Ideally this must be called once per frame, but it seems to be impossible because
@@ -554,6 +796,19 @@ impl PhysicsWorld {
         // of the frame.
         query.update(&self.islands, &self.bodies.set, &self.colliders.set);
 
+        query
+    }
+
+    fn collider_handle(&self, handle: &ColliderHandle) -> Handle<Node> {
+        self.colliders.map.value_of(handle).cloned().unwrap()
+    }
+
+    /// Casts a ray with given options.
+    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
+        let time = instant::Instant::now();
+
+        let query = self.updated_query();
+
         query_buffer.clear();
         let ray = Ray::new(
             opts.ray_origin,
@@ -567,13 +822,10 @@ impl PhysicsWorld {
             &ray,
             opts.max_len,
             true,
-            QueryFilter::new().groups(InteractionGroups::new(
-                u32_to_group(opts.groups.memberships.0),
-                u32_to_group(opts.groups.filter.0),
-            )),
+            query_filter(opts.groups),
             |handle, intersection| {
                 query_buffer.push(Intersection {
-                    collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
+                    collider: self.collider_handle(&handle),
                     normal: intersection.normal,
                     position: ray.point_at(intersection.toi),
                     feature: intersection.feature.into(),
@@ -599,6 +851,179 @@ impl PhysicsWorld {
         );
     }
 
+    /// Casts a shape with given options and returns the first collider hit by the shape. Could be used, for
+    /// example, to check whether a character fits in some place.
+    pub fn cast_shape(&self, opts: ShapeCastOptions) -> Option<ShapeCastResult> {
+        let shape = collider_shape_into_native_shape(&opts.shape)?;
+
+        let query = self.updated_query();
+
+        query
+            .cast_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.shape_position,
+                &opts.shape_velocity,
+                &*shape,
+                opts.max_toi,
+                opts.stop_at_penetration,
+                query_filter(opts.groups),
+            )
+            .map(|(handle, toi)| {
+                // Second witness and normal are in local coordinates of the cast shape.
+                let shape_position = Isometry2::from_parts(
+                    Translation2::from(
+                        opts.shape_position.translation.vector + opts.shape_velocity * toi.toi,
+                    ),
+                    opts.shape_position.rotation,
+                );
+
+                ShapeCastResult {
+                    collider: self.collider_handle(&handle),
+                    toi: toi.toi,
+                    witness1: toi.witness1,
+                    witness2: shape_position * toi.witness2,
+                    normal1: toi.normal1.into_inner(),
+                    normal2: shape_position.rotation * toi.normal2.into_inner(),
+                    status: toi.status.into(),
+                }
+            })
+    }
+
+    /// Collects all colliders that intersect with given shape. Could be used, for example, to find all
+    /// colliders inside an explosion radius. Triangle meshes and height fields are not supported.
+    pub fn intersections_with_shape(
+        &self,
+        shape: &ColliderShape,
+        shape_position: &Isometry2<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let shape = match collider_shape_into_native_shape(shape) {
+            Some(shape) => shape,
+            None => return,
+        };
+
+        let query = self.updated_query();
+
+        query.intersections_with_shape(
+            &self.bodies.set,
+            &self.colliders.set,
+            shape_position,
+            &*shape,
+            query_filter(groups),
+            |handle| {
+                query_buffer.push(self.collider_handle(&handle));
+                true
+            },
+        );
+    }
+
+    /// Projects a point on the closest collider. If `solid` is `true`, points inside colliders will be
+    /// projected on themselves, otherwise they will be projected on the boundaries of the colliders.
+    pub fn project_point(
+        &self,
+        point: Point2<f32>,
+        solid: bool,
+        groups: collider::InteractionGroups,
+    ) -> Option<PointProjection> {
+        let query = self.updated_query();
+
+        query
+            .project_point(
+                &self.bodies.set,
+                &self.colliders.set,
+                &point,
+                solid,
+                query_filter(groups),
+            )
+            .map(|(handle, projection)| PointProjection {
+                collider: self.collider_handle(&handle),
+                is_inside: projection.is_inside,
+                point: projection.point,
+            })
+    }
+
+    /// Collects all colliders that contain given point.
+    pub fn intersections_with_point(
+        &self,
+        point: Point2<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let query = self.updated_query();
+
+        query.intersections_with_point(
+            &self.bodies.set,
+            &self.colliders.set,
+            &point,
+            query_filter(groups),
+            |handle| {
+                query_buffer.push(self.collider_handle(&handle));
+                true
+            },
+        );
+    }
+
+    pub(crate) fn move_character(
+        &self,
+        character: &dim2::character_controller::CharacterController,
+        desired_translation: Vector2<f32>,
+        dt: f32,
+        collisions: &mut Vec<dim2::character_controller::CharacterCollision>,
+    ) -> Option<EffectiveCharacterMovement> {
+        let body_handle = character.rigid_body_ref().native.get();
+        let body = self.bodies.set.get(body_handle)?;
+        // Shape of the character is defined by its first collider.
+        let collider = self.colliders.set.get(*body.colliders().first()?)?;
+
+        let controller = KinematicCharacterController {
+            up: UnitVector2::new_normalize(character.up_vector()),
+            offset: CharacterLength::Absolute(character.offset()),
+            slide: character.is_slide(),
+            autostep: character.autostep().map(|autostep| CharacterAutostep {
+                max_height: CharacterLength::Absolute(autostep.max_height),
+                min_width: CharacterLength::Absolute(autostep.min_width),
+                include_dynamic_bodies: autostep.include_dynamic_bodies,
+            }),
+            max_slope_climb_angle: character.max_slope_climb_angle(),
+            min_slope_slide_angle: character.min_slope_slide_angle(),
+            snap_to_ground: character.snap_to_ground().map(CharacterLength::Absolute),
+        };
+
+        let query = self.updated_query();
+
+        Some(
+            controller.move_shape(
+                dt,
+                &self.bodies.set,
+                &self.colliders.set,
+                &*query,
+                collider.shape(),
+                collider.position(),
+                desired_translation,
+                QueryFilter::new()
+                    .exclude_rigid_body(body_handle)
+                    .exclude_sensors()
+                    .groups(collider.collision_groups()),
+                |collision| {
+                    collisions.push(dim2::character_controller::CharacterCollision {
+                        collider: self.collider_handle(&collision.handle),
+                        translation_applied: collision.translation_applied,
+                        translation_remaining: collision.translation_remaining,
+                        toi: collision.toi.toi,
+                        position: collision.toi.witness1,
+                        normal: collision.toi.normal1.into_inner(),
+                    })
+                },
+            ),
+        )
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::dim2::rigidbody::RigidBody,
@@ -622,6 +1047,12 @@ impl PhysicsWorld {
         if self.enabled {
             if let Some(native) = self.bodies.set.get(rigid_body.native.get()) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    // Remember the pose before the step, it is used for interpolation.
+                    rigid_body.previous_pose = Some((
+                        **rigid_body.local_transform.position(),
+                        **rigid_body.local_transform.rotation(),
+                    ));
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
@@ -648,6 +1079,8 @@ impl PhysicsWorld {
                         .ang_vel
                         .set_value_with_flags(native.angvel(), VariableFlags::MODIFIED);
                     rigid_body.sleeping = native.is_sleeping();
+                } else {
+                    rigid_body.previous_pose = None;
                 }
             }
         }
@@ -840,7 +1273,7 @@ impl PhysicsWorld {
             }
         } else if let Some(parent_body) = nodes
             .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>())
+            .and_then(|n| n.query_component_ref::<dim2::rigidbody::RigidBody>())
         {
             if parent_body.native.get() != RigidBodyHandle::invalid() {
                 let rigid_body_native = parent_body.native.get();
@@ -866,7 +1299,8 @@ impl PhysicsWorld {
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(ActiveEvents::COLLISION_EVENTS);
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -899,7 +1333,7 @@ impl PhysicsWorld {
             joint.body1.try_sync_model(|v| {
                 if let Some(rigid_body_node) = nodes
                     .try_borrow(v)
-                    .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>())
+                    .and_then(|n| n.query_component_ref::<dim2::rigidbody::RigidBody>())
                 {
                     native.body1 = rigid_body_node.native.get();
                 }
@@ -907,7 +1341,7 @@ impl PhysicsWorld {
             joint.body2.try_sync_model(|v| {
                 if let Some(rigid_body_node) = nodes
                     .try_borrow(v)
-                    .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>())
+                    .and_then(|n| n.query_component_ref::<dim2::rigidbody::RigidBody>())
                 {
                     native.body2 = rigid_body_node.native.get();
                 }
@@ -944,10 +1378,10 @@ impl PhysicsWorld {
             if let (Some(body1), Some(body2)) = (
                 nodes
                     .try_borrow(body1_handle)
-                    .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>()),
+                    .and_then(|n| n.query_component_ref::<dim2::rigidbody::RigidBody>()),
                 nodes
                     .try_borrow(body2_handle)
-                    .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>()),
+                    .and_then(|n| n.query_component_ref::<dim2::rigidbody::RigidBody>()),
             ) {
                 // Calculate local frames first.
                 let (local_frame1, local_frame2) = calculate_local_frames(joint, body1, body2);
@@ -1064,3 +1498,243 @@ impl Debug for PhysicsWorld {
         write!(f, "PhysicsWorld")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Isometry2, Point2, Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            collider::InteractionGroups,
+            dim2::{
+                collider::{ColliderBuilder, ColliderShape, GeometrySource},
+                joint::{GenericJoint, Joint, JointBuilder, JointParams, PrismaticJoint},
+                physics::ShapeCastOptions,
+                rigidbody::RigidBodyBuilder,
+            },
+            graph::Graph,
+            joint::{JointAxisParams, JointMotor},
+            node::Node,
+            rigidbody::RigidBodyType,
+            transform::TransformBuilder,
+        },
+    };
+    use rapier2d::prelude::{JointAxesMask, JointAxis};
+
+    // Creates a world with a single static 2x2 square at (5, 0).
+    fn make_world() -> (Graph, Handle<Node>) {
+        let mut graph = Graph::new();
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 1.0))
+            .build(&mut graph);
+
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(5.0, 0.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        // Native colliders are created and positioned during the update.
+        graph.update(Vector2::new(800.0, 600.0), 1.0);
+        graph.update(Vector2::new(800.0, 600.0), 1.0);
+
+        (graph, collider)
+    }
+
+    fn cast_options(shape: ColliderShape) -> ShapeCastOptions {
+        ShapeCastOptions {
+            shape,
+            shape_position: Isometry2::translation(0.0, 0.0),
+            shape_velocity: Vector2::new(1.0, 0.0),
+            max_toi: 100.0,
+            groups: InteractionGroups::default(),
+            stop_at_penetration: true,
+        }
+    }
+
+    fn assert_approx_eq(a: Point2<f32>, b: Point2<f32>) {
+        assert!((a - b).norm() < 0.001, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_cast_shape_2d() {
+        let (graph, collider) = make_world();
+
+        let result = graph
+            .physics2d
+            .cast_shape(cast_options(ColliderShape::ball(0.5)))
+            .unwrap();
+        assert_eq!(result.collider, collider);
+        // The ball touches the near side of the square (x = 4) when its center is at x = 3.5.
+        assert!((result.toi - 3.5).abs() < 0.001);
+        assert_approx_eq(result.witness1, Point2::new(4.0, 0.0));
+        assert_approx_eq(result.witness2, Point2::new(4.0, 0.0));
+        assert!((result.normal1 - Vector2::new(-1.0, 0.0)).norm() < 0.001);
+        assert!((result.normal2 - Vector2::new(1.0, 0.0)).norm() < 0.001);
+
+        // Moving away from the square.
+        let mut options = cast_options(ColliderShape::ball(0.5));
+        options.shape_velocity = Vector2::new(-1.0, 0.0);
+        assert!(graph.physics2d.cast_shape(options).is_none());
+    }
+
+    #[test]
+    fn test_intersections_with_shape_2d() {
+        let (graph, collider) = make_world();
+        let mut buffer = Vec::new();
+
+        graph.physics2d.intersections_with_shape(
+            &ColliderShape::ball(1.0),
+            &Isometry2::translation(3.5, 0.0),
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![collider]);
+
+        graph.physics2d.intersections_with_shape(
+            &ColliderShape::ball(1.0),
+            &Isometry2::translation(-5.0, 0.0),
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_point_queries_2d() {
+        let (graph, collider) = make_world();
+
+        let projection = graph
+            .physics2d
+            .project_point(Point2::origin(), true, InteractionGroups::default())
+            .unwrap();
+        assert_eq!(projection.collider, collider);
+        assert!(!projection.is_inside);
+        assert_approx_eq(projection.point, Point2::new(4.0, 0.0));
+
+        let inner_point = Point2::new(5.0, 0.5);
+        let projection = graph
+            .physics2d
+            .project_point(inner_point, true, InteractionGroups::default())
+            .unwrap();
+        assert!(projection.is_inside);
+        assert_approx_eq(projection.point, inner_point);
+
+        let mut buffer = Vec::new();
+        graph.physics2d.intersections_with_point(
+            inner_point,
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![collider]);
+
+        graph.physics2d.intersections_with_point(
+            Point2::origin(),
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_queries_with_unsupported_shapes_2d() {
+        let (graph, _) = make_world();
+
+        for shape in [
+            ColliderShape::trimesh(vec![GeometrySource(Handle::NONE)]),
+            ColliderShape::heightfield(GeometrySource(Handle::NONE)),
+        ] {
+            assert!(graph
+                .physics2d
+                .cast_shape(cast_options(shape.clone()))
+                .is_none());
+
+            let mut buffer = vec![Handle::NONE];
+            graph.physics2d.intersections_with_shape(
+                &shape,
+                &Isometry2::translation(5.0, 0.0),
+                InteractionGroups::default(),
+                &mut buffer,
+            );
+            assert!(buffer.is_empty());
+        }
+    }
+
+    fn native_joint(params: JointParams) -> rapier2d::dynamics::GenericJoint {
+        let mut graph = Graph::new();
+        let body1 = RigidBodyBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let body2 = RigidBodyBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let joint = JointBuilder::new(BaseBuilder::new())
+            .with_params(params)
+            .with_body1(body1)
+            .with_body2(body2)
+            .build(&mut graph);
+
+        // Native joint is created when both native bodies exist.
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0);
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0);
+
+        let native = graph[joint].cast::<Joint>().unwrap().native.get();
+        graph.physics2d.joints.set.get(native).unwrap().data
+    }
+
+    fn check_native_motor(
+        joint: &rapier2d::dynamics::GenericJoint,
+        axis: JointAxis,
+        motor: &JointMotor,
+    ) {
+        let native_motor = joint.motor(axis).unwrap();
+        assert_eq!(native_motor.target_vel, motor.target_velocity);
+        assert_eq!(native_motor.target_pos, motor.target_position);
+        assert_eq!(native_motor.stiffness, motor.stiffness);
+        assert_eq!(native_motor.damping, motor.damping);
+        assert_eq!(native_motor.max_force, motor.max_force);
+    }
+
+    #[test]
+    fn test_joint_motors_2d() {
+        let motor = JointMotor {
+            target_velocity: 1.0,
+            target_position: 2.0,
+            stiffness: 3.0,
+            damping: 4.0,
+            max_force: 5.0,
+        };
+
+        let joint = native_joint(JointParams::PrismaticJoint(PrismaticJoint {
+            limits_enabled: false,
+            limits: -1.0..1.0,
+            motor: Some(motor.clone()),
+        }));
+        assert_eq!(joint.locked_axes, JointAxesMask::LOCKED_PRISMATIC_AXES);
+        check_native_motor(&joint, JointAxis::X, &motor);
+
+        let joint = native_joint(JointParams::GenericJoint(GenericJoint {
+            x: JointAxisParams::locked(),
+            y: JointAxisParams {
+                limits_enabled: true,
+                limits: -2.0..3.0,
+                ..Default::default()
+            },
+            angular: JointAxisParams {
+                motor: Some(motor.clone()),
+                ..Default::default()
+            },
+        }));
+        assert_eq!(joint.locked_axes, JointAxesMask::X);
+        let limits = joint.limits(JointAxis::Y).unwrap();
+        assert_eq!([limits.min, limits.max], [-2.0, 3.0]);
+        check_native_motor(&joint, JointAxis::AngX, &motor);
+        assert!(joint.motor(JointAxis::Y).is_none());
+    }
+}
@@ -10,7 +10,7 @@
 //! [`RigidBody::set_can_sleep`] with `false` value.
 use crate::{
     core::{
-        algebra::{Matrix4, Vector2},
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
         math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
         parking_lot::Mutex,
         pool::Handle,
@@ -101,6 +101,14 @@ pub struct RigidBody {
     #[reflect(setter = "set_gravity_scale")]
     pub(crate) gravity_scale: InheritableVariable<f32>,
 
+    #[visit(optional)] // Backward compatibility
+    #[reflect(setter = "set_interpolation")]
+    pub(crate) interpolation: InheritableVariable<bool>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) previous_pose: Option<(Vector3<f32>, UnitQuaternion<f32>)>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) sleeping: bool,
@@ -137,6 +145,8 @@ impl Default for RigidBody {
             can_sleep: InheritableVariable::new(true),
             dominance: Default::default(),
             gravity_scale: InheritableVariable::new(1.0),
+            interpolation: Default::default(),
+            previous_pose: None,
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
         }
@@ -174,6 +184,8 @@ impl Clone for RigidBody {
             can_sleep: self.can_sleep.clone(),
             dominance: self.dominance.clone(),
             gravity_scale: self.gravity_scale.clone(),
+            interpolation: self.interpolation.clone(),
+            previous_pose: None,
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
@@ -300,6 +312,36 @@ impl RigidBody {
         *self.gravity_scale
     }
 
+    /// Enables or disables interpolation of the transform of the body between physics steps. Physics is
+    /// simulated at fixed rate, which is usually lower than the frame rate, so fast moving bodies may look
+    /// jerky. When interpolation is enabled, the body (and all its descendants) will be rendered at a position
+    /// between two last physics steps, which makes the movement smooth at the cost of a delay of one physics
+    /// step. Interpolation affects only dynamic bodies and it is performed by [`crate::engine::Engine::update_frame`].
+    pub fn set_interpolation(&mut self, enabled: bool) -> bool {
+        self.interpolation.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if interpolation of the transform of the body is enabled, `false` - otherwise.
+    pub fn is_interpolation_enabled(&self) -> bool {
+        *self.interpolation
+    }
+
+    /// Returns local transform matrix of the body interpolated between two last physics steps. `None` is
+    /// returned if the interpolation is disabled or there is nothing to interpolate.
+    pub(crate) fn interpolated_local_transform(&self, alpha: f32) -> Option<Matrix4<f32>> {
+        if !*self.interpolation {
+            return None;
+        }
+
+        let (previous_position, previous_rotation) = self.previous_pose?;
+
+        let mut transform = self.local_transform().clone();
+        let position = previous_position.lerp(&**transform.position(), alpha);
+        let rotation = previous_rotation.nlerp(&**transform.rotation(), alpha);
+        transform.set_position(position).set_rotation(rotation);
+        Some(transform.matrix())
+    }
+
     /// Sets dominance group of the rigid body. A rigid body with higher dominance group will not
     /// be affected by an object with lower dominance group (it will behave like it has an infinite
     /// mass). This is very importance feature for character physics in games, you can set highest
@@ -464,6 +506,7 @@ pub struct RigidBodyBuilder {
     can_sleep: bool,
     dominance: i8,
     gravity_scale: f32,
+    interpolation: bool,
 }
 
 impl RigidBodyBuilder {
@@ -484,6 +527,7 @@ impl RigidBodyBuilder {
             can_sleep: true,
             dominance: 0,
             gravity_scale: 1.0,
+            interpolation: false,
         }
     }
 
@@ -565,6 +609,12 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets whether the transform of the body should be interpolated between physics steps or not.
+    pub fn with_interpolation(mut self, enabled: bool) -> Self {
+        self.interpolation = enabled;
+        self
+    }
+
     /// Creates RigidBody node but does not add it to the graph.
     pub fn build_rigid_body(self) -> RigidBody {
         RigidBody {
@@ -582,6 +632,8 @@ impl RigidBodyBuilder {
             can_sleep: self.can_sleep.into(),
             dominance: self.dominance.into(),
             gravity_scale: self.gravity_scale.into(),
+            interpolation: self.interpolation.into(),
+            previous_pose: None,
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
         }
@@ -27,7 +27,7 @@ use crate::{
     core::{
         algebra::{Matrix4, Rotation3, UnitQuaternion, Vector2, Vector3},
         instant,
-        math::Matrix4Ext,
+        math::{m4x4_approx_eq, Matrix4Ext},
         pool::{Handle, MultiBorrowContext, Pool, Ticket},
         reflect::prelude::*,
         variable::try_inherit_properties,
@@ -47,12 +47,15 @@ use crate::{
         mesh::Mesh,
         node::{container::NodeContainer, Node, SyncContext, UpdateContext},
         pivot::Pivot,
+        ragdoll::Ragdoll,
+        rigidbody::RigidBody,
         sound::context::SoundContext,
         transform::TransformBuilder,
     },
     script::ScriptTrait,
     utils::log::{Log, MessageKind},
 };
+use fxhash::FxHashMap;
 use rapier3d::geometry::ColliderHandle;
 use std::{
     fmt::Debug,
@@ -135,6 +138,11 @@ pub struct Graph {
     pub(crate) script_message_sender: Sender<ScriptMessage>,
     #[reflect(hidden)]
     pub(crate) script_message_receiver: Receiver<ScriptMessage>,
+
+    // Global transforms of nodes that were overwritten by interpolation, they're restored on the next update
+    // of hierarchical data, so the interpolation won't leak into physics.
+    #[reflect(hidden)]
+    interpolation_backup: FxHashMap<Handle<Node>, Matrix4<f32>>,
 }
 
 impl Default for Graph {
@@ -152,6 +160,7 @@ impl Default for Graph {
             event_broadcaster: Default::default(),
             script_message_receiver: rx,
             script_message_sender: tx,
+            interpolation_backup: Default::default(),
         }
     }
 }
@@ -235,6 +244,7 @@ impl Graph {
             event_broadcaster: Default::default(),
             script_message_receiver: rx,
             script_message_sender: tx,
+            interpolation_backup: Default::default(),
         }
     }
 
@@ -876,6 +886,8 @@ impl Graph {
     /// need to know global transform of nodes before entering update loop, then you can call
     /// this method.
     pub fn update_hierarchical_data(&mut self) {
+        self.restore_interpolated_transforms();
+
         fn update_recursively(
             nodes: &NodePool,
             sound_context: &mut SoundContext,
@@ -928,6 +940,63 @@ impl Graph {
         );
     }
 
+    fn restore_interpolated_transforms(&mut self) {
+        for (handle, global_transform) in self.interpolation_backup.drain() {
+            if let Some(node) = self.pool.try_borrow(handle) {
+                node.global_transform.set(global_transform);
+            }
+        }
+    }
+
+    /// Recalculates global transforms of nodes using local transforms of rigid bodies interpolated between two
+    /// last physics steps (see [`RigidBody::set_interpolation`]). `alpha` defines the position between the steps
+    /// and must be in `[0; 1]` range. Global transforms of other nodes are recalculated too, so any changes of
+    /// local transforms made since the last update will be visible. View matrices of cameras are updated as well.
+    ///
+    /// The method affects only global transforms and it is intended to be used once per rendered frame. Every
+    /// change will be discarded on next update of the graph. Normally, it is called by
+    /// [`crate::engine::Engine::update_frame`] and there is no need to call it manually.
+    pub fn update_interpolated_transforms(&mut self, alpha: f32, frame_size: Vector2<f32>) {
+        self.stack.clear();
+        self.stack.push(self.root);
+
+        while let Some(handle) = self.stack.pop() {
+            let node = &self.pool[handle];
+
+            let parent_global_transform = self
+                .pool
+                .try_borrow(node.parent())
+                .map(|parent| parent.global_transform())
+                .unwrap_or_else(Matrix4::identity);
+
+            let local_transform = node
+                .query_component_ref::<RigidBody>()
+                .and_then(|body| body.interpolated_local_transform(alpha))
+                .or_else(|| {
+                    node.query_component_ref::<dim2::rigidbody::RigidBody>()
+                        .and_then(|body| body.interpolated_local_transform(alpha))
+                })
+                .unwrap_or_else(|| node.local_transform().matrix());
+
+            let new_global_transform = parent_global_transform * local_transform;
+            let old_global_transform = node.global_transform();
+
+            if !m4x4_approx_eq(&new_global_transform, &old_global_transform) {
+                self.interpolation_backup
+                    .entry(handle)
+                    .or_insert(old_global_transform);
+
+                node.global_transform.set(new_global_transform);
+
+                if let Some(camera) = self.pool[handle].cast_mut::<Camera>() {
+                    camera.calculate_matrices(frame_size);
+                }
+            }
+
+            self.stack.extend_from_slice(self.pool[handle].children());
+        }
+    }
+
     /// Checks whether given node handle is valid or not.
     pub fn is_valid_handle(&self, node_handle: Handle<Node>) -> bool {
         self.pool.is_valid_handle(node_handle)
@@ -946,7 +1015,35 @@ impl Graph {
         }
     }
 
+    fn update_node(&mut self, handle: Handle<Node>, frame_size: Vector2<f32>, dt: f32) {
+        if let Some((ticket, mut node)) = self.pool.try_take_reserve(handle) {
+            node.transform_modified.set(false);
+
+            let is_alive = if node.is_globally_enabled() {
+                node.update(&mut UpdateContext {
+                    frame_size,
+                    dt,
+                    nodes: &mut self.pool,
+                    physics: &mut self.physics,
+                    physics2d: &mut self.physics2d,
+                    sound_context: &mut self.sound_context,
+                })
+            } else {
+                true
+            };
+
+            self.pool.put_back(ticket, node);
+
+            if !is_alive {
+                self.remove_node(handle);
+            }
+        }
+    }
+
     /// Updates nodes in graph using given delta time. There is no need to call it manually.
+    ///
+    /// Ragdolls are updated after every other node, because they override poses of skeletons
+    /// produced by animations.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32) {
         let last_time = instant::Instant::now();
         self.update_hierarchical_data();
@@ -968,31 +1065,19 @@ impl Graph {
         self.sound_context.update(&self.pool);
         self.performance_statistics.sound_update_time = self.sound_context.full_render_duration();
 
+        let mut ragdolls = Vec::new();
         for i in 0..self.pool.get_capacity() {
             let handle = self.pool.handle_from_index(i);
-            if let Some((ticket, mut node)) = self.pool.try_take_reserve(handle) {
-                node.transform_modified.set(false);
-
-                let is_alive = if node.is_globally_enabled() {
-                    node.update(&mut UpdateContext {
-                        frame_size,
-                        dt,
-                        nodes: &mut self.pool,
-                        physics: &mut self.physics,
-                        physics2d: &mut self.physics2d,
-                        sound_context: &mut self.sound_context,
-                    })
-                } else {
-                    true
-                };
-
-                self.pool.put_back(ticket, node);
-
-                if !is_alive {
-                    self.remove_node(handle);
-                }
+            match self.pool.try_borrow(handle) {
+                Some(node) if node.cast::<Ragdoll>().is_some() => ragdolls.push(handle),
+                Some(_) => self.update_node(handle, frame_size, dt),
+                None => (),
             }
         }
+
+        for handle in ragdolls {
+            self.update_node(handle, frame_size, dt);
+        }
     }
 
     /// Returns capacity of internal pool. Can be used to iterate over all **potentially**
@@ -1347,8 +1432,14 @@ impl Visit for Graph {
 #[cfg(test)]
 mod test {
     use crate::{
-        core::pool::Handle,
-        scene::{graph::Graph, node::Node, pivot::Pivot},
+        core::{
+            algebra::{UnitQuaternion, Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::Pivot, pivot::PivotBuilder,
+            rigidbody::RigidBody, rigidbody::RigidBodyBuilder, transform::TransformBuilder,
+        },
     };
 
     #[test]
@@ -1366,4 +1457,42 @@ mod test {
         graph.add_node(Node::new(Pivot::default()));
         assert_eq!(graph.pool.alive_count(), 4);
     }
+
+    #[test]
+    fn graph_interpolation_test() {
+        let mut graph = Graph::new();
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let body = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[child])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(2.0, 0.0, 0.0))
+                        .build(),
+                ),
+        )
+        .with_interpolation(true)
+        .build(&mut graph);
+
+        graph.update_hierarchical_data();
+
+        graph[body].cast_mut::<RigidBody>().unwrap().previous_pose =
+            Some((Vector3::default(), UnitQuaternion::identity()));
+
+        graph.update_interpolated_transforms(0.5, Vector2::new(1.0, 1.0));
+        assert_eq!(graph[body].global_position(), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(graph[child].global_position(), Vector3::new(1.0, 1.0, 0.0));
+
+        // Interpolation must be discarded on the next update.
+        graph.update_hierarchical_data();
+        assert_eq!(graph[body].global_position(), Vector3::new(2.0, 0.0, 0.0));
+        assert_eq!(graph[child].global_position(), Vector3::new(2.0, 1.0, 0.0));
+    }
 }
@@ -4,8 +4,8 @@ use crate::scene::node::NodeTrait;
 use crate::{
     core::{
         algebra::{
-            DMatrix, Dynamic, Isometry3, Matrix4, Point3, Translation3, UnitQuaternion, VecStorage,
-            Vector2, Vector3,
+            DMatrix, Dynamic, Isometry3, Matrix4, Point3, Translation3, UnitQuaternion,
+            UnitVector3, VecStorage, Vector2, Vector3,
         },
         arrayvec::ArrayVec,
         instant,
@@ -38,6 +38,10 @@ use crate::{
 use fyrox_core::parking_lot::Mutex;
 use rapier3d::pipeline::{DebugRenderPipeline, QueryFilter};
 use rapier3d::{
+    control::{
+        CharacterAutostep, CharacterLength, EffectiveCharacterMovement,
+        KinematicCharacterController,
+    },
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
         IslandManager, JointAxesMask, MultibodyJointHandle, MultibodyJointSet, RigidBody,
@@ -47,11 +51,11 @@ use rapier3d::{
         BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{EventHandler, PhysicsPipeline, QueryPipeline},
+    pipeline::{ActiveEvents, EventHandler, PhysicsPipeline, QueryPipeline},
     prelude::JointAxis,
 };
 use std::{
-    cell::{Cell, RefCell},
+    cell::{Cell, RefCell, RefMut},
     cmp::Ordering,
     fmt::{Debug, Formatter},
     hash::Hash,
@@ -278,6 +282,93 @@ impl<const CAP: usize> QueryResultsStorage for ArrayVec<Intersection, CAP> {
     }
 }
 
+/// A set of options for the shape cast.
+pub struct ShapeCastOptions {
+    /// A shape to cast. Only primitive shapes could be used for queries, triangle meshes, height fields and
+    /// polyhedrons require a geometry source and cannot be used.
+    pub shape: ColliderShape,
+
+    /// Initial position of the shape in world coordinates.
+    pub shape_position: Isometry3<f32>,
+
+    /// Linear velocity of the shape. Can be non-normalized, time of impact is measured in units of the
+    /// length of the velocity.
+    pub shape_velocity: Vector3<f32>,
+
+    /// Maximum time of impact.
+    pub max_toi: f32,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// If `true`, the cast will stop immediately if the shape is penetrating a collider at its initial
+    /// position. If `false`, penetrating colliders will be ignored if the shape is moving away from them.
+    pub stop_at_penetration: bool,
+}
+
+/// Status of the time-of-impact computation.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ShapeCastStatus {
+    /// The algorithm ran out of iterations before achieving convergence. The result may be inaccurate.
+    OutOfIterations,
+    /// The algorithm converged successfully.
+    Converged,
+    /// Something went wrong during the computation, the result may be inaccurate.
+    Failed,
+    /// The shape is penetrating a collider at its initial position.
+    Penetrating,
+}
+
+impl From<rapier3d::parry::query::TOIStatus> for ShapeCastStatus {
+    fn from(v: rapier3d::parry::query::TOIStatus) -> Self {
+        match v {
+            rapier3d::parry::query::TOIStatus::OutOfIterations => Self::OutOfIterations,
+            rapier3d::parry::query::TOIStatus::Converged => Self::Converged,
+            rapier3d::parry::query::TOIStatus::Failed => Self::Failed,
+            rapier3d::parry::query::TOIStatus::Penetrating => Self::Penetrating,
+        }
+    }
+}
+
+/// A shape cast result.
+#[derive(Debug, Clone)]
+pub struct ShapeCastResult {
+    /// A handle of the first collider hit by the shape.
+    pub collider: Handle<Node>,
+
+    /// Time of impact. Position of the shape at the time of impact is `shape_position.translation +
+    /// shape_velocity * toi`.
+    pub toi: f32,
+
+    /// Closest point on the collider at the time of impact in world coordinates.
+    pub witness1: Point3<f32>,
+
+    /// Closest point on the cast shape at the time of impact in world coordinates.
+    pub witness2: Point3<f32>,
+
+    /// Normal of the collider at `witness1` in world coordinates.
+    pub normal1: Vector3<f32>,
+
+    /// Normal of the cast shape at `witness2` in world coordinates.
+    pub normal2: Vector3<f32>,
+
+    /// Status of the computation.
+    pub status: ShapeCastStatus,
+}
+
+/// A point projection result.
+#[derive(Debug, Clone)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// Whether the point is inside the collider or not.
+    pub is_inside: bool,
+
+    /// Projection of the point on the collider in world coordinates.
+    pub point: Point3<f32>,
+}
+
 /// Data of the contact.
 pub struct ContactData {
     /// The contact point in the local-space of the first shape.
@@ -333,6 +424,97 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+/// Kind of a collision event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollisionEventKind {
+    /// Two non-sensor colliders started touching each other.
+    ContactStarted,
+    /// Two non-sensor colliders stopped touching each other.
+    ContactStopped,
+    /// A collider started intersecting with a sensor collider.
+    IntersectionStarted,
+    /// A collider stopped intersecting with a sensor collider.
+    IntersectionStopped,
+}
+
+/// A collision event produced by a physics world during a simulation step. Events of the last step
+/// are available via [`PhysicsWorld::collision_events`] (or [`crate::scene::Scene::collision_events`]
+/// for both 2D and 3D physics), also they are passed to the scripts of involved colliders and their
+/// ancestors via [`crate::script::ScriptTrait::on_collision_event`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CollisionEvent {
+    /// Kind of the event.
+    pub kind: CollisionEventKind,
+    /// The first collider involved in the event. Could be [`Handle::NONE`] if the collider was
+    /// removed from the graph before the event was produced.
+    pub collider1: Handle<Node>,
+    /// The second collider involved in the event. Could be [`Handle::NONE`] if the collider was
+    /// removed from the graph before the event was produced.
+    pub collider2: Handle<Node>,
+    /// Magnitude of the sum of contact forces between the colliders at the end of the simulation
+    /// step. Always zero for every kind of event except [`CollisionEventKind::ContactStarted`].
+    pub total_force_magnitude: f32,
+    /// Magnitude of the strongest contact force between the colliders at the end of the simulation
+    /// step. Always zero for every kind of event except [`CollisionEventKind::ContactStarted`].
+    pub max_force_magnitude: f32,
+}
+
+impl CollisionEvent {
+    /// Returns `true` if the event is about started contact or intersection.
+    pub fn is_started(&self) -> bool {
+        matches!(
+            self.kind,
+            CollisionEventKind::ContactStarted | CollisionEventKind::IntersectionStarted
+        )
+    }
+
+    /// Returns `true` if the event is about intersection with a sensor collider.
+    pub fn is_intersection(&self) -> bool {
+        matches!(
+            self.kind,
+            CollisionEventKind::IntersectionStarted | CollisionEventKind::IntersectionStopped
+        )
+    }
+
+    /// Returns a copy of the event with swapped colliders.
+    pub fn swapped(&self) -> Self {
+        Self {
+            collider1: self.collider2,
+            collider2: self.collider1,
+            ..*self
+        }
+    }
+}
+
+// Collects collision events during a simulation step. The events are converted to the engine
+// representation right after the step.
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<rapier3d::geometry::CollisionEvent>>,
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: rapier3d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier3d::geometry::ContactPair>,
+    ) {
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier3d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -341,6 +523,31 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &scene::joint::JointMotor) {
+    joint
+        .set_motor(
+            axis,
+            motor.target_position,
+            motor.target_velocity,
+            motor.stiffness,
+            motor.damping,
+        )
+        .set_motor_max_force(axis, motor.max_force);
+}
+
+fn set_joint_axis_params(
+    joint: &mut GenericJoint,
+    axis: JointAxis,
+    params: &scene::joint::JointAxisParams,
+) {
+    if params.limits_enabled {
+        joint.set_limits(axis, [params.limits.start, params.limits.end]);
+    }
+    if let Some(motor) = params.motor.as_ref() {
+        set_joint_motor(joint, axis, motor);
+    }
+}
+
 fn convert_joint_params(
     params: scene::joint::JointParams,
     local_frame1: Isometry3<f32>,
@@ -351,12 +558,30 @@ fn convert_joint_params(
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
         JointParams::RevoluteJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
+        JointParams::RopeJoint(_) => JointAxesMask::empty(),
+        JointParams::GenericJoint(ref v) => [
+            (&v.x, JointAxesMask::X),
+            (&v.y, JointAxesMask::Y),
+            (&v.z, JointAxesMask::Z),
+            (&v.angular_x, JointAxesMask::ANG_X),
+            (&v.angular_y, JointAxesMask::ANG_Y),
+            (&v.angular_z, JointAxesMask::ANG_Z),
+        ]
+        .into_iter()
+        .filter(|(axis, _)| axis.locked)
+        .fold(JointAxesMask::empty(), |mask, (_, axis_mask)| {
+            mask | axis_mask
+        }),
     };
 
-    let mut joint = GenericJointBuilder::new(locked_axis)
+    let mut builder = GenericJointBuilder::new(locked_axis)
         .local_frame1(local_frame1)
-        .local_frame2(local_frame2)
-        .build();
+        .local_frame2(local_frame2);
+    if let JointParams::RopeJoint(_) = params {
+        // Distance between anchors is limited along a coupled linear axis.
+        builder = builder.coupled_axes(JointAxesMask::LIN_AXES);
+    }
+    let mut joint = builder.build();
 
     match params {
         scene::joint::JointParams::BallJoint(v) => {
@@ -384,11 +609,34 @@ fn convert_joint_params(
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            if let Some(motor) = v.motor.as_ref() {
+                set_joint_motor(&mut joint, JointAxis::X, motor);
+            }
         }
         scene::joint::JointParams::RevoluteJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::AngX, [v.limits.start, v.limits.end]);
             }
+            if let Some(motor) = v.motor.as_ref() {
+                set_joint_motor(&mut joint, JointAxis::AngX, motor);
+            }
+        }
+        scene::joint::JointParams::RopeJoint(v) => {
+            joint.set_limits(JointAxis::X, [0.0, v.max_distance]);
+        }
+        scene::joint::JointParams::GenericJoint(v) => {
+            for (params, axis) in [
+                (&v.x, JointAxis::X),
+                (&v.y, JointAxis::Y),
+                (&v.z, JointAxis::Z),
+                (&v.angular_x, JointAxis::AngX),
+                (&v.angular_y, JointAxis::AngY),
+                (&v.angular_z, JointAxis::AngZ),
+            ] {
+                if !params.locked {
+                    set_joint_axis_params(&mut joint, axis, params);
+                }
+            }
         }
     }
 
@@ -615,13 +863,9 @@ fn make_heightfield(terrain: &Terrain) -> SharedShape {
     )
 }
 
-// Converts descriptor in a shared shape.
-fn collider_shape_into_native_shape(
-    shape: &ColliderShape,
-    owner_inv_global_transform: Matrix4<f32>,
-    owner_collider: Handle<Node>,
-    pool: &NodePool,
-) -> Option<SharedShape> {
+// Converts descriptor of a primitive shape in a shared shape. Returns `None` for shapes that require
+// a geometry source.
+fn primitive_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape> {
     match shape {
         ColliderShape::Ball(ball) => Some(SharedShape::ball(ball.radius)),
 
@@ -646,6 +890,20 @@ fn collider_shape_into_native_shape(
             Point3::from(triangle.b),
             Point3::from(triangle.c),
         )),
+        ColliderShape::Trimesh(_)
+        | ColliderShape::Heightfield(_)
+        | ColliderShape::Polyhedron(_) => None,
+    }
+}
+
+// Converts descriptor in a shared shape.
+fn collider_shape_into_native_shape(
+    shape: &ColliderShape,
+    owner_inv_global_transform: Matrix4<f32>,
+    owner_collider: Handle<Node>,
+    pool: &NodePool,
+) -> Option<SharedShape> {
+    match shape {
         ColliderShape::Trimesh(trimesh) => {
             if trimesh.sources.is_empty() {
                 None
@@ -666,6 +924,7 @@ fn collider_shape_into_native_shape(
             .try_borrow(polyhedron.geometry_source.0)
             .and_then(|n| n.cast::<Mesh>())
             .map(|mesh| make_polyhedron_shape(owner_inv_global_transform, mesh)),
+        _ => primitive_shape_into_native_shape(shape),
     }
 }
 
@@ -887,7 +1146,11 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
+    // Collision events of the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
@@ -925,6 +1188,13 @@ fn u32_to_group(v: u32) -> rapier3d::geometry::Group {
     rapier3d::geometry::Group::from_bits(v).unwrap_or_else(rapier3d::geometry::Group::all)
 }
 
+fn query_filter<'a>(groups: collider::InteractionGroups) -> QueryFilter<'a> {
+    QueryFilter::new().groups(InteractionGroups::new(
+        u32_to_group(groups.memberships.0),
+        u32_to_group(groups.filter.0),
+    ))
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(super) fn new() -> Self {
@@ -953,7 +1223,8 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -963,6 +1234,8 @@ impl PhysicsWorld {
     pub(super) fn update(&mut self, dt: f32) {
         let time = instant::Instant::now();
 
+        self.collision_events.clear();
+
         if self.enabled {
             let integration_parameters = rapier3d::dynamics::IntegrationParameters {
                 dt: self.integration_parameters.dt.unwrap_or(dt),
@@ -1003,13 +1276,72 @@ impl PhysicsWorld {
                 &mut self.multibody_joints.set,
                 &mut self.ccd_solver,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
+
+            let events = std::mem::take(&mut *self.event_handler.events.lock());
+            self.collision_events = events
+                .into_iter()
+                .map(|event| self.convert_collision_event(event, integration_parameters.dt))
+                .collect();
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    fn convert_collision_event(
+        &self,
+        event: rapier3d::geometry::CollisionEvent,
+        dt: f32,
+    ) -> CollisionEvent {
+        let kind = match (event.started(), event.sensor()) {
+            (true, false) => CollisionEventKind::ContactStarted,
+            (false, false) => CollisionEventKind::ContactStopped,
+            (true, true) => CollisionEventKind::IntersectionStarted,
+            (false, true) => CollisionEventKind::IntersectionStopped,
+        };
+
+        let mut total_force = Vector3::default();
+        let mut max_force_magnitude = 0.0f32;
+        if kind == CollisionEventKind::ContactStarted && dt > 0.0 {
+            if let Some(pair) = self
+                .narrow_phase
+                .contact_pair(event.collider1(), event.collider2())
+            {
+                for manifold in pair.manifolds.iter() {
+                    for point in manifold.points.iter() {
+                        total_force += manifold.data.normal.scale(point.data.impulse / dt);
+                        max_force_magnitude = max_force_magnitude.max(point.data.impulse / dt);
+                    }
+                }
+            }
+        }
+
+        CollisionEvent {
+            kind,
+            collider1: self
+                .colliders
+                .map
+                .value_of(&event.collider1())
+                .cloned()
+                .unwrap_or_default(),
+            collider2: self
+                .colliders
+                .map
+                .value_of(&event.collider2())
+                .cloned()
+                .unwrap_or_default(),
+            total_force_magnitude: total_force.norm(),
+            max_force_magnitude,
+        }
+    }
+
+    /// Returns collision events produced during the last simulation step. The list is cleared
+    /// at the beginning of every step.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     pub(super) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
@@ -1086,10 +1418,7 @@ impl PhysicsWorld {
         );
     }
 
-    /// Casts a ray with given options.
-    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
-        let time = instant::Instant::now();
-
+    fn updated_query(&self) -> RefMut<QueryPipeline> {
         let mut query = self.query.borrow_mut();
 
         // TODO: Ideally this must be called once per frame, but it seems to be impossible because
@@ -1099,6 +1428,19 @@ impl PhysicsWorld {
         // of the frame.
         query.update(&self.islands, &self.bodies.set, &self.colliders.set);
 
+        query
+    }
+
+    fn collider_handle(&self, handle: &ColliderHandle) -> Handle<Node> {
+        self.colliders.map.value_of(handle).cloned().unwrap()
+    }
+
+    /// Casts a ray with given options.
+    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
+        let time = instant::Instant::now();
+
+        let query = self.updated_query();
+
         query_buffer.clear();
         let ray = Ray::new(
             opts.ray_origin,
@@ -1112,13 +1454,10 @@ impl PhysicsWorld {
             &ray,
             opts.max_len,
             true,
-            QueryFilter::new().groups(InteractionGroups::new(
-                u32_to_group(opts.groups.memberships.0),
-                u32_to_group(opts.groups.filter.0),
-            )),
+            query_filter(opts.groups),
             |handle, intersection| {
                 query_buffer.push(Intersection {
-                    collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
+                    collider: self.collider_handle(&handle),
                     normal: intersection.normal,
                     position: ray.point_at(intersection.toi),
                     feature: intersection.feature.into(),
@@ -1144,6 +1483,322 @@ impl PhysicsWorld {
         );
     }
 
+    /// Casts a shape with given options and returns the first collider hit by the shape. Could be used, for
+    /// example, to check whether a character fits in some place.
+    pub fn cast_shape(&self, opts: ShapeCastOptions) -> Option<ShapeCastResult> {
+        let shape = primitive_shape_into_native_shape(&opts.shape)?;
+
+        let query = self.updated_query();
+
+        query
+            .cast_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.shape_position,
+                &opts.shape_velocity,
+                &*shape,
+                opts.max_toi,
+                opts.stop_at_penetration,
+                query_filter(opts.groups),
+            )
+            .map(|(handle, toi)| {
+                // Second witness and normal are in local coordinates of the cast shape.
+                let shape_position = Isometry3::from_parts(
+                    Translation3::from(
+                        opts.shape_position.translation.vector + opts.shape_velocity * toi.toi,
+                    ),
+                    opts.shape_position.rotation,
+                );
+
+                ShapeCastResult {
+                    collider: self.collider_handle(&handle),
+                    toi: toi.toi,
+                    witness1: toi.witness1,
+                    witness2: shape_position * toi.witness2,
+                    normal1: toi.normal1.into_inner(),
+                    normal2: shape_position.rotation * toi.normal2.into_inner(),
+                    status: toi.status.into(),
+                }
+            })
+    }
+
+    /// Collects all colliders that intersect with given shape. Could be used, for example, to find all
+    /// colliders inside an explosion radius. Only primitive shapes could be used, see
+    /// [`ShapeCastOptions::shape`] for more info.
+    pub fn intersections_with_shape(
+        &self,
+        shape: &ColliderShape,
+        shape_position: &Isometry3<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let shape = match primitive_shape_into_native_shape(shape) {
+            Some(shape) => shape,
+            None => return,
+        };
+
+        let query = self.updated_query();
+
+        query.intersections_with_shape(
+            &self.bodies.set,
+            &self.colliders.set,
+            shape_position,
+            &*shape,
+            query_filter(groups),
+            |handle| {
+                query_buffer.push(self.collider_handle(&handle));
+                true
+            },
+        );
+    }
+
+    /// Projects a point on the closest collider. If `solid` is `true`, points inside colliders will be
+    /// projected on themselves, otherwise they will be projected on the boundaries of the colliders.
+    pub fn project_point(
+        &self,
+        point: Point3<f32>,
+        solid: bool,
+        groups: collider::InteractionGroups,
+    ) -> Option<PointProjection> {
+        let query = self.updated_query();
+
+        query
+            .project_point(
+                &self.bodies.set,
+                &self.colliders.set,
+                &point,
+                solid,
+                query_filter(groups),
+            )
+            .map(|(handle, projection)| PointProjection {
+                collider: self.collider_handle(&handle),
+                is_inside: projection.is_inside,
+                point: projection.point,
+            })
+    }
+
+    /// Collects all colliders that contain given point.
+    pub fn intersections_with_point(
+        &self,
+        point: Point3<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let query = self.updated_query();
+
+        query.intersections_with_point(
+            &self.bodies.set,
+            &self.colliders.set,
+            &point,
+            query_filter(groups),
+            |handle| {
+                query_buffer.push(self.collider_handle(&handle));
+                true
+            },
+        );
+    }
+
+    pub(crate) fn move_character(
+        &self,
+        character: &scene::character_controller::CharacterController,
+        desired_translation: Vector3<f32>,
+        dt: f32,
+        collisions: &mut Vec<scene::character_controller::CharacterCollision>,
+    ) -> Option<EffectiveCharacterMovement> {
+        let body_handle = character.rigid_body_ref().native.get();
+        let body = self.bodies.set.get(body_handle)?;
+        // Shape of the character is defined by its first collider.
+        let collider = self.colliders.set.get(*body.colliders().first()?)?;
+
+        let controller = KinematicCharacterController {
+            up: UnitVector3::new_normalize(character.up_vector()),
+            offset: CharacterLength::Absolute(character.offset()),
+            slide: character.is_slide(),
+            autostep: character.autostep().map(|autostep| CharacterAutostep {
+                max_height: CharacterLength::Absolute(autostep.max_height),
+                min_width: CharacterLength::Absolute(autostep.min_width),
+                include_dynamic_bodies: autostep.include_dynamic_bodies,
+            }),
+            max_slope_climb_angle: character.max_slope_climb_angle(),
+            min_slope_slide_angle: character.min_slope_slide_angle(),
+            snap_to_ground: character.snap_to_ground().map(CharacterLength::Absolute),
+        };
+
+        let query = self.updated_query();
+
+        Some(
+            controller.move_shape(
+                dt,
+                &self.bodies.set,
+                &self.colliders.set,
+                &query,
+                collider.shape(),
+                collider.position(),
+                desired_translation,
+                QueryFilter::new()
+                    .exclude_rigid_body(body_handle)
+                    .exclude_sensors()
+                    .groups(collider.collision_groups()),
+                |collision| {
+                    collisions.push(scene::character_controller::CharacterCollision {
+                        collider: self.collider_handle(&collision.handle),
+                        translation_applied: collision.translation_applied,
+                        translation_remaining: collision.translation_remaining,
+                        toi: collision.toi.toi,
+                        position: collision.toi.witness1,
+                        normal: collision.toi.normal1.into_inner(),
+                    })
+                },
+            ),
+        )
+    }
+
+    pub(crate) fn simulate_vehicle(
+        &mut self,
+        vehicle: &scene::vehicle::Vehicle,
+        wheel_states: &mut [scene::vehicle::WheelState],
+        dt: f32,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let dt = self.integration_parameters.dt.unwrap_or(dt);
+        let body_handle = vehicle.rigid_body_ref().native.get();
+        let wheel_count = vehicle.wheels().len().max(1) as f32;
+        let driven_wheel_count = vehicle.wheels().iter().filter(|w| w.drive).count().max(1) as f32;
+
+        let mut impulses = Vec::with_capacity(wheel_states.len());
+        {
+            let body = match self.bodies.set.get(body_handle) {
+                Some(body) if body.is_dynamic() => body,
+                _ => return,
+            };
+            let position = *body.position();
+            let mass = body.mass();
+            let up = position.rotation * Vector3::y();
+
+            let query = self.updated_query();
+
+            for (wheel, state) in vehicle.wheels().iter().zip(wheel_states.iter_mut()) {
+                state.steering_angle = if wheel.steering {
+                    vehicle.steering() * vehicle.max_steering_angle()
+                } else {
+                    0.0
+                };
+
+                let wheel_rotation = position.rotation
+                    * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), state.steering_angle);
+                let forward = wheel_rotation * Vector3::z();
+                let side = wheel_rotation * Vector3::x();
+
+                let origin = position * Point3::from(wheel.connection_point);
+                let ray = Ray::new(origin, -up);
+                let max_len = wheel.suspension_rest_length + wheel.radius;
+
+                let hit = query.cast_ray_and_get_normal(
+                    &self.bodies.set,
+                    &self.colliders.set,
+                    &ray,
+                    max_len,
+                    true,
+                    QueryFilter::new()
+                        .exclude_rigid_body(body_handle)
+                        .exclude_sensors(),
+                );
+
+                if let Some((collider, intersection)) = hit {
+                    let contact_point = ray.point_at(intersection.toi);
+
+                    state.in_contact = true;
+                    state.ground = self
+                        .colliders
+                        .map
+                        .value_of(&collider)
+                        .cloned()
+                        .unwrap_or_default();
+                    state.contact_point = contact_point.coords;
+                    state.contact_normal = intersection.normal;
+                    state.suspension_length = (intersection.toi - wheel.radius)
+                        .max(0.0)
+                        .min(wheel.suspension_rest_length);
+
+                    let point_velocity = body.velocity_at_point(&contact_point);
+
+                    // Spring-damper suspension, it is scaled by the mass of the chassis.
+                    let compression = wheel.suspension_rest_length - state.suspension_length;
+                    state.suspension_force = ((wheel.suspension_stiffness * compression
+                        - wheel.suspension_damping * point_velocity.dot(&up))
+                        * mass)
+                        .max(0.0)
+                        .min(wheel.max_suspension_force);
+
+                    // Tire friction. Mass of the chassis is distributed evenly across the wheels, which is
+                    // a rough approximation, but it is good enough for the most of vehicles.
+                    let mass_per_wheel = mass / wheel_count;
+
+                    let side_impulse =
+                        -point_velocity.dot(&side) * mass_per_wheel * wheel.side_friction_stiffness;
+
+                    let forward_velocity = point_velocity.dot(&forward);
+                    let mut forward_impulse = 0.0;
+                    if wheel.drive {
+                        forward_impulse += vehicle.throttle() * vehicle.max_engine_force() * dt
+                            / driven_wheel_count;
+                    }
+                    if wheel.brake && vehicle.brake() > 0.0 {
+                        let max_brake_impulse = forward_velocity.abs() * mass_per_wheel;
+                        forward_impulse -= forward_velocity.signum()
+                            * (vehicle.brake() * vehicle.max_brake_force() * dt)
+                                .min(max_brake_impulse);
+                    }
+
+                    // Clamp friction by the friction circle.
+                    let mut friction_impulse = Vector2::new(forward_impulse, side_impulse);
+                    let max_friction_impulse = wheel.friction_slip * state.suspension_force * dt;
+                    if friction_impulse.norm() > max_friction_impulse {
+                        friction_impulse = friction_impulse
+                            .try_normalize(f32::EPSILON)
+                            .unwrap_or_default()
+                            .scale(max_friction_impulse);
+                    }
+
+                    impulses.push((
+                        up.scale(state.suspension_force * dt)
+                            + forward.scale(friction_impulse.x)
+                            + side.scale(friction_impulse.y),
+                        contact_point,
+                    ));
+
+                    state.angular_velocity = if wheel.radius > 0.0 {
+                        forward_velocity / wheel.radius
+                    } else {
+                        0.0
+                    };
+                } else {
+                    state.in_contact = false;
+                    state.ground = Handle::NONE;
+                    state.suspension_length = wheel.suspension_rest_length;
+                    state.suspension_force = 0.0;
+                    // Wheels in the air are slowly stopping.
+                    state.angular_velocity *= 0.99;
+                }
+
+                state.rotation += state.angular_velocity * dt;
+            }
+        }
+
+        if let Some(body) = self.bodies.set.get_mut(body_handle) {
+            for (impulse, point) in impulses {
+                body.apply_impulse_at_point(impulse, point, true);
+            }
+        }
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::rigidbody::RigidBody,
@@ -1167,6 +1822,12 @@ impl PhysicsWorld {
         if self.enabled {
             if let Some(native) = self.bodies.set.get(rigid_body.native.get()) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    // Remember the pose before the step, it is used for interpolation.
+                    rigid_body.previous_pose = Some((
+                        **rigid_body.local_transform.position(),
+                        **rigid_body.local_transform.rotation(),
+                    ));
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
@@ -1195,6 +1856,8 @@ impl PhysicsWorld {
                         .ang_vel
                         .set_value_with_flags(*native.angvel(), VariableFlags::MODIFIED);
                     rigid_body.sleeping = native.is_sleeping();
+                } else {
+                    rigid_body.previous_pose = None;
                 }
             }
         }
@@ -1412,7 +2075,7 @@ impl PhysicsWorld {
             }
         } else if let Some(parent_body) = nodes
             .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+            .and_then(|n| n.query_component_ref::<scene::rigidbody::RigidBody>())
         {
             if parent_body.native.get() != RigidBodyHandle::invalid() {
                 let inv_global_transform = isometric_global_transform(nodes, handle)
@@ -1444,7 +2107,8 @@ impl PhysicsWorld {
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(ActiveEvents::COLLISION_EVENTS);
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -1477,7 +2141,7 @@ impl PhysicsWorld {
             joint.body1.try_sync_model(|v| {
                 if let Some(rigid_body_node) = nodes
                     .try_borrow(v)
-                    .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+                    .and_then(|n| n.query_component_ref::<scene::rigidbody::RigidBody>())
                 {
                     native.body1 = rigid_body_node.native.get();
                 }
@@ -1485,7 +2149,7 @@ impl PhysicsWorld {
             joint.body2.try_sync_model(|v| {
                 if let Some(rigid_body_node) = nodes
                     .try_borrow(v)
-                    .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+                    .and_then(|n| n.query_component_ref::<scene::rigidbody::RigidBody>())
                 {
                     native.body2 = rigid_body_node.native.get();
                 }
@@ -1502,10 +2166,10 @@ impl PhysicsWorld {
                 if let (Some(body1), Some(body2)) = (
                     nodes
                         .try_borrow(joint.body1())
-                        .and_then(|n| n.cast::<scene::rigidbody::RigidBody>()),
+                        .and_then(|n| n.query_component_ref::<scene::rigidbody::RigidBody>()),
                     nodes
                         .try_borrow(joint.body2())
-                        .and_then(|n| n.cast::<scene::rigidbody::RigidBody>()),
+                        .and_then(|n| n.query_component_ref::<scene::rigidbody::RigidBody>()),
                 ) {
                     let (local_frame1, local_frame2) = calculate_local_frames(joint, body1, body2);
                     native.data =
@@ -1522,11 +2186,11 @@ impl PhysicsWorld {
             // native bodies exists.
             if let (Some(body1), Some(body2)) = (
                 nodes.try_borrow(body1_handle).and_then(|n| {
-                    n.cast::<scene::rigidbody::RigidBody>()
+                    n.query_component_ref::<scene::rigidbody::RigidBody>()
                         .filter(|b| self.bodies.set.get(b.native.get()).is_some())
                 }),
                 nodes.try_borrow(body2_handle).and_then(|n| {
-                    n.cast::<scene::rigidbody::RigidBody>()
+                    n.query_component_ref::<scene::rigidbody::RigidBody>()
                         .filter(|b| self.bodies.set.get(b.native.get()).is_some())
                 }),
             ) {
@@ -1644,3 +2308,246 @@ impl Debug for PhysicsWorld {
         write!(f, "PhysicsWorld")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Isometry3, Point3, Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape, GeometrySource, InteractionGroups},
+            graph::{physics::ShapeCastOptions, Graph},
+            joint::{
+                GenericJoint, Joint, JointAxisParams, JointBuilder, JointMotor, JointParams,
+                RevoluteJoint,
+            },
+            node::Node,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+        },
+    };
+    use rapier3d::prelude::{JointAxesMask, JointAxis};
+
+    // Creates a world with a single static 2x2x2 cube at (0, 0, 5).
+    fn make_world() -> (Graph, Handle<Node>) {
+        let mut graph = Graph::new();
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 1.0, 1.0))
+            .build(&mut graph);
+
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        // Native colliders are created and positioned during the update.
+        graph.update(Vector2::new(800.0, 600.0), 1.0);
+        graph.update(Vector2::new(800.0, 600.0), 1.0);
+
+        (graph, collider)
+    }
+
+    fn cast_options(shape: ColliderShape) -> ShapeCastOptions {
+        ShapeCastOptions {
+            shape,
+            shape_position: Isometry3::translation(0.0, 0.0, 0.0),
+            shape_velocity: Vector3::new(0.0, 0.0, 1.0),
+            max_toi: 100.0,
+            groups: InteractionGroups::default(),
+            stop_at_penetration: true,
+        }
+    }
+
+    fn assert_approx_eq(a: Point3<f32>, b: Point3<f32>) {
+        assert!((a - b).norm() < 0.001, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_cast_shape() {
+        let (graph, collider) = make_world();
+
+        let result = graph
+            .physics
+            .cast_shape(cast_options(ColliderShape::ball(0.5)))
+            .unwrap();
+        assert_eq!(result.collider, collider);
+        // The ball touches the near face of the cube (z = 4) when its center is at z = 3.5.
+        assert!((result.toi - 3.5).abs() < 0.001);
+        assert_approx_eq(result.witness1, Point3::new(0.0, 0.0, 4.0));
+        assert_approx_eq(result.witness2, Point3::new(0.0, 0.0, 4.0));
+        assert!((result.normal1 - Vector3::new(0.0, 0.0, -1.0)).norm() < 0.001);
+        assert!((result.normal2 - Vector3::new(0.0, 0.0, 1.0)).norm() < 0.001);
+
+        // Moving away from the cube.
+        let mut options = cast_options(ColliderShape::ball(0.5));
+        options.shape_velocity = Vector3::new(0.0, 0.0, -1.0);
+        assert!(graph.physics.cast_shape(options).is_none());
+    }
+
+    #[test]
+    fn test_intersections_with_shape() {
+        let (graph, collider) = make_world();
+        let mut buffer = Vec::new();
+
+        graph.physics.intersections_with_shape(
+            &ColliderShape::ball(1.0),
+            &Isometry3::translation(0.0, 0.0, 3.5),
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![collider]);
+
+        graph.physics.intersections_with_shape(
+            &ColliderShape::ball(1.0),
+            &Isometry3::translation(0.0, 0.0, -5.0),
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_point_queries() {
+        let (graph, collider) = make_world();
+
+        let projection = graph
+            .physics
+            .project_point(Point3::origin(), true, InteractionGroups::default())
+            .unwrap();
+        assert_eq!(projection.collider, collider);
+        assert!(!projection.is_inside);
+        assert_approx_eq(projection.point, Point3::new(0.0, 0.0, 4.0));
+
+        let inner_point = Point3::new(0.0, 0.5, 5.0);
+        let projection = graph
+            .physics
+            .project_point(inner_point, true, InteractionGroups::default())
+            .unwrap();
+        assert!(projection.is_inside);
+        assert_approx_eq(projection.point, inner_point);
+
+        let mut buffer = Vec::new();
+        graph.physics.intersections_with_point(
+            inner_point,
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![collider]);
+
+        graph.physics.intersections_with_point(
+            Point3::origin(),
+            InteractionGroups::default(),
+            &mut buffer,
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_queries_with_unsupported_shapes() {
+        let (graph, _) = make_world();
+
+        for shape in [
+            ColliderShape::trimesh(vec![GeometrySource(Handle::NONE)]),
+            ColliderShape::heightfield(GeometrySource(Handle::NONE)),
+        ] {
+            assert!(graph
+                .physics
+                .cast_shape(cast_options(shape.clone()))
+                .is_none());
+
+            let mut buffer = vec![Handle::NONE];
+            graph.physics.intersections_with_shape(
+                &shape,
+                &Isometry3::translation(0.0, 0.0, 5.0),
+                InteractionGroups::default(),
+                &mut buffer,
+            );
+            assert!(buffer.is_empty());
+        }
+    }
+
+    fn native_joint(params: JointParams) -> rapier3d::dynamics::GenericJoint {
+        let mut graph = Graph::new();
+        let body1 = RigidBodyBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let body2 = RigidBodyBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let joint = JointBuilder::new(BaseBuilder::new())
+            .with_params(params)
+            .with_body1(body1)
+            .with_body2(body2)
+            .build(&mut graph);
+
+        // Native joint is created when both native bodies exist.
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0);
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0);
+
+        let native = graph[joint].cast::<Joint>().unwrap().native.get();
+        graph.physics.joints.set.get(native).unwrap().data
+    }
+
+    fn check_native_motor(
+        joint: &rapier3d::dynamics::GenericJoint,
+        axis: JointAxis,
+        motor: &JointMotor,
+    ) {
+        let native_motor = joint.motor(axis).unwrap();
+        assert_eq!(native_motor.target_vel, motor.target_velocity);
+        assert_eq!(native_motor.target_pos, motor.target_position);
+        assert_eq!(native_motor.stiffness, motor.stiffness);
+        assert_eq!(native_motor.damping, motor.damping);
+        assert_eq!(native_motor.max_force, motor.max_force);
+    }
+
+    #[test]
+    fn test_joint_motors() {
+        let motor = JointMotor {
+            target_velocity: 1.0,
+            target_position: 2.0,
+            stiffness: 3.0,
+            damping: 4.0,
+            max_force: 5.0,
+        };
+
+        let joint = native_joint(JointParams::RevoluteJoint(RevoluteJoint {
+            limits_enabled: false,
+            limits: -1.0..1.0,
+            motor: Some(motor.clone()),
+        }));
+        assert_eq!(joint.locked_axes, JointAxesMask::LOCKED_REVOLUTE_AXES);
+        check_native_motor(&joint, JointAxis::AngX, &motor);
+
+        let joint = native_joint(JointParams::GenericJoint(GenericJoint {
+            x: JointAxisParams::locked(),
+            y: JointAxisParams {
+                limits_enabled: true,
+                limits: -2.0..3.0,
+                ..Default::default()
+            },
+            z: JointAxisParams {
+                motor: Some(motor.clone()),
+                ..Default::default()
+            },
+            angular_x: JointAxisParams::locked(),
+            angular_y: JointAxisParams::locked(),
+            angular_z: JointAxisParams::locked(),
+        }));
+        assert_eq!(
+            joint.locked_axes,
+            JointAxesMask::X | JointAxesMask::ANG_X | JointAxesMask::ANG_Y | JointAxesMask::ANG_Z
+        );
+        let limits = joint.limits(JointAxis::Y).unwrap();
+        assert_eq!([limits.min, limits.max], [-2.0, 3.0]);
+        check_native_motor(&joint, JointAxis::Z, &motor);
+        assert!(joint.motor(JointAxis::Y).is_none());
+    }
+}
@@ -27,6 +27,97 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+/// Joint motor drives relative movement of attached bodies along (or around) a free axis of a joint. The
+/// motor is modelled as a spring: it applies a force proportional to the difference between the target and
+/// the current position (scaled by the stiffness) and to the difference between the target and the current
+/// velocity (scaled by the damping). For example, a motor with zero stiffness and some damping works as a
+/// velocity motor, that tries to keep the target velocity.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointMotor {
+    /// Target relative velocity of the attached bodies along the axis (in units per second or radians per
+    /// second for angular axes).
+    #[reflect(description = "Target relative velocity of the attached bodies along the axis.")]
+    pub target_velocity: f32,
+
+    /// Target relative position of the attached bodies along the axis (in units or radians for angular axes).
+    #[reflect(description = "Target relative position of the attached bodies along the axis.")]
+    pub target_position: f32,
+
+    /// Stiffness of the motor, defines how strongly the motor tries to reach the target position.
+    #[reflect(
+        description = "Stiffness of the motor, defines how strongly the motor tries to reach the target position."
+    )]
+    pub stiffness: f32,
+
+    /// Damping of the motor, defines how strongly the motor tries to reach the target velocity.
+    #[reflect(
+        description = "Damping of the motor, defines how strongly the motor tries to reach the target velocity."
+    )]
+    pub damping: f32,
+
+    /// Maximum force (or torque for angular axes) that the motor can apply.
+    #[reflect(
+        description = "Maximum force (or torque for angular axes) that the motor can apply."
+    )]
+    pub max_force: f32,
+}
+
+impl Default for JointMotor {
+    fn default() -> Self {
+        Self {
+            target_velocity: 0.0,
+            target_position: 0.0,
+            stiffness: 0.0,
+            damping: 1.0,
+            max_force: f32::MAX,
+        }
+    }
+}
+
+/// Motion settings of a single axis of a [`GenericJoint`].
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointAxisParams {
+    /// Whether any relative movement along the axis is prohibited or not. Default is `false`.
+    #[reflect(description = "Whether any relative movement along the axis is prohibited or not.")]
+    pub locked: bool,
+
+    /// Whether limits of the axis are enabled or not. Default is `false`.
+    #[reflect(description = "Whether limits of the axis are enabled or not.")]
+    pub limits_enabled: bool,
+
+    /// Allowed range of relative position of the attached bodies along the axis (in units or radians
+    /// for angular axes).
+    #[reflect(
+        description = "Allowed range of relative position of the attached bodies along the axis."
+    )]
+    pub limits: Range<f32>,
+
+    /// Optional motor of the axis.
+    #[reflect(description = "Optional motor of the axis.")]
+    pub motor: Option<JointMotor>,
+}
+
+impl Default for JointAxisParams {
+    fn default() -> Self {
+        Self {
+            locked: false,
+            limits_enabled: false,
+            limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: None,
+        }
+    }
+}
+
+impl JointAxisParams {
+    /// Creates parameters of a locked axis.
+    pub fn locked() -> Self {
+        Self {
+            locked: true,
+            ..Default::default()
+        }
+    }
+}
+
 /// Ball joint locks any translational moves between two objects on the axis between objects, but
 /// allows rigid bodies to perform relative rotations. The real world example is a human shoulder,
 /// pendulum, etc.
@@ -96,6 +187,13 @@ pub struct PrismaticJoint {
     )]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Optional motor, that drives relative movement of the attached bodies along local X axis of the joint.
+    #[reflect(
+        description = "Optional motor, that drives relative movement of the attached bodies along local X axis of the joint."
+    )]
+    #[visit(optional)] // Backward compatibility
+    pub motor: Option<JointMotor>,
 }
 
 impl Default for PrismaticJoint {
@@ -103,6 +201,7 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: None,
         }
     }
 }
@@ -123,6 +222,13 @@ pub struct RevoluteJoint {
     #[reflect(description = "Allowed angle range around local X axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Optional motor, that drives relative rotation of the attached bodies around local X axis of the joint.
+    #[reflect(
+        description = "Optional motor, that drives relative rotation of the attached bodies around local X axis of the joint."
+    )]
+    #[visit(optional)] // Backward compatibility
+    pub motor: Option<JointMotor>,
 }
 
 impl Default for RevoluteJoint {
@@ -130,10 +236,59 @@ impl Default for RevoluteJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: None,
         }
     }
 }
 
+/// Rope joint limits maximum distance between anchors of two rigid bodies, but allows any other relative
+/// movement. The bodies can move closer to each other freely, but can't move apart further than the given
+/// distance. The real world example is a rope, a chain, etc. Use it together with a
+/// [`PrismaticJoint`] motor if you need a spring.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between anchors of the attached bodies.
+    #[reflect(description = "Maximum distance between anchors of the attached bodies.")]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
+/// Generic joint allows you to configure every degree of freedom (three linear and three angular axes)
+/// of relative movement of two rigid bodies separately. Every axis could be locked, limited and driven
+/// by a motor. It could be used to model complex mechanisms, that can't be modelled by other joints, or
+/// joints of ragdolls.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect, Default)]
+pub struct GenericJoint {
+    /// Motion settings of local X axis of the joint.
+    #[reflect(description = "Motion settings of local X axis of the joint.")]
+    pub x: JointAxisParams,
+
+    /// Motion settings of local Y axis of the joint.
+    #[reflect(description = "Motion settings of local Y axis of the joint.")]
+    pub y: JointAxisParams,
+
+    /// Motion settings of local Z axis of the joint.
+    #[reflect(description = "Motion settings of local Z axis of the joint.")]
+    pub z: JointAxisParams,
+
+    /// Motion settings of rotation around local X axis of the joint.
+    #[reflect(description = "Motion settings of rotation around local X axis of the joint.")]
+    pub angular_x: JointAxisParams,
+
+    /// Motion settings of rotation around local Y axis of the joint.
+    #[reflect(description = "Motion settings of rotation around local Y axis of the joint.")]
+    pub angular_y: JointAxisParams,
+
+    /// Motion settings of rotation around local Z axis of the joint.
+    #[reflect(description = "Motion settings of rotation around local Z axis of the joint.")]
+    pub angular_z: JointAxisParams,
+}
+
 /// The exact kind of the joint.
 #[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
 pub enum JointParams {
@@ -145,6 +300,10 @@ pub enum JointParams {
     PrismaticJoint(PrismaticJoint),
     /// See [`RevoluteJoint`] for more info.
     RevoluteJoint(RevoluteJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`GenericJoint`] for more info.
+    GenericJoint(GenericJoint),
 }
 
 impl Default for JointParams {
@@ -426,9 +585,13 @@ impl JointBuilder {
 mod test {
     use crate::core::reflect::Reflect;
     use crate::core::variable::try_inherit_properties;
+    use crate::core::visitor::prelude::*;
     use crate::scene::{
         base::{test::check_inheritable_properties_equality, BaseBuilder},
-        joint::{BallJoint, Joint, JointBuilder, JointParams},
+        joint::{
+            BallJoint, GenericJoint, Joint, JointAxisParams, JointBuilder, JointMotor, JointParams,
+            PrismaticJoint, RevoluteJoint, RopeJoint,
+        },
     };
 
     #[test]
@@ -446,4 +609,59 @@ mod test {
         check_inheritable_properties_equality(&child.base, &parent.base);
         check_inheritable_properties_equality(&child, parent);
     }
+
+    fn check_visit_round_trip(params: JointParams) {
+        let mut visitor = Visitor::new();
+        params.clone().visit("Params", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = JointParams::default();
+        loaded.visit("Params", &mut visitor).unwrap();
+
+        assert_eq!(loaded, params);
+    }
+
+    #[test]
+    fn test_joint_params_visit() {
+        let motor = JointMotor {
+            target_velocity: 1.0,
+            target_position: 2.0,
+            stiffness: 3.0,
+            damping: 4.0,
+            max_force: 5.0,
+        };
+
+        check_visit_round_trip(JointParams::PrismaticJoint(PrismaticJoint {
+            limits_enabled: true,
+            limits: -1.0..1.0,
+            motor: Some(motor.clone()),
+        }));
+        check_visit_round_trip(JointParams::RevoluteJoint(RevoluteJoint {
+            limits_enabled: true,
+            limits: -1.0..1.0,
+            motor: Some(motor.clone()),
+        }));
+        check_visit_round_trip(JointParams::RopeJoint(RopeJoint { max_distance: 2.5 }));
+        check_visit_round_trip(JointParams::GenericJoint(GenericJoint {
+            x: JointAxisParams::locked(),
+            y: JointAxisParams {
+                locked: false,
+                limits_enabled: true,
+                limits: -2.0..3.0,
+                motor: None,
+            },
+            z: JointAxisParams {
+                motor: Some(motor.clone()),
+                ..Default::default()
+            },
+            angular_x: JointAxisParams::locked(),
+            angular_y: Default::default(),
+            angular_z: JointAxisParams {
+                limits_enabled: true,
+                motor: Some(motor),
+                ..Default::default()
+            },
+        }));
+    }
 }
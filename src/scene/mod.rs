@@ -8,6 +8,7 @@ pub mod accel;
 pub mod animation;
 pub mod base;
 pub mod camera;
+pub mod character_controller;
 pub mod collider;
 pub mod debug;
 pub mod decal;
@@ -20,11 +21,13 @@ pub mod mesh;
 pub mod node;
 pub mod particle_system;
 pub mod pivot;
+pub mod ragdoll;
 pub mod rigidbody;
 pub mod sound;
 pub mod sprite;
 pub mod terrain;
 pub mod transform;
+pub mod vehicle;
 pub mod visibility;
 
 use crate::{
@@ -43,7 +46,7 @@ use crate::{
     scene::{
         camera::Camera,
         debug::SceneDrawingContext,
-        graph::{map::NodeHandleMap, Graph, GraphPerformanceStatistics},
+        graph::{map::NodeHandleMap, physics::CollisionEvent, Graph, GraphPerformanceStatistics},
         mesh::buffer::{
             VertexAttributeDataType, VertexAttributeDescriptor, VertexAttributeUsage,
             VertexWriteTrait,
@@ -242,13 +245,14 @@ pub struct SceneLoader {
 }
 
 impl SceneLoader {
-    /// Tries to load scene from given file. File can contain any scene in native engine format.
-    /// Such scenes can be made in rusty editor.
+    /// Tries to load scene from given file. File can contain any scene in native engine format
+    /// (binary or text, see [`crate::core::visitor::VisitorFormat`]). Such scenes can be made in
+    /// rusty editor.
     pub async fn from_file<P: AsRef<Path>>(
         path: P,
         serialization_context: Arc<SerializationContext>,
     ) -> Result<Self, VisitError> {
-        let mut visitor = Visitor::load_binary(path).await?;
+        let mut visitor = Visitor::load_from_file(path).await?;
         Self::load("Scene", serialization_context, &mut visitor)
     }
 
@@ -504,6 +508,16 @@ impl Scene {
         self.performance_statistics.graph = self.graph.performance_statistics.clone();
     }
 
+    /// Returns an iterator over collision events of both 3D and 2D physics produced during the
+    /// last update of the scene.
+    pub fn collision_events(&self) -> impl Iterator<Item = &CollisionEvent> {
+        self.graph
+            .physics
+            .collision_events()
+            .iter()
+            .chain(self.graph.physics2d.collision_events().iter())
+    }
+
     /// Creates deep copy of a scene, filter predicate allows you to filter out nodes
     /// by your criteria.
     pub fn clone<F>(&self, filter: &mut F) -> (Self, NodeHandleMap)
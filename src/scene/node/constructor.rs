@@ -1,11 +1,15 @@
 //! A special container that is able to create nodes by their type UUID.
 
 use crate::{
-    core::{parking_lot::Mutex, uuid::Uuid},
+    core::{
+        parking_lot::{Mutex, MutexGuard},
+        uuid::Uuid,
+    },
     scene::{
         self,
         animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
         camera::Camera,
+        character_controller::CharacterController,
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
         light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
@@ -13,9 +17,11 @@ use crate::{
         node::{Node, NodeTrait, TypeUuidProvider},
         particle_system::ParticleSystem,
         pivot::Pivot,
+        ragdoll::Ragdoll,
         sound::{listener::Listener, Sound},
         sprite::Sprite,
         terrain::Terrain,
+        vehicle::Vehicle,
     },
 };
 use fxhash::FxHashMap;
@@ -38,6 +44,7 @@ impl NodeConstructorContainer {
         container.add::<dim2::joint::Joint>();
         container.add::<Rectangle>();
         container.add::<dim2::rigidbody::RigidBody>();
+        container.add::<dim2::character_controller::CharacterController>();
         container.add::<DirectionalLight>();
         container.add::<PointLight>();
         container.add::<SpotLight>();
@@ -51,6 +58,9 @@ impl NodeConstructorContainer {
         container.add::<scene::joint::Joint>();
         container.add::<Pivot>();
         container.add::<scene::rigidbody::RigidBody>();
+        container.add::<CharacterController>();
+        container.add::<Ragdoll>();
+        container.add::<Vehicle>();
         container.add::<Sprite>();
         container.add::<Terrain>();
         container.add::<AnimationPlayer>();
@@ -89,6 +99,11 @@ impl NodeConstructorContainer {
         self.map.lock().get_mut(type_uuid).map(|c| (c)())
     }
 
+    /// Returns inner map of node constructors.
+    pub fn map(&self) -> MutexGuard<FxHashMap<Uuid, NodeConstructor>> {
+        self.map.lock()
+    }
+
     /// Returns total amount of constructors.
     pub fn len(&self) -> usize {
         self.map.lock().len()
@@ -0,0 +1,812 @@
+//! Ragdoll is a set of rigid bodies linked with joints, that simulates a body of a character. See [`Ragdoll`]
+//! docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    scene::{
+        base::{Base, BaseBuilder},
+        collider::{CapsuleShape, ColliderBuilder, ColliderShape, InteractionGroups},
+        graph::{Graph, NodePool},
+        joint::{BallJoint, JointBuilder, JointParams},
+        mesh::Mesh,
+        node::{Node, NodeTrait, TypeUuidProvider, UpdateContext},
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+    },
+    utils::log::Log,
+};
+use fxhash::FxHashMap;
+use std::ops::{Deref, DerefMut};
+
+/// Limb is a bone of a skeleton with a rigid body attached to it. The rigid body drives the bone when
+/// the ragdoll is active and follows the bone otherwise.
+#[derive(Clone, Debug, PartialEq, Default, Visit, Reflect)]
+pub struct Limb {
+    /// A handle of a bone of a skeleton.
+    pub bone: Handle<Node>,
+    /// A handle of a rigid body attached to the bone.
+    pub physical_bone: Handle<Node>,
+    /// Child limbs of the limb.
+    pub children: Vec<Limb>,
+}
+
+impl Limb {
+    /// Calls the given closure for the limb and every descendant limb of it.
+    pub fn iterate_recursive<F>(&self, func: &mut F)
+    where
+        F: FnMut(&Limb),
+    {
+        func(self);
+
+        for child in self.children.iter() {
+            child.iterate_recursive(func)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct BlendOut {
+    elapsed_time: f32,
+    pose: Vec<(Handle<Node>, Vector3<f32>, UnitQuaternion<f32>)>,
+}
+
+/// Ragdoll is a set of rigid bodies linked with joints, that simulates a body of a character. Every rigid
+/// body is attached to a bone of a skeleton (see [`Limb`]). A ragdoll could be either active or inactive:
+///
+/// - Active ragdoll is driven by physics: rigid bodies are dynamic and the bones copy their transforms.
+/// - Inactive ragdoll is driven by animation: rigid bodies are kinematic and they follow the bones, this
+/// way animated characters still can interact with other physical objects.
+///
+/// When the ragdoll becomes inactive, the skeleton does not jump back to the animation pose immediately,
+/// instead it smoothly blends the physical pose to the animation pose over [`Ragdoll::blend_out_time`].
+///
+/// Use [`RagdollBuilder::generate`] to create a ragdoll for a skinned mesh automatically.
+///
+/// # Update order
+///
+/// The ragdoll overwrites local transforms of the bones, so it must be updated after the nodes that
+/// animate the skeleton (animation players and animation blending state machines). The graph updates
+/// ragdolls after every other node, see [`Graph::update`].
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct Ragdoll {
+    base: Base,
+
+    #[reflect(setter = "set_active")]
+    is_active: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_blend_out_time", min_value = 0.0, step = 0.05)]
+    blend_out_time: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_root_limb")]
+    root_limb: InheritableVariable<Limb>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    was_active: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    blend_out: Option<BlendOut>,
+}
+
+impl Default for Ragdoll {
+    fn default() -> Self {
+        RagdollBuilder::new(BaseBuilder::new()).build_ragdoll()
+    }
+}
+
+impl Deref for Ragdoll {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Ragdoll {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Ragdoll {
+    fn type_uuid() -> Uuid {
+        uuid!("f0c6b3a1-2d5e-4c8b-9e7a-6b1d3f5a8c24")
+    }
+}
+
+// Extracts rotation part of a transform matrix, that could contain scaling.
+fn rotation_from_matrix(transform: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    let mut basis = transform.basis();
+    for mut column in basis.column_iter_mut() {
+        column.try_normalize_mut(f32::EPSILON);
+    }
+    UnitQuaternion::from_matrix_eps(&basis, f32::EPSILON, 16, UnitQuaternion::identity())
+}
+
+fn scale_from_matrix(transform: &Matrix4<f32>) -> Vector3<f32> {
+    let basis = transform.basis();
+    Vector3::new(
+        basis.column(0).norm(),
+        basis.column(1).norm(),
+        basis.column(2).norm(),
+    )
+}
+
+// Calculates global transform of a node using current local transforms of its ancestors. Global transforms
+// of nodes are calculated once per frame, so they do not reflect the changes made in the current frame.
+// The `overrides` map allows to provide global transforms for some nodes explicitly.
+fn actual_global_transform(
+    nodes: &NodePool,
+    overrides: &FxHashMap<Handle<Node>, Matrix4<f32>>,
+    mut handle: Handle<Node>,
+) -> Matrix4<f32> {
+    let mut transform = Matrix4::identity();
+    while let Some(node) = nodes.try_borrow(handle) {
+        if let Some(global_transform) = overrides.get(&handle) {
+            return global_transform * transform;
+        }
+        transform = node.local_transform().matrix() * transform;
+        handle = node.parent();
+    }
+    transform
+}
+
+impl Ragdoll {
+    /// Makes the ragdoll active (driven by physics) or inactive (driven by animation).
+    pub fn set_active(&mut self, active: bool) -> bool {
+        self.is_active.set_value_and_mark_modified(active)
+    }
+
+    /// Returns `true` if the ragdoll is active (driven by physics), `false` - otherwise.
+    pub fn is_active(&self) -> bool {
+        *self.is_active
+    }
+
+    /// Sets the time (in seconds) of blending from the physical pose to the animation pose after
+    /// the ragdoll became inactive. Zero means that there is no blending.
+    pub fn set_blend_out_time(&mut self, time: f32) -> f32 {
+        self.blend_out_time
+            .set_value_and_mark_modified(time.max(0.0))
+    }
+
+    /// Returns the time (in seconds) of blending from the physical pose to the animation pose.
+    pub fn blend_out_time(&self) -> f32 {
+        *self.blend_out_time
+    }
+
+    /// Returns `true` if the ragdoll is blending the physical pose to the animation pose at the moment.
+    pub fn is_blending_out(&self) -> bool {
+        self.blend_out.is_some()
+    }
+
+    /// Sets the new root limb of the ragdoll.
+    pub fn set_root_limb(&mut self, root_limb: Limb) -> Limb {
+        self.root_limb.set_value_and_mark_modified(root_limb)
+    }
+
+    /// Returns a reference to the root limb of the ragdoll.
+    pub fn root_limb(&self) -> &Limb {
+        &self.root_limb
+    }
+
+    fn set_body_types(&self, nodes: &mut NodePool, body_type: RigidBodyType) {
+        self.root_limb.iterate_recursive(&mut |limb| {
+            if let Some(rigid_body) = nodes
+                .try_borrow_mut(limb.physical_bone)
+                .and_then(|n| n.query_component_mut::<RigidBody>())
+            {
+                rigid_body.set_body_type(body_type);
+            }
+        });
+    }
+
+    // Calculates local transforms of the bones, that match transforms of the rigid bodies.
+    fn physical_pose(
+        &self,
+        nodes: &NodePool,
+    ) -> Vec<(Handle<Node>, Vector3<f32>, UnitQuaternion<f32>)> {
+        let mut bone_transforms = FxHashMap::default();
+        self.root_limb.iterate_recursive(&mut |limb| {
+            if let (Some(rigid_body), Some(bone)) = (
+                nodes.try_borrow(limb.physical_bone),
+                nodes.try_borrow(limb.bone),
+            ) {
+                // Rigid bodies do not have scale, but bones could have it.
+                let scale = scale_from_matrix(&bone.global_transform());
+                bone_transforms.insert(
+                    limb.bone,
+                    rigid_body.global_transform() * Matrix4::new_nonuniform_scaling(&scale),
+                );
+            }
+        });
+
+        let mut pose = Vec::new();
+        self.root_limb.iterate_recursive(&mut |limb| {
+            if let (Some(global_transform), Some(parent)) = (
+                bone_transforms.get(&limb.bone),
+                nodes.try_borrow(limb.bone).map(|b| b.parent()),
+            ) {
+                let parent_transform = actual_global_transform(nodes, &bone_transforms, parent);
+                let local_transform = parent_transform
+                    .try_inverse()
+                    .unwrap_or_else(Matrix4::identity)
+                    * global_transform;
+
+                pose.push((
+                    limb.bone,
+                    local_transform.position(),
+                    rotation_from_matrix(&local_transform),
+                ));
+            }
+        });
+        pose
+    }
+
+    // Copies transforms of the rigid bodies to the bones.
+    fn apply_physical_pose(&self, nodes: &mut NodePool) {
+        for (bone, position, rotation) in self.physical_pose(nodes) {
+            nodes[bone]
+                .local_transform_mut()
+                .set_position(position)
+                .set_rotation(rotation);
+        }
+    }
+
+    // Blends the physical pose, captured at the moment of deactivation, with the current animation pose.
+    // The animation pose is already applied to the bones, because ragdolls are updated last.
+    fn apply_blend_out(&mut self, nodes: &mut NodePool, dt: f32) {
+        let blend_out = match self.blend_out.as_mut() {
+            Some(blend_out) => blend_out,
+            None => return,
+        };
+
+        blend_out.elapsed_time += dt;
+        let t = if *self.blend_out_time > 0.0 {
+            (blend_out.elapsed_time / *self.blend_out_time).min(1.0)
+        } else {
+            1.0
+        };
+
+        for (bone, position, rotation) in blend_out.pose.iter() {
+            if let Some(bone) = nodes.try_borrow_mut(*bone) {
+                let transform = bone.local_transform_mut();
+                let animation_position = **transform.position();
+                let animation_rotation = **transform.rotation();
+                transform
+                    .set_position(position.lerp(&animation_position, t))
+                    .set_rotation(rotation.nlerp(&animation_rotation, t));
+            }
+        }
+
+        if t >= 1.0 {
+            self.blend_out = None;
+        }
+    }
+
+    // Moves the rigid bodies to the bones.
+    fn follow_bones(&self, nodes: &mut NodePool) {
+        let no_overrides = FxHashMap::default();
+        self.root_limb.iterate_recursive(&mut |limb| {
+            if let (true, Some(parent)) = (
+                nodes.is_valid_handle(limb.bone),
+                nodes.try_borrow(limb.physical_bone).map(|b| b.parent()),
+            ) {
+                let bone_transform = actual_global_transform(nodes, &no_overrides, limb.bone);
+
+                // Rigid bodies are usually children of the ragdoll, which is not available while
+                // it is being updated.
+                let parent_transform = nodes
+                    .try_borrow(parent)
+                    .map(|p| p.global_transform())
+                    .unwrap_or_else(|| self.global_transform());
+
+                let local_transform = parent_transform
+                    .try_inverse()
+                    .unwrap_or_else(Matrix4::identity)
+                    * bone_transform;
+
+                nodes[limb.physical_bone]
+                    .local_transform_mut()
+                    .set_position(local_transform.position())
+                    .set_rotation(rotation_from_matrix(&local_transform));
+            }
+        });
+    }
+}
+
+impl NodeTrait for Ragdoll {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.base.restore_resources(resource_manager)
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) -> bool {
+        let is_active = *self.is_active;
+        if is_active != self.was_active {
+            if is_active {
+                self.set_body_types(context.nodes, RigidBodyType::Dynamic);
+                self.blend_out = None;
+            } else {
+                self.set_body_types(context.nodes, RigidBodyType::KinematicPositionBased);
+                if *self.blend_out_time > 0.0 {
+                    // The bones are already animated at this moment, but the rigid bodies still have
+                    // the last physical pose.
+                    self.blend_out = Some(BlendOut {
+                        elapsed_time: 0.0,
+                        pose: self.physical_pose(context.nodes),
+                    });
+                }
+            }
+            self.was_active = is_active;
+        }
+
+        if is_active {
+            self.apply_physical_pose(context.nodes);
+        } else {
+            self.apply_blend_out(context.nodes, context.dt);
+            self.follow_bones(context.nodes);
+        }
+
+        self.base.update_lifetime(context.dt)
+    }
+}
+
+/// A set of parameters for automatic ragdoll generation, see [`RagdollBuilder::generate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RagdollGenerationSettings {
+    /// Radius of a capsule collider of a limb relative to the length of its bone.
+    pub radius_factor: f32,
+    /// Minimal radius of a capsule collider of a limb.
+    pub min_radius: f32,
+    /// Length of a bone without child bones (hands, feet, head) relative to the length of its
+    /// parent bone.
+    pub leaf_length_factor: f32,
+    /// Density of the colliders of the limbs. `None` means default density.
+    pub density: Option<f32>,
+    /// Angular limits of the ball joints (in radians), the same limits are used for every axis.
+    pub joint_angle_limit: f32,
+    /// Collision groups of the colliders of the limbs.
+    pub collision_groups: InteractionGroups,
+}
+
+impl Default for RagdollGenerationSettings {
+    fn default() -> Self {
+        Self {
+            radius_factor: 0.2,
+            min_radius: 0.02,
+            leaf_length_factor: 0.5,
+            density: None,
+            joint_angle_limit: 45.0f32.to_radians(),
+            collision_groups: Default::default(),
+        }
+    }
+}
+
+struct LimbGenerator<'a> {
+    graph: &'a mut Graph,
+    settings: &'a RagdollGenerationSettings,
+    children: FxHashMap<Handle<Node>, Vec<Handle<Node>>>,
+    ragdoll: Handle<Node>,
+    inv_ragdoll_transform: Matrix4<f32>,
+    is_active: bool,
+}
+
+impl<'a> LimbGenerator<'a> {
+    fn generate(
+        &mut self,
+        bone: Handle<Node>,
+        parent_body: Handle<Node>,
+        parent_direction: Vector3<f32>,
+    ) -> Limb {
+        let bone_transform = self.graph[bone].global_transform();
+        let bone_position = bone_transform.position();
+        let bone_rotation = rotation_from_matrix(&bone_transform);
+        let bone_name = self.graph[bone].name_owned();
+        let children = self.children.get(&bone).cloned().unwrap_or_default();
+
+        // A limb spans from its bone to the center of its child bones.
+        let mut direction = if children.is_empty() {
+            parent_direction.scale(self.settings.leaf_length_factor)
+        } else {
+            children
+                .iter()
+                .map(|c| self.graph[*c].global_position())
+                .sum::<Vector3<f32>>()
+                .scale(1.0 / children.len() as f32)
+                - bone_position
+        };
+        if direction.norm() <= f32::EPSILON {
+            direction = bone_rotation * Vector3::new(0.0, 2.0 * self.settings.min_radius, 0.0);
+        }
+        let length = direction.norm();
+        let radius = (length * self.settings.radius_factor).max(self.settings.min_radius);
+        let local_direction = bone_rotation.inverse() * direction.scale(1.0 / length);
+        let half_length = length * 0.5;
+
+        let collider =
+            ColliderBuilder::new(BaseBuilder::new().with_name(format!("{}_Collider", bone_name)))
+                .with_shape(ColliderShape::Capsule(CapsuleShape {
+                    begin: local_direction.scale(radius.min(half_length)),
+                    end: local_direction.scale((length - radius).max(half_length)),
+                    radius,
+                }))
+                .with_density(self.settings.density)
+                .with_collision_groups(self.settings.collision_groups)
+                .build(self.graph);
+
+        let local_transform = self.inv_ragdoll_transform
+            * Matrix4::new_translation(&bone_position)
+            * bone_rotation.to_homogeneous();
+        let local_position = local_transform.position();
+        let local_rotation = rotation_from_matrix(&local_transform);
+
+        let body = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_name(format!("{}_RigidBody", bone_name))
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(local_position)
+                        .with_local_rotation(local_rotation)
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(if self.is_active {
+            RigidBodyType::Dynamic
+        } else {
+            RigidBodyType::KinematicPositionBased
+        })
+        .build(self.graph);
+        self.graph.link_nodes(body, self.ragdoll);
+
+        if parent_body.is_some() {
+            let limit = self.settings.joint_angle_limit;
+            let joint = JointBuilder::new(
+                BaseBuilder::new()
+                    .with_name(format!("{}_Joint", bone_name))
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(local_position)
+                            .with_local_rotation(local_rotation)
+                            .build(),
+                    ),
+            )
+            .with_params(JointParams::BallJoint(BallJoint {
+                x_limits_enabled: true,
+                x_limits_angles: -limit..limit,
+                y_limits_enabled: true,
+                y_limits_angles: -limit..limit,
+                z_limits_enabled: true,
+                z_limits_angles: -limit..limit,
+            }))
+            .with_body1(parent_body)
+            .with_body2(body)
+            .with_contacts_enabled(false)
+            .build(self.graph);
+            self.graph.link_nodes(joint, self.ragdoll);
+        }
+
+        Limb {
+            bone,
+            physical_bone: body,
+            children: children
+                .into_iter()
+                .map(|child| self.generate(child, body, direction))
+                .collect(),
+        }
+    }
+}
+
+/// Allows you to create ragdolls in declarative manner.
+pub struct RagdollBuilder {
+    base_builder: BaseBuilder,
+    is_active: bool,
+    blend_out_time: f32,
+    root_limb: Limb,
+}
+
+impl RagdollBuilder {
+    /// Creates new ragdoll builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            is_active: false,
+            blend_out_time: 0.5,
+            root_limb: Default::default(),
+        }
+    }
+
+    /// Sets whether the ragdoll is active (driven by physics) or not.
+    pub fn with_active(mut self, active: bool) -> Self {
+        self.is_active = active;
+        self
+    }
+
+    /// Sets the time (in seconds) of blending from the physical pose to the animation pose.
+    pub fn with_blend_out_time(mut self, time: f32) -> Self {
+        self.blend_out_time = time;
+        self
+    }
+
+    /// Sets the root limb of the ragdoll.
+    pub fn with_root_limb(mut self, root_limb: Limb) -> Self {
+        self.root_limb = root_limb;
+        self
+    }
+
+    /// Creates new ragdoll instance.
+    pub fn build_ragdoll(self) -> Ragdoll {
+        Ragdoll {
+            base: self.base_builder.build_base(),
+            is_active: self.is_active.into(),
+            blend_out_time: self.blend_out_time.max(0.0).into(),
+            root_limb: self.root_limb.into(),
+            // Body types of the limbs are already set by whoever created them.
+            was_active: self.is_active,
+            blend_out: None,
+        }
+    }
+
+    /// Creates new ragdoll node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_ragdoll())
+    }
+
+    /// Creates new ragdoll node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+
+    /// Generates a ragdoll for the given skinned mesh and adds it to the graph. Every bone of the mesh
+    /// gets a rigid body with a capsule collider, that spans from the bone to its child bones, bodies of
+    /// adjacent bones are linked with ball joints. The rigid bodies and the joints are added as children
+    /// of the ragdoll. Root limb, specified in the builder, is ignored.
+    ///
+    /// Bones of the mesh must form a single hierarchy, if there are multiple root bones, only the
+    /// top-most one (with all its descendant bones) is used.
+    pub fn generate(
+        mut self,
+        graph: &mut Graph,
+        mesh: Handle<Node>,
+        settings: &RagdollGenerationSettings,
+    ) -> Handle<Node> {
+        // Bones could be moved since last update, make sure we'll use actual transforms.
+        graph.update_hierarchical_data();
+
+        let mut bones = Vec::new();
+        if let Some(mesh) = graph.try_get(mesh).and_then(|n| n.cast::<Mesh>()) {
+            for surface in mesh.surfaces() {
+                for bone in surface.bones() {
+                    if !bones.contains(bone) && graph.is_valid_handle(*bone) {
+                        bones.push(*bone);
+                    }
+                }
+            }
+        }
+
+        // Link every bone with its closest ancestor bone.
+        let mut children = FxHashMap::<Handle<Node>, Vec<Handle<Node>>>::default();
+        let mut root = (Handle::NONE, usize::MAX);
+        for &bone in bones.iter() {
+            let mut depth = 0;
+            let mut parent = graph[bone].parent();
+            while parent.is_some() && !bones.contains(&parent) {
+                parent = graph[parent].parent();
+                depth += 1;
+            }
+
+            if parent.is_some() {
+                children.entry(parent).or_default().push(bone);
+            } else if depth < root.1 {
+                root = (bone, depth);
+            }
+        }
+
+        let is_active = self.is_active;
+        self.root_limb = Default::default();
+        let ragdoll = self.build(graph);
+        // The ragdoll is attached to the root of the graph, so its local transform is the global one.
+        let ragdoll_transform = graph[ragdoll].local_transform().matrix();
+
+        let root_bone = root.0;
+        if root_bone.is_none() {
+            Log::warn(format!(
+                "Unable to generate a ragdoll for {}, because it does not have any bones!",
+                mesh
+            ));
+            return ragdoll;
+        }
+
+        let root_limb = LimbGenerator {
+            graph,
+            settings,
+            children,
+            ragdoll,
+            inv_ragdoll_transform: ragdoll_transform
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity),
+            is_active,
+        }
+        .generate(root_bone, Handle::NONE, Vector3::default());
+
+        graph[ragdoll]
+            .cast_mut::<Ragdoll>()
+            .unwrap()
+            .set_root_limb(root_limb);
+
+        ragdoll
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, Vector2, Vector3},
+            pool::Handle,
+            reflect::Reflect,
+            variable::try_inherit_properties,
+        },
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            node::Node,
+            pivot::PivotBuilder,
+            ragdoll::{Limb, Ragdoll, RagdollBuilder, RagdollGenerationSettings},
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_ragdoll_inheritance() {
+        let parent = RagdollBuilder::new(BaseBuilder::new())
+            .with_active(true)
+            .with_blend_out_time(1.0)
+            .build_node();
+
+        let mut child = RagdollBuilder::new(BaseBuilder::new()).build_ragdoll();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<Ragdoll>().unwrap();
+
+        check_inheritable_properties_equality(&child.base, &parent.base);
+        check_inheritable_properties_equality(&child, parent);
+    }
+
+    #[test]
+    fn test_ragdoll_generation() {
+        let mut graph = Graph::new();
+
+        let make_bone = |graph: &mut Graph, y: f32| {
+            PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, y, 0.0))
+                        .build(),
+                ),
+            )
+            .build(graph)
+        };
+        let hips = make_bone(&mut graph, 1.0);
+        let spine = make_bone(&mut graph, 0.5);
+        let head = make_bone(&mut graph, 0.5);
+        graph.link_nodes(spine, hips);
+        graph.link_nodes(head, spine);
+
+        let mesh = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .with_bones(vec![head, hips, spine])
+            .build()])
+            .build(&mut graph);
+
+        let ragdoll = RagdollBuilder::new(BaseBuilder::new()).generate(
+            &mut graph,
+            mesh,
+            &RagdollGenerationSettings::default(),
+        );
+
+        let ragdoll = graph[ragdoll].cast::<Ragdoll>().unwrap();
+        let root_limb = ragdoll.root_limb();
+        assert_eq!(root_limb.bone, hips);
+        assert_eq!(root_limb.children.len(), 1);
+        assert_eq!(root_limb.children[0].bone, spine);
+        assert_eq!(root_limb.children[0].children.len(), 1);
+        assert_eq!(root_limb.children[0].children[0].bone, head);
+
+        // Three rigid bodies and two joints.
+        assert_eq!(ragdoll.children().len(), 5);
+    }
+
+    #[test]
+    fn test_ragdoll_blend_out() {
+        let mut graph = Graph::new();
+        graph.physics.gravity = Vector3::default();
+
+        let bone = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let body = RigidBodyBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 0.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_body_type(RigidBodyType::Dynamic)
+        .build(&mut graph);
+        let ragdoll = RagdollBuilder::new(BaseBuilder::new())
+            .with_active(true)
+            .with_blend_out_time(1.0)
+            .with_root_limb(Limb {
+                bone,
+                physical_bone: body,
+                children: vec![],
+            })
+            .build(&mut graph);
+
+        let bone_position = |graph: &Graph| **graph[bone].local_transform().position();
+        // Emulates an animation, that sets the bone to the origin every frame before the ragdoll
+        // is updated.
+        let animate = |graph: &mut Graph, bone: Handle<Node>| {
+            graph[bone]
+                .local_transform_mut()
+                .set_position(Vector3::default());
+        };
+
+        // Active ragdoll moves the bone to the rigid body.
+        animate(&mut graph, bone);
+        graph.update(Vector2::new(1.0, 1.0), 0.25);
+        assert!(bone_position(&graph).metric_distance(&Vector3::new(1.0, 0.0, 0.0)) < 1.0e-4);
+
+        // Inactive ragdoll blends from the physical pose to the animation pose.
+        graph[ragdoll]
+            .cast_mut::<Ragdoll>()
+            .unwrap()
+            .set_active(false);
+        for expected in [0.75, 0.5, 0.25] {
+            animate(&mut graph, bone);
+            graph.update(Vector2::new(1.0, 1.0), 0.25);
+            assert!(
+                bone_position(&graph).metric_distance(&Vector3::new(expected, 0.0, 0.0)) < 1.0e-4,
+                "expected {}, got {}",
+                expected,
+                bone_position(&graph)
+            );
+            assert!(graph[ragdoll].cast::<Ragdoll>().unwrap().is_blending_out());
+        }
+
+        animate(&mut graph, bone);
+        graph.update(Vector2::new(1.0, 1.0), 0.25);
+        assert!(bone_position(&graph).norm() < 1.0e-4);
+        assert!(!graph[ragdoll].cast::<Ragdoll>().unwrap().is_blending_out());
+    }
+}
@@ -0,0 +1,193 @@
+//! Audio buses. See [`AudioBus`] docs for more info.
+
+use crate::{
+    core::{pool::Handle, reflect::prelude::*, variable::InheritableVariable, visitor::prelude::*},
+    define_with,
+    scene::sound::{context::SoundContext, effect::Effect},
+};
+use std::cell::Cell;
+
+pub use fyrox_sound::bus::MASTER_BUS_NAME;
+
+/// Audio bus allows you to group sounds and control them all at once. Buses form a tree, the root
+/// of the tree is the master bus of a sound context. Every sound is routed to a bus by its name
+/// (see [`crate::scene::sound::Sound::set_audio_bus`]). Each bus mixes its sounds, processes the
+/// mix by its chain of effects, applies its gain and passes the result to its parent bus.
+///
+/// Muted bus produces no sound, including the sound of its descendant buses. If any bus is soloed,
+/// only soloed buses (and their descendants) are audible.
+///
+/// Gain and mute state of a bus can also be changed at runtime without modifying the scene, see
+/// [`crate::plugin::SoundEngineHelper::set_bus_gain`].
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct AudioBus {
+    #[reflect(setter = "set_name_internal")]
+    pub(crate) name: InheritableVariable<String>,
+
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_gain")]
+    pub(crate) gain: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_muted")]
+    pub(crate) muted: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_soloed")]
+    pub(crate) soloed: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_effects")]
+    pub(crate) effects: InheritableVariable<Vec<Handle<Effect>>>,
+
+    #[reflect(hidden)]
+    pub(crate) parent: Handle<AudioBus>,
+
+    #[reflect(hidden)]
+    pub(crate) children: Vec<Handle<AudioBus>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<Handle<fyrox_sound::bus::AudioBus>>,
+}
+
+impl Default for AudioBus {
+    fn default() -> Self {
+        Self {
+            name: InheritableVariable::new("".to_string()),
+            gain: InheritableVariable::new(1.0),
+            muted: InheritableVariable::new(false),
+            soloed: InheritableVariable::new(false),
+            effects: Default::default(),
+            parent: Default::default(),
+            children: Default::default(),
+            native: Default::default(),
+        }
+    }
+}
+
+impl AudioBus {
+    /// Returns shared reference to the current name of the bus.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets new name of the bus. Sounds are routed to buses by their names, so renaming a bus will
+    /// re-route sounds.
+    pub fn set_name<S: AsRef<str>>(&mut self, name: S) {
+        self.set_name_internal(name.as_ref().to_owned());
+    }
+
+    fn set_name_internal(&mut self, name: String) -> String {
+        self.name.set_value_and_mark_modified(name)
+    }
+
+    /// Returns gain of the bus.
+    pub fn gain(&self) -> f32 {
+        *self.gain
+    }
+
+    /// Sets gain of the bus, it is applied after the effects.
+    pub fn set_gain(&mut self, gain: f32) -> f32 {
+        self.gain.set_value_and_mark_modified(gain.max(0.0))
+    }
+
+    /// Returns true if the bus is muted.
+    pub fn is_muted(&self) -> bool {
+        *self.muted
+    }
+
+    /// Mutes or unmutes the bus.
+    pub fn set_muted(&mut self, muted: bool) -> bool {
+        self.muted.set_value_and_mark_modified(muted)
+    }
+
+    /// Returns true if the bus is soloed.
+    pub fn is_soloed(&self) -> bool {
+        *self.soloed
+    }
+
+    /// Solos the bus. If any bus is soloed, only soloed buses are audible.
+    pub fn set_soloed(&mut self, soloed: bool) -> bool {
+        self.soloed.set_value_and_mark_modified(soloed)
+    }
+
+    /// Returns a chain of effects of the bus.
+    pub fn effects(&self) -> &[Handle<Effect>] {
+        &self.effects
+    }
+
+    /// Sets new chain of effects of the bus. Effects used by a bus ignore their inputs and process
+    /// the mix of the bus instead. An effect must not be shared between buses.
+    pub fn set_effects(&mut self, effects: Vec<Handle<Effect>>) -> Vec<Handle<Effect>> {
+        self.effects.set_value_and_mark_modified(effects)
+    }
+
+    /// Returns handle of the parent bus.
+    pub fn parent(&self) -> Handle<AudioBus> {
+        self.parent
+    }
+
+    /// Returns handles of child buses.
+    pub fn children(&self) -> &[Handle<AudioBus>] {
+        &self.children
+    }
+}
+
+/// Audio bus builder allows you to create a new [`AudioBus`] and add it to a sound context.
+pub struct AudioBusBuilder {
+    name: String,
+    gain: f32,
+    muted: bool,
+    soloed: bool,
+    effects: Vec<Handle<Effect>>,
+}
+
+impl AudioBusBuilder {
+    /// Creates new audio bus builder.
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            gain: 1.0,
+            muted: false,
+            soloed: false,
+            effects: Default::default(),
+        }
+    }
+
+    define_with!(
+        /// Sets desired gain of the bus.
+        fn with_gain(gain: f32)
+    );
+
+    define_with!(
+        /// Sets whether the bus is muted or not.
+        fn with_muted(muted: bool)
+    );
+
+    define_with!(
+        /// Sets whether the bus is soloed or not.
+        fn with_soloed(soloed: bool)
+    );
+
+    define_with!(
+        /// Sets desired chain of effects of the bus.
+        fn with_effects(effects: Vec<Handle<Effect>>)
+    );
+
+    /// Creates new audio bus.
+    pub fn build_bus(self) -> AudioBus {
+        AudioBus {
+            name: self.name.into(),
+            gain: self.gain.into(),
+            muted: self.muted.into(),
+            soloed: self.soloed.into(),
+            effects: self.effects.into(),
+            parent: Default::default(),
+            children: Default::default(),
+            native: Default::default(),
+        }
+    }
+
+    /// Creates new audio bus and adds it to the context as a child of the given parent bus.
+    pub fn build(self, parent: Handle<AudioBus>, context: &mut SoundContext) -> Handle<AudioBus> {
+        context.add_bus(self.build_bus(), parent)
+    }
+}
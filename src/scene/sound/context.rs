@@ -9,13 +9,22 @@ use crate::{
     resource::model::Model,
     scene::{
         graph::{map::NodeHandleMap, NodePool},
-        sound::{self, effect::Effect, Sound},
+        sound::{
+            self,
+            bus::{AudioBus, MASTER_BUS_NAME},
+            effect::Effect,
+            Sound,
+        },
     },
     utils::log::{Log, MessageKind},
 };
 use fyrox_sound::{
     context::DistanceModel,
-    effects::{reverb::Reverb, BaseEffect, EffectInput, InputFilter},
+    effects::{
+        chorus::Chorus, compressor::Compressor, delay::Delay, distortion::Distortion,
+        equalizer::Equalizer, flanger::Flanger, reverb::Reverb, BaseEffect, Effect as NativeEffect,
+        EffectInput, InputFilter,
+    },
     renderer::Renderer,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
@@ -30,6 +39,12 @@ pub struct SoundContext {
     paused: bool,
     #[reflect(hidden)]
     pub(crate) effects: Pool<Effect>,
+    #[visit(optional)] // Backward compatibility
+    #[reflect(hidden)]
+    pub(crate) buses: Pool<AudioBus>,
+    #[visit(optional)] // Backward compatibility
+    #[reflect(hidden)]
+    master_bus: Handle<AudioBus>,
     #[reflect(read_only)]
     // A model resource from which this context was instantiated from.
     pub(crate) resource: Option<Model>,
@@ -40,18 +55,88 @@ pub struct SoundContext {
 
 impl Default for SoundContext {
     fn default() -> Self {
+        let mut buses = Pool::new();
+        let mut master_bus = AudioBus::default();
+        master_bus.set_name(MASTER_BUS_NAME);
+        let master_bus = buses.spawn(master_bus);
+
         Self {
             master_gain: 1.0,
             renderer: Default::default(),
             distance_model: Default::default(),
             paused: false,
             effects: Default::default(),
+            buses,
+            master_bus,
             resource: None,
             native: fyrox_sound::context::SoundContext::new(),
         }
     }
 }
 
+/// Creates native effect with the same parameters as the given scene effect. Gain and inputs are
+/// not set.
+fn make_native_effect(effect: &Effect) -> NativeEffect {
+    match effect {
+        Effect::Reverb(reverb) => {
+            let mut native_reverb = Reverb::new(BaseEffect::default());
+            native_reverb.set_fc(reverb.fc());
+            native_reverb.set_decay_time(Duration::from_secs_f32(reverb.decay_time()));
+            native_reverb.set_dry(reverb.dry());
+            native_reverb.set_wet(reverb.wet());
+            NativeEffect::Reverb(native_reverb)
+        }
+        Effect::Chorus(chorus) => {
+            let mut native_chorus = Chorus::new(BaseEffect::default());
+            native_chorus.set_dry(chorus.dry());
+            native_chorus.set_wet(chorus.wet());
+            native_chorus.set_delay(Duration::from_secs_f32(chorus.delay()));
+            native_chorus.set_depth(Duration::from_secs_f32(chorus.depth()));
+            native_chorus.set_rate(chorus.rate());
+            NativeEffect::Chorus(native_chorus)
+        }
+        Effect::Flanger(flanger) => {
+            let mut native_flanger = Flanger::new(BaseEffect::default());
+            native_flanger.set_dry(flanger.dry());
+            native_flanger.set_wet(flanger.wet());
+            native_flanger.set_delay(Duration::from_secs_f32(flanger.delay()));
+            native_flanger.set_depth(Duration::from_secs_f32(flanger.depth()));
+            native_flanger.set_rate(flanger.rate());
+            native_flanger.set_feedback(flanger.feedback());
+            NativeEffect::Flanger(native_flanger)
+        }
+        Effect::Delay(delay) => {
+            let mut native_delay = Delay::new(BaseEffect::default());
+            native_delay.set_dry(delay.dry());
+            native_delay.set_wet(delay.wet());
+            native_delay.set_feedback(delay.feedback());
+            native_delay.set_delay_time(Duration::from_secs_f32(delay.delay_time()));
+            NativeEffect::Delay(native_delay)
+        }
+        Effect::Distortion(distortion) => {
+            let mut native_distortion = Distortion::new(BaseEffect::default());
+            native_distortion.set_dry(distortion.dry());
+            native_distortion.set_wet(distortion.wet());
+            native_distortion.set_drive(distortion.drive());
+            NativeEffect::Distortion(native_distortion)
+        }
+        Effect::Compressor(compressor) => {
+            let mut native_compressor = Compressor::new(BaseEffect::default());
+            native_compressor.set_threshold(compressor.threshold());
+            native_compressor.set_ratio(compressor.ratio());
+            native_compressor.set_attack(Duration::from_secs_f32(compressor.attack()));
+            native_compressor.set_release(Duration::from_secs_f32(compressor.release()));
+            native_compressor.set_makeup_gain(compressor.makeup_gain());
+            NativeEffect::Compressor(native_compressor)
+        }
+        Effect::Equalizer(equalizer) => {
+            let mut native_equalizer = Equalizer::new(BaseEffect::default());
+            native_equalizer.set_bands(equalizer.bands().to_vec());
+            NativeEffect::Equalizer(native_equalizer)
+        }
+    }
+}
+
 impl SoundContext {
     pub(crate) fn new() -> Self {
         Default::default()
@@ -112,6 +197,122 @@ impl SoundContext {
         self.effects.alive_count()
     }
 
+    /// Returns handle of the master bus. Master bus is the root of the tree of audio buses, it
+    /// cannot be removed.
+    pub fn master_bus(&self) -> Handle<AudioBus> {
+        self.master_bus
+    }
+
+    /// Adds new audio bus and attaches it to the given parent bus. If the parent is invalid, the
+    /// bus will be attached to the master bus.
+    pub fn add_bus(&mut self, mut bus: AudioBus, parent: Handle<AudioBus>) -> Handle<AudioBus> {
+        let parent = if self.buses.is_valid_handle(parent) {
+            parent
+        } else {
+            self.master_bus
+        };
+        bus.parent = parent;
+        bus.children.clear();
+        bus.native.set(Default::default());
+        let handle = self.buses.spawn(bus);
+        self.buses[parent].children.push(handle);
+        handle
+    }
+
+    /// Removes the bus and all its descendants. Master bus cannot be removed.
+    pub fn remove_bus(&mut self, handle: Handle<AudioBus>) {
+        if handle == self.master_bus || !self.buses.is_valid_handle(handle) {
+            return;
+        }
+
+        let parent = self.buses[handle].parent;
+        if let Some(parent) = self.buses.try_borrow_mut(parent) {
+            parent.children.retain(|c| *c != handle);
+        }
+
+        self.native
+            .state()
+            .bus_graph_mut()
+            .remove_bus(self.buses[handle].native.get());
+
+        let mut stack = vec![handle];
+        while let Some(bus) = stack.pop() {
+            stack.extend_from_slice(&self.buses[bus].children);
+            self.buses.free(bus);
+        }
+    }
+
+    /// Attaches the child bus to the new parent bus. Does nothing if the child is the master bus
+    /// or the parent is a descendant of the child.
+    pub fn link_buses(&mut self, child: Handle<AudioBus>, parent: Handle<AudioBus>) {
+        if child == self.master_bus
+            || !self.buses.is_valid_handle(child)
+            || !self.buses.is_valid_handle(parent)
+        {
+            return;
+        }
+
+        // Prevent cycles.
+        let mut ancestor = parent;
+        while ancestor.is_some() {
+            if ancestor == child {
+                return;
+            }
+            ancestor = self.buses[ancestor].parent;
+        }
+
+        let old_parent = self.buses[child].parent;
+        if let Some(old_parent) = self.buses.try_borrow_mut(old_parent) {
+            old_parent.children.retain(|c| *c != child);
+        }
+        self.buses[child].parent = parent;
+        self.buses[parent].children.push(child);
+
+        self.native.state().bus_graph_mut().link_buses(
+            self.buses[child].native.get(),
+            self.buses[parent].native.get(),
+        );
+    }
+
+    /// Borrows an audio bus.
+    pub fn bus(&self, handle: Handle<AudioBus>) -> &AudioBus {
+        &self.buses[handle]
+    }
+
+    /// Borrows an audio bus as mutable.
+    pub fn bus_mut(&mut self, handle: Handle<AudioBus>) -> &mut AudioBus {
+        &mut self.buses[handle]
+    }
+
+    /// Borrows an audio bus.
+    pub fn try_get_bus(&self, handle: Handle<AudioBus>) -> Option<&AudioBus> {
+        self.buses.try_borrow(handle)
+    }
+
+    /// Borrows an audio bus as mutable.
+    pub fn try_get_bus_mut(&mut self, handle: Handle<AudioBus>) -> Option<&mut AudioBus> {
+        self.buses.try_borrow_mut(handle)
+    }
+
+    /// Searches for an audio bus with the given name, returns [`Handle::NONE`] if there's no
+    /// such bus.
+    pub fn find_bus_by_name(&self, name: &str) -> Handle<AudioBus> {
+        self.buses
+            .pair_iter()
+            .find_map(|(h, b)| if b.name() == name { Some(h) } else { None })
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over all audio buses.
+    pub fn buses(&self) -> impl Iterator<Item = (Handle<AudioBus>, &AudioBus)> {
+        self.buses.pair_iter()
+    }
+
+    /// Returns total amount of audio buses (including the master bus).
+    pub fn buses_count(&self) -> u32 {
+        self.buses.alive_count()
+    }
+
     /// Pause/unpause the sound context. Paused context won't play any sounds.
     pub fn pause(&mut self, pause: bool) {
         self.paused = pause;
@@ -198,38 +399,148 @@ impl SoundContext {
         for effect in self.effects.iter() {
             if effect.native.get().is_some() {
                 let native_effect = state.effect_mut(effect.native.get());
-                if let (
-                    fyrox_sound::effects::Effect::Reverb(native_reverb),
-                    Effect::Reverb(reverb),
-                ) = (native_effect, effect)
-                {
-                    reverb.decay_time.try_sync_model(|v| {
-                        native_reverb.set_decay_time(Duration::from_secs_f32(v))
-                    });
-                    reverb.gain.try_sync_model(|v| native_reverb.set_gain(v));
-                    reverb.wet.try_sync_model(|v| native_reverb.set_wet(v));
-                    reverb.dry.try_sync_model(|v| native_reverb.set_dry(v));
-                    reverb.fc.try_sync_model(|v| native_reverb.set_fc(v));
-                    reverb.inputs.try_sync_model(|v| {
-                        native_reverb.clear_inputs();
-                        sync_effect_inputs(native_reverb, &v, nodes)
-                    });
-                }
-            } else {
-                match effect {
-                    Effect::Reverb(reverb) => {
-                        let mut native_reverb = Reverb::new(BaseEffect::default());
-                        native_reverb.set_gain(reverb.gain());
-                        native_reverb.set_fc(reverb.fc());
-                        native_reverb.set_decay_time(Duration::from_secs_f32(reverb.decay_time()));
-                        native_reverb.set_dry(reverb.dry());
-                        native_reverb.set_wet(reverb.wet());
-                        sync_effect_inputs(&mut native_reverb, &reverb.inputs, nodes);
-                        let native =
-                            state.add_effect(fyrox_sound::effects::Effect::Reverb(native_reverb));
-                        reverb.native.set(native);
+                effect.gain.try_sync_model(|v| native_effect.set_gain(v));
+                effect.inputs.try_sync_model(|v| {
+                    native_effect.clear_inputs();
+                    sync_effect_inputs(native_effect, &v, nodes)
+                });
+                match (native_effect, effect) {
+                    (NativeEffect::Reverb(native_reverb), Effect::Reverb(reverb)) => {
+                        reverb.decay_time.try_sync_model(|v| {
+                            native_reverb.set_decay_time(Duration::from_secs_f32(v))
+                        });
+                        reverb.wet.try_sync_model(|v| native_reverb.set_wet(v));
+                        reverb.dry.try_sync_model(|v| native_reverb.set_dry(v));
+                        reverb.fc.try_sync_model(|v| native_reverb.set_fc(v));
+                    }
+                    (NativeEffect::Chorus(native_chorus), Effect::Chorus(chorus)) => {
+                        chorus.dry.try_sync_model(|v| native_chorus.set_dry(v));
+                        chorus.wet.try_sync_model(|v| native_chorus.set_wet(v));
+                        chorus.delay.try_sync_model(|v| {
+                            native_chorus.set_delay(Duration::from_secs_f32(v))
+                        });
+                        chorus.depth.try_sync_model(|v| {
+                            native_chorus.set_depth(Duration::from_secs_f32(v))
+                        });
+                        chorus.rate.try_sync_model(|v| native_chorus.set_rate(v));
+                    }
+                    (NativeEffect::Flanger(native_flanger), Effect::Flanger(flanger)) => {
+                        flanger.dry.try_sync_model(|v| native_flanger.set_dry(v));
+                        flanger.wet.try_sync_model(|v| native_flanger.set_wet(v));
+                        flanger.delay.try_sync_model(|v| {
+                            native_flanger.set_delay(Duration::from_secs_f32(v))
+                        });
+                        flanger.depth.try_sync_model(|v| {
+                            native_flanger.set_depth(Duration::from_secs_f32(v))
+                        });
+                        flanger.rate.try_sync_model(|v| native_flanger.set_rate(v));
+                        flanger
+                            .feedback
+                            .try_sync_model(|v| native_flanger.set_feedback(v));
+                    }
+                    (NativeEffect::Delay(native_delay), Effect::Delay(delay)) => {
+                        delay.dry.try_sync_model(|v| native_delay.set_dry(v));
+                        delay.wet.try_sync_model(|v| native_delay.set_wet(v));
+                        delay
+                            .feedback
+                            .try_sync_model(|v| native_delay.set_feedback(v));
+                        delay.delay_time.try_sync_model(|v| {
+                            native_delay.set_delay_time(Duration::from_secs_f32(v))
+                        });
                     }
+                    (
+                        NativeEffect::Distortion(native_distortion),
+                        Effect::Distortion(distortion),
+                    ) => {
+                        distortion
+                            .dry
+                            .try_sync_model(|v| native_distortion.set_dry(v));
+                        distortion
+                            .wet
+                            .try_sync_model(|v| native_distortion.set_wet(v));
+                        distortion
+                            .drive
+                            .try_sync_model(|v| native_distortion.set_drive(v));
+                    }
+                    (
+                        NativeEffect::Compressor(native_compressor),
+                        Effect::Compressor(compressor),
+                    ) => {
+                        compressor
+                            .threshold
+                            .try_sync_model(|v| native_compressor.set_threshold(v));
+                        compressor
+                            .ratio
+                            .try_sync_model(|v| native_compressor.set_ratio(v));
+                        compressor.attack.try_sync_model(|v| {
+                            native_compressor.set_attack(Duration::from_secs_f32(v))
+                        });
+                        compressor.release.try_sync_model(|v| {
+                            native_compressor.set_release(Duration::from_secs_f32(v))
+                        });
+                        compressor
+                            .makeup_gain
+                            .try_sync_model(|v| native_compressor.set_makeup_gain(v));
+                    }
+                    (NativeEffect::Equalizer(native_equalizer), Effect::Equalizer(equalizer)) => {
+                        equalizer
+                            .bands
+                            .try_sync_model(|v| native_equalizer.set_bands(v));
+                    }
+                    _ => (),
                 }
+            } else {
+                let mut native_effect = make_native_effect(effect);
+                native_effect.set_gain(effect.gain());
+                sync_effect_inputs(&mut native_effect, &effect.inputs, nodes);
+                let native = state.add_effect(native_effect);
+                effect.native.set(native);
+            }
+        }
+
+        let map_effects = |effects: &[Handle<Effect>]| {
+            effects
+                .iter()
+                .filter_map(|e| self.effects.try_borrow(*e).map(|e| e.native.get()))
+                .collect::<Vec<_>>()
+        };
+
+        // Parents must be synced before their children, so the tree is traversed from the root.
+        let mut stack = vec![self.master_bus];
+        while let Some(handle) = stack.pop() {
+            let bus = match self.buses.try_borrow(handle) {
+                Some(bus) => bus,
+                None => continue,
+            };
+            stack.extend_from_slice(&bus.children);
+
+            let bus_graph = state.bus_graph_mut();
+            if bus.native.get().is_none() {
+                let native = if handle == self.master_bus {
+                    bus_graph.root()
+                } else {
+                    let parent = self
+                        .buses
+                        .try_borrow(bus.parent)
+                        .map(|p| p.native.get())
+                        .unwrap_or_default();
+                    bus_graph.add_bus(fyrox_sound::bus::AudioBus::new(bus.name()), parent)
+                };
+                bus.native.set(native);
+
+                let native_bus = bus_graph.bus_mut(native);
+                native_bus.set_name(bus.name());
+                native_bus.set_gain(bus.gain());
+                native_bus.set_muted(bus.is_muted());
+                native_bus.set_soloed(bus.is_soloed());
+                native_bus.set_effects(map_effects(bus.effects()));
+            } else if let Some(native_bus) = bus_graph.try_get_bus_mut(bus.native.get()) {
+                bus.name.try_sync_model(|v| native_bus.set_name(v));
+                bus.gain.try_sync_model(|v| native_bus.set_gain(v));
+                bus.muted.try_sync_model(|v| native_bus.set_muted(v));
+                bus.soloed.try_sync_model(|v| native_bus.set_soloed(v));
+                bus.effects
+                    .try_sync_model(|v| native_bus.set_effects(map_effects(&v)));
             }
         }
     }
@@ -269,7 +580,7 @@ impl SoundContext {
                 source.set_radius(v);
             });
             sound.playback_time.try_sync_model(|v| {
-                source.set_playback_time(v);
+                Log::verify(source.set_playback_time(v));
             });
             sound.pitch.try_sync_model(|v| {
                 source.set_pitch(v);
@@ -286,6 +597,7 @@ impl SoundContext {
             sound
                 .spatial_blend
                 .try_sync_model(|v| source.set_spatial_blend(v));
+            sound.audio_bus.try_sync_model(|v| source.set_bus(v));
             sound.status.try_sync_model(|v| match v {
                 Status::Stopped => {
                     Log::verify(source.stop());
@@ -310,6 +622,7 @@ impl SoundContext {
                 .with_radius(sound.radius())
                 .with_max_distance(sound.max_distance())
                 .with_rolloff_factor(sound.rolloff_factor())
+                .with_bus(sound.audio_bus())
                 .build()
             {
                 Ok(source) => {
@@ -15,6 +15,8 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub use fyrox_sound::effects::equalizer::{EqualizerBand, EqualizerBandKind};
+
 const DEFAULT_FC: f32 = 0.25615; // 11296 Hz at 44100 Hz sample rate
 
 /// Effect input allows you to setup a source of samples for an effect with an optional filtering.
@@ -105,6 +107,18 @@ impl Default for BaseEffect {
 pub enum Effect {
     /// See [`ReverbEffect`] docs.
     Reverb(ReverbEffect),
+    /// See [`ChorusEffect`] docs.
+    Chorus(ChorusEffect),
+    /// See [`FlangerEffect`] docs.
+    Flanger(FlangerEffect),
+    /// See [`DelayEffect`] docs.
+    Delay(DelayEffect),
+    /// See [`DistortionEffect`] docs.
+    Distortion(DistortionEffect),
+    /// See [`CompressorEffect`] docs.
+    Compressor(CompressorEffect),
+    /// See [`EqualizerEffect`] docs.
+    Equalizer(EqualizerEffect),
 }
 
 impl Deref for Effect {
@@ -113,6 +127,12 @@ impl Deref for Effect {
     fn deref(&self) -> &Self::Target {
         match self {
             Effect::Reverb(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Flanger(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Distortion(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
@@ -121,6 +141,12 @@ impl DerefMut for Effect {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
             Effect::Reverb(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Flanger(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Distortion(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
@@ -133,6 +159,12 @@ impl Default for Effect {
 
 impl Effect {
     define_is_as!(Effect : Reverb -> ref ReverbEffect => fn is_reverb, fn as_reverb, fn as_reverb_mut);
+    define_is_as!(Effect : Chorus -> ref ChorusEffect => fn is_chorus, fn as_chorus, fn as_chorus_mut);
+    define_is_as!(Effect : Flanger -> ref FlangerEffect => fn is_flanger, fn as_flanger, fn as_flanger_mut);
+    define_is_as!(Effect : Delay -> ref DelayEffect => fn is_delay, fn as_delay, fn as_delay_mut);
+    define_is_as!(Effect : Distortion -> ref DistortionEffect => fn is_distortion, fn as_distortion, fn as_distortion_mut);
+    define_is_as!(Effect : Compressor -> ref CompressorEffect => fn is_compressor, fn as_compressor, fn as_compressor_mut);
+    define_is_as!(Effect : Equalizer -> ref EqualizerEffect => fn is_equalizer, fn as_equalizer, fn as_equalizer_mut);
 }
 
 /// Base effect builder allows you to build an effect.
@@ -343,3 +375,889 @@ impl ReverbEffectBuilder {
         context.add_effect(self.build_effect())
     }
 }
+
+/// Chorus effect mixes a signal with its copy delayed by slowly changing amount of time, this makes
+/// sound "thicker" and wider.
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct ChorusEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_dry")]
+    pub(crate) dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wet")]
+    pub(crate) wet: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_delay")]
+    pub(crate) delay: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_depth")]
+    pub(crate) depth: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_rate")]
+    pub(crate) rate: InheritableVariable<f32>,
+}
+
+impl Default for ChorusEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            dry: InheritableVariable::new(1.0),
+            wet: InheritableVariable::new(0.5),
+            delay: InheritableVariable::new(0.02),
+            depth: InheritableVariable::new(0.005),
+            rate: InheritableVariable::new(1.5),
+        }
+    }
+}
+
+impl Deref for ChorusEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ChorusEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl ChorusEffect {
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set_value_and_mark_modified(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output. Default value is 0.5.
+    pub fn set_wet(&mut self, wet: f32) -> f32 {
+        self.wet.set_value_and_mark_modified(wet.clamp(0.0, 1.0))
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+
+    /// Sets average delay (in seconds) of modulated signal. Typical values are in 0.01-0.03 range.
+    pub fn set_delay(&mut self, delay: f32) -> f32 {
+        self.delay.set_value_and_mark_modified(delay.max(0.0))
+    }
+
+    /// Returns average delay (in seconds) of modulated signal.
+    pub fn delay(&self) -> f32 {
+        *self.delay
+    }
+
+    /// Sets maximum deviation (in seconds) of delay from its average value.
+    pub fn set_depth(&mut self, depth: f32) -> f32 {
+        self.depth.set_value_and_mark_modified(depth.max(0.0))
+    }
+
+    /// Returns maximum deviation (in seconds) of delay from its average value.
+    pub fn depth(&self) -> f32 {
+        *self.depth
+    }
+
+    /// Sets frequency (in Hz) of delay modulation. Typical values are in 0.1-3 Hz range.
+    pub fn set_rate(&mut self, rate: f32) -> f32 {
+        self.rate.set_value_and_mark_modified(rate.max(0.0))
+    }
+
+    /// Returns frequency (in Hz) of delay modulation.
+    pub fn rate(&self) -> f32 {
+        *self.rate
+    }
+}
+
+/// Allows you to create a new chorus effect.
+pub struct ChorusEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    dry: f32,
+    wet: f32,
+    delay: f32,
+    depth: f32,
+    rate: f32,
+}
+
+impl ChorusEffectBuilder {
+    /// Creates new chorus effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            dry: 1.0,
+            wet: 0.5,
+            delay: 0.02,
+            depth: 0.005,
+            rate: 1.5,
+        }
+    }
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    define_with!(
+        /// Sets desired average delay (in seconds).
+        fn with_delay(delay: f32)
+    );
+
+    define_with!(
+        /// Sets desired maximum deviation of delay (in seconds).
+        fn with_depth(depth: f32)
+    );
+
+    define_with!(
+        /// Sets desired frequency of delay modulation (in Hz).
+        fn with_rate(rate: f32)
+    );
+
+    /// Creates new chorus effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Chorus(ChorusEffect {
+            base: self.base_builder.build(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+            delay: self.delay.into(),
+            depth: self.depth.into(),
+            rate: self.rate.into(),
+        })
+    }
+
+    /// Creates new chorus effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Flanger effect mixes a signal with its slightly delayed copy with feedback, the delay is
+/// sweeping and gives characteristic "jet plane" sound.
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct FlangerEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_dry")]
+    pub(crate) dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wet")]
+    pub(crate) wet: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_delay")]
+    pub(crate) delay: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_depth")]
+    pub(crate) depth: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_rate")]
+    pub(crate) rate: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_feedback")]
+    pub(crate) feedback: InheritableVariable<f32>,
+}
+
+impl Default for FlangerEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            dry: InheritableVariable::new(1.0),
+            wet: InheritableVariable::new(0.7),
+            delay: InheritableVariable::new(0.001),
+            depth: InheritableVariable::new(0.003),
+            rate: InheritableVariable::new(0.25),
+            feedback: InheritableVariable::new(0.5),
+        }
+    }
+}
+
+impl Deref for FlangerEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for FlangerEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl FlangerEffect {
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set_value_and_mark_modified(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output. Default value is 0.7.
+    pub fn set_wet(&mut self, wet: f32) -> f32 {
+        self.wet.set_value_and_mark_modified(wet.clamp(0.0, 1.0))
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+
+    /// Sets minimal delay (in seconds) of modulated signal. Typical values are in 0-0.005 range.
+    pub fn set_delay(&mut self, delay: f32) -> f32 {
+        self.delay.set_value_and_mark_modified(delay.max(0.0))
+    }
+
+    /// Returns minimal delay (in seconds) of modulated signal.
+    pub fn delay(&self) -> f32 {
+        *self.delay
+    }
+
+    /// Sets range (in seconds) of delay modulation, delay will sweep from `delay` to `delay + depth`.
+    pub fn set_depth(&mut self, depth: f32) -> f32 {
+        self.depth.set_value_and_mark_modified(depth.max(0.0))
+    }
+
+    /// Returns range (in seconds) of delay modulation.
+    pub fn depth(&self) -> f32 {
+        *self.depth
+    }
+
+    /// Sets frequency (in Hz) of delay modulation. Typical values are in 0.05-1 Hz range.
+    pub fn set_rate(&mut self, rate: f32) -> f32 {
+        self.rate.set_value_and_mark_modified(rate.max(0.0))
+    }
+
+    /// Returns frequency (in Hz) of delay modulation.
+    pub fn rate(&self) -> f32 {
+        *self.rate
+    }
+
+    /// Sets how much of delayed signal should be fed back to the input. Should be in `[0; 1)` range.
+    pub fn set_feedback(&mut self, feedback: f32) -> f32 {
+        self.feedback
+            .set_value_and_mark_modified(feedback.clamp(0.0, 0.99))
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        *self.feedback
+    }
+}
+
+/// Allows you to create a new flanger effect.
+pub struct FlangerEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    dry: f32,
+    wet: f32,
+    delay: f32,
+    depth: f32,
+    rate: f32,
+    feedback: f32,
+}
+
+impl FlangerEffectBuilder {
+    /// Creates new flanger effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            dry: 1.0,
+            wet: 0.7,
+            delay: 0.001,
+            depth: 0.003,
+            rate: 0.25,
+            feedback: 0.5,
+        }
+    }
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    define_with!(
+        /// Sets desired minimal delay (in seconds).
+        fn with_delay(delay: f32)
+    );
+
+    define_with!(
+        /// Sets desired range of delay modulation (in seconds).
+        fn with_depth(depth: f32)
+    );
+
+    define_with!(
+        /// Sets desired frequency of delay modulation (in Hz).
+        fn with_rate(rate: f32)
+    );
+
+    define_with!(
+        /// Sets desired feedback.
+        fn with_feedback(feedback: f32)
+    );
+
+    /// Creates new flanger effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Flanger(FlangerEffect {
+            base: self.base_builder.build(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+            delay: self.delay.into(),
+            depth: self.depth.into(),
+            rate: self.rate.into(),
+            feedback: self.feedback.into(),
+        })
+    }
+
+    /// Creates new flanger effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Delay effect repeats a signal after some period of time, every next repetition is quieter than
+/// previous one. It can be used to simulate echo in large open spaces.
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct DelayEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_dry")]
+    pub(crate) dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wet")]
+    pub(crate) wet: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_feedback")]
+    pub(crate) feedback: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_delay_time")]
+    pub(crate) delay_time: InheritableVariable<f32>,
+}
+
+impl Default for DelayEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            dry: InheritableVariable::new(1.0),
+            wet: InheritableVariable::new(1.0),
+            feedback: InheritableVariable::new(0.5),
+            delay_time: InheritableVariable::new(0.25),
+        }
+    }
+}
+
+impl Deref for DelayEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for DelayEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl DelayEffect {
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set_value_and_mark_modified(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of delayed signal should be passed to output. Default value is 1.0.
+    pub fn set_wet(&mut self, wet: f32) -> f32 {
+        self.wet.set_value_and_mark_modified(wet.clamp(0.0, 1.0))
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+
+    /// Sets how much of delayed signal should be fed back to the input, in other words it defines
+    /// how fast echoes will fade out. Should be in `[0; 1)` range.
+    pub fn set_feedback(&mut self, feedback: f32) -> f32 {
+        self.feedback
+            .set_value_and_mark_modified(feedback.clamp(0.0, 0.99))
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        *self.feedback
+    }
+
+    /// Sets time (in seconds) between repetitions.
+    pub fn set_delay_time(&mut self, delay_time: f32) -> f32 {
+        self.delay_time
+            .set_value_and_mark_modified(delay_time.max(0.0))
+    }
+
+    /// Returns time (in seconds) between repetitions.
+    pub fn delay_time(&self) -> f32 {
+        *self.delay_time
+    }
+}
+
+/// Allows you to create a new delay effect.
+pub struct DelayEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    dry: f32,
+    wet: f32,
+    feedback: f32,
+    delay_time: f32,
+}
+
+impl DelayEffectBuilder {
+    /// Creates new delay effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            dry: 1.0,
+            wet: 1.0,
+            feedback: 0.5,
+            delay_time: 0.25,
+        }
+    }
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    define_with!(
+        /// Sets desired feedback.
+        fn with_feedback(feedback: f32)
+    );
+
+    define_with!(
+        /// Sets desired time between repetitions (in seconds).
+        fn with_delay_time(delay_time: f32)
+    );
+
+    /// Creates new delay effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Delay(DelayEffect {
+            base: self.base_builder.build(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+            feedback: self.feedback.into(),
+            delay_time: self.delay_time.into(),
+        })
+    }
+
+    /// Creates new delay effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Distortion effect amplifies a signal and softly clips it, giving it "dirty" overdriven sound.
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct DistortionEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_dry")]
+    pub(crate) dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wet")]
+    pub(crate) wet: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_drive")]
+    pub(crate) drive: InheritableVariable<f32>,
+}
+
+impl Default for DistortionEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            dry: InheritableVariable::new(0.0),
+            wet: InheritableVariable::new(1.0),
+            drive: InheritableVariable::new(10.0),
+        }
+    }
+}
+
+impl Deref for DistortionEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for DistortionEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl DistortionEffect {
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 0.0.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set_value_and_mark_modified(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of distorted signal should be passed to output. Default value is 1.0.
+    pub fn set_wet(&mut self, wet: f32) -> f32 {
+        self.wet.set_value_and_mark_modified(wet.clamp(0.0, 1.0))
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+
+    /// Sets amplification of input signal before clipping. The higher the value, the more "dirty"
+    /// the sound will be.
+    pub fn set_drive(&mut self, drive: f32) -> f32 {
+        self.drive.set_value_and_mark_modified(drive.max(0.0))
+    }
+
+    /// Returns current drive.
+    pub fn drive(&self) -> f32 {
+        *self.drive
+    }
+}
+
+/// Allows you to create a new distortion effect.
+pub struct DistortionEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    dry: f32,
+    wet: f32,
+    drive: f32,
+}
+
+impl DistortionEffectBuilder {
+    /// Creates new distortion effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            dry: 0.0,
+            wet: 1.0,
+            drive: 10.0,
+        }
+    }
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    define_with!(
+        /// Sets desired drive.
+        fn with_drive(drive: f32)
+    );
+
+    /// Creates new distortion effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Distortion(DistortionEffect {
+            base: self.base_builder.build(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+            drive: self.drive.into(),
+        })
+    }
+
+    /// Creates new distortion effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Compressor effect attenuates parts of a signal that are louder than some threshold. With high
+/// ratio and short attack time it acts as a limiter.
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct CompressorEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_threshold")]
+    pub(crate) threshold: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_ratio")]
+    pub(crate) ratio: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_attack")]
+    pub(crate) attack: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_release")]
+    pub(crate) release: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_makeup_gain")]
+    pub(crate) makeup_gain: InheritableVariable<f32>,
+}
+
+impl Default for CompressorEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            threshold: InheritableVariable::new(-20.0),
+            ratio: InheritableVariable::new(4.0),
+            attack: InheritableVariable::new(0.005),
+            release: InheritableVariable::new(0.1),
+            makeup_gain: InheritableVariable::new(0.0),
+        }
+    }
+}
+
+impl Deref for CompressorEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CompressorEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl CompressorEffect {
+    /// Sets level (in decibels) above which the signal will be attenuated.
+    pub fn set_threshold(&mut self, threshold: f32) -> f32 {
+        self.threshold
+            .set_value_and_mark_modified(threshold.min(0.0))
+    }
+
+    /// Returns current threshold in decibels.
+    pub fn threshold(&self) -> f32 {
+        *self.threshold
+    }
+
+    /// Sets compression ratio, for example ratio of 4.0 means that every 4 dB above threshold will
+    /// become 1 dB. Use high values (20.0 and more) to turn compressor into limiter.
+    pub fn set_ratio(&mut self, ratio: f32) -> f32 {
+        self.ratio.set_value_and_mark_modified(ratio.max(1.0))
+    }
+
+    /// Returns compression ratio.
+    pub fn ratio(&self) -> f32 {
+        *self.ratio
+    }
+
+    /// Sets time (in seconds) that compressor needs to react on loud signals.
+    pub fn set_attack(&mut self, attack: f32) -> f32 {
+        self.attack.set_value_and_mark_modified(attack.max(0.0))
+    }
+
+    /// Returns attack time in seconds.
+    pub fn attack(&self) -> f32 {
+        *self.attack
+    }
+
+    /// Sets time (in seconds) that compressor needs to stop attenuating the signal after it went
+    /// below the threshold.
+    pub fn set_release(&mut self, release: f32) -> f32 {
+        self.release.set_value_and_mark_modified(release.max(0.0))
+    }
+
+    /// Returns release time in seconds.
+    pub fn release(&self) -> f32 {
+        *self.release
+    }
+
+    /// Sets gain (in decibels) that will be applied to compressed signal to compensate loss of
+    /// loudness.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) -> f32 {
+        self.makeup_gain.set_value_and_mark_modified(makeup_gain)
+    }
+
+    /// Returns makeup gain in decibels.
+    pub fn makeup_gain(&self) -> f32 {
+        *self.makeup_gain
+    }
+}
+
+/// Allows you to create a new compressor effect.
+pub struct CompressorEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    threshold: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+    makeup_gain: f32,
+}
+
+impl CompressorEffectBuilder {
+    /// Creates new compressor effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            threshold: -20.0,
+            ratio: 4.0,
+            attack: 0.005,
+            release: 0.1,
+            makeup_gain: 0.0,
+        }
+    }
+
+    define_with!(
+        /// Sets desired threshold (in decibels).
+        fn with_threshold(threshold: f32)
+    );
+
+    define_with!(
+        /// Sets desired compression ratio.
+        fn with_ratio(ratio: f32)
+    );
+
+    define_with!(
+        /// Sets desired attack time (in seconds).
+        fn with_attack(attack: f32)
+    );
+
+    define_with!(
+        /// Sets desired release time (in seconds).
+        fn with_release(release: f32)
+    );
+
+    define_with!(
+        /// Sets desired makeup gain (in decibels).
+        fn with_makeup_gain(makeup_gain: f32)
+    );
+
+    /// Creates new compressor effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Compressor(CompressorEffect {
+            base: self.base_builder.build(),
+            threshold: self.threshold.into(),
+            ratio: self.ratio.into(),
+            attack: self.attack.into(),
+            release: self.release.into(),
+            makeup_gain: self.makeup_gain.into(),
+        })
+    }
+
+    /// Creates new compressor effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Parametric equalizer allows you to boost or cut specific frequency ranges of a signal.
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct EqualizerEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_bands")]
+    pub(crate) bands: InheritableVariable<Vec<EqualizerBand>>,
+}
+
+impl Default for EqualizerEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            bands: InheritableVariable::new(Default::default()),
+        }
+    }
+}
+
+impl Deref for EqualizerEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for EqualizerEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl EqualizerEffect {
+    /// Sets new bands of the equalizer. Bands are applied one after another.
+    pub fn set_bands(&mut self, bands: Vec<EqualizerBand>) -> Vec<EqualizerBand> {
+        self.bands.set_value_and_mark_modified(bands)
+    }
+
+    /// Returns shared reference to the bands of the equalizer.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+
+    /// Returns mutable reference to the bands of the equalizer.
+    pub fn bands_mut(&mut self) -> &mut Vec<EqualizerBand> {
+        self.bands.get_value_mut_and_mark_modified()
+    }
+}
+
+/// Allows you to create a new equalizer effect.
+pub struct EqualizerEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    bands: Vec<EqualizerBand>,
+}
+
+impl EqualizerEffectBuilder {
+    /// Creates new equalizer effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            bands: Default::default(),
+        }
+    }
+
+    define_with!(
+        /// Sets desired bands of the equalizer.
+        fn with_bands(bands: Vec<EqualizerBand>)
+    );
+
+    /// Creates new equalizer effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Equalizer(EqualizerEffect {
+            base: self.base_builder.build(),
+            bands: self.bands.into(),
+        })
+    }
+
+    /// Creates new equalizer effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
@@ -39,6 +39,7 @@ use std::{
     time::Duration,
 };
 
+pub mod bus;
 pub mod context;
 pub mod effect;
 pub mod listener;
@@ -90,6 +91,10 @@ pub struct Sound {
     #[reflect(setter = "set_spatial_blend")]
     spatial_blend: InheritableVariable<f32>,
 
+    #[visit(optional)] // Backward compatibility
+    #[reflect(setter = "set_audio_bus")]
+    audio_bus: InheritableVariable<String>,
+
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) native: Cell<Handle<SoundSource>>,
@@ -125,6 +130,7 @@ impl Default for Sound {
             rolloff_factor: InheritableVariable::new(1.0),
             playback_time: Default::default(),
             spatial_blend: InheritableVariable::new(1.0),
+            audio_bus: Default::default(),
             native: Default::default(),
         }
     }
@@ -146,6 +152,7 @@ impl Clone for Sound {
             rolloff_factor: self.rolloff_factor.clone(),
             playback_time: self.playback_time.clone(),
             spatial_blend: self.spatial_blend.clone(),
+            audio_bus: self.audio_bus.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Default::default(),
         }
@@ -327,6 +334,18 @@ impl Sound {
     pub fn max_distance(&self) -> f32 {
         *self.max_distance
     }
+
+    /// Sets the name of an audio bus to which the sound will be routed. Empty name (default) or
+    /// a name of non-existing bus routes the sound to the master bus. See [`bus`] module docs for
+    /// more info.
+    pub fn set_audio_bus(&mut self, audio_bus: String) -> String {
+        self.audio_bus.set_value_and_mark_modified(audio_bus)
+    }
+
+    /// Returns the name of an audio bus to which the sound is routed.
+    pub fn audio_bus(&self) -> &str {
+        &self.audio_bus
+    }
 }
 
 impl NodeTrait for Sound {
@@ -397,6 +416,7 @@ pub struct SoundBuilder {
     rolloff_factor: f32,
     playback_time: Duration,
     spatial_blend: f32,
+    audio_bus: String,
 }
 
 impl SoundBuilder {
@@ -416,6 +436,7 @@ impl SoundBuilder {
             rolloff_factor: 1.0,
             spatial_blend: 1.0,
             playback_time: Default::default(),
+            audio_bus: Default::default(),
         }
     }
 
@@ -479,6 +500,11 @@ impl SoundBuilder {
         fn with_playback_time(playback_time: Duration)
     );
 
+    define_with!(
+        /// Sets desired audio bus. See [`Sound::set_audio_bus`] for more info.
+        fn with_audio_bus(audio_bus: String)
+    );
+
     /// Creates a new [`Sound`] node.
     #[must_use]
     pub fn build_sound(self) -> Sound {
@@ -496,6 +522,7 @@ impl SoundBuilder {
             rolloff_factor: self.rolloff_factor.into(),
             playback_time: self.playback_time.into(),
             spatial_blend: self.spatial_blend.into(),
+            audio_bus: self.audio_bus.into(),
             native: Default::default(),
         }
     }
@@ -0,0 +1,676 @@
+//! Vehicle is a dynamic rigid body with a set of raycast wheels. See [`Vehicle`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    scene::{
+        base::Base,
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext, TypeUuidProvider, UpdateContext},
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+        Scene,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Wheel of a vehicle. The wheel does not have a collider, instead it casts a ray from its connection point
+/// along the down axis of the chassis to find the ground and simulates a spring-damper suspension and tire
+/// friction at the contact point.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct Wheel {
+    /// A point (in local coordinates of the chassis) at which the suspension is attached to the chassis.
+    pub connection_point: Vector3<f32>,
+    /// Radius of the wheel.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub radius: f32,
+    /// Length of the suspension when it is not loaded.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub suspension_rest_length: f32,
+    /// Stiffness of the suspension spring. Spring force is scaled by the mass of the chassis, so the same
+    /// value gives similar behaviour for light and heavy vehicles.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub suspension_stiffness: f32,
+    /// Damping of the suspension, it is scaled by the mass of the chassis too. Higher values decreases
+    /// oscillations of the chassis.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub suspension_damping: f32,
+    /// Maximum force that can be applied by the suspension.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub max_suspension_force: f32,
+    /// Friction coefficient of the tire. Maximum friction force of the wheel is the suspension force
+    /// multiplied by this coefficient.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub friction_slip: f32,
+    /// Defines how much of the sideways velocity of the wheel is cancelled by the tire friction on each step.
+    /// `1.0` - the wheel does not skid at all (unless the friction limit is exceeded), `0.0` - the wheel slides
+    /// sideways freely.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub side_friction_stiffness: f32,
+    /// Whether the wheel is turned by the steering input or not.
+    pub steering: bool,
+    /// Whether the wheel is driven by the engine or not.
+    pub drive: bool,
+    /// Whether the wheel is affected by the brake input or not.
+    pub brake: bool,
+    /// An optional handle of a node that will be used as visual representation of the wheel. The vehicle
+    /// moves, steers and spins the node each frame, so it must be a direct child of the vehicle.
+    pub visual: Handle<Node>,
+}
+
+impl Default for Wheel {
+    fn default() -> Self {
+        Self {
+            connection_point: Default::default(),
+            radius: 0.35,
+            suspension_rest_length: 0.3,
+            suspension_stiffness: 40.0,
+            suspension_damping: 4.0,
+            max_suspension_force: 10000.0,
+            friction_slip: 1.5,
+            side_friction_stiffness: 1.0,
+            steering: false,
+            drive: false,
+            brake: true,
+            visual: Default::default(),
+        }
+    }
+}
+
+/// Runtime state of a wheel, it is calculated by the physics on each step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WheelState {
+    /// `true` if the wheel touches the ground, `false` - otherwise.
+    pub in_contact: bool,
+    /// A handle of the collider on which the wheel stands.
+    pub ground: Handle<Node>,
+    /// A point of contact with the ground in world coordinates.
+    pub contact_point: Vector3<f32>,
+    /// Normal of the ground at the contact point in world coordinates.
+    pub contact_normal: Vector3<f32>,
+    /// Current length of the suspension.
+    pub suspension_length: f32,
+    /// Force that the suspension applied to the chassis on the last step.
+    pub suspension_force: f32,
+    /// Current steering angle (in radians) of the wheel.
+    pub steering_angle: f32,
+    /// Rotation angle (in radians) of the wheel around its axle.
+    pub rotation: f32,
+    /// Angular velocity (in radians per second) of the wheel around its axle.
+    pub angular_velocity: f32,
+}
+
+/// Vehicle is a dynamic rigid body (chassis) with a set of raycast [`Wheel`]s. It simulates suspension,
+/// tire friction and engine/brake forces each physics step, which is enough for most of arcade- and
+/// semi-realistic driving games.
+///
+/// # Coordinate system
+///
+/// Vehicle uses local coordinate system of its chassis: `+Z` is the forward direction, `+Y` is the up
+/// direction and `+X` is the axle direction. Suspension of each wheel goes down along `-Y` from the wheel's
+/// connection point.
+///
+/// # Controls
+///
+/// Vehicle is controlled by three inputs: throttle ([`Vehicle::set_throttle`]), brake ([`Vehicle::set_brake`])
+/// and steering ([`Vehicle::set_steering`]). The inputs are not serialized and should be set by a script each
+/// frame (or when they change). Negative throttle moves the vehicle backwards.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector3, pool::Handle},
+///     scene::{
+///         base::BaseBuilder,
+///         collider::{ColliderBuilder, ColliderShape},
+///         graph::Graph,
+///         node::Node,
+///         rigidbody::RigidBodyBuilder,
+///         vehicle::{VehicleBuilder, Wheel},
+///     },
+/// };
+///
+/// fn create_car(graph: &mut Graph) -> Handle<Node> {
+///     let wheel = |x: f32, z: f32, front: bool| Wheel {
+///         connection_point: Vector3::new(x, -0.2, z),
+///         steering: front,
+///         drive: !front,
+///         ..Default::default()
+///     };
+///
+///     VehicleBuilder::new(RigidBodyBuilder::new(
+///         BaseBuilder::new().with_children(&[ColliderBuilder::new(BaseBuilder::new())
+///             .with_shape(ColliderShape::cuboid(0.9, 0.4, 2.0))
+///             .build(graph)]),
+///     ))
+///     .with_wheels(vec![
+///         wheel(-0.8, 1.3, true),
+///         wheel(0.8, 1.3, true),
+///         wheel(-0.8, -1.3, false),
+///         wheel(0.8, -1.3, false),
+///     ])
+///     .with_max_engine_force(4000.0)
+///     .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct Vehicle {
+    rigid_body: RigidBody,
+
+    #[reflect(setter = "set_wheels")]
+    wheels: InheritableVariable<Vec<Wheel>>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_max_engine_force")]
+    max_engine_force: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_max_brake_force")]
+    max_brake_force: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, max_value = 1.5708, step = 0.01)]
+    #[reflect(setter = "set_max_steering_angle")]
+    max_steering_angle: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    throttle: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    brake: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    steering: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    wheel_states: Vec<WheelState>,
+}
+
+impl Default for Vehicle {
+    fn default() -> Self {
+        VehicleBuilder::new(RigidBodyBuilder::new(Default::default())).build_vehicle()
+    }
+}
+
+impl Deref for Vehicle {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rigid_body
+    }
+}
+
+impl DerefMut for Vehicle {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rigid_body
+    }
+}
+
+impl TypeUuidProvider for Vehicle {
+    fn type_uuid() -> Uuid {
+        uuid!("3c5d7e91-0a4b-4f2e-8d6c-9b1a2e4f7c35")
+    }
+}
+
+impl Vehicle {
+    /// Returns a reference to the rigid body (chassis) of the vehicle.
+    pub fn rigid_body_ref(&self) -> &RigidBody {
+        &self.rigid_body
+    }
+
+    /// Returns a reference to the rigid body (chassis) of the vehicle.
+    pub fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        &mut self.rigid_body
+    }
+
+    /// Sets new set of wheels of the vehicle.
+    pub fn set_wheels(&mut self, wheels: Vec<Wheel>) -> Vec<Wheel> {
+        self.wheels.set_value_and_mark_modified(wheels)
+    }
+
+    /// Returns a slice with wheels of the vehicle.
+    pub fn wheels(&self) -> &[Wheel] {
+        &self.wheels
+    }
+
+    /// Returns runtime state of the wheels, the order of states matches the order of the wheels. The states
+    /// are updated by the physics, so they will be empty until the first update of the scene graph.
+    pub fn wheel_states(&self) -> &[WheelState] {
+        &self.wheel_states
+    }
+
+    /// Sets maximum force that the engine applies to the driven wheels when the throttle is fully pressed.
+    /// The force is distributed evenly across all driven wheels.
+    pub fn set_max_engine_force(&mut self, force: f32) -> f32 {
+        self.max_engine_force
+            .set_value_and_mark_modified(force.max(0.0))
+    }
+
+    /// Returns maximum engine force.
+    pub fn max_engine_force(&self) -> f32 {
+        *self.max_engine_force
+    }
+
+    /// Sets maximum braking force of each braking wheel.
+    pub fn set_max_brake_force(&mut self, force: f32) -> f32 {
+        self.max_brake_force
+            .set_value_and_mark_modified(force.max(0.0))
+    }
+
+    /// Returns maximum braking force of each braking wheel.
+    pub fn max_brake_force(&self) -> f32 {
+        *self.max_brake_force
+    }
+
+    /// Sets maximum angle (in radians) at which steering wheels can be turned.
+    pub fn set_max_steering_angle(&mut self, angle: f32) -> f32 {
+        self.max_steering_angle.set_value_and_mark_modified(angle)
+    }
+
+    /// Returns maximum steering angle (in radians).
+    pub fn max_steering_angle(&self) -> f32 {
+        *self.max_steering_angle
+    }
+
+    /// Sets throttle input in `[-1; 1]` range. Negative values moves the vehicle backwards.
+    pub fn set_throttle(&mut self, throttle: f32) {
+        self.throttle = throttle.clamp(-1.0, 1.0);
+    }
+
+    /// Returns current throttle input.
+    pub fn throttle(&self) -> f32 {
+        self.throttle
+    }
+
+    /// Sets brake input in `[0; 1]` range.
+    pub fn set_brake(&mut self, brake: f32) {
+        self.brake = brake.clamp(0.0, 1.0);
+    }
+
+    /// Returns current brake input.
+    pub fn brake(&self) -> f32 {
+        self.brake
+    }
+
+    /// Sets steering input in `[-1; 1]` range. Positive values turns steering wheels to the left (around
+    /// `+Y` axis of the chassis).
+    pub fn set_steering(&mut self, steering: f32) {
+        self.steering = steering.clamp(-1.0, 1.0);
+    }
+
+    /// Returns current steering input.
+    pub fn steering(&self) -> f32 {
+        self.steering
+    }
+
+    /// Returns current speed (in meters per second) of the vehicle along its forward axis. Negative values
+    /// means that the vehicle moves backwards.
+    pub fn speed(&self) -> f32 {
+        self.look_vector()
+            .try_normalize(f32::EPSILON)
+            .map(|forward| self.rigid_body.lin_vel().dot(&forward))
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if at least one wheel of the vehicle touches the ground.
+    pub fn is_on_ground(&self) -> bool {
+        self.wheel_states.iter().any(|state| state.in_contact)
+    }
+
+    fn update_wheel_visuals(&self, context: &mut UpdateContext) {
+        for (wheel, state) in self.wheels.iter().zip(self.wheel_states.iter()) {
+            if let Some(visual) = context.nodes.try_borrow_mut(wheel.visual) {
+                visual
+                    .local_transform_mut()
+                    .set_position(
+                        wheel.connection_point - Vector3::y().scale(state.suspension_length),
+                    )
+                    .set_rotation(
+                        UnitQuaternion::from_axis_angle(&Vector3::y_axis(), state.steering_angle)
+                            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), state.rotation),
+                    );
+            }
+        }
+    }
+}
+
+impl NodeTrait for Vehicle {
+    crate::impl_query_component!(rigid_body: RigidBody);
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.rigid_body.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.rigid_body.world_bounding_box()
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.rigid_body.restore_resources(resource_manager);
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn clean_up(&mut self, graph: &mut Graph) {
+        self.rigid_body.clean_up(graph)
+    }
+
+    fn sync_native(&self, self_handle: Handle<Node>, context: &mut SyncContext) {
+        self.rigid_body.sync_native(self_handle, context)
+    }
+
+    fn sync_transform(&self, new_global_transform: &Matrix4<f32>, context: &mut SyncContext) {
+        self.rigid_body
+            .sync_transform(new_global_transform, context)
+    }
+
+    fn validate(&self, _scene: &Scene) -> Result<(), String> {
+        if self.rigid_body.body_type() != RigidBodyType::Dynamic {
+            Err(
+                "Body type of a vehicle must be dynamic, otherwise it won't be affected by \
+            the wheels!"
+                    .to_string(),
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) -> bool {
+        let mut wheel_states = std::mem::take(&mut self.wheel_states);
+        wheel_states.resize_with(self.wheels.len(), Default::default);
+        context
+            .physics
+            .simulate_vehicle(self, &mut wheel_states, context.dt);
+        self.wheel_states = wheel_states;
+
+        self.update_wheel_visuals(context);
+
+        self.rigid_body.update(context)
+    }
+}
+
+/// Allows you to create vehicles in declarative manner.
+pub struct VehicleBuilder {
+    rigid_body_builder: RigidBodyBuilder,
+    wheels: Vec<Wheel>,
+    max_engine_force: f32,
+    max_brake_force: f32,
+    max_steering_angle: f32,
+}
+
+impl VehicleBuilder {
+    /// Creates new vehicle builder. Body type of the rigid body will be forced to [`RigidBodyType::Dynamic`].
+    pub fn new(rigid_body_builder: RigidBodyBuilder) -> Self {
+        Self {
+            rigid_body_builder,
+            wheels: Default::default(),
+            max_engine_force: 2000.0,
+            max_brake_force: 1500.0,
+            max_steering_angle: 35.0f32.to_radians(),
+        }
+    }
+
+    /// Sets desired wheels.
+    pub fn with_wheels(mut self, wheels: Vec<Wheel>) -> Self {
+        self.wheels = wheels;
+        self
+    }
+
+    /// Sets desired maximum engine force.
+    pub fn with_max_engine_force(mut self, force: f32) -> Self {
+        self.max_engine_force = force.max(0.0);
+        self
+    }
+
+    /// Sets desired maximum brake force.
+    pub fn with_max_brake_force(mut self, force: f32) -> Self {
+        self.max_brake_force = force.max(0.0);
+        self
+    }
+
+    /// Sets desired maximum steering angle (in radians).
+    pub fn with_max_steering_angle(mut self, angle: f32) -> Self {
+        self.max_steering_angle = angle;
+        self
+    }
+
+    /// Creates Vehicle node but does not add it to the graph.
+    pub fn build_vehicle(self) -> Vehicle {
+        Vehicle {
+            rigid_body: self
+                .rigid_body_builder
+                .with_body_type(RigidBodyType::Dynamic)
+                .build_rigid_body(),
+            wheels: self.wheels.into(),
+            max_engine_force: self.max_engine_force.into(),
+            max_brake_force: self.max_brake_force.into(),
+            max_steering_angle: self.max_steering_angle.into(),
+            throttle: 0.0,
+            brake: 0.0,
+            steering: 0.0,
+            wheel_states: Default::default(),
+        }
+    }
+
+    /// Creates Vehicle node but does not add it to the graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_vehicle())
+    }
+
+    /// Creates Vehicle node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::reflect::Reflect;
+    use crate::core::variable::try_inherit_properties;
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            collider::{ColliderBuilder, ColliderShape},
+            graph::Graph,
+            node::Node,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+            vehicle::{Vehicle, VehicleBuilder, Wheel},
+        },
+    };
+
+    #[test]
+    fn test_vehicle_inheritance() {
+        let parent = VehicleBuilder::new(RigidBodyBuilder::new(BaseBuilder::new()))
+            .with_wheels(vec![Wheel {
+                connection_point: Vector3::new(1.0, 0.0, 1.0),
+                steering: true,
+                ..Default::default()
+            }])
+            .with_max_engine_force(123.0)
+            .with_max_brake_force(321.0)
+            .with_max_steering_angle(0.5)
+            .build_node();
+
+        let mut child =
+            VehicleBuilder::new(RigidBodyBuilder::new(BaseBuilder::new())).build_vehicle();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<Vehicle>().unwrap();
+
+        check_inheritable_properties_equality(&child, parent);
+    }
+
+    // Creates flat ground with its top at zero height, returns a handle of its collider.
+    fn add_ground(graph: &mut Graph) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(50.0, 0.5, 50.0))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(graph);
+        collider
+    }
+
+    // Creates a vehicle with four wheels, its chassis center is at the given height. Suspension of
+    // the wheels is at rest length when the height is 0.85.
+    fn add_vehicle(graph: &mut Graph, height: f32, suspension_stiffness: f32) -> Handle<Node> {
+        let wheel = |x: f32, z: f32| Wheel {
+            connection_point: Vector3::new(x, -0.2, z),
+            radius: 0.35,
+            suspension_rest_length: 0.3,
+            suspension_stiffness,
+            ..Default::default()
+        };
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.9, 0.4, 2.0))
+            .build(graph);
+        VehicleBuilder::new(RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, height, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        ))
+        .with_wheels(vec![
+            wheel(-0.8, 1.3),
+            wheel(0.8, 1.3),
+            wheel(-0.8, -1.3),
+            wheel(0.8, -1.3),
+        ])
+        .build(graph)
+    }
+
+    fn update(graph: &mut Graph, frames: usize) {
+        for _ in 0..frames {
+            graph.update(Vector2::new(1.0, 1.0), 1.0 / 60.0);
+        }
+    }
+
+    #[test]
+    fn test_vehicle_wheels_without_contact() {
+        // No ground at all.
+        let mut graph = Graph::new();
+        let vehicle = add_vehicle(&mut graph, 10.0, 40.0);
+        update(&mut graph, 1);
+
+        let vehicle_ref = graph[vehicle].cast::<Vehicle>().unwrap();
+        assert!(!vehicle_ref.is_on_ground());
+        assert_eq!(vehicle_ref.wheel_states().len(), 4);
+        for state in vehicle_ref.wheel_states() {
+            assert!(!state.in_contact);
+            assert_eq!(state.ground, Handle::NONE);
+            assert_eq!(state.suspension_length, 0.3);
+            assert_eq!(state.suspension_force, 0.0);
+        }
+
+        // Ground is slightly out of reach of the wheels.
+        let mut graph = Graph::new();
+        add_ground(&mut graph);
+        let vehicle = add_vehicle(&mut graph, 0.9, 40.0);
+        update(&mut graph, 1);
+
+        let vehicle_ref = graph[vehicle].cast::<Vehicle>().unwrap();
+        assert!(!vehicle_ref.is_on_ground());
+        for state in vehicle_ref.wheel_states() {
+            assert!(!state.in_contact);
+            assert_eq!(state.suspension_length, 0.3);
+            assert_eq!(state.suspension_force, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_vehicle_wheels_contact() {
+        let mut graph = Graph::new();
+        let ground = add_ground(&mut graph);
+        // The wheels barely touch the ground, suspension is almost at rest length.
+        let vehicle = add_vehicle(&mut graph, 0.84, 40.0);
+        update(&mut graph, 1);
+
+        let vehicle_ref = graph[vehicle].cast::<Vehicle>().unwrap();
+        assert!(vehicle_ref.is_on_ground());
+        assert_eq!(vehicle_ref.wheel_states().len(), 4);
+        for (wheel, state) in vehicle_ref.wheels().iter().zip(vehicle_ref.wheel_states()) {
+            assert!(state.in_contact);
+            assert_eq!(state.ground, ground);
+            assert!(
+                state.contact_point.y.abs() < 0.01,
+                "{}",
+                state.contact_point
+            );
+            assert!((state.contact_point.x - wheel.connection_point.x).abs() < 0.01);
+            assert!((state.contact_point.z - wheel.connection_point.z).abs() < 0.01);
+            assert!((state.contact_normal - Vector3::y()).norm() < 0.01);
+            assert!(
+                state.suspension_length > 0.27 && state.suspension_length <= 0.3,
+                "{}",
+                state.suspension_length
+            );
+            assert!(state.suspension_force > 0.0);
+        }
+    }
+
+    // Returns compression of the suspension of each wheel when the vehicle stands still on the ground.
+    fn rest_compression(suspension_stiffness: f32) -> Vec<f32> {
+        let mut graph = Graph::new();
+        add_ground(&mut graph);
+        let vehicle = add_vehicle(&mut graph, 0.8, suspension_stiffness);
+        update(&mut graph, 300);
+
+        let vehicle_ref = graph[vehicle].cast::<Vehicle>().unwrap();
+        vehicle_ref
+            .wheels()
+            .iter()
+            .zip(vehicle_ref.wheel_states())
+            .map(|(wheel, state)| {
+                assert!(state.in_contact);
+                wheel.suspension_rest_length - state.suspension_length
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_vehicle_suspension_compression() {
+        // Suspension force is scaled by the mass of the chassis, so each of four wheels holds a quarter
+        // of the weight when `4 * stiffness * compression == g`.
+        let soft = rest_compression(40.0);
+        let stiff = rest_compression(80.0);
+        assert_eq!(soft.len(), 4);
+        assert_eq!(stiff.len(), 4);
+        for (soft, stiff) in soft.into_iter().zip(stiff) {
+            assert!((soft - 9.81 / 160.0).abs() < 0.02, "{}", soft);
+            assert!((stiff - 9.81 / 320.0).abs() < 0.02, "{}", stiff);
+            assert!(soft > stiff);
+        }
+    }
+}
@@ -9,17 +9,23 @@ use crate::{
         uuid::Uuid,
         visitor::{Visit, VisitResult, Visitor},
     },
-    engine::resource_manager::ResourceManager,
+    engine::{resource_manager::ResourceManager, task::TaskScheduler, FixedUpdateStage},
     event::Event,
     plugin::Plugin,
-    scene::{node::Node, Scene},
+    scene::{
+        graph::{physics::CollisionEvent, Graph},
+        node::Node,
+        Scene,
+    },
     utils::{component::ComponentProvider, log::Log},
 };
+use fxhash::{FxHashMap, FxHashSet};
 use fyrox_core::reflect::FieldInfo;
 use std::{
     any::{Any, TypeId},
     fmt::Debug,
     ops::{Deref, DerefMut},
+    sync::mpsc::{Receiver, Sender},
 };
 
 pub mod constructor;
@@ -41,7 +47,9 @@ where
 
 /// A set of data, that provides contextual information for script methods.
 pub struct ScriptContext<'a, 'b> {
-    /// Amount of time that passed from last call. It has valid values only when called from `on_update`.
+    /// Amount of time that passed from last call. It has valid values only when called from `on_update`,
+    /// `on_fixed_update` (fixed time step in both cases) and `on_frame` (real time that passed since the last
+    /// frame).
     pub dt: f32,
 
     /// Amount of time (in seconds) that passed from creation of the engine. Keep in mind, that
@@ -68,8 +76,234 @@ pub struct ScriptContext<'a, 'b> {
     /// in most of the script methods.
     pub scene: &'b mut Scene,
 
+    /// Handle of the scene the script instance belongs to.
+    pub scene_handle: Handle<Scene>,
+
     /// A reference to resource manager, use it to load resources.
     pub resource_manager: &'a ResourceManager,
+
+    /// A message sender, use it to send messages to other scripts of the scene. Messages will be delivered
+    /// to subscribed scripts on the next update via [`ScriptTrait::on_message`].
+    pub message_sender: &'a ScriptMessageSender,
+
+    /// A message dispatcher, use it to subscribe the script to messages of a particular type.
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # struct Damage(f32);
+    /// # fn foo(context: &mut ScriptContext) {
+    /// context.message_dispatcher.subscribe_to::<Damage>(context.handle);
+    /// # }
+    /// ```
+    pub message_dispatcher: &'a mut ScriptMessageDispatcher,
+
+    /// A task scheduler, use it to run asynchronous tasks (for example some scripted sequences that span
+    /// multiple frames).
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # fn foo(context: &mut ScriptContext) {
+    /// let node = context.handle;
+    /// context
+    ///     .task_scheduler
+    ///     .spawn(context.scene_handle, move |ctx| async move {
+    ///         ctx.sleep(1.0).await;
+    ///         ctx.with_scene(|scene| scene.remove_node(node));
+    ///     });
+    /// # }
+    /// ```
+    pub task_scheduler: &'a mut TaskScheduler,
+}
+
+/// A payload of a script message. It is implemented for every `'static + Send` type, so any such type
+/// could be used as a message.
+pub trait ScriptMessagePayload: Any + Send {
+    /// Returns `self` as `&dyn Any`.
+    fn as_any_ref(&self) -> &dyn Any;
+
+    /// Returns `self` as `&mut dyn Any`.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl dyn ScriptMessagePayload {
+    /// Tries to cast the payload to a particular type.
+    pub fn downcast_ref<T: ScriptMessagePayload>(&self) -> Option<&T> {
+        self.as_any_ref().downcast_ref::<T>()
+    }
+
+    /// Tries to cast the payload to a particular type.
+    pub fn downcast_mut<T: ScriptMessagePayload>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+}
+
+impl<T> ScriptMessagePayload for T
+where
+    T: 'static + Send,
+{
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Defines which scripts will receive a script message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScriptMessageKind {
+    /// The message will be passed to the script of the specified node only.
+    Targeted(Handle<Node>),
+
+    /// The message will be passed to the scripts of the specified node and all of its descendant nodes.
+    Hierarchical(Handle<Node>),
+
+    /// The message will be passed to every script of the scene.
+    Global,
+}
+
+/// A message that can be sent from one script to another. Only scripts that are subscribed to the type of the
+/// payload will receive the message, see [`ScriptMessageDispatcher::subscribe_to`].
+pub struct ScriptMessage {
+    /// Actual data of the message.
+    pub payload: Box<dyn ScriptMessagePayload>,
+
+    /// Defines which scripts will receive the message.
+    pub kind: ScriptMessageKind,
+}
+
+impl ScriptMessage {
+    /// Creates new script message with the given payload.
+    pub fn new<T: ScriptMessagePayload>(payload: T, kind: ScriptMessageKind) -> Self {
+        Self {
+            payload: Box::new(payload),
+            kind,
+        }
+    }
+}
+
+/// A sender of script messages. It can be cloned and stored anywhere (for example in a script) to send
+/// messages later.
+#[derive(Clone, Debug)]
+pub struct ScriptMessageSender {
+    sender: Sender<ScriptMessage>,
+}
+
+impl ScriptMessageSender {
+    /// Sends a message.
+    pub fn send(&self, message: ScriptMessage) {
+        if self.sender.send(message).is_err() {
+            Log::err("Failed to send script message, the scene was destroyed!");
+        }
+    }
+
+    /// Sends a message to the script of the specified node.
+    pub fn send_to_target<T: ScriptMessagePayload>(&self, target: Handle<Node>, payload: T) {
+        self.send(ScriptMessage::new(
+            payload,
+            ScriptMessageKind::Targeted(target),
+        ))
+    }
+
+    /// Sends a message to the scripts of the specified node and all of its descendant nodes.
+    pub fn send_hierarchical<T: ScriptMessagePayload>(&self, root: Handle<Node>, payload: T) {
+        self.send(ScriptMessage::new(
+            payload,
+            ScriptMessageKind::Hierarchical(root),
+        ))
+    }
+
+    /// Sends a message to every script of the scene.
+    pub fn send_global<T: ScriptMessagePayload>(&self, payload: T) {
+        self.send(ScriptMessage::new(payload, ScriptMessageKind::Global))
+    }
+}
+
+/// Script message dispatcher keeps track of subscriptions of scripts to messages of particular types and
+/// collects messages sent by [`ScriptMessageSender`]. Every scripted scene has its own dispatcher.
+pub struct ScriptMessageDispatcher {
+    type_groups: FxHashMap<TypeId, FxHashSet<Handle<Node>>>,
+    message_receiver: Receiver<ScriptMessage>,
+}
+
+impl ScriptMessageDispatcher {
+    pub(crate) fn new() -> (ScriptMessageSender, Self) {
+        let (sender, message_receiver) = std::sync::mpsc::channel();
+        (
+            ScriptMessageSender { sender },
+            Self {
+                type_groups: Default::default(),
+                message_receiver,
+            },
+        )
+    }
+
+    /// Subscribes a script of the given node to messages of the given type.
+    pub fn subscribe_to<T: ScriptMessagePayload>(&mut self, receiver: Handle<Node>) {
+        self.type_groups
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(receiver);
+    }
+
+    /// Unsubscribes a script of the given node from messages of the given type.
+    pub fn unsubscribe_from<T: ScriptMessagePayload>(&mut self, receiver: Handle<Node>) {
+        if let Some(group) = self.type_groups.get_mut(&TypeId::of::<T>()) {
+            group.remove(&receiver);
+        }
+    }
+
+    /// Unsubscribes a script of the given node from messages of every type.
+    pub fn unsubscribe(&mut self, receiver: Handle<Node>) {
+        for group in self.type_groups.values_mut() {
+            group.remove(&receiver);
+        }
+    }
+
+    /// Returns `true` if a script of the given node is subscribed to messages of the given type.
+    pub fn is_subscribed_to<T: ScriptMessagePayload>(&self, receiver: Handle<Node>) -> bool {
+        self.type_groups
+            .get(&TypeId::of::<T>())
+            .map_or(false, |group| group.contains(&receiver))
+    }
+
+    pub(crate) fn take_messages(&self) -> Vec<ScriptMessage> {
+        self.message_receiver.try_iter().collect()
+    }
+
+    pub(crate) fn recipients(&self, message: &ScriptMessage, graph: &Graph) -> Vec<Handle<Node>> {
+        // Deref is needed here, otherwise type id of the box will be taken.
+        let type_id = (*message.payload).as_any_ref().type_id();
+        let group = match self.type_groups.get(&type_id) {
+            Some(group) => group,
+            None => return Default::default(),
+        };
+
+        match message.kind {
+            ScriptMessageKind::Targeted(target) => {
+                if group.contains(&target) {
+                    vec![target]
+                } else {
+                    Default::default()
+                }
+            }
+            ScriptMessageKind::Hierarchical(root) => {
+                let mut recipients = Vec::new();
+                let mut stack = vec![root];
+                while let Some(handle) = stack.pop() {
+                    if let Some(node) = graph.try_get(handle) {
+                        if group.contains(&handle) {
+                            recipients.push(handle);
+                        }
+                        stack.extend_from_slice(node.children());
+                    }
+                }
+                recipients
+            }
+            ScriptMessageKind::Global => group.iter().cloned().collect(),
+        }
+    }
 }
 
 /// A set of data that will be passed to a script instance just before its destruction.
@@ -116,6 +350,12 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     /// be called once.
     fn on_start(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
+    /// The method is called for every started script of a dynamic plugin, right after the plugin was
+    /// reloaded (see [`crate::plugin::dynamic`] for more info). The state of the script is restored at
+    /// this point, but its message subscriptions are dropped, because they could reference types of the
+    /// old version of the code. Use this method to subscribe to the messages once again.
+    fn on_hot_reload(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
+
     /// The method is called when the script is about to be destroyed. It is guaranteed to be called last.
     fn on_deinit(&mut self, #[allow(unused_variables)] ctx: &mut ScriptDeinitContext) {}
 
@@ -129,11 +369,86 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     ) {
     }
 
+    /// Called when a collider, attached to the node of the script or to one of its descendants, started
+    /// or stopped touching (or intersecting) with some other collider. `collider1` of the event is always
+    /// the collider from the hierarchy of the node; a node that is an ancestor of both colliders receives
+    /// the event only once. Events are passed once per frame, right before [`ScriptTrait::on_update`].
+    fn on_collision_event(
+        &mut self,
+        #[allow(unused_variables)] event: &CollisionEvent,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called when there is a message for the script. The script must be subscribed to messages of the
+    /// payload type (see [`ScriptMessageDispatcher::subscribe_to`]), otherwise the method won't be called.
+    /// Messages are sent using [`ScriptContext::message_sender`] and delivered on the next update, right
+    /// before [`ScriptTrait::on_update`].
+    ///
+    /// ```rust
+    /// # use fyrox::{
+    /// #     core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+    /// #     impl_component_provider,
+    /// #     script::{ScriptContext, ScriptMessagePayload, ScriptTrait},
+    /// # };
+    /// struct Damage(f32);
+    ///
+    /// #[derive(Reflect, Visit, Debug, Clone)]
+    /// struct Health {
+    ///     amount: f32,
+    /// }
+    ///
+    /// # impl_component_provider!(Health);
+    /// impl ScriptTrait for Health {
+    ///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+    ///         ctx.message_dispatcher.subscribe_to::<Damage>(ctx.handle);
+    ///     }
+    ///
+    ///     fn on_message(&mut self, message: &mut dyn ScriptMessagePayload, _ctx: &mut ScriptContext) {
+    ///         if let Some(damage) = message.downcast_ref::<Damage>() {
+    ///             self.amount -= damage.0;
+    ///         }
+    ///     }
+    ///
+    /// #   fn id(&self) -> Uuid {
+    /// #       todo!()
+    /// #   }
+    /// }
+    /// ```
+    fn on_message(
+        &mut self,
+        #[allow(unused_variables)] message: &mut dyn ScriptMessagePayload,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
     /// Performs a single update tick of the script. The method may be called multiple times per
     /// frame, but it is guaranteed that the rate of call is stable and usually it will be called
     /// 60 times per second (this may change in future releases).
     fn on_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
+    /// Called at fixed rate right before and right after each physics step, `stage` tells which one. Use
+    /// [`FixedUpdateStage::PrePhysics`] to apply forces or to set velocities of rigid bodies and
+    /// [`FixedUpdateStage::PostPhysics`] to read the results of the simulation.
+    fn on_fixed_update(
+        &mut self,
+        #[allow(unused_variables)] stage: FixedUpdateStage,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called once per rendered frame, [`ScriptContext::dt`] contains real time that passed since the last
+    /// frame. `interpolation_alpha` (in `[0; 1]` range) defines how far the frame is between the last fixed update
+    /// and the next one. The method is the best place for things that must be smooth regardless of the update
+    /// rate, for example camera smoothing. Global transforms of rigid bodies with enabled interpolation are
+    /// already interpolated at the moment of the call.
+    fn on_frame(
+        &mut self,
+        #[allow(unused_variables)] interpolation_alpha: f32,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
     /// Allows you to restore resources after deserialization.
     ///
     /// # Motivation
@@ -294,6 +609,11 @@ impl Visit for Script {
 
             self.instance.visit(name, visitor)?;
 
+            visitor.report_migration(format!(
+                "script instance {} converted to new format",
+                self.id()
+            ));
+
             Log::warn(format!(
                 "Script instance of id {} loaded successfully using compatibility loader! Resave the script!",
                 self.id()
@@ -392,10 +392,21 @@ fyroxed_base = "0.15"
         format!(
             r#"//! Editor with your game connected to it as a plugin.
 use fyrox::event_loop::EventLoop;
-use fyroxed_base::{{Editor, StartupData}};
+use fyroxed_base::{{build, upgrade, Editor, StartupData}};
 use {}::GameConstructor;
 
 fn main() {{
+    // `cargo run --package editor -- upgrade [--dry-run]` upgrades scenes of the project to current format.
+    if upgrade::handle_command_line(&[&GameConstructor]) {{
+        return;
+    }}
+
+    // `cargo run --package editor -- build [--release] [--pack <out>]` builds the game and packs its
+    // assets into an asset pack, which could be mounted by the executor with `--pack <out>` option.
+    if build::handle_command_line() {{
+        return;
+    }}
+
     let event_loop = EventLoop::new();
     let mut editor = Editor::new(
         &event_loop,